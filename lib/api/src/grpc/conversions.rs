@@ -1,23 +1,35 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
-use chrono::{NaiveDateTime, Timelike};
+use chrono::{DateTime, NaiveDateTime, Timelike, Utc};
 use segment::types::{PayloadSelector, WithPayloadInterface};
 use tonic::Status;
 use uuid::Uuid;
 
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
+use crate::grpc::qdrant::decay_params::Origin;
+use crate::grpc::qdrant::facet_value::Variant as FacetVariant;
+use crate::grpc::qdrant::order_by::StartFrom;
+use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::r#match::MatchValue;
+use crate::grpc::qdrant::score_expression::Variant;
 use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
-    CollectionDescription, CollectionOperationResponse, Condition, FieldCondition, Filter,
-    GeoBoundingBox, GeoPoint, GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff,
-    IsEmptyCondition, ListCollectionsResponse, ListValue, Match, PayloadExcludeSelector,
-    PayloadIncludeSelector, PayloadSchemaInfo, PayloadSchemaType, PointId, Range, ScoredPoint,
-    SearchParams, Struct, Value, ValuesCount, WithPayloadSelector,
+    aggregation_value, AggregationValue, CollectionDescription, CollectionOperationResponse,
+    Condition, DatetimeIndexParams, DatetimeRange, DecayParams, Direction, FacetValue,
+    FacetValueHit, FieldCondition, FieldType, Filter, FilterCardinality, FloatIndexParams,
+    GeoBoundingBox, GeoIndexParams, GeoLineString, GeoPoint, GeoPolygon, GeoRadius,
+    HasFieldCondition, HasIdCondition, HealthCheckReply, HistogramBucket, HnswConfigDiff,
+    IntegerIndexParams, IsEmptyCondition, IsNullCondition, KeywordIndexParams, KeywordRange,
+    ListCollectionsResponse, ListValue, Match, MinShould, NestedCondition, NumericAggregation,
+    NumericIndexStats, OrderBy, PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams,
+    PayloadIndexStats, PayloadSchemaInfo, PayloadSchemaType, PayloadVariable, PointId,
+    QuantileValue, QueryStrategy, Range, RepeatedIntegers, RepeatedStrings, Rescore,
+    ScoreExpression, ScoreExpressionList, ScoredPoint, SearchParams, Struct, TextIndexParams,
+    TokenizerType, UuidIndexParams, Value, ValuesCount, WithPayloadSelector,
 };
 
 pub fn payload_to_proto(payload: segment::types::Payload) -> HashMap<String, Value> {
@@ -27,7 +39,7 @@ pub fn payload_to_proto(payload: segment::types::Payload) -> HashMap<String, Val
         .collect()
 }
 
-fn json_to_proto(json_value: serde_json::Value) -> Value {
+pub fn json_to_proto(json_value: serde_json::Value) -> Value {
     match json_value {
         serde_json::Value::Null => Value {
             kind: Some(Kind::NullValue(0)),
@@ -68,7 +80,7 @@ pub fn proto_to_payloads(proto: HashMap<String, Value>) -> Result<segment::types
     Ok(map.into())
 }
 
-fn proto_to_json(proto: Value) -> Result<serde_json::Value, Status> {
+pub fn proto_to_json(proto: Value) -> Result<serde_json::Value, Status> {
     match proto.kind {
         None => Ok(serde_json::Value::default()),
         Some(kind) => match kind {
@@ -125,16 +137,199 @@ impl From<(Instant, CollectionsResponse)> for ListCollectionsResponse {
     }
 }
 
+impl From<segment::types::PayloadSchemaType> for PayloadSchemaType {
+    fn from(schema_type: segment::types::PayloadSchemaType) -> Self {
+        match schema_type {
+            segment::types::PayloadSchemaType::Keyword => PayloadSchemaType::Keyword,
+            segment::types::PayloadSchemaType::Integer => PayloadSchemaType::Integer,
+            segment::types::PayloadSchemaType::Float => PayloadSchemaType::Float,
+            segment::types::PayloadSchemaType::Geo => PayloadSchemaType::Geo,
+            segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
+            segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
+            segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
+            segment::types::PayloadSchemaType::Uuid => PayloadSchemaType::Uuid,
+        }
+    }
+}
+
+impl From<segment::types::PayloadSchemaType> for FieldType {
+    fn from(schema_type: segment::types::PayloadSchemaType) -> Self {
+        match schema_type {
+            segment::types::PayloadSchemaType::Keyword => FieldType::Keyword,
+            segment::types::PayloadSchemaType::Integer => FieldType::Integer,
+            segment::types::PayloadSchemaType::Float => FieldType::Float,
+            segment::types::PayloadSchemaType::Geo => FieldType::Geo,
+            segment::types::PayloadSchemaType::Text => FieldType::Text,
+            segment::types::PayloadSchemaType::Datetime => FieldType::Datetime,
+            segment::types::PayloadSchemaType::Bool => FieldType::Bool,
+            segment::types::PayloadSchemaType::Uuid => FieldType::Uuid,
+        }
+    }
+}
+
+impl From<FieldType> for segment::types::PayloadSchemaType {
+    fn from(field_type: FieldType) -> Self {
+        match field_type {
+            FieldType::Keyword => segment::types::PayloadSchemaType::Keyword,
+            FieldType::Integer => segment::types::PayloadSchemaType::Integer,
+            FieldType::Float => segment::types::PayloadSchemaType::Float,
+            FieldType::Geo => segment::types::PayloadSchemaType::Geo,
+            FieldType::Text => segment::types::PayloadSchemaType::Text,
+            FieldType::Datetime => segment::types::PayloadSchemaType::Datetime,
+            FieldType::Bool => segment::types::PayloadSchemaType::Bool,
+            FieldType::Uuid => segment::types::PayloadSchemaType::Uuid,
+        }
+    }
+}
+
+impl From<segment::types::TokenizerType> for TokenizerType {
+    fn from(tokenizer_type: segment::types::TokenizerType) -> Self {
+        match tokenizer_type {
+            segment::types::TokenizerType::Prefix => TokenizerType::Prefix,
+            segment::types::TokenizerType::Whitespace => TokenizerType::Whitespace,
+            segment::types::TokenizerType::Word => TokenizerType::Word,
+        }
+    }
+}
+
+impl From<segment::types::TextIndexParams> for TextIndexParams {
+    fn from(params: segment::types::TextIndexParams) -> Self {
+        TextIndexParams {
+            tokenizer: TokenizerType::from(params.tokenizer) as i32,
+            lowercase: params.lowercase,
+            min_token_len: params.min_token_len.map(|x| x as u64),
+            max_token_len: params.max_token_len.map(|x| x as u64),
+        }
+    }
+}
+
+impl TryFrom<TextIndexParams> for segment::types::TextIndexParams {
+    type Error = Status;
+
+    fn try_from(params: TextIndexParams) -> Result<Self, Self::Error> {
+        let tokenizer = match TokenizerType::from_i32(params.tokenizer) {
+            Some(TokenizerType::Prefix) => segment::types::TokenizerType::Prefix,
+            Some(TokenizerType::Whitespace) => segment::types::TokenizerType::Whitespace,
+            Some(TokenizerType::Word) => segment::types::TokenizerType::Word,
+            Some(TokenizerType::Unknown) | None => {
+                return Err(Status::invalid_argument("Unknown tokenizer type"))
+            }
+        };
+        Ok(segment::types::TextIndexParams {
+            r#type: segment::types::TextIndexType::Text,
+            tokenizer,
+            min_token_len: params.min_token_len.map(|x| x as usize),
+            max_token_len: params.max_token_len.map(|x| x as usize),
+            lowercase: params.lowercase,
+        })
+    }
+}
+
+impl From<segment::types::PayloadSchemaParams> for PayloadIndexParams {
+    fn from(params: segment::types::PayloadSchemaParams) -> Self {
+        let index_params = match params {
+            segment::types::PayloadSchemaParams::Text(text_index_params) => {
+                IndexParams::TextIndexParams(text_index_params.into())
+            }
+            segment::types::PayloadSchemaParams::Keyword(params) => {
+                IndexParams::KeywordIndexParams(KeywordIndexParams {
+                    on_disk: params.on_disk,
+                    is_tenant: params.is_tenant,
+                })
+            }
+            segment::types::PayloadSchemaParams::Integer(params) => {
+                IndexParams::IntegerIndexParams(IntegerIndexParams {
+                    on_disk: params.on_disk,
+                    lookup: params.lookup,
+                    range: params.range,
+                })
+            }
+            segment::types::PayloadSchemaParams::Float(params) => {
+                IndexParams::FloatIndexParams(FloatIndexParams {
+                    on_disk: params.on_disk,
+                })
+            }
+            segment::types::PayloadSchemaParams::Geo(params) => {
+                IndexParams::GeoIndexParams(GeoIndexParams {
+                    on_disk: params.on_disk,
+                })
+            }
+            segment::types::PayloadSchemaParams::Datetime(params) => {
+                IndexParams::DatetimeIndexParams(DatetimeIndexParams {
+                    on_disk: params.on_disk,
+                })
+            }
+            segment::types::PayloadSchemaParams::Uuid(params) => {
+                IndexParams::UuidIndexParams(UuidIndexParams {
+                    on_disk: params.on_disk,
+                })
+            }
+        };
+        PayloadIndexParams {
+            index_params: Some(index_params),
+        }
+    }
+}
+
+impl TryFrom<PayloadIndexParams> for segment::types::PayloadSchemaParams {
+    type Error = Status;
+
+    fn try_from(params: PayloadIndexParams) -> Result<Self, Self::Error> {
+        match params.index_params {
+            Some(IndexParams::TextIndexParams(text_index_params)) => Ok(
+                segment::types::PayloadSchemaParams::Text(text_index_params.try_into()?),
+            ),
+            Some(IndexParams::KeywordIndexParams(params)) => Ok(
+                segment::types::PayloadSchemaParams::Keyword(segment::types::KeywordIndexParams {
+                    on_disk: params.on_disk,
+                    is_tenant: params.is_tenant,
+                    ..Default::default()
+                }),
+            ),
+            Some(IndexParams::IntegerIndexParams(params)) => Ok(
+                segment::types::PayloadSchemaParams::Integer(segment::types::IntegerIndexParams {
+                    on_disk: params.on_disk,
+                    lookup: params.lookup,
+                    range: params.range,
+                    ..Default::default()
+                }),
+            ),
+            Some(IndexParams::FloatIndexParams(params)) => Ok(
+                segment::types::PayloadSchemaParams::Float(segment::types::FloatIndexParams {
+                    on_disk: params.on_disk,
+                    ..Default::default()
+                }),
+            ),
+            Some(IndexParams::GeoIndexParams(params)) => Ok(
+                segment::types::PayloadSchemaParams::Geo(segment::types::GeoIndexParams {
+                    on_disk: params.on_disk,
+                    ..Default::default()
+                }),
+            ),
+            Some(IndexParams::DatetimeIndexParams(params)) => {
+                Ok(segment::types::PayloadSchemaParams::Datetime(
+                    segment::types::DatetimeIndexParams {
+                        on_disk: params.on_disk,
+                        ..Default::default()
+                    },
+                ))
+            }
+            Some(IndexParams::UuidIndexParams(params)) => Ok(
+                segment::types::PayloadSchemaParams::Uuid(segment::types::UuidIndexParams {
+                    on_disk: params.on_disk,
+                    ..Default::default()
+                }),
+            ),
+            None => Err(Status::invalid_argument("Payload index params are missing")),
+        }
+    }
+}
+
 impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
     fn from(schema: segment::types::PayloadIndexInfo) -> Self {
         PayloadSchemaInfo {
-            data_type: match schema.data_type {
-                segment::types::PayloadSchemaType::Keyword => PayloadSchemaType::Keyword,
-                segment::types::PayloadSchemaType::Integer => PayloadSchemaType::Integer,
-                segment::types::PayloadSchemaType::Float => PayloadSchemaType::Float,
-                segment::types::PayloadSchemaType::Geo => PayloadSchemaType::Geo,
-            }
-            .into(),
+            data_type: PayloadSchemaType::from(schema.data_type).into(),
+            params: schema.params.map(|params| params.into()),
         }
     }
 }
@@ -147,6 +342,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
             None => Err(Status::invalid_argument("No PayloadSelector".to_string())),
             Some(payload_schema_type) => Ok(segment::types::PayloadIndexInfo {
                 data_type: payload_schema_type,
+                params: schema.params.map(|params| params.try_into()).transpose()?,
             }),
         }
     }
@@ -228,6 +424,217 @@ impl From<segment::types::SearchParams> for SearchParams {
     }
 }
 
+impl From<PayloadVariable> for segment::types::PayloadVariable {
+    fn from(value: PayloadVariable) -> Self {
+        Self {
+            key: value.key,
+            default: value.default.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<segment::types::PayloadVariable> for PayloadVariable {
+    fn from(value: segment::types::PayloadVariable) -> Self {
+        Self {
+            key: value.key,
+            default: Some(value.default),
+        }
+    }
+}
+
+impl TryFrom<DecayParams> for segment::types::DecayParams {
+    type Error = Status;
+
+    fn try_from(value: DecayParams) -> Result<Self, Self::Error> {
+        let origin = match value.origin {
+            Some(Origin::Number(number)) => segment::types::DecayOrigin::Number(number),
+            Some(Origin::Datetime(datetime)) => segment::types::DecayOrigin::Datetime(
+                DateTime::parse_from_rfc3339(&datetime)
+                    .map_err(|err| {
+                        Status::invalid_argument(format!("Malformed decay origin: {}", err))
+                    })?
+                    .with_timezone(&Utc),
+            ),
+            None => return Err(Status::invalid_argument("Decay origin is required")),
+        };
+        Ok(Self {
+            key: value.key,
+            origin,
+            scale: value.scale,
+            midpoint: value.midpoint,
+        })
+    }
+}
+
+impl From<segment::types::DecayParams> for DecayParams {
+    fn from(value: segment::types::DecayParams) -> Self {
+        let origin = match value.origin {
+            segment::types::DecayOrigin::Number(number) => Origin::Number(number),
+            segment::types::DecayOrigin::Datetime(datetime) => {
+                Origin::Datetime(datetime.to_rfc3339())
+            }
+        };
+        Self {
+            key: value.key,
+            origin: Some(origin),
+            scale: value.scale,
+            midpoint: value.midpoint,
+        }
+    }
+}
+
+fn expressions_from_grpc(
+    list: ScoreExpressionList,
+) -> Result<Vec<segment::types::ScoreExpression>, Status> {
+    list.expressions
+        .into_iter()
+        .map(|expression| expression.try_into())
+        .collect()
+}
+
+fn expressions_to_grpc(expressions: Vec<segment::types::ScoreExpression>) -> ScoreExpressionList {
+    ScoreExpressionList {
+        expressions: expressions
+            .into_iter()
+            .map(|expression| expression.into())
+            .collect(),
+    }
+}
+
+impl TryFrom<ScoreExpression> for segment::types::ScoreExpression {
+    type Error = Status;
+
+    fn try_from(value: ScoreExpression) -> Result<Self, Self::Error> {
+        if let Some(variant) = value.variant {
+            return match variant {
+                Variant::Constant(constant) => {
+                    Ok(segment::types::ScoreExpression::Constant(constant))
+                }
+                Variant::Score(_) => Ok(segment::types::ScoreExpression::Score),
+                Variant::Payload(variable) => {
+                    Ok(segment::types::ScoreExpression::Payload(variable.into()))
+                }
+                Variant::Mult(list) => Ok(segment::types::ScoreExpression::Mult(
+                    expressions_from_grpc(list)?,
+                )),
+                Variant::Sum(list) => Ok(segment::types::ScoreExpression::Sum(
+                    expressions_from_grpc(list)?,
+                )),
+                Variant::Ln(expression) => Ok(segment::types::ScoreExpression::Ln(Box::new(
+                    (*expression).try_into()?,
+                ))),
+                Variant::LinDecay(params) => Ok(segment::types::ScoreExpression::LinDecay(
+                    params.try_into()?,
+                )),
+                Variant::ExpDecay(params) => Ok(segment::types::ScoreExpression::ExpDecay(
+                    params.try_into()?,
+                )),
+                Variant::GaussDecay(params) => Ok(segment::types::ScoreExpression::GaussDecay(
+                    params.try_into()?,
+                )),
+            };
+        }
+        Err(Status::invalid_argument("Malformed ScoreExpression type"))
+    }
+}
+
+impl From<segment::types::ScoreExpression> for ScoreExpression {
+    fn from(value: segment::types::ScoreExpression) -> Self {
+        let variant = match value {
+            segment::types::ScoreExpression::Constant(constant) => Variant::Constant(constant),
+            segment::types::ScoreExpression::Score => Variant::Score(true),
+            segment::types::ScoreExpression::Payload(variable) => Variant::Payload(variable.into()),
+            segment::types::ScoreExpression::Mult(expressions) => {
+                Variant::Mult(expressions_to_grpc(expressions))
+            }
+            segment::types::ScoreExpression::Sum(expressions) => {
+                Variant::Sum(expressions_to_grpc(expressions))
+            }
+            segment::types::ScoreExpression::Ln(expression) => {
+                Variant::Ln(Box::new((*expression).into()))
+            }
+            segment::types::ScoreExpression::LinDecay(params) => Variant::LinDecay(params.into()),
+            segment::types::ScoreExpression::ExpDecay(params) => Variant::ExpDecay(params.into()),
+            segment::types::ScoreExpression::GaussDecay(params) => {
+                Variant::GaussDecay(params.into())
+            }
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<Rescore> for segment::types::Rescore {
+    type Error = Status;
+
+    fn try_from(value: Rescore) -> Result<Self, Self::Error> {
+        Ok(Self {
+            expression: value
+                .expression
+                .ok_or_else(|| Status::invalid_argument("Rescore expression is required"))?
+                .try_into()?,
+            oversampling: value.oversampling.map(|x| x as usize),
+        })
+    }
+}
+
+impl From<segment::types::Rescore> for Rescore {
+    fn from(value: segment::types::Rescore) -> Self {
+        Self {
+            expression: Some(value.expression.into()),
+            oversampling: value.oversampling.map(|x| x as u64),
+        }
+    }
+}
+
+impl From<QueryStrategy> for segment::types::QueryStrategy {
+    fn from(value: QueryStrategy) -> Self {
+        match value {
+            QueryStrategy::FullScan => segment::types::QueryStrategy::FullScan,
+            QueryStrategy::FilteredScan => segment::types::QueryStrategy::FilteredScan,
+            QueryStrategy::PayloadIndex => segment::types::QueryStrategy::PayloadIndex,
+            QueryStrategy::Hnsw => segment::types::QueryStrategy::Hnsw,
+            QueryStrategy::FilteredHnsw => segment::types::QueryStrategy::FilteredHnsw,
+            QueryStrategy::Estimation => segment::types::QueryStrategy::Estimation,
+        }
+    }
+}
+
+impl From<segment::types::QueryStrategy> for QueryStrategy {
+    fn from(value: segment::types::QueryStrategy) -> Self {
+        match value {
+            segment::types::QueryStrategy::FullScan => QueryStrategy::FullScan,
+            segment::types::QueryStrategy::FilteredScan => QueryStrategy::FilteredScan,
+            segment::types::QueryStrategy::PayloadIndex => QueryStrategy::PayloadIndex,
+            segment::types::QueryStrategy::Hnsw => QueryStrategy::Hnsw,
+            segment::types::QueryStrategy::FilteredHnsw => QueryStrategy::FilteredHnsw,
+            segment::types::QueryStrategy::Estimation => QueryStrategy::Estimation,
+        }
+    }
+}
+
+impl From<FilterCardinality> for segment::types::FilterCardinality {
+    fn from(value: FilterCardinality) -> Self {
+        Self {
+            min: value.min as usize,
+            exp: value.exp as usize,
+            max: value.max as usize,
+        }
+    }
+}
+
+impl From<segment::types::FilterCardinality> for FilterCardinality {
+    fn from(value: segment::types::FilterCardinality) -> Self {
+        Self {
+            min: value.min as u64,
+            exp: value.exp as u64,
+            max: value.max as u64,
+        }
+    }
+}
+
 impl From<segment::types::PointIdType> for PointId {
     fn from(point_id: segment::types::PointIdType) -> Self {
         PointId {
@@ -321,16 +728,42 @@ impl TryFrom<Filter> for segment::types::Filter {
             should: conditions_helper_from_grpc(value.should)?,
             must: conditions_helper_from_grpc(value.must)?,
             must_not: conditions_helper_from_grpc(value.must_not)?,
+            min_should: value.min_should.map(|v| v.try_into()).transpose()?,
         })
     }
 }
 
+impl TryFrom<MinShould> for segment::types::MinShould {
+    type Error = Status;
+
+    fn try_from(value: MinShould) -> Result<Self, Self::Error> {
+        Ok(Self {
+            conditions: value
+                .conditions
+                .into_iter()
+                .map(|c| c.try_into())
+                .collect::<Result<_, _>>()?,
+            min_count: value.min_count as usize,
+        })
+    }
+}
+
+impl From<segment::types::MinShould> for MinShould {
+    fn from(value: segment::types::MinShould) -> Self {
+        Self {
+            conditions: value.conditions.into_iter().map(|c| c.into()).collect(),
+            min_count: value.min_count as u64,
+        }
+    }
+}
+
 impl From<segment::types::Filter> for Filter {
     fn from(value: segment::types::Filter) -> Self {
         Self {
             should: conditions_helper_to_grpc(value.should),
             must: conditions_helper_to_grpc(value.must),
             must_not: conditions_helper_to_grpc(value.must_not),
+            min_should: value.min_should.map(|v| v.into()),
         }
     }
 }
@@ -353,6 +786,15 @@ impl TryFrom<Condition> for segment::types::Condition {
                 ConditionOneOf::IsEmpty(is_empty) => {
                     Ok(segment::types::Condition::IsEmpty(is_empty.into()))
                 }
+                ConditionOneOf::Nested(nested) => {
+                    Ok(segment::types::Condition::Nested(nested.try_into()?))
+                }
+                ConditionOneOf::IsNull(is_null) => {
+                    Ok(segment::types::Condition::IsNull(is_null.into()))
+                }
+                ConditionOneOf::HasField(has_field) => {
+                    Ok(segment::types::Condition::HasField(has_field.into()))
+                }
             };
         }
         Err(Status::invalid_argument("Malformed Condition type"))
@@ -368,6 +810,11 @@ impl From<segment::types::Condition> for Condition {
             }
             segment::types::Condition::HasId(has_id) => ConditionOneOf::HasId(has_id.into()),
             segment::types::Condition::Filter(filter) => ConditionOneOf::Filter(filter.into()),
+            segment::types::Condition::Nested(nested) => ConditionOneOf::Nested(nested.into()),
+            segment::types::Condition::IsNull(is_null) => ConditionOneOf::IsNull(is_null.into()),
+            segment::types::Condition::HasField(has_field) => {
+                ConditionOneOf::HasField(has_field.into())
+            }
         };
 
         Self {
@@ -376,6 +823,31 @@ impl From<segment::types::Condition> for Condition {
     }
 }
 
+impl TryFrom<NestedCondition> for segment::types::NestedCondition {
+    type Error = Status;
+
+    fn try_from(value: NestedCondition) -> Result<Self, Self::Error> {
+        match value.filter {
+            Some(filter) => Ok(segment::types::NestedCondition::new(
+                segment::types::Nested {
+                    key: value.key,
+                    filter: filter.try_into()?,
+                },
+            )),
+            None => Err(Status::invalid_argument("Malformed NestedCondition type")),
+        }
+    }
+}
+
+impl From<segment::types::NestedCondition> for NestedCondition {
+    fn from(value: segment::types::NestedCondition) -> Self {
+        Self {
+            key: value.nested.key,
+            filter: Some(value.nested.filter.into()),
+        }
+    }
+}
+
 impl From<IsEmptyCondition> for segment::types::IsEmptyCondition {
     fn from(value: IsEmptyCondition) -> Self {
         segment::types::IsEmptyCondition {
@@ -392,6 +864,38 @@ impl From<segment::types::IsEmptyCondition> for IsEmptyCondition {
     }
 }
 
+impl From<IsNullCondition> for segment::types::IsNullCondition {
+    fn from(value: IsNullCondition) -> Self {
+        segment::types::IsNullCondition {
+            is_null: segment::types::PayloadField { key: value.key },
+        }
+    }
+}
+
+impl From<segment::types::IsNullCondition> for IsNullCondition {
+    fn from(value: segment::types::IsNullCondition) -> Self {
+        Self {
+            key: value.is_null.key,
+        }
+    }
+}
+
+impl From<HasFieldCondition> for segment::types::HasFieldCondition {
+    fn from(value: HasFieldCondition) -> Self {
+        segment::types::HasFieldCondition {
+            has_field: segment::types::PayloadField { key: value.key },
+        }
+    }
+}
+
+impl From<segment::types::HasFieldCondition> for HasFieldCondition {
+    fn from(value: segment::types::HasFieldCondition) -> Self {
+        Self {
+            key: value.has_field.key,
+        }
+    }
+}
+
 impl TryFrom<HasIdCondition> for segment::types::HasIdCondition {
     type Error = Status;
 
@@ -423,17 +927,31 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             geo_bounding_box,
             geo_radius,
             values_count,
+            datetime_range,
+            geo_polygon,
         } = value;
 
         let geo_bounding_box =
             geo_bounding_box.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
         let geo_radius = geo_radius.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
+        let geo_polygon = geo_polygon.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
+        let range = match (range, datetime_range) {
+            (Some(_), Some(_)) => {
+                return Err(Status::invalid_argument(
+                    "Only one of range and datetime_range can be specified",
+                ))
+            }
+            (Some(range), None) => Some(range.into()),
+            (None, Some(datetime_range)) => Some(datetime_range.into()),
+            (None, None) => None,
+        };
         Ok(Self {
             key,
             r#match: r#match.map_or_else(|| Ok(None), |m| m.try_into().map(Some))?,
-            range: range.map(|r| r.into()),
+            range,
             geo_bounding_box,
             geo_radius,
+            geo_polygon,
             values_count: values_count.map(|r| r.into()),
         })
     }
@@ -447,11 +965,13 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             range,
             geo_bounding_box,
             geo_radius,
+            geo_polygon,
             values_count,
         } = value;
 
         let geo_bounding_box = geo_bounding_box.map(|g| g.into());
         let geo_radius = geo_radius.map(|g| g.into());
+        let geo_polygon = geo_polygon.map(|g| g.into());
         Self {
             key,
             r#match: r#match.map(|m| m.into()),
@@ -459,6 +979,8 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             geo_bounding_box,
             geo_radius,
             values_count: values_count.map(|r| r.into()),
+            datetime_range: None,
+            geo_polygon,
         }
     }
 }
@@ -515,6 +1037,59 @@ impl From<segment::types::GeoRadius> for GeoRadius {
     }
 }
 
+impl From<GeoLineString> for segment::types::GeoLineString {
+    fn from(value: GeoLineString) -> Self {
+        Self {
+            points: value.points.into_iter().map(|p| p.into()).collect(),
+        }
+    }
+}
+
+impl From<segment::types::GeoLineString> for GeoLineString {
+    fn from(value: segment::types::GeoLineString) -> Self {
+        Self {
+            points: value.points.into_iter().map(|p| p.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<GeoPolygon> for segment::types::GeoPolygon {
+    type Error = Status;
+
+    fn try_from(value: GeoPolygon) -> Result<Self, Self::Error> {
+        match value {
+            GeoPolygon {
+                exterior: Some(e),
+                interiors,
+            } => {
+                let polygon = Self {
+                    exterior: e.into(),
+                    interiors: Some(interiors.into_iter().map(|l| l.into()).collect()),
+                };
+                polygon
+                    .validate()
+                    .map_err(|e| Status::invalid_argument(e.to_string()))?;
+                Ok(polygon)
+            }
+            _ => Err(Status::invalid_argument("Malformed GeoPolygon type")),
+        }
+    }
+}
+
+impl From<segment::types::GeoPolygon> for GeoPolygon {
+    fn from(value: segment::types::GeoPolygon) -> Self {
+        Self {
+            exterior: Some(value.exterior.into()),
+            interiors: value
+                .interiors
+                .into_iter()
+                .flatten()
+                .map(|l| l.into())
+                .collect(),
+        }
+    }
+}
+
 impl From<GeoPoint> for segment::types::GeoPoint {
     fn from(value: GeoPoint) -> Self {
         Self {
@@ -546,6 +1121,320 @@ impl From<segment::types::Range> for Range {
     }
 }
 
+fn timestamp_to_seconds(timestamp: prost_types::Timestamp) -> f64 {
+    timestamp.seconds as f64 + timestamp.nanos as f64 / 1_000_000_000.0
+}
+
+impl From<segment::types::OrderBy> for OrderBy {
+    fn from(value: segment::types::OrderBy) -> Self {
+        let direction = match value.direction {
+            segment::types::Direction::Asc => Direction::Asc,
+            segment::types::Direction::Desc => Direction::Desc,
+        };
+        Self {
+            key: value.key,
+            direction: Some(direction as i32),
+            start_from: value.start_from.map(StartFrom::Float),
+        }
+    }
+}
+
+impl TryFrom<OrderBy> for segment::types::OrderBy {
+    type Error = Status;
+
+    fn try_from(value: OrderBy) -> Result<Self, Self::Error> {
+        let direction = match value.direction.map(Direction::from_i32) {
+            None | Some(Some(Direction::Asc)) => segment::types::Direction::Asc,
+            Some(Some(Direction::Desc)) => segment::types::Direction::Desc,
+            Some(None) => return Err(Status::invalid_argument("Unknown order direction")),
+        };
+        Ok(Self {
+            key: value.key,
+            direction,
+            start_from: value.start_from.map(|start_from| match start_from {
+                StartFrom::Float(value) => value,
+                StartFrom::Datetime(timestamp) => timestamp_to_seconds(timestamp),
+            }),
+        })
+    }
+}
+
+impl From<segment::types::FacetValue> for FacetValue {
+    fn from(value: segment::types::FacetValue) -> Self {
+        let variant = match value {
+            segment::types::FacetValue::Keyword(keyword) => FacetVariant::Keyword(keyword),
+            segment::types::FacetValue::Integer(integer) => FacetVariant::Integer(integer),
+        };
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<FacetValue> for segment::types::FacetValue {
+    type Error = Status;
+
+    fn try_from(value: FacetValue) -> Result<Self, Self::Error> {
+        match value.variant {
+            Some(FacetVariant::Keyword(keyword)) => Ok(Self::Keyword(keyword)),
+            Some(FacetVariant::Integer(integer)) => Ok(Self::Integer(integer)),
+            None => Err(Status::invalid_argument("Malformed FacetValue type")),
+        }
+    }
+}
+
+impl From<segment::types::FacetValueHit> for FacetValueHit {
+    fn from(hit: segment::types::FacetValueHit) -> Self {
+        Self {
+            value: Some(hit.value.into()),
+            count: hit.count as u64,
+        }
+    }
+}
+
+impl TryFrom<FacetValueHit> for segment::types::FacetValueHit {
+    type Error = Status;
+
+    fn try_from(hit: FacetValueHit) -> Result<Self, Self::Error> {
+        let value = hit
+            .value
+            .ok_or_else(|| Status::invalid_argument("Malformed FacetValueHit type"))?;
+        Ok(Self {
+            value: value.try_into()?,
+            count: hit.count as usize,
+        })
+    }
+}
+
+impl From<segment::types::AggregationValue> for AggregationValue {
+    fn from(value: segment::types::AggregationValue) -> Self {
+        let value = match value {
+            segment::types::AggregationValue::Number(number) => {
+                aggregation_value::Value::Number(number)
+            }
+            segment::types::AggregationValue::Datetime(datetime) => {
+                aggregation_value::Value::Datetime(datetime.to_rfc3339())
+            }
+        };
+        Self { value: Some(value) }
+    }
+}
+
+impl TryFrom<AggregationValue> for segment::types::AggregationValue {
+    type Error = Status;
+
+    fn try_from(value: AggregationValue) -> Result<Self, Self::Error> {
+        match value.value {
+            Some(aggregation_value::Value::Number(number)) => {
+                Ok(segment::types::AggregationValue::Number(number))
+            }
+            Some(aggregation_value::Value::Datetime(datetime)) => {
+                let datetime = DateTime::parse_from_rfc3339(&datetime).map_err(|err| {
+                    Status::invalid_argument(format!("Malformed aggregation value: {}", err))
+                })?;
+                Ok(segment::types::AggregationValue::Datetime(
+                    datetime.with_timezone(&Utc),
+                ))
+            }
+            None => Err(Status::invalid_argument("Aggregation value is missing")),
+        }
+    }
+}
+
+impl From<segment::types::HistogramBucket> for HistogramBucket {
+    fn from(bucket: segment::types::HistogramBucket) -> Self {
+        Self {
+            from: Some(bucket.from.into()),
+            to: Some(bucket.to.into()),
+            count: bucket.count as u64,
+        }
+    }
+}
+
+impl TryFrom<HistogramBucket> for segment::types::HistogramBucket {
+    type Error = Status;
+
+    fn try_from(bucket: HistogramBucket) -> Result<Self, Self::Error> {
+        let bound = |value: Option<AggregationValue>| {
+            value
+                .ok_or_else(|| Status::invalid_argument("Histogram bucket bound is missing"))?
+                .try_into()
+        };
+        Ok(Self {
+            from: bound(bucket.from)?,
+            to: bound(bucket.to)?,
+            count: bucket.count as usize,
+        })
+    }
+}
+
+impl From<segment::types::NumericAggregation> for NumericAggregation {
+    fn from(aggregation: segment::types::NumericAggregation) -> Self {
+        Self {
+            count: aggregation.count as u64,
+            min: aggregation.min.map(|value| value.into()),
+            max: aggregation.max.map(|value| value.into()),
+            sum: aggregation.sum,
+            avg: aggregation.avg.map(|value| value.into()),
+            histogram: aggregation
+                .histogram
+                .unwrap_or_default()
+                .into_iter()
+                .map(|bucket| bucket.into())
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<NumericAggregation> for segment::types::NumericAggregation {
+    type Error = Status;
+
+    /// Empty histogram is indistinguishable from the not requested one
+    fn try_from(aggregation: NumericAggregation) -> Result<Self, Self::Error> {
+        let histogram = if aggregation.histogram.is_empty() {
+            None
+        } else {
+            Some(
+                aggregation
+                    .histogram
+                    .into_iter()
+                    .map(|bucket| bucket.try_into())
+                    .collect::<Result<_, _>>()?,
+            )
+        };
+        Ok(Self {
+            count: aggregation.count as usize,
+            min: aggregation.min.map(|value| value.try_into()).transpose()?,
+            max: aggregation.max.map(|value| value.try_into()).transpose()?,
+            sum: aggregation.sum,
+            avg: aggregation.avg.map(|value| value.try_into()).transpose()?,
+            histogram,
+        })
+    }
+}
+
+impl From<segment::types::QuantileValue> for QuantileValue {
+    fn from(value: segment::types::QuantileValue) -> Self {
+        Self {
+            quantile: value.quantile,
+            value: value.value,
+        }
+    }
+}
+
+impl From<QuantileValue> for segment::types::QuantileValue {
+    fn from(value: QuantileValue) -> Self {
+        Self {
+            quantile: value.quantile,
+            value: value.value,
+        }
+    }
+}
+
+impl From<segment::types::NumericIndexStats> for NumericIndexStats {
+    fn from(stats: segment::types::NumericIndexStats) -> Self {
+        Self {
+            values_count: stats.values_count as u64,
+            min: stats.min,
+            max: stats.max,
+            quantiles: stats.quantiles.into_iter().map(|q| q.into()).collect(),
+        }
+    }
+}
+
+impl From<NumericIndexStats> for segment::types::NumericIndexStats {
+    fn from(stats: NumericIndexStats) -> Self {
+        Self {
+            values_count: stats.values_count as usize,
+            min: stats.min,
+            max: stats.max,
+            quantiles: stats.quantiles.into_iter().map(|q| q.into()).collect(),
+        }
+    }
+}
+
+impl From<segment::types::PayloadIndexStats> for PayloadIndexStats {
+    fn from(stats: segment::types::PayloadIndexStats) -> Self {
+        Self {
+            data_type: PayloadSchemaType::from(stats.data_type).into(),
+            points_count: stats.points_count as u64,
+            distinct_values: stats.distinct_values.map(|count| count as u64),
+            top_values: stats
+                .top_values
+                .unwrap_or_default()
+                .into_iter()
+                .map(|hit| hit.into())
+                .collect(),
+            numeric: stats.numeric.map(|numeric| numeric.into()),
+            ram_usage_bytes: stats.ram_usage_bytes as u64,
+            disk_usage_bytes: stats.disk_usage_bytes as u64,
+        }
+    }
+}
+
+impl TryFrom<PayloadIndexStats> for segment::types::PayloadIndexStats {
+    type Error = Status;
+
+    /// Empty list of top values is indistinguishable from the not supported one
+    fn try_from(stats: PayloadIndexStats) -> Result<Self, Self::Error> {
+        let data_type = segment::types::PayloadSchemaType::from_index(stats.data_type)
+            .ok_or_else(|| Status::invalid_argument("Malformed PayloadSchemaType type"))?;
+        let top_values = if stats.top_values.is_empty() {
+            None
+        } else {
+            Some(
+                stats
+                    .top_values
+                    .into_iter()
+                    .map(|hit| hit.try_into())
+                    .collect::<Result<_, _>>()?,
+            )
+        };
+        Ok(Self {
+            data_type,
+            points_count: stats.points_count as usize,
+            distinct_values: stats.distinct_values.map(|count| count as usize),
+            top_values,
+            numeric: stats.numeric.map(|numeric| numeric.into()),
+            ram_usage_bytes: stats.ram_usage_bytes as usize,
+            disk_usage_bytes: stats.disk_usage_bytes as usize,
+        })
+    }
+}
+
+impl From<KeywordRange> for segment::types::KeywordRange {
+    fn from(value: KeywordRange) -> Self {
+        Self {
+            lt: value.lt,
+            gt: value.gt,
+            gte: value.gte,
+            lte: value.lte,
+        }
+    }
+}
+
+impl From<segment::types::KeywordRange> for KeywordRange {
+    fn from(value: segment::types::KeywordRange) -> Self {
+        Self {
+            lt: value.lt,
+            gt: value.gt,
+            gte: value.gte,
+            lte: value.lte,
+        }
+    }
+}
+
+impl From<DatetimeRange> for segment::types::Range {
+    fn from(value: DatetimeRange) -> Self {
+        Self {
+            lt: value.lt.map(timestamp_to_seconds),
+            gt: value.gt.map(timestamp_to_seconds),
+            gte: value.gte.map(timestamp_to_seconds),
+            lte: value.lte.map(timestamp_to_seconds),
+        }
+    }
+}
+
 impl From<ValuesCount> for segment::types::ValuesCount {
     fn from(value: ValuesCount) -> Self {
         Self {
@@ -577,6 +1466,48 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::Keyword(kw) => kw.into(),
                 MatchValue::Integer(int) => int.into(),
                 MatchValue::Boolean(flag) => flag.into(),
+                MatchValue::Text(text) => {
+                    segment::types::Match::Text(segment::types::MatchText { text })
+                }
+                MatchValue::Keywords(kwds) => {
+                    segment::types::Match::Any(segment::types::MatchAny {
+                        any: segment::types::AnyVariants::Keywords(
+                            kwds.strings.into_iter().collect(),
+                        ),
+                    })
+                }
+                MatchValue::Integers(ints) => {
+                    segment::types::Match::Any(segment::types::MatchAny {
+                        any: segment::types::AnyVariants::Integers(
+                            ints.integers.into_iter().collect(),
+                        ),
+                    })
+                }
+                MatchValue::ExceptKeywords(kwds) => {
+                    segment::types::Match::Except(segment::types::MatchExcept {
+                        except: segment::types::AnyVariants::Keywords(
+                            kwds.strings.into_iter().collect(),
+                        ),
+                    })
+                }
+                MatchValue::ExceptIntegers(ints) => {
+                    segment::types::Match::Except(segment::types::MatchExcept {
+                        except: segment::types::AnyVariants::Integers(
+                            ints.integers.into_iter().collect(),
+                        ),
+                    })
+                }
+                MatchValue::Prefix(prefix) => {
+                    segment::types::Match::Prefix(segment::types::MatchPrefix { prefix })
+                }
+                MatchValue::KeywordRange(range) => {
+                    segment::types::Match::KeywordRange(segment::types::MatchKeywordRange {
+                        keyword_range: range.into(),
+                    })
+                }
+                MatchValue::Wildcard(wildcard) => {
+                    segment::types::Match::Wildcard(segment::types::MatchWildcard { wildcard })
+                }
             }),
             _ => Err(Status::invalid_argument("Malformed Match condition")),
         }
@@ -592,6 +1523,42 @@ impl From<segment::types::Match> for Match {
                 segment::types::ValueVariants::Integer(int) => MatchValue::Integer(int),
                 segment::types::ValueVariants::Bool(flag) => MatchValue::Boolean(flag),
             },
+            segment::types::Match::Text(segment::types::MatchText { text }) => {
+                MatchValue::Text(text)
+            }
+            segment::types::Match::Any(any) => match any.any {
+                segment::types::AnyVariants::Keywords(strings) => {
+                    MatchValue::Keywords(RepeatedStrings {
+                        strings: strings.into_iter().collect(),
+                    })
+                }
+                segment::types::AnyVariants::Integers(integers) => {
+                    MatchValue::Integers(RepeatedIntegers {
+                        integers: integers.into_iter().collect(),
+                    })
+                }
+            },
+            segment::types::Match::Except(except) => match except.except {
+                segment::types::AnyVariants::Keywords(strings) => {
+                    MatchValue::ExceptKeywords(RepeatedStrings {
+                        strings: strings.into_iter().collect(),
+                    })
+                }
+                segment::types::AnyVariants::Integers(integers) => {
+                    MatchValue::ExceptIntegers(RepeatedIntegers {
+                        integers: integers.into_iter().collect(),
+                    })
+                }
+            },
+            segment::types::Match::Prefix(segment::types::MatchPrefix { prefix }) => {
+                MatchValue::Prefix(prefix)
+            }
+            segment::types::Match::KeywordRange(segment::types::MatchKeywordRange {
+                keyword_range,
+            }) => MatchValue::KeywordRange(keyword_range.into()),
+            segment::types::Match::Wildcard(segment::types::MatchWildcard { wildcard }) => {
+                MatchValue::Wildcard(wildcard)
+            }
             segment::types::Match::Keyword(kw) => MatchValue::Keyword(kw.keyword),
             segment::types::Match::Integer(int) => MatchValue::Integer(int.integer),
         };
@@ -149,6 +149,9 @@ pub struct CreateCollection {
     /// Wait timeout for operation commit in seconds, if not specified - default value will be supplied
     #[prost(uint64, optional, tag="9")]
     pub timeout: ::core::option::Option<u64>,
+    /// If set - payloads, which do not match the schema, are rejected
+    #[prost(message, optional, tag="10")]
+    pub payload_schema: ::core::option::Option<PayloadSchema>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateCollection {
@@ -194,6 +197,27 @@ pub struct CollectionParams {
     /// If true - point's payload will not be stored in memory
     #[prost(bool, tag="4")]
     pub on_disk_payload: bool,
+    /// Schema of the point payloads
+    #[prost(message, optional, tag="5")]
+    pub payload_schema: ::core::option::Option<PayloadSchema>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadSchemaField {
+    /// Type of the field value
+    #[prost(enumeration="PayloadSchemaType", tag="1")]
+    pub r#type: i32,
+    /// If true - each point must have a non-null value of this field
+    #[prost(bool, tag="2")]
+    pub required: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadSchema {
+    /// Declared fields, nested fields are addressed by dot-separated paths
+    #[prost(map="string, message", tag="1")]
+    pub fields: ::std::collections::HashMap<::prost::alloc::string::String, PayloadSchemaField>,
+    /// If false - payloads with undeclared fields are rejected, default = true
+    #[prost(bool, optional, tag="2")]
+    pub allow_unknown_fields: ::core::option::Option<bool>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CollectionConfig {
@@ -211,10 +235,105 @@ pub struct CollectionConfig {
     pub wal_config: ::core::option::Option<WalConfigDiff>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextIndexParams {
+    /// Tokenizer type
+    #[prost(enumeration="TokenizerType", tag="1")]
+    pub tokenizer: i32,
+    /// If true - all tokens will be lowercase
+    #[prost(bool, optional, tag="2")]
+    pub lowercase: ::core::option::Option<bool>,
+    /// Minimal token length
+    #[prost(uint64, optional, tag="3")]
+    pub min_token_len: ::core::option::Option<u64>,
+    /// Maximal token length
+    #[prost(uint64, optional, tag="4")]
+    pub max_token_len: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeywordIndexParams {
+    /// If true - store index on disk in non-appendable segments
+    #[prost(bool, optional, tag="1")]
+    pub on_disk: ::core::option::Option<bool>,
+    /// If true - the field identifies a tenant, points of each tenant are stored and indexed together
+    #[prost(bool, optional, tag="2")]
+    pub is_tenant: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IntegerIndexParams {
+    /// If true - store index on disk in non-appendable segments
+    #[prost(bool, optional, tag="1")]
+    pub on_disk: ::core::option::Option<bool>,
+    /// If true - support direct lookups. Default: true
+    #[prost(bool, optional, tag="2")]
+    pub lookup: ::core::option::Option<bool>,
+    /// If true - support ranges filters. Default: true
+    #[prost(bool, optional, tag="3")]
+    pub range: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FloatIndexParams {
+    /// If true - store index on disk in non-appendable segments
+    #[prost(bool, optional, tag="1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoIndexParams {
+    /// If true - store index on disk in non-appendable segments
+    #[prost(bool, optional, tag="1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatetimeIndexParams {
+    /// If true - store index on disk in non-appendable segments
+    #[prost(bool, optional, tag="1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UuidIndexParams {
+    /// If true - store index on disk in non-appendable segments
+    #[prost(bool, optional, tag="1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadIndexParams {
+    #[prost(oneof="payload_index_params::IndexParams", tags="1, 2, 3, 4, 5, 6, 7")]
+    pub index_params: ::core::option::Option<payload_index_params::IndexParams>,
+}
+/// Nested message and enum types in `PayloadIndexParams`.
+pub mod payload_index_params {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum IndexParams {
+        /// Parameters for text index
+        #[prost(message, tag="1")]
+        TextIndexParams(super::TextIndexParams),
+        /// Parameters for keyword index
+        #[prost(message, tag="2")]
+        KeywordIndexParams(super::KeywordIndexParams),
+        /// Parameters for integer index
+        #[prost(message, tag="3")]
+        IntegerIndexParams(super::IntegerIndexParams),
+        /// Parameters for float index
+        #[prost(message, tag="4")]
+        FloatIndexParams(super::FloatIndexParams),
+        /// Parameters for geo index
+        #[prost(message, tag="5")]
+        GeoIndexParams(super::GeoIndexParams),
+        /// Parameters for datetime index
+        #[prost(message, tag="6")]
+        DatetimeIndexParams(super::DatetimeIndexParams),
+        /// Parameters for UUID index
+        #[prost(message, tag="7")]
+        UuidIndexParams(super::UuidIndexParams),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PayloadSchemaInfo {
     /// Field data type
     #[prost(enumeration="PayloadSchemaType", tag="1")]
     pub data_type: i32,
+    /// Field index parameters
+    #[prost(message, optional, tag="2")]
+    pub params: ::core::option::Option<PayloadIndexParams>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CollectionInfo {
@@ -323,6 +442,18 @@ pub enum PayloadSchemaType {
     Integer = 2,
     Float = 3,
     Geo = 4,
+    Text = 5,
+    Datetime = 6,
+    Bool = 7,
+    Uuid = 8,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TokenizerType {
+    Unknown = 0,
+    Prefix = 1,
+    Whitespace = 2,
+    Word = 3,
 }
 /// Generated client implementations.
 pub mod collections_client {
@@ -1329,9 +1460,15 @@ pub struct SetPayloadPoints {
     /// New payload values
     #[prost(map="string, message", tag="3")]
     pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
-    /// List of point to modify
+    /// List of point to modify, ignored if `points_selector` is set
     #[prost(message, repeated, tag="4")]
     pub points: ::prost::alloc::vec::Vec<PointId>,
+    /// If set - payload is merged into the nested object located by this path
+    #[prost(string, optional, tag="5")]
+    pub key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Affected points, selected by ids or by filter
+    #[prost(message, optional, tag="6")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeletePayloadPoints {
@@ -1341,12 +1478,15 @@ pub struct DeletePayloadPoints {
     /// Wait until the changes have been applied?
     #[prost(bool, optional, tag="2")]
     pub wait: ::core::option::Option<bool>,
-    /// List of keys to delete
+    /// List of keys to delete, nested paths like `a.b\[2\].c` are supported
     #[prost(string, repeated, tag="3")]
     pub keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
-    /// Affected points
+    /// Affected points, ignored if `points_selector` is set
     #[prost(message, repeated, tag="4")]
     pub points: ::prost::alloc::vec::Vec<PointId>,
+    /// Affected points, selected by ids or by filter
+    #[prost(message, optional, tag="5")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ClearPayloadPoints {
@@ -1361,6 +1501,59 @@ pub struct ClearPayloadPoints {
     pub points: ::core::option::Option<PointsSelector>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadIncrement {
+    /// Numeric field, nested keys like `a.b` are supported
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    /// Number to add, negative value decrements the field
+    #[prost(message, optional, tag="2")]
+    pub value: ::core::option::Option<Value>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadArrayValues {
+    /// Array field, nested keys like `a.b` are supported
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    /// Values to append or remove
+    #[prost(message, repeated, tag="2")]
+    pub values: ::prost::alloc::vec::Vec<Value>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadModification {
+    #[prost(oneof="payload_modification::Modification", tags="1, 2, 3")]
+    pub modification: ::core::option::Option<payload_modification::Modification>,
+}
+/// Nested message and enum types in `PayloadModification`.
+pub mod payload_modification {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Modification {
+        /// Add number to the numeric field, missing field is treated as 0
+        #[prost(message, tag="1")]
+        Increment(super::PayloadIncrement),
+        /// Append values to the end of the array field
+        #[prost(message, tag="2")]
+        Append(super::PayloadArrayValues),
+        /// Remove all occurrences of values from the array field
+        #[prost(message, tag="3")]
+        Remove(super::PayloadArrayValues),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ModifyPayloadPoints {
+    /// name of the collection
+    #[prost(string, tag="1")]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag="2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Modifications applied to each point in the given order
+    #[prost(message, repeated, tag="3")]
+    pub modifications: ::prost::alloc::vec::Vec<PayloadModification>,
+    /// Affected points
+    #[prost(message, optional, tag="4")]
+    pub points: ::core::option::Option<PointsSelector>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateFieldIndexCollection {
     /// name of the collection
     #[prost(string, tag="1")]
@@ -1374,6 +1567,9 @@ pub struct CreateFieldIndexCollection {
     /// Field type.
     #[prost(enumeration="FieldType", optional, tag="4")]
     pub field_type: ::core::option::Option<i32>,
+    /// Payload index params.
+    #[prost(message, optional, tag="5")]
+    pub field_index_params: ::core::option::Option<PayloadIndexParams>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteFieldIndexCollection {
@@ -1426,6 +1622,93 @@ pub struct SearchParams {
     pub hnsw_ef: ::core::option::Option<u64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadVariable {
+    /// Payload field to read the value from
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    /// Value to use if the point has no numeric value in the field
+    #[prost(double, optional, tag="2")]
+    pub default: ::core::option::Option<f64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecayParams {
+    /// Payload field with the numeric value or datetime in RFC 3339 format
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    /// Distance from the origin, at which the decay is equal to `midpoint`. For datetime fields - number of seconds
+    #[prost(double, tag="4")]
+    pub scale: f64,
+    /// Decay at the `scale` distance from the origin, in range (0, 1). Default: 0.5
+    #[prost(double, optional, tag="5")]
+    pub midpoint: ::core::option::Option<f64>,
+    #[prost(oneof="decay_params::Origin", tags="2, 3")]
+    pub origin: ::core::option::Option<decay_params::Origin>,
+}
+/// Nested message and enum types in `DecayParams`.
+pub mod decay_params {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Origin {
+        /// Numeric origin
+        #[prost(double, tag="2")]
+        Number(f64),
+        /// Datetime origin in RFC 3339 format
+        #[prost(string, tag="3")]
+        Datetime(::prost::alloc::string::String),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScoreExpressionList {
+    #[prost(message, repeated, tag="1")]
+    pub expressions: ::prost::alloc::vec::Vec<ScoreExpression>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScoreExpression {
+    #[prost(oneof="score_expression::Variant", tags="1, 2, 3, 4, 5, 6, 7, 8, 9")]
+    pub variant: ::core::option::Option<score_expression::Variant>,
+}
+/// Nested message and enum types in `ScoreExpression`.
+pub mod score_expression {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Constant value
+        #[prost(double, tag="1")]
+        Constant(f64),
+        /// Similarity score of the point. For Euclid distance smaller is better, while the expression is maximized
+        #[prost(bool, tag="2")]
+        Score(bool),
+        /// Numeric value of the payload field
+        #[prost(message, tag="3")]
+        Payload(super::PayloadVariable),
+        /// Product of the expressions
+        #[prost(message, tag="4")]
+        Mult(super::ScoreExpressionList),
+        /// Sum of the expressions
+        #[prost(message, tag="5")]
+        Sum(super::ScoreExpressionList),
+        /// Natural logarithm of the expression
+        #[prost(message, tag="6")]
+        Ln(::prost::alloc::boxed::Box<super::ScoreExpression>),
+        /// Linear decay
+        #[prost(message, tag="7")]
+        LinDecay(super::DecayParams),
+        /// Exponential decay
+        #[prost(message, tag="8")]
+        ExpDecay(super::DecayParams),
+        /// Gaussian decay
+        #[prost(message, tag="9")]
+        GaussDecay(super::DecayParams),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Rescore {
+    /// Expression which defines the final score of the point
+    #[prost(message, optional, tag="1")]
+    pub expression: ::core::option::Option<ScoreExpression>,
+    /// How many times more candidates to take from the vector search
+    #[prost(uint64, optional, tag="2")]
+    pub oversampling: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchPoints {
     /// name of the collection
     #[prost(string, tag="1")]
@@ -1454,6 +1737,90 @@ pub struct SearchPoints {
     /// Offset of the result
     #[prost(uint64, optional, tag="9")]
     pub offset: ::core::option::Option<u64>,
+    /// Recompute score of the found points using payload values
+    #[prost(message, optional, tag="10")]
+    pub rescore: ::core::option::Option<Rescore>,
+    /// If `true` - return explanation of how the search was executed
+    #[prost(bool, optional, tag="11")]
+    pub explain: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FusionSearch {
+    /// vector
+    #[prost(float, repeated, tag="1")]
+    pub vector: ::prost::alloc::vec::Vec<f32>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag="2")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Search config
+    #[prost(message, optional, tag="3")]
+    pub params: ::core::option::Option<SearchParams>,
+    /// Max number of candidates this search contributes to the fusion
+    #[prost(uint64, optional, tag="4")]
+    pub limit: ::core::option::Option<u64>,
+    /// If provided - cut off candidates with worse scores
+    #[prost(float, optional, tag="5")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Weight of this search in the fused score
+    #[prost(float, optional, tag="6")]
+    pub weight: ::core::option::Option<f32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchFusionPoints {
+    /// name of the collection
+    #[prost(string, tag="1")]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Searches, results of which should be merged
+    #[prost(message, repeated, tag="2")]
+    pub searches: ::prost::alloc::vec::Vec<FusionSearch>,
+    /// Method of merging the results
+    #[prost(enumeration="Fusion", optional, tag="3")]
+    pub fusion: ::core::option::Option<i32>,
+    /// Max number of result
+    #[prost(uint64, tag="4")]
+    pub limit: u64,
+    /// Offset of the result
+    #[prost(uint64, optional, tag="5")]
+    pub offset: ::core::option::Option<u64>,
+    /// Return point vector with the result.
+    #[prost(bool, optional, tag="6")]
+    pub with_vector: ::core::option::Option<bool>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag="7")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchCollections {
+    /// Names or aliases of the collections to search in
+    #[prost(string, repeated, tag="1")]
+    pub collection_names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// vector
+    #[prost(float, repeated, tag="2")]
+    pub vector: ::prost::alloc::vec::Vec<f32>,
+    /// Filter conditions - return only those points that satisfy the specified conditions
+    #[prost(message, optional, tag="3")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result
+    #[prost(uint64, tag="4")]
+    pub limit: u64,
+    /// Return point vector with the result.
+    #[prost(bool, optional, tag="5")]
+    pub with_vector: ::core::option::Option<bool>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag="6")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Search config
+    #[prost(message, optional, tag="7")]
+    pub params: ::core::option::Option<SearchParams>,
+    /// If provided - cut off results with worse scores
+    #[prost(float, optional, tag="8")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Offset of the result
+    #[prost(uint64, optional, tag="9")]
+    pub offset: ::core::option::Option<u64>,
+    /// Recompute score of the found points using payload values
+    #[prost(message, optional, tag="10")]
+    pub rescore: ::core::option::Option<Rescore>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScrollPoints {
@@ -1474,6 +1841,32 @@ pub struct ScrollPoints {
     /// Options for specifying which payload to include or not
     #[prost(message, optional, tag="6")]
     pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Order the points by the value of the indexed payload field instead of the ID
+    #[prost(message, optional, tag="7")]
+    pub order_by: ::core::option::Option<OrderBy>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderBy {
+    /// Payload key to order by, must have an integer, float or datetime index
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    /// Direction of ordering. Default: Asc
+    #[prost(enumeration="Direction", optional, tag="2")]
+    pub direction: ::core::option::Option<i32>,
+    /// Order value to start from, including. Together with the `offset` forms a cursor for the pagination
+    #[prost(oneof="order_by::StartFrom", tags="3, 4")]
+    pub start_from: ::core::option::Option<order_by::StartFrom>,
+}
+/// Nested message and enum types in `OrderBy`.
+pub mod order_by {
+    /// Order value to start from, including. Together with the `offset` forms a cursor for the pagination
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum StartFrom {
+        #[prost(double, tag="3")]
+        Float(f64),
+        #[prost(message, tag="4")]
+        Datetime(::prost_types::Timestamp),
+    }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RecommendPoints {
@@ -1507,6 +1900,9 @@ pub struct RecommendPoints {
     /// Offset of the result
     #[prost(uint64, optional, tag="10")]
     pub offset: ::core::option::Option<u64>,
+    /// If `true` - return explanation of how the search was executed
+    #[prost(bool, optional, tag="11")]
+    pub explain: ::core::option::Option<bool>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CountPoints {
@@ -1519,6 +1915,48 @@ pub struct CountPoints {
     /// If `true` - return exact count, if `false` - return approximate count
     #[prost(bool, optional, tag="3")]
     pub exact: ::core::option::Option<bool>,
+    /// If `true` - return explanation of how the points were counted
+    #[prost(bool, optional, tag="4")]
+    pub explain: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetPoints {
+    /// name of the collection
+    #[prost(string, tag="1")]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload key to count values of, must have a keyword or integer index
+    #[prost(string, tag="2")]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - count values only of the points which satisfy the specified conditions
+    #[prost(message, optional, tag="3")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of values to return, the most frequent values first. Default: 10
+    #[prost(uint64, optional, tag="4")]
+    pub limit: ::core::option::Option<u64>,
+    /// If `true` - return exact counts, if `false` - estimate counts of the filtered points. Default: false
+    #[prost(bool, optional, tag="5")]
+    pub exact: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregatePoints {
+    /// name of the collection
+    #[prost(string, tag="1")]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload key of the integer, float or datetime field to aggregate
+    #[prost(string, tag="2")]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - aggregate values only of the points which satisfy the specified conditions
+    #[prost(message, optional, tag="3")]
+    pub filter: ::core::option::Option<Filter>,
+    /// If set - also count values in buckets of this width
+    #[prost(double, optional, tag="4")]
+    pub histogram_interval: ::core::option::Option<f64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadIndexStatsRequest {
+    /// name of the collection
+    #[prost(string, tag="1")]
+    pub collection_name: ::prost::alloc::string::String,
 }
 // ---------------------------------------------
 // ---------------- RPC Response ---------------
@@ -1560,12 +1998,74 @@ pub struct ScoredPoint {
     pub version: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FilterCardinality {
+    /// Minimal possible number of matching points
+    #[prost(uint64, tag="1")]
+    pub min: u64,
+    /// Expected number of matching points
+    #[prost(uint64, tag="2")]
+    pub exp: u64,
+    /// Maximal possible number of matching points
+    #[prost(uint64, tag="3")]
+    pub max: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SegmentExplain {
+    /// Id of the segment within the shard
+    #[prost(uint64, tag="1")]
+    pub segment_id: u64,
+    /// Estimated number of points which satisfy the filter
+    #[prost(message, optional, tag="2")]
+    pub cardinality: ::core::option::Option<FilterCardinality>,
+    /// Method of processing the query, absent if the segment was skipped
+    #[prost(enumeration="QueryStrategy", optional, tag="3")]
+    pub strategy: ::core::option::Option<i32>,
+    /// Number of computed distances
+    #[prost(uint64, tag="4")]
+    pub distance_computations: u64,
+    /// Number of points considered during the query
+    #[prost(uint64, tag="5")]
+    pub visited_nodes: u64,
+    /// Time spent in the segment
+    #[prost(double, tag="6")]
+    pub time: f64,
+    /// True, if the segment can't contain matching points
+    #[prost(bool, tag="7")]
+    pub skipped: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardExplain {
+    #[prost(uint32, tag="1")]
+    pub shard_id: u32,
+    #[prost(message, repeated, tag="2")]
+    pub segments: ::prost::alloc::vec::Vec<SegmentExplain>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchResponse {
     #[prost(message, repeated, tag="1")]
     pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
     /// Time spent to process
     #[prost(double, tag="2")]
     pub time: f64,
+    /// Explanation of the search execution, if requested
+    #[prost(message, repeated, tag="3")]
+    pub explain: ::prost::alloc::vec::Vec<ShardExplain>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectionScoredPoint {
+    /// Name of the collection the point belongs to
+    #[prost(string, tag="1")]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub point: ::core::option::Option<ScoredPoint>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchCollectionsResponse {
+    #[prost(message, repeated, tag="1")]
+    pub result: ::prost::alloc::vec::Vec<CollectionScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag="2")]
+    pub time: f64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CountResponse {
@@ -1574,12 +2074,18 @@ pub struct CountResponse {
     /// Time spent to process
     #[prost(double, tag="2")]
     pub time: f64,
+    /// Explanation of the count execution, if requested
+    #[prost(message, repeated, tag="3")]
+    pub explain: ::prost::alloc::vec::Vec<ShardExplain>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScrollResponse {
     /// Use this offset for the next query
     #[prost(message, optional, tag="1")]
     pub next_page_offset: ::core::option::Option<PointId>,
+    /// Use this value as `order_by.start_from` for the next query
+    #[prost(double, optional, tag="4")]
+    pub next_page_start_from: ::core::option::Option<f64>,
     #[prost(message, repeated, tag="2")]
     pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
     /// Time spent to process
@@ -1592,49 +2098,202 @@ pub struct CountResult {
     pub count: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RetrievedPoint {
-    #[prost(message, optional, tag="1")]
-    pub id: ::core::option::Option<PointId>,
-    #[prost(map="string, message", tag="2")]
-    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
-    #[prost(float, repeated, tag="3")]
-    pub vector: ::prost::alloc::vec::Vec<f32>,
-}
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetResponse {
+pub struct FacetResponse {
     #[prost(message, repeated, tag="1")]
-    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
+    pub hits: ::prost::alloc::vec::Vec<FacetValueHit>,
     /// Time spent to process
     #[prost(double, tag="2")]
     pub time: f64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct RecommendResponse {
-    #[prost(message, repeated, tag="1")]
-    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
-    /// Time spent to process
-    #[prost(double, tag="2")]
-    pub time: f64,
+pub struct FacetValue {
+    #[prost(oneof="facet_value::Variant", tags="1, 2")]
+    pub variant: ::core::option::Option<facet_value::Variant>,
 }
-// ---------------------------------------------
-// ------------- Filter Conditions -------------
-// ---------------------------------------------
-
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Filter {
-    /// At least one of those conditions should match
-    #[prost(message, repeated, tag="1")]
-    pub should: ::prost::alloc::vec::Vec<Condition>,
+/// Nested message and enum types in `FacetValue`.
+pub mod facet_value {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        #[prost(string, tag="1")]
+        Keyword(::prost::alloc::string::String),
+        #[prost(int64, tag="2")]
+        Integer(i64),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetValueHit {
+    #[prost(message, optional, tag="1")]
+    pub value: ::core::option::Option<FacetValue>,
+    /// Number of points with this value
+    #[prost(uint64, tag="2")]
+    pub count: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResponse {
+    #[prost(message, optional, tag="1")]
+    pub result: ::core::option::Option<NumericAggregation>,
+    /// Time spent to process
+    #[prost(double, tag="2")]
+    pub time: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregationValue {
+    #[prost(oneof="aggregation_value::Value", tags="1, 2")]
+    pub value: ::core::option::Option<aggregation_value::Value>,
+}
+/// Nested message and enum types in `AggregationValue`.
+pub mod aggregation_value {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(double, tag="1")]
+        Number(f64),
+        /// Aggregated value of the datetime field in RFC 3339 format
+        #[prost(string, tag="2")]
+        Datetime(::prost::alloc::string::String),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NumericAggregation {
+    /// Number of aggregated values
+    #[prost(uint64, tag="1")]
+    pub count: u64,
+    #[prost(message, optional, tag="2")]
+    pub min: ::core::option::Option<AggregationValue>,
+    #[prost(message, optional, tag="3")]
+    pub max: ::core::option::Option<AggregationValue>,
+    /// Sum of the values. For datetime fields - sum of seconds since epoch
+    #[prost(double, tag="4")]
+    pub sum: f64,
+    #[prost(message, optional, tag="5")]
+    pub avg: ::core::option::Option<AggregationValue>,
+    /// Non-empty buckets, ordered by value
+    #[prost(message, repeated, tag="6")]
+    pub histogram: ::prost::alloc::vec::Vec<HistogramBucket>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramBucket {
+    #[prost(message, optional, tag="1")]
+    pub from: ::core::option::Option<AggregationValue>,
+    #[prost(message, optional, tag="2")]
+    pub to: ::core::option::Option<AggregationValue>,
+    /// Number of values in `[from, to)`
+    #[prost(uint64, tag="3")]
+    pub count: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadIndexStatsResponse {
+    /// Statistics of the index of each indexed field
+    #[prost(map="string, message", tag="1")]
+    pub result: ::std::collections::HashMap<::prost::alloc::string::String, PayloadIndexStats>,
+    /// Time spent to process
+    #[prost(double, tag="2")]
+    pub time: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadIndexStats {
+    /// Field data type
+    #[prost(enumeration="PayloadSchemaType", tag="1")]
+    pub data_type: i32,
+    /// Number of points with at least one indexed value
+    #[prost(uint64, tag="2")]
+    pub points_count: u64,
+    /// Upper bound of the number of distinct indexed values
+    #[prost(uint64, optional, tag="3")]
+    pub distinct_values: ::core::option::Option<u64>,
+    /// Most frequent values, only for keyword and integer indexes
+    #[prost(message, repeated, tag="4")]
+    pub top_values: ::prost::alloc::vec::Vec<FacetValueHit>,
+    /// Distribution of the values, only for numeric indexes
+    #[prost(message, optional, tag="5")]
+    pub numeric: ::core::option::Option<NumericIndexStats>,
+    /// Estimated memory, used by the index
+    #[prost(uint64, tag="6")]
+    pub ram_usage_bytes: u64,
+    /// Size of the persisted index data
+    #[prost(uint64, tag="7")]
+    pub disk_usage_bytes: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NumericIndexStats {
+    /// Number of indexed values
+    #[prost(uint64, tag="1")]
+    pub values_count: u64,
+    #[prost(double, tag="2")]
+    pub min: f64,
+    #[prost(double, tag="3")]
+    pub max: f64,
+    /// Approximate quantiles of the values
+    #[prost(message, repeated, tag="4")]
+    pub quantiles: ::prost::alloc::vec::Vec<QuantileValue>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuantileValue {
+    #[prost(double, tag="1")]
+    pub quantile: f64,
+    #[prost(double, tag="2")]
+    pub value: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RetrievedPoint {
+    #[prost(message, optional, tag="1")]
+    pub id: ::core::option::Option<PointId>,
+    #[prost(map="string, message", tag="2")]
+    pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    #[prost(float, repeated, tag="3")]
+    pub vector: ::prost::alloc::vec::Vec<f32>,
+    /// Value of the `order_by` field the point was ordered by
+    #[prost(double, optional, tag="4")]
+    pub order_value: ::core::option::Option<f64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResponse {
+    #[prost(message, repeated, tag="1")]
+    pub result: ::prost::alloc::vec::Vec<RetrievedPoint>,
+    /// Time spent to process
+    #[prost(double, tag="2")]
+    pub time: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendResponse {
+    #[prost(message, repeated, tag="1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    /// Time spent to process
+    #[prost(double, tag="2")]
+    pub time: f64,
+    /// Explanation of the search execution, if requested
+    #[prost(message, repeated, tag="3")]
+    pub explain: ::prost::alloc::vec::Vec<ShardExplain>,
+}
+// ---------------------------------------------
+// ------------- Filter Conditions -------------
+// ---------------------------------------------
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Filter {
+    /// At least one of those conditions should match
+    #[prost(message, repeated, tag="1")]
+    pub should: ::prost::alloc::vec::Vec<Condition>,
     /// All conditions must match
     #[prost(message, repeated, tag="2")]
     pub must: ::prost::alloc::vec::Vec<Condition>,
     /// All conditions must NOT match
     #[prost(message, repeated, tag="3")]
     pub must_not: ::prost::alloc::vec::Vec<Condition>,
+    /// At least minimum amount of given conditions should match
+    #[prost(message, optional, tag="4")]
+    pub min_should: ::core::option::Option<MinShould>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MinShould {
+    #[prost(message, repeated, tag="1")]
+    pub conditions: ::prost::alloc::vec::Vec<Condition>,
+    /// Minimal number of conditions which should match
+    #[prost(uint64, tag="2")]
+    pub min_count: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Condition {
-    #[prost(oneof="condition::ConditionOneOf", tags="1, 2, 3, 4")]
+    #[prost(oneof="condition::ConditionOneOf", tags="1, 2, 3, 4, 5, 6, 7")]
     pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
 }
 /// Nested message and enum types in `Condition`.
@@ -1649,14 +2308,39 @@ pub mod condition {
         HasId(super::HasIdCondition),
         #[prost(message, tag="4")]
         Filter(super::Filter),
+        #[prost(message, tag="5")]
+        Nested(super::NestedCondition),
+        #[prost(message, tag="6")]
+        IsNull(super::IsNullCondition),
+        #[prost(message, tag="7")]
+        HasField(super::HasFieldCondition),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NestedCondition {
+    /// Path to the array of objects
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+    /// Filter applied to each element of the array
+    #[prost(message, optional, tag="2")]
+    pub filter: ::core::option::Option<Filter>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct IsEmptyCondition {
     #[prost(string, tag="1")]
     pub key: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IsNullCondition {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasFieldCondition {
+    #[prost(string, tag="1")]
+    pub key: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct HasIdCondition {
     #[prost(message, repeated, tag="1")]
     pub has_id: ::prost::alloc::vec::Vec<PointId>,
@@ -1680,10 +2364,16 @@ pub struct FieldCondition {
     /// Check number of values for a specific field
     #[prost(message, optional, tag="6")]
     pub values_count: ::core::option::Option<ValuesCount>,
+    /// Check if datetime value lies in a given range
+    #[prost(message, optional, tag="7")]
+    pub datetime_range: ::core::option::Option<DatetimeRange>,
+    /// Check if geo point is within a given polygon
+    #[prost(message, optional, tag="8")]
+    pub geo_polygon: ::core::option::Option<GeoPolygon>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof="r#match::MatchValue", tags="1, 2, 3")]
+    #[prost(oneof="r#match::MatchValue", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -1699,9 +2389,54 @@ pub mod r#match {
         /// Match boolean
         #[prost(bool, tag="3")]
         Boolean(bool),
+        /// Match text
+        #[prost(string, tag="4")]
+        Text(::prost::alloc::string::String),
+        /// Match multiple keywords
+        #[prost(message, tag="5")]
+        Keywords(super::RepeatedStrings),
+        /// Match multiple integers
+        #[prost(message, tag="6")]
+        Integers(super::RepeatedIntegers),
+        /// Match any other value except those keywords
+        #[prost(message, tag="7")]
+        ExceptKeywords(super::RepeatedStrings),
+        /// Match any other value except those integers
+        #[prost(message, tag="8")]
+        ExceptIntegers(super::RepeatedIntegers),
+        /// Match keywords which start with the prefix
+        #[prost(string, tag="9")]
+        Prefix(::prost::alloc::string::String),
+        /// Match keywords within the lexicographic range
+        #[prost(message, tag="10")]
+        KeywordRange(super::KeywordRange),
+        /// Match keywords by the pattern, `*` - any sequence of characters, `?` - any single character
+        #[prost(string, tag="11")]
+        Wildcard(::prost::alloc::string::String),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeywordRange {
+    #[prost(string, optional, tag="1")]
+    pub lt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="2")]
+    pub gt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="3")]
+    pub gte: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="4")]
+    pub lte: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RepeatedStrings {
+    #[prost(string, repeated, tag="1")]
+    pub strings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RepeatedIntegers {
+    #[prost(int64, repeated, tag="1")]
+    pub integers: ::prost::alloc::vec::Vec<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Range {
     #[prost(double, optional, tag="1")]
     pub lt: ::core::option::Option<f64>,
@@ -1713,6 +2448,17 @@ pub struct Range {
     pub lte: ::core::option::Option<f64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatetimeRange {
+    #[prost(message, optional, tag="1")]
+    pub lt: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag="2")]
+    pub gt: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag="3")]
+    pub gte: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag="4")]
+    pub lte: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GeoBoundingBox {
     /// north-west corner
     #[prost(message, optional, tag="1")]
@@ -1731,6 +2477,21 @@ pub struct GeoRadius {
     pub radius: f32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoLineString {
+    /// Ordered sequence of GeoPoints representing the line
+    #[prost(message, repeated, tag="1")]
+    pub points: ::prost::alloc::vec::Vec<GeoPoint>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoPolygon {
+    /// The exterior line bounds the surface
+    #[prost(message, optional, tag="1")]
+    pub exterior: ::core::option::Option<GeoLineString>,
+    /// Interior lines (if present) bound holes within the surface
+    #[prost(message, repeated, tag="2")]
+    pub interiors: ::prost::alloc::vec::Vec<GeoLineString>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ValuesCount {
     #[prost(uint64, optional, tag="1")]
     pub lt: ::core::option::Option<u64>,
@@ -1792,6 +2553,24 @@ pub enum FieldType {
     Integer = 1,
     Float = 2,
     Geo = 3,
+    Text = 4,
+    Datetime = 5,
+    Bool = 6,
+    Uuid = 7,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Fusion {
+    /// Reciprocal rank fusion
+    Rrf = 0,
+    /// Weighted sum of normalized scores
+    Weighted = 1,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Direction {
+    Asc = 0,
+    Desc = 1,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -1802,6 +2581,22 @@ pub enum UpdateStatus {
     /// Update is applied and ready for search
     Completed = 2,
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum QueryStrategy {
+    /// Check every point of the segment
+    FullScan = 0,
+    /// Check the filter on every point of the segment
+    FilteredScan = 1,
+    /// Iterate over points, selected with the payload index
+    PayloadIndex = 2,
+    /// Traverse the HNSW graph
+    Hnsw = 3,
+    /// Traverse the HNSW graph, checking the filter on each visited point
+    FilteredHnsw = 4,
+    /// Estimate number of matching points with the payload index
+    Estimation = 5,
+}
 /// Generated client implementations.
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -1985,6 +2780,27 @@ pub mod points_client {
             self.inner.unary(request.into_request(), path, codec).await
         }
         ///
+        ///Atomically increment numeric payload fields, append to or remove from array fields
+        pub async fn modify_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ModifyPayloadPoints>,
+        ) -> Result<tonic::Response<super::PointsOperationResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/ModifyPayload",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        ///
         ///Create index for field in collection
         pub async fn create_field_index(
             &mut self,
@@ -2046,6 +2862,48 @@ pub mod points_client {
             self.inner.unary(request.into_request(), path, codec).await
         }
         ///
+        ///Run several searches and merge their results into a single ranking
+        pub async fn search_fusion(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchFusionPoints>,
+        ) -> Result<tonic::Response<super::SearchResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchFusion",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        ///
+        ///Retrieve closest points from several collections at once, merged into a single ranking
+        pub async fn search_collections(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SearchCollections>,
+        ) -> Result<tonic::Response<super::SearchCollectionsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchCollections",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        ///
         ///Iterate over all or filtered points points
         pub async fn scroll(
             &mut self,
@@ -2102,6 +2960,65 @@ pub mod points_client {
             let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Count");
             self.inner.unary(request.into_request(), path, codec).await
         }
+        ///
+        ///Count points for each value of the payload field, among the points which satisfy given filtering conditions
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetPoints>,
+        ) -> Result<tonic::Response<super::FacetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Facet");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        ///
+        ///Compute count, min, max, sum, avg and optional histogram of the numeric payload field over the points which satisfy given filtering conditions
+        pub async fn aggregate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AggregatePoints>,
+        ) -> Result<tonic::Response<super::AggregateResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Aggregate");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        ///
+        ///Get statistics of the payload indexes of the collection: indexed points, distinct and most frequent values, value distribution and memory usage
+        pub async fn payload_index_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PayloadIndexStatsRequest>,
+        ) -> Result<tonic::Response<super::PayloadIndexStatsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/PayloadIndexStats",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -2148,6 +3065,12 @@ pub mod points_server {
             request: tonic::Request<super::ClearPayloadPoints>,
         ) -> Result<tonic::Response<super::PointsOperationResponse>, tonic::Status>;
         ///
+        ///Atomically increment numeric payload fields, append to or remove from array fields
+        async fn modify_payload(
+            &self,
+            request: tonic::Request<super::ModifyPayloadPoints>,
+        ) -> Result<tonic::Response<super::PointsOperationResponse>, tonic::Status>;
+        ///
         ///Create index for field in collection
         async fn create_field_index(
             &self,
@@ -2166,6 +3089,18 @@ pub mod points_server {
             request: tonic::Request<super::SearchPoints>,
         ) -> Result<tonic::Response<super::SearchResponse>, tonic::Status>;
         ///
+        ///Run several searches and merge their results into a single ranking
+        async fn search_fusion(
+            &self,
+            request: tonic::Request<super::SearchFusionPoints>,
+        ) -> Result<tonic::Response<super::SearchResponse>, tonic::Status>;
+        ///
+        ///Retrieve closest points from several collections at once, merged into a single ranking
+        async fn search_collections(
+            &self,
+            request: tonic::Request<super::SearchCollections>,
+        ) -> Result<tonic::Response<super::SearchCollectionsResponse>, tonic::Status>;
+        ///
         ///Iterate over all or filtered points points
         async fn scroll(
             &self,
@@ -2183,6 +3118,24 @@ pub mod points_server {
             &self,
             request: tonic::Request<super::CountPoints>,
         ) -> Result<tonic::Response<super::CountResponse>, tonic::Status>;
+        ///
+        ///Count points for each value of the payload field, among the points which satisfy given filtering conditions
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetPoints>,
+        ) -> Result<tonic::Response<super::FacetResponse>, tonic::Status>;
+        ///
+        ///Compute count, min, max, sum, avg and optional histogram of the numeric payload field over the points which satisfy given filtering conditions
+        async fn aggregate(
+            &self,
+            request: tonic::Request<super::AggregatePoints>,
+        ) -> Result<tonic::Response<super::AggregateResponse>, tonic::Status>;
+        ///
+        ///Get statistics of the payload indexes of the collection: indexed points, distinct and most frequent values, value distribution and memory usage
+        async fn payload_index_stats(
+            &self,
+            request: tonic::Request<super::PayloadIndexStatsRequest>,
+        ) -> Result<tonic::Response<super::PayloadIndexStatsResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -2455,6 +3408,46 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/ModifyPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct ModifyPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::ModifyPayloadPoints>
+                    for ModifyPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ModifyPayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).modify_payload(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ModifyPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.Points/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
                     struct CreateFieldIndexSvc<T: Points>(pub Arc<T>);
@@ -2535,22 +3528,208 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Search" => {
+                "/qdrant.Points/Search" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SearchPoints>
+                    for SearchSvc<T> {
+                        type Response = super::SearchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchPoints>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).search(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchFusion" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchFusionSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchFusionPoints>
+                    for SearchFusionSvc<T> {
+                        type Response = super::SearchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchFusionPoints>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).search_fusion(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchFusionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchCollections" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchCollectionsSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SearchCollections>
+                    for SearchCollectionsSvc<T> {
+                        type Response = super::SearchCollectionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchCollections>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).search_collections(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchCollectionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Scroll" => {
+                    #[allow(non_camel_case_types)]
+                    struct ScrollSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::ScrollPoints>
+                    for ScrollSvc<T> {
+                        type Response = super::ScrollResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ScrollPoints>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).scroll(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ScrollSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Recommend" => {
+                    #[allow(non_camel_case_types)]
+                    struct RecommendSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::RecommendPoints>
+                    for RecommendSvc<T> {
+                        type Response = super::RecommendResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RecommendPoints>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).recommend(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RecommendSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Count" => {
                     #[allow(non_camel_case_types)]
-                    struct SearchSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::SearchPoints>
-                    for SearchSvc<T> {
-                        type Response = super::SearchResponse;
+                    struct CountSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::CountPoints>
+                    for CountSvc<T> {
+                        type Response = super::CountResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::SearchPoints>,
+                            request: tonic::Request<super::CountPoints>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).search(request).await };
+                            let fut = async move { (*inner).count(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -2559,7 +3738,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = SearchSvc(inner);
+                        let method = CountSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -2571,22 +3750,22 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Scroll" => {
+                "/qdrant.Points/Facet" => {
                     #[allow(non_camel_case_types)]
-                    struct ScrollSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::ScrollPoints>
-                    for ScrollSvc<T> {
-                        type Response = super::ScrollResponse;
+                    struct FacetSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::FacetPoints>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ScrollPoints>,
+                            request: tonic::Request<super::FacetPoints>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).scroll(request).await };
+                            let fut = async move { (*inner).facet(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -2595,7 +3774,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = ScrollSvc(inner);
+                        let method = FacetSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -2607,22 +3786,22 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Recommend" => {
+                "/qdrant.Points/Aggregate" => {
                     #[allow(non_camel_case_types)]
-                    struct RecommendSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::RecommendPoints>
-                    for RecommendSvc<T> {
-                        type Response = super::RecommendResponse;
+                    struct AggregateSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::AggregatePoints>
+                    for AggregateSvc<T> {
+                        type Response = super::AggregateResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::RecommendPoints>,
+                            request: tonic::Request<super::AggregatePoints>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).recommend(request).await };
+                            let fut = async move { (*inner).aggregate(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -2631,7 +3810,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RecommendSvc(inner);
+                        let method = AggregateSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -2643,22 +3822,26 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Count" => {
+                "/qdrant.Points/PayloadIndexStats" => {
                     #[allow(non_camel_case_types)]
-                    struct CountSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::CountPoints>
-                    for CountSvc<T> {
-                        type Response = super::CountResponse;
+                    struct PayloadIndexStatsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::PayloadIndexStatsRequest>
+                    for PayloadIndexStatsSvc<T> {
+                        type Response = super::PayloadIndexStatsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CountPoints>,
+                            request: tonic::Request<super::PayloadIndexStatsRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).count(request).await };
+                            let fut = async move {
+                                (*inner).payload_index_stats(request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -2667,7 +3850,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = CountSvc(inner);
+                        let method = PayloadIndexStatsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -2754,6 +3937,13 @@ pub struct ClearPayloadPointsInternal {
     pub shard_id: u32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ModifyPayloadPointsInternal {
+    #[prost(message, optional, tag="1")]
+    pub modify_payload_points: ::core::option::Option<ModifyPayloadPoints>,
+    #[prost(uint32, tag="2")]
+    pub shard_id: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateFieldIndexCollectionInternal {
     #[prost(message, optional, tag="1")]
     pub create_field_index_collection: ::core::option::Option<CreateFieldIndexCollection>,
@@ -2802,6 +3992,27 @@ pub struct CountPointsInternal {
     #[prost(uint32, tag="2")]
     pub shard_id: u32,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetPointsInternal {
+    #[prost(message, optional, tag="1")]
+    pub facet_points: ::core::option::Option<FacetPoints>,
+    #[prost(uint32, tag="2")]
+    pub shard_id: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregatePointsInternal {
+    #[prost(message, optional, tag="1")]
+    pub aggregate_points: ::core::option::Option<AggregatePoints>,
+    #[prost(uint32, tag="2")]
+    pub shard_id: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadIndexStatsInternal {
+    #[prost(message, optional, tag="1")]
+    pub payload_index_stats: ::core::option::Option<PayloadIndexStatsRequest>,
+    #[prost(uint32, tag="2")]
+    pub shard_id: u32,
+}
 /// Generated client implementations.
 pub mod points_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -2961,6 +4172,25 @@ pub mod points_internal_client {
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
+        pub async fn modify_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ModifyPayloadPointsInternal>,
+        ) -> Result<tonic::Response<super::PointsOperationResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/ModifyPayload",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
         pub async fn create_field_index(
             &mut self,
             request: impl tonic::IntoRequest<super::CreateFieldIndexCollectionInternal>,
@@ -3094,6 +4324,63 @@ pub mod points_internal_client {
             );
             self.inner.unary(request.into_request(), path, codec).await
         }
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetPointsInternal>,
+        ) -> Result<tonic::Response<super::FacetResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Facet",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn aggregate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AggregatePointsInternal>,
+        ) -> Result<tonic::Response<super::AggregateResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Aggregate",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn payload_index_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PayloadIndexStatsInternal>,
+        ) -> Result<tonic::Response<super::PayloadIndexStatsResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/PayloadIndexStats",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -3123,6 +4410,10 @@ pub mod points_internal_server {
             &self,
             request: tonic::Request<super::ClearPayloadPointsInternal>,
         ) -> Result<tonic::Response<super::PointsOperationResponse>, tonic::Status>;
+        async fn modify_payload(
+            &self,
+            request: tonic::Request<super::ModifyPayloadPointsInternal>,
+        ) -> Result<tonic::Response<super::PointsOperationResponse>, tonic::Status>;
         async fn create_field_index(
             &self,
             request: tonic::Request<super::CreateFieldIndexCollectionInternal>,
@@ -3151,6 +4442,18 @@ pub mod points_internal_server {
             &self,
             request: tonic::Request<super::GetPointsInternal>,
         ) -> Result<tonic::Response<super::GetResponse>, tonic::Status>;
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetPointsInternal>,
+        ) -> Result<tonic::Response<super::FacetResponse>, tonic::Status>;
+        async fn aggregate(
+            &self,
+            request: tonic::Request<super::AggregatePointsInternal>,
+        ) -> Result<tonic::Response<super::AggregateResponse>, tonic::Status>;
+        async fn payload_index_stats(
+            &self,
+            request: tonic::Request<super::PayloadIndexStatsInternal>,
+        ) -> Result<tonic::Response<super::PayloadIndexStatsResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -3393,6 +4696,46 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/ModifyPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct ModifyPayloadSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::ModifyPayloadPointsInternal>
+                    for ModifyPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ModifyPayloadPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).modify_payload(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ModifyPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/qdrant.PointsInternal/CreateFieldIndex" => {
                     #[allow(non_camel_case_types)]
                     struct CreateFieldIndexSvc<T: PointsInternal>(pub Arc<T>);
@@ -3669,6 +5012,122 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::FacetPointsInternal>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).facet(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/Aggregate" => {
+                    #[allow(non_camel_case_types)]
+                    struct AggregateSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::AggregatePointsInternal>
+                    for AggregateSvc<T> {
+                        type Response = super::AggregateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AggregatePointsInternal>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).aggregate(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AggregateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/PayloadIndexStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct PayloadIndexStatsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::PayloadIndexStatsInternal>
+                    for PayloadIndexStatsSvc<T> {
+                        type Response = super::PayloadIndexStatsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PayloadIndexStatsInternal>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).payload_index_stats(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PayloadIndexStatsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -1,4 +1,4 @@
-use std::cmp::max;
+use std::cmp::{max, Ordering};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -8,7 +8,8 @@ use itertools::Itertools;
 use segment::common::version::StorageVersion;
 use segment::spaces::tools::{peek_top_largest_scores_iterable, peek_top_smallest_scores_iterable};
 use segment::types::{
-    Condition, ExtendedPointId, Filter, HasIdCondition, Order, ScoredPoint, VectorElementType,
+    Condition, ExtendedPointId, FacetValue, FacetValueHit, Filter, HasIdCondition,
+    NumericAggregation, Order, PayloadIndexStats, PayloadKeyType, ScoredPoint, VectorElementType,
     WithPayload, WithPayloadInterface,
 };
 use semver::{Version, VersionReq};
@@ -17,17 +18,20 @@ use tokio::fs::{copy, create_dir_all, remove_dir_all, remove_file, rename};
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
 
+use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_state::State;
-use crate::config::CollectionConfig;
+use crate::common::fusion::fuse_search_results;
+use crate::config::{CollectionConfig, CollectionParams};
 use crate::hash_ring::HashRing;
 use crate::operations::config_diff::{DiffConfig, OptimizersConfigDiff};
 use crate::operations::snapshot_ops::{
     get_snapshot_description, list_snapshots_in_directory, SnapshotDescription,
 };
 use crate::operations::types::{
-    CollectionClusterInfo, CollectionError, CollectionInfo, CollectionResult, CountRequest,
-    CountResult, LocalShardInfo, PointRequest, RecommendRequest, Record, RemoteShardInfo,
-    ScrollRequest, ScrollResult, SearchRequest, UpdateResult,
+    AggregateRequest, CollectionClusterInfo, CollectionError, CollectionInfo, CollectionResult,
+    CountExplainResult, CountRequest, CountResult, FacetRequest, FacetResult, FusionRequest,
+    LocalShardInfo, PointRequest, RecommendRequest, Record, RemoteShardInfo, ScrollRequest,
+    ScrollResult, SearchExplainResult, SearchRequest, ShardExplain, UpdateResult,
 };
 use crate::operations::{CollectionUpdateOperations, Validate};
 use crate::optimizers_builder::OptimizersConfig;
@@ -67,6 +71,10 @@ impl Collection {
         self.id.clone()
     }
 
+    pub async fn params(&self) -> CollectionParams {
+        self.config.read().await.params.clone()
+    }
+
     pub async fn new(
         id: CollectionId,
         path: &Path,
@@ -348,6 +356,9 @@ impl Collection {
         wait: bool,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
+        if let Some(schema) = &self.config.read().await.params.payload_schema {
+            CollectionUpdater::validate_payload_schema(schema, &operation)?;
+        }
 
         let mut results = {
             let shards_holder = self.shards_holder.read().await;
@@ -391,6 +402,33 @@ impl Collection {
         search_runtime_handle: &Handle,
         shard_selection: Option<ShardId>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
+        let search_request = self
+            .recommend_search_request(request, shard_selection)
+            .await?;
+        self.search(search_request, search_runtime_handle, shard_selection)
+            .await
+    }
+
+    /// Same as `recommend_by`, but also explains how the search was executed
+    pub async fn recommend_explain(
+        &self,
+        request: RecommendRequest,
+        search_runtime_handle: &Handle,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<SearchExplainResult> {
+        let search_request = self
+            .recommend_search_request(request, shard_selection)
+            .await?;
+        self.search_explain(search_request, search_runtime_handle, shard_selection)
+            .await
+    }
+
+    /// Convert recommendation request into the search for the average of the examples
+    async fn recommend_search_request(
+        &self,
+        request: RecommendRequest,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<SearchRequest> {
         if request.positive.is_empty() {
             return Err(CollectionError::BadRequest {
                 description: "At least one positive vector ID required".to_owned(),
@@ -463,6 +501,7 @@ impl Collection {
                 must_not: Some(vec![Condition::HasId(HasIdCondition {
                     has_id: reference_vectors_ids.iter().cloned().collect(),
                 })]),
+                min_should: None,
             }),
             with_payload: request.with_payload.clone(),
             with_vector: request.with_vector,
@@ -470,10 +509,11 @@ impl Collection {
             limit: request.limit,
             score_threshold: request.score_threshold,
             offset: request.offset,
+            rescore: None,
+            explain: request.explain,
         };
 
-        self.search(search_request, search_runtime_handle, shard_selection)
-            .await
+        Ok(search_request)
     }
 
     async fn _search(
@@ -493,8 +533,21 @@ impl Collection {
             try_join_all(all_searches).await?.into_iter().flatten()
         };
 
-        let distance = self.config.read().await.params.distance;
-        let mut top_result = match distance.distance_order() {
+        Ok(self.merge_search_results(&request, all_searches_res).await)
+    }
+
+    /// Select top results of the request among the results of all shards
+    async fn merge_search_results(
+        &self,
+        request: &SearchRequest,
+        all_searches_res: impl Iterator<Item = ScoredPoint>,
+    ) -> Vec<ScoredPoint> {
+        let order = if request.rescore.is_some() {
+            Order::LargeBetter
+        } else {
+            self.config.read().await.params.distance.distance_order()
+        };
+        let mut top_result = match order {
             Order::LargeBetter => {
                 peek_top_largest_scores_iterable(all_searches_res, request.limit + request.offset)
             }
@@ -507,7 +560,7 @@ impl Collection {
             // Remove offset from top result.
             top_result.drain(..request.offset);
         }
-        Ok(top_result)
+        top_result
     }
 
     async fn fill_search_result_with_payload(
@@ -553,6 +606,8 @@ impl Collection {
         // Should be adjusted based on usage statistics.
         const PAYLOAD_TRANSFERS_FACTOR_THRESHOLD: usize = 10;
 
+        validate_rescore(&request)?;
+
         let is_payload_required = if let Some(with_payload) = &request.with_payload {
             with_payload.is_required()
         } else {
@@ -602,6 +657,107 @@ impl Collection {
         }
     }
 
+    /// Same as `search`, but also explains how the search was executed in each shard and segment.
+    /// Payload and vector are retrieved together with the search results.
+    pub async fn search_explain(
+        &self,
+        request: SearchRequest,
+        search_runtime_handle: &Handle,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<SearchExplainResult> {
+        validate_rescore(&request)?;
+
+        let request = Arc::new(request);
+
+        let (all_searches_res, explain): (Vec<_>, Vec<_>) = {
+            let shard_holder = self.shards_holder.read().await;
+            let target_shards = shard_holder.target_shards_with_id(shard_selection)?;
+            let all_searches = target_shards.into_iter().map(|(shard_id, shard)| {
+                let request = request.clone();
+                async move {
+                    let (result, segments) = shard
+                        .get()
+                        .search_explain(request, search_runtime_handle)
+                        .await?;
+                    CollectionResult::Ok((result, ShardExplain { shard_id, segments }))
+                }
+            });
+            try_join_all(all_searches).await?.into_iter().unzip()
+        };
+
+        let result = self
+            .merge_search_results(&request, all_searches_res.into_iter().flatten())
+            .await;
+        Ok(SearchExplainResult { result, explain })
+    }
+
+    /// Run several searches and merge their results into a single ranking.
+    ///
+    /// Each search is executed across all target shards first, so that the fusion operates on
+    /// complete per-search rankings. Payload and vector are retrieved only for the final page.
+    pub async fn search_fusion(
+        &self,
+        request: FusionRequest,
+        search_runtime_handle: &Handle,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        if request.searches.is_empty() {
+            return Err(CollectionError::BadRequest {
+                description: "At least one search required".to_owned(),
+            });
+        }
+
+        let all_searches = request.searches.iter().map(|search| {
+            let search_request = SearchRequest {
+                vector: search.vector.clone(),
+                filter: search.filter.clone(),
+                params: search.params,
+                limit: search.limit.unwrap_or(request.limit + request.offset),
+                offset: 0,
+                with_payload: None,
+                with_vector: false,
+                score_threshold: search.score_threshold,
+                rescore: None,
+                explain: false,
+            };
+            self._search(search_request, search_runtime_handle, shard_selection)
+        });
+        let all_searches_res = try_join_all(all_searches).await?;
+
+        let weighted_results = request
+            .searches
+            .iter()
+            .map(|search| search.weight.unwrap_or(1.0))
+            .zip(all_searches_res)
+            .collect();
+
+        let distance = self.config.read().await.params.distance;
+        let fused_result =
+            fuse_search_results(weighted_results, request.fusion, distance.distance_order())
+                .into_iter()
+                .skip(request.offset)
+                .take(request.limit)
+                .collect_vec();
+
+        let is_payload_required = if let Some(with_payload) = &request.with_payload {
+            with_payload.is_required()
+        } else {
+            false
+        };
+
+        if is_payload_required || request.with_vector {
+            self.fill_search_result_with_payload(
+                fused_result,
+                request.with_payload,
+                request.with_vector,
+                shard_selection,
+            )
+            .await
+        } else {
+            Ok(fused_result)
+        }
+    }
+
     pub async fn scroll_by(
         &self,
         request: ScrollRequest,
@@ -625,6 +781,18 @@ impl Collection {
             });
         }
 
+        let order_by = request.order_by.as_ref();
+        // Cursor of the ordered scroll is (start_from, offset), `offset` alone is ambiguous
+        if let Some(order_by) = order_by {
+            if offset.is_some() && order_by.start_from.is_none() {
+                return Err(CollectionError::BadRequest {
+                    description:
+                        "Offset of the scroll ordered by a field requires `order_by.start_from`"
+                            .to_string(),
+                });
+            }
+        }
+
         // Needed to return next page offset.
         let limit = limit + 1;
         let retrieved_points: Vec<_> = {
@@ -637,28 +805,60 @@ impl Collection {
                     &with_payload_interface,
                     with_vector,
                     request.filter.as_ref(),
+                    order_by,
                 )
             });
 
             try_join_all(scroll_futures).await?
         };
-        let mut points: Vec<_> = retrieved_points
-            .into_iter()
-            .flatten()
-            .sorted_by_key(|point| point.id)
-            .take(limit)
-            .collect();
+        let mut points: Vec<_> = match order_by {
+            None => retrieved_points
+                .into_iter()
+                .flatten()
+                .sorted_by_key(|point| point.id)
+                .take(limit)
+                .collect(),
+            // Points of each shard are already ordered
+            Some(order_by) => {
+                // Shards return each point of the ordered scroll with its value of the field
+                let shards_points = retrieved_points
+                    .into_iter()
+                    .map(|shard_points| {
+                        shard_points
+                            .into_iter()
+                            .map(|point| match point.order_value {
+                                Some(order_value) => Ok((order_value, point)),
+                                None => Err(CollectionError::service_error(format!(
+                                    "Point {} of the ordered scroll has no order value",
+                                    point.id
+                                ))),
+                            })
+                            .collect::<CollectionResult<Vec<_>>>()
+                    })
+                    .collect::<CollectionResult<Vec<_>>>()?;
+                shards_points
+                    .into_iter()
+                    .kmerge_by(|(a_value, a), (b_value, b)| {
+                        order_by.compare((*a_value, a.id), (*b_value, b.id)) == Ordering::Less
+                    })
+                    .map(|(_, point)| point)
+                    .take(limit)
+                    .collect()
+            }
+        };
 
-        let next_page_offset = if points.len() < limit {
+        let (next_page_offset, next_page_start_from) = if points.len() < limit {
             // This was the last page
-            None
+            (None, None)
         } else {
             // remove extra point, it would be a first point of the next page
-            Some(points.pop().unwrap().id)
+            let next_point = points.pop().unwrap();
+            (Some(next_point.id), next_point.order_value)
         };
         Ok(ScrollResult {
             points,
             next_page_offset,
+            next_page_start_from,
         })
     }
 
@@ -683,6 +883,115 @@ impl Collection {
         Ok(aggregated_count)
     }
 
+    pub async fn facet(
+        &self,
+        request: FacetRequest,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<FacetResult> {
+        let request = Arc::new(request);
+
+        let shard_hits: Vec<_> = {
+            let shards_holder = self.shards_holder.read().await;
+            let target_shards = shards_holder.target_shards(shard_selection)?;
+            let facet_futures = target_shards
+                .into_iter()
+                .map(|shard| shard.get().facet(request.clone()));
+            try_join_all(facet_futures).await?
+        };
+
+        // Request of a single shard comes from another peer, which merges counts of all shards
+        let limit = match shard_selection {
+            Some(_) => usize::MAX,
+            None => request.limit,
+        };
+        let mut counts: HashMap<FacetValue, usize> = HashMap::new();
+        for hit in shard_hits.into_iter().flatten() {
+            *counts.entry(hit.value).or_default() += hit.count;
+        }
+        let hits = counts
+            .into_iter()
+            .map(|(value, count)| FacetValueHit { value, count })
+            .sorted_by(|a, b| a.compare(b))
+            .take(limit)
+            .collect();
+        Ok(FacetResult { hits })
+    }
+
+    pub async fn aggregate(
+        &self,
+        request: AggregateRequest,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<NumericAggregation> {
+        let request = Arc::new(request);
+
+        let shard_aggregations: Vec<_> = {
+            let shards_holder = self.shards_holder.read().await;
+            let target_shards = shards_holder.target_shards(shard_selection)?;
+            let aggregate_futures = target_shards
+                .into_iter()
+                .map(|shard| shard.get().aggregate(request.clone()));
+            try_join_all(aggregate_futures).await?
+        };
+
+        let mut aggregation = NumericAggregation {
+            histogram: request.histogram.as_ref().map(|_| vec![]),
+            ..Default::default()
+        };
+        for shard_aggregation in shard_aggregations {
+            aggregation.merge(shard_aggregation);
+        }
+        Ok(aggregation)
+    }
+
+    /// Statistics of the payload indexes, combined over all shards
+    pub async fn payload_index_stats(
+        &self,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<HashMap<PayloadKeyType, PayloadIndexStats>> {
+        let shard_stats: Vec<_> = {
+            let shards_holder = self.shards_holder.read().await;
+            let target_shards = shards_holder.target_shards(shard_selection)?;
+            let stats_futures = target_shards
+                .into_iter()
+                .map(|shard| shard.get().payload_index_stats());
+            try_join_all(stats_futures).await?
+        };
+
+        let mut stats = HashMap::new();
+        for shard_stats in shard_stats {
+            PayloadIndexStats::merge_fields(&mut stats, shard_stats);
+        }
+        Ok(stats)
+    }
+
+    /// Same as `count`, but also explains how the points were counted in each shard and segment
+    pub async fn count_explain(
+        &self,
+        request: CountRequest,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<CountExplainResult> {
+        let request = Arc::new(request);
+
+        let (counts, explain): (Vec<_>, Vec<_>) = {
+            let shards_holder = self.shards_holder.read().await;
+            let target_shards = shards_holder.target_shards_with_id(shard_selection)?;
+            let count_futures = target_shards.into_iter().map(|(shard_id, shard)| {
+                let request = request.clone();
+                async move {
+                    let (count, segments) = shard.get().count_explain(request).await?;
+                    CollectionResult::Ok((count, ShardExplain { shard_id, segments }))
+                }
+            });
+            try_join_all(count_futures).await?.into_iter().unzip()
+        };
+
+        let total_count = counts.iter().map(|x| x.count).sum::<usize>();
+        Ok(CountExplainResult {
+            result: CountResult { count: total_count },
+            explain,
+        })
+    }
+
     pub async fn retrieve(
         &self,
         request: PointRequest,
@@ -815,6 +1124,7 @@ impl Collection {
         let count_request = Arc::new(CountRequest {
             filter: None,
             exact: true,
+            explain: false,
         });
         for (shard_id, shard) in shards.get_shards() {
             let shard_id = *shard_id;
@@ -1050,3 +1360,13 @@ fn avg_vectors<'a>(
 
     avg_vector
 }
+
+fn validate_rescore(request: &SearchRequest) -> CollectionResult<()> {
+    if let Some(rescore) = &request.rescore {
+        rescore
+            .expression
+            .validate()
+            .map_err(|description| CollectionError::BadRequest { description })?;
+    }
+    Ok(())
+}
@@ -1,11 +1,22 @@
 use parking_lot::RwLock;
-use segment::types::SeqNumberType;
+use segment::common::utils::{paths_overlap, strip_array_selectors};
+use segment::entry::entry_point::{OperationError, OperationResult};
+use segment::types::{
+    parse_datetime_seconds, parse_uuid, GeoPoint, Payload, PayloadSchemaType, SeqNumberType,
+};
+use serde_json::{Map, Value};
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::collection_manager::segments_updater::*;
+use crate::config::{PayloadSchema, PayloadSchemaField};
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointInsertOperations, PointOperations};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::CollectionUpdateOperations;
 
+/// Max number of schema violations listed in the error message
+const MAX_REPORTED_VIOLATIONS: usize = 10;
+
 /// Implementation of the update operation
 #[derive(Default)]
 pub struct CollectionUpdater {}
@@ -47,6 +58,7 @@ impl CollectionUpdater {
         segments: &RwLock<SegmentHolder>,
         op_num: SeqNumberType,
         operation: CollectionUpdateOperations,
+        payload_schema: Option<&PayloadSchema>,
     ) -> CollectionResult<usize> {
         // Allow only one update at a time, ensure no data races between segments.
         // let _lock = self.update_lock.lock().unwrap();
@@ -55,7 +67,7 @@ impl CollectionUpdater {
                 process_point_operation(segments, op_num, point_operation)
             }
             CollectionUpdateOperations::PayloadOperation(payload_operation) => {
-                process_payload_operation(segments, op_num, payload_operation)
+                process_payload_operation(segments, op_num, payload_operation, payload_schema)
             }
             CollectionUpdateOperations::FieldIndexOperation(index_operation) => {
                 process_field_index_operation(segments, op_num, &index_operation)
@@ -66,19 +78,321 @@ impl CollectionUpdater {
 
         operation_result
     }
+
+    /// Check that the operation does not produce payloads, which violate the collection schema.
+    /// Should be called once for the whole operation, before it is split by shards and written into WAL.
+    /// Results of read-modify-write operations depend on the stored values,
+    /// so they are additionally checked by [`check_modified_payload`] while applied.
+    pub fn validate_payload_schema(
+        schema: &PayloadSchema,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let mut violations = vec![];
+        match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperations::PointsBatch(batch),
+            )) => {
+                for (idx, id) in batch.ids.iter().enumerate() {
+                    let payload = batch
+                        .payloads
+                        .as_ref()
+                        .and_then(|payloads| payloads.get(idx))
+                        .and_then(|payload| payload.as_ref());
+                    violations.extend(
+                        payload_violations(schema, payload, true)
+                            .into_iter()
+                            .map(|violation| format!("point {}: {}", id, violation)),
+                    );
+                }
+            }
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperations::PointsList(points),
+            )) => {
+                for point in points {
+                    violations.extend(
+                        payload_violations(schema, point.payload.as_ref(), true)
+                            .into_iter()
+                            .map(|violation| format!("point {}: {}", point.id, violation)),
+                    );
+                }
+            }
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(set_payload)) => {
+                let nested_payload;
+                let payload = match &set_payload.key {
+                    None => &set_payload.payload,
+                    Some(key) => {
+                        let mut payload = Payload::default();
+                        payload.merge_by_key(&set_payload.payload, key);
+                        nested_payload = payload;
+                        &nested_payload
+                    }
+                };
+                violations = payload_violations(schema, Some(payload), false);
+
+                // Values are replaced as a whole, so nested required fields must be present
+                let written_paths = set_payload
+                    .payload
+                    .0
+                    .keys()
+                    .map(|top_key| match &set_payload.key {
+                        None => top_key.clone(),
+                        Some(key) => format!("{}.{}", key, top_key),
+                    });
+                for written_path in written_paths {
+                    violations.extend(
+                        schema
+                            .fields
+                            .iter()
+                            .filter(|(path, field)| {
+                                field.required
+                                    && is_parent_path(&written_path, path)
+                                    && payload.get_value_cow(path).is_none()
+                            })
+                            .map(|(path, _)| {
+                                format!(
+                                    "required field `{}` would be removed by overwriting `{}`",
+                                    path, written_path
+                                )
+                            }),
+                    );
+                }
+            }
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::DeletePayload(
+                delete_payload,
+            )) => {
+                for key in &delete_payload.keys {
+                    violations.extend(
+                        schema
+                            .fields
+                            .iter()
+                            .filter(|(path, field)| field.required && removes_path(key, path))
+                            .map(|(path, _)| {
+                                if path == key {
+                                    format!("required field `{}` can't be deleted", key)
+                                } else {
+                                    format!(
+                                        "required field `{}` can't be deleted with `{}`",
+                                        path, key
+                                    )
+                                }
+                            }),
+                    );
+                }
+            }
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::ModifyPayload(
+                modify_payload,
+            )) => {
+                for payload in modify_payload
+                    .modifications
+                    .iter()
+                    .filter_map(|modification| modification.written_values())
+                {
+                    violations.extend(payload_violations(schema, Some(&payload), false));
+                }
+            }
+            CollectionUpdateOperations::PayloadOperation(
+                PayloadOps::ClearPayload { .. } | PayloadOps::ClearPayloadByFilter(_),
+            ) => {
+                violations = schema
+                    .fields
+                    .iter()
+                    .filter(|(_, field)| field.required)
+                    .map(|(key, _)| format!("required field `{}` can't be cleared", key))
+                    .collect();
+            }
+            _ => {}
+        }
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let mut description = format!(
+            "Payload does not match the collection schema: {}",
+            violations
+                .iter()
+                .take(MAX_REPORTED_VIOLATIONS)
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+        if violations.len() > MAX_REPORTED_VIOLATIONS {
+            description += &format!("; and {} more", violations.len() - MAX_REPORTED_VIOLATIONS);
+        }
+        Err(CollectionError::BadInput { description })
+    }
+}
+
+/// Check the resulting payload of a point after read-modify-write modifications of `modified_keys`.
+/// Only the schema fields, which might be affected by the modifications, are checked.
+pub(crate) fn check_modified_payload<'a>(
+    schema: &PayloadSchema,
+    payload: &Payload,
+    modified_keys: impl Iterator<Item = &'a str> + Clone,
+) -> OperationResult<()> {
+    let violations: Vec<_> = schema
+        .fields
+        .iter()
+        .filter(|(path, _)| modified_keys.clone().any(|key| paths_overlap(key, path)))
+        .filter_map(|(path, field)| {
+            field_violation(path, field, payload.get_value_cow(path).as_deref(), true)
+        })
+        .collect();
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(OperationError::ValidationError {
+        description: format!(
+            "Payload does not match the collection schema: {}",
+            violations.join("; ")
+        ),
+    })
+}
+
+/// Describe all the ways in which the payload violates the schema.
+/// `check_required` should be false for partial payloads, which are merged into existing ones.
+fn payload_violations(
+    schema: &PayloadSchema,
+    payload: Option<&Payload>,
+    check_required: bool,
+) -> Vec<String> {
+    let mut violations: Vec<_> = schema
+        .fields
+        .iter()
+        .filter_map(|(key, field)| {
+            let value = payload.and_then(|payload| payload.get_value_cow(key));
+            field_violation(key, field, value.as_deref(), check_required)
+        })
+        .collect();
+    if !schema.allow_unknown_fields {
+        if let Some(payload) = payload {
+            let declared_paths: Vec<_> = schema
+                .fields
+                .keys()
+                .map(|path| strip_array_selectors(path))
+                .collect();
+            collect_unknown_fields(&declared_paths, None, &payload.0, &mut violations);
+        }
+    }
+    violations
+}
+
+/// Describe fields of the object, which are not declared and don't contain declared fields.
+/// Objects, which contain declared fields, are checked recursively.
+/// Array selectors are not distinguished, elements of an array have the path of the array itself.
+fn collect_unknown_fields(
+    declared_paths: &[String],
+    parent_path: Option<&str>,
+    object: &Map<String, Value>,
+    violations: &mut Vec<String>,
+) {
+    for (key, value) in object {
+        let path = match parent_path {
+            None => key.clone(),
+            Some(parent_path) => format!("{}.{}", parent_path, key),
+        };
+        if declared_paths.contains(&path) || value.is_null() {
+            continue;
+        }
+        let is_parent = declared_paths.iter().any(|declared_path| {
+            declared_path
+                .strip_prefix(path.as_str())
+                .map_or(false, |rest| rest.starts_with('.'))
+        });
+        let nested_objects = match value {
+            Value::Object(nested) if is_parent => vec![nested],
+            Value::Array(values) if is_parent => values
+                .iter()
+                .map(Value::as_object)
+                .collect::<Option<Vec<_>>>()
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+        if nested_objects.is_empty() && !matches!(value, Value::Array(values) if values.is_empty())
+        {
+            let violation = format!("unknown field `{}`", path);
+            if !violations.contains(&violation) {
+                violations.push(violation);
+            }
+        }
+        for nested in nested_objects {
+            collect_unknown_fields(declared_paths, Some(&path), nested, violations);
+        }
+    }
+}
+
+fn field_violation(
+    key: &str,
+    field: &PayloadSchemaField,
+    value: Option<&Value>,
+    check_required: bool,
+) -> Option<String> {
+    match value {
+        None if check_required && field.required => {
+            Some(format!("missing required field `{}`", key))
+        }
+        Some(Value::Null) if field.required => {
+            Some(format!("required field `{}` can't be null", key))
+        }
+        None | Some(Value::Null) => None,
+        Some(value) if !value_matches_type(value, field.r#type) => Some(format!(
+            "field `{}` is expected to be {:?}, got {}",
+            key, field.r#type, value
+        )),
+        Some(_) => None,
+    }
+}
+
+fn path_depth(path: &str) -> usize {
+    path.split('.')
+        .filter(|element| !element.is_empty())
+        .count()
+}
+
+/// Check if `parent` is a strict prefix of `path`, ignoring array selectors
+fn is_parent_path(parent: &str, path: &str) -> bool {
+    path_depth(parent) < path_depth(path) && paths_overlap(parent, path)
+}
+
+/// Check if deletion of the `deleted` key removes the value of `path` completely.
+/// Deletion of a single array element, like `a.b[1]`, keeps the rest of the values in place.
+fn removes_path(deleted: &str, path: &str) -> bool {
+    let removes_element = deleted.ends_with(']') && !deleted.ends_with("[]");
+    !removes_element && path_depth(deleted) <= path_depth(path) && paths_overlap(deleted, path)
+}
+
+fn value_matches_type(value: &Value, schema_type: PayloadSchemaType) -> bool {
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .all(|value| !value.is_array() && value_matches_type(value, schema_type)),
+        value => match schema_type {
+            PayloadSchemaType::Keyword | PayloadSchemaType::Text => value.is_string(),
+            PayloadSchemaType::Integer => value.is_i64(),
+            PayloadSchemaType::Float => value.is_number(),
+            PayloadSchemaType::Bool => value.is_boolean(),
+            PayloadSchemaType::Geo => serde_json::from_value::<GeoPoint>(value.clone()).is_ok(),
+            PayloadSchemaType::Datetime => value
+                .as_str()
+                .map_or(false, |string| parse_datetime_seconds(string).is_some()),
+            PayloadSchemaType::Uuid => value
+                .as_str()
+                .map_or(false, |string| parse_uuid(string).is_some()),
+        },
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use segment::types::{Payload, WithPayload};
+    use segment::types::{Condition, FieldCondition, Filter, Payload, WithPayload};
     use tempdir::TempDir;
 
     use super::*;
     use crate::collection_manager::fixtures::build_test_holder;
     use crate::collection_manager::segments_searcher::SegmentsSearcher;
     use crate::collection_manager::segments_updater::upsert_points;
-    use crate::operations::payload_ops::{DeletePayload, PayloadOps, SetPayload};
-    use crate::operations::point_ops::PointOperations;
+    use crate::operations::payload_ops::{DeletePayload, ModifyPayload, PayloadOps, SetPayload};
+    use crate::operations::point_ops::{PointOperations, PointStruct};
+    use crate::operations::Validate;
 
     #[tokio::test]
     async fn test_point_ops() {
@@ -152,8 +466,11 @@ mod tests {
             100,
             PayloadOps::SetPayload(SetPayload {
                 payload,
-                points: points.clone(),
+                points: Some(points.clone()),
+                filter: None,
+                key: None,
             }),
+            None,
         )
         .unwrap();
 
@@ -178,9 +495,11 @@ mod tests {
             &segments,
             101,
             PayloadOps::DeletePayload(DeletePayload {
-                points: vec![3.into()],
+                points: Some(vec![3.into()]),
+                filter: None,
                 keys: vec!["color".to_string(), "empty".to_string()],
             }),
+            None,
         )
         .unwrap();
 
@@ -206,6 +525,7 @@ mod tests {
             PayloadOps::ClearPayload {
                 points: vec![2.into()],
             },
+            None,
         )
         .unwrap();
         let res =
@@ -215,4 +535,287 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert!(!res[0].payload.as_ref().unwrap().contains_key("color"));
     }
+
+    #[tokio::test]
+    async fn test_payload_ops_by_filter() {
+        let dir = TempDir::new("segment_dir").unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let blue_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "color".to_string(),
+            "blue".to_string().into(),
+        )));
+        let payload: Payload = serde_json::from_str(r#"{"archived":true}"#).unwrap();
+
+        process_payload_operation(
+            &segments,
+            100,
+            PayloadOps::SetPayload(SetPayload {
+                payload,
+                points: None,
+                filter: Some(blue_filter),
+                key: None,
+            }),
+            None,
+        )
+        .unwrap();
+
+        let res = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into(), 3.into()],
+            &WithPayload::from(true),
+            false,
+        )
+        .await
+        .unwrap();
+        for record in &res {
+            let payload = record.payload.as_ref().unwrap();
+            assert_eq!(payload.contains_key("archived"), record.id == 3.into());
+        }
+
+        let archived_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "archived".to_string(),
+            true.into(),
+        )));
+        process_payload_operation(
+            &segments,
+            101,
+            PayloadOps::DeletePayload(DeletePayload {
+                keys: vec!["color".to_string()],
+                points: None,
+                filter: Some(archived_filter),
+            }),
+            None,
+        )
+        .unwrap();
+
+        let res = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into(), 3.into()],
+            &WithPayload::from(true),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.len(), 2);
+        for record in &res {
+            let payload = record.payload.as_ref().unwrap();
+            assert_eq!(payload.contains_key("color"), record.id == 1.into());
+        }
+
+        let no_selector = PayloadOps::DeletePayload(DeletePayload {
+            keys: vec!["color".to_string()],
+            points: None,
+            filter: None,
+        });
+        assert!(no_selector.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_modify_payload() {
+        let dir = TempDir::new("segment_dir").unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let increment = PayloadOps::ModifyPayload(ModifyPayload {
+            modifications: serde_json::from_value(serde_json::json!([
+                {"increment": {"key": "views", "value": 2}},
+                {"append": {"key": "color", "values": ["green"]}},
+            ]))
+            .unwrap(),
+            points: Some(vec![1.into(), 2.into()]),
+            filter: None,
+        });
+
+        process_payload_operation(&segments, 100, increment.clone(), None).unwrap();
+        process_payload_operation(&segments, 101, increment.clone(), None).unwrap();
+        // Replay of the already applied operation, e.g. on WAL recovery, changes nothing
+        process_payload_operation(&segments, 101, increment, None).unwrap();
+
+        let res = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into(), 2.into()],
+            &WithPayload::from(true),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.len(), 2);
+        for record in &res {
+            let payload = record.payload.as_ref().unwrap();
+            assert_eq!(payload.get_value("views"), Some(&serde_json::json!(4)));
+            assert_eq!(
+                payload.get_value("color"),
+                Some(&serde_json::json!(["red", "green", "green"]))
+            );
+        }
+
+        let schema: PayloadSchema = serde_json::from_value(serde_json::json!({
+            "fields": {"views": {"type": "integer"}},
+        }))
+        .unwrap();
+        let overflow = PayloadOps::ModifyPayload(ModifyPayload {
+            modifications: serde_json::from_value(serde_json::json!([
+                {"increment": {"key": "views", "value": i64::MAX}},
+            ]))
+            .unwrap(),
+            points: Some(vec![1.into()]),
+            filter: None,
+        });
+        assert!(process_payload_operation(&segments, 102, overflow, Some(&schema)).is_err());
+
+        // Failure of a single point rejects the operation before any point is changed
+        process_payload_operation(
+            &segments,
+            103,
+            PayloadOps::SetPayload(SetPayload {
+                payload: serde_json::json!({"views": "many"}).into(),
+                points: Some(vec![2.into()]),
+                filter: None,
+                key: None,
+            }),
+            None,
+        )
+        .unwrap();
+        let increment = PayloadOps::ModifyPayload(ModifyPayload {
+            modifications: serde_json::from_value(serde_json::json!([
+                {"increment": {"key": "views", "value": 1}},
+            ]))
+            .unwrap(),
+            points: Some(vec![1.into(), 2.into()]),
+            filter: None,
+        });
+        assert!(process_payload_operation(&segments, 104, increment, None).is_err());
+        let res =
+            SegmentsSearcher::retrieve(&segments, &[1.into()], &WithPayload::from(true), false)
+                .await
+                .unwrap();
+        let payload = res[0].payload.as_ref().unwrap();
+        assert_eq!(payload.get_value("views"), Some(&serde_json::json!(4)));
+    }
+
+    #[test]
+    fn test_payload_schema_validation() {
+        let schema: PayloadSchema = serde_json::from_value(serde_json::json!({
+            "fields": {
+                "name": {"type": "keyword", "required": true},
+                "price": {"type": "integer"},
+                "store.location": {"type": "geo", "required": true},
+            },
+            "allow_unknown_fields": false,
+        }))
+        .unwrap();
+
+        let upsert = |payload: serde_json::Value| {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperations::PointsList(vec![PointStruct {
+                    id: 1.into(),
+                    vector: vec![1., 1., 1., 1.],
+                    payload: Some(payload.into()),
+                }]),
+            ))
+        };
+        let set_payload = |payload: serde_json::Value| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayload {
+                payload: payload.into(),
+                points: Some(vec![1.into()]),
+                filter: None,
+                key: None,
+            }))
+        };
+
+        let valid = upsert(serde_json::json!({
+            "name": "cheese",
+            "price": [10, 20],
+            "store": {"location": {"lon": 13.4, "lat": 52.5}},
+        }));
+        assert!(CollectionUpdater::validate_payload_schema(&schema, &valid).is_ok());
+
+        let invalid = upsert(serde_json::json!({"price": "10", "color": "red"}));
+        let error = CollectionUpdater::validate_payload_schema(&schema, &invalid)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("point 1: missing required field `name`"));
+        assert!(error.contains("point 1: field `price`"));
+        assert!(error.contains("point 1: unknown field `color`"));
+
+        let nested_unknown = upsert(serde_json::json!({
+            "name": "cheese",
+            "store": [
+                {"location": {"lon": 13.4, "lat": 52.5}},
+                {"location": {"lon": 2.3, "lat": 48.8}, "address": "Paris"},
+            ],
+        }));
+        let error = CollectionUpdater::validate_payload_schema(&schema, &nested_unknown)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("point 1: unknown field `store.address`"));
+
+        let scalar_parent = upsert(serde_json::json!({"name": "cheese", "store": "Berlin"}));
+        let error = CollectionUpdater::validate_payload_schema(&schema, &scalar_parent)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("point 1: unknown field `store`"));
+
+        let partial = set_payload(serde_json::json!({"price": 10}));
+        assert!(CollectionUpdater::validate_payload_schema(&schema, &partial).is_ok());
+
+        let removes_required = set_payload(serde_json::json!({"name": null}));
+        assert!(CollectionUpdater::validate_payload_schema(&schema, &removes_required).is_err());
+
+        let delete_required = CollectionUpdateOperations::PayloadOperation(
+            PayloadOps::DeletePayload(DeletePayload {
+                keys: vec!["name".to_string()],
+                points: Some(vec![1.into()]),
+                filter: None,
+            }),
+        );
+        assert!(CollectionUpdater::validate_payload_schema(&schema, &delete_required).is_err());
+
+        let delete_parent_of_required = CollectionUpdateOperations::PayloadOperation(
+            PayloadOps::DeletePayload(DeletePayload {
+                keys: vec!["store".to_string()],
+                points: Some(vec![1.into()]),
+                filter: None,
+            }),
+        );
+        let error = CollectionUpdater::validate_payload_schema(&schema, &delete_parent_of_required)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("required field `store.location` can't be deleted with `store`"));
+
+        let overwrite_parent = set_payload(serde_json::json!({"store": {"city": "Berlin"}}));
+        assert!(CollectionUpdater::validate_payload_schema(&schema, &overwrite_parent).is_err());
+        let overwrite_parent = set_payload(serde_json::json!({
+            "store": {"location": {"lon": 13.4, "lat": 52.5}}
+        }));
+        assert!(CollectionUpdater::validate_payload_schema(&schema, &overwrite_parent).is_ok());
+
+        let modify_payload = |modification: serde_json::Value| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::ModifyPayload(ModifyPayload {
+                modifications: vec![serde_json::from_value(modification).unwrap()],
+                points: Some(vec![1.into()]),
+                filter: None,
+            }))
+        };
+        let increment_price = modify_payload(serde_json::json!({
+            "increment": {"key": "price", "value": 5}
+        }));
+        assert!(CollectionUpdater::validate_payload_schema(&schema, &increment_price).is_ok());
+        let increment_price_by_float = modify_payload(serde_json::json!({
+            "increment": {"key": "price", "value": 0.5}
+        }));
+        assert!(
+            CollectionUpdater::validate_payload_schema(&schema, &increment_price_by_float).is_err()
+        );
+
+        // Resulting values of read-modify-write operations are only known while applied
+        let modified: Payload = serde_json::json!({
+            "name": "cheese",
+            "price": 9.3e18,
+            "store": {"location": {"lon": 13.4, "lat": 52.5}},
+        })
+        .into();
+        assert!(check_modified_payload(&schema, &modified, ["price"].into_iter()).is_err());
+        assert!(check_modified_payload(&schema, &modified, ["name"].into_iter()).is_ok());
+    }
 }
@@ -122,6 +122,7 @@ pub(crate) fn get_merge_optimizer(
             distance: Distance::Dot,
             shard_number: NonZeroU32::new(1).unwrap(),
             on_disk_payload: false,
+            payload_schema: None,
         },
         Default::default(),
     )
@@ -145,6 +146,7 @@ pub(crate) fn get_indexing_optimizer(
             distance: Distance::Dot,
             shard_number: NonZeroU32::new(1).unwrap(),
             on_disk_payload: false,
+            payload_schema: None,
         },
         Default::default(),
     )
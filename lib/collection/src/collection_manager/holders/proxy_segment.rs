@@ -10,9 +10,10 @@ use segment::entry::entry_point::{OperationResult, SegmentEntry, SegmentFailedSt
 use segment::index::field_index::CardinalityEstimation;
 use segment::segment_constructor::load_segment;
 use segment::types::{
-    Condition, Filter, Payload, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType,
-    VectorElementType, WithPayload,
+    Condition, FacetValue, Filter, NumericAggregation, OrderBy, Payload, PayloadFieldSchema,
+    PayloadIndexStats, PayloadKeyType, PayloadKeyTypeRef, PointIdType, QueryExplain, ScoredPoint,
+    SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, VectorElementType,
+    WithPayload,
 };
 use uuid::Uuid;
 
@@ -20,7 +21,7 @@ use crate::collection_manager::holders::segment_holder::LockedSegment;
 
 type LockedRmSet = Arc<RwLock<HashSet<PointIdType>>>;
 type LockedFieldsSet = Arc<RwLock<HashSet<PayloadKeyType>>>;
-type LockedFieldsMap = Arc<RwLock<HashMap<PayloadKeyType, PayloadSchemaType>>>;
+type LockedFieldsMap = Arc<RwLock<HashMap<PayloadKeyType, PayloadFieldSchema>>>;
 
 /// This object is a wrapper around read-only segment.
 /// It could be used to provide all read and write operations while wrapped segment is being optimized (i.e. not available for writing)
@@ -150,20 +151,33 @@ impl SegmentEntry for ProxySegment {
         top: usize,
         params: Option<&SearchParams>,
     ) -> OperationResult<Vec<ScoredPoint>> {
+        self.search_explain(vector, with_payload, with_vector, filter, top, params)
+            .map(|(result, _explain)| result)
+    }
+
+    fn search_explain(
+        &self,
+        vector: &[VectorElementType],
+        with_payload: &WithPayload,
+        with_vector: bool,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+    ) -> OperationResult<(Vec<ScoredPoint>, QueryExplain)> {
         let deleted_points = self.deleted_points.read();
 
         // Some point might be deleted after temporary segment creation
         // We need to prevent them from being found by search request
         // That is why we need to pass additional filter for deleted points
         let do_update_filter = !deleted_points.is_empty();
-        let mut wrapped_result = if do_update_filter {
+        let (mut wrapped_result, mut explain) = if do_update_filter {
             // ToDo: Come up with better way to pass deleted points into Filter
             // e.g. implement AtomicRefCell for Serializer.
             // This copy might slow process down if there will be a lot of deleted points
             let wrapped_filter =
                 self.add_deleted_points_condition_to_filter(filter, &deleted_points);
 
-            self.wrapped_segment.get().read().search(
+            self.wrapped_segment.get().read().search_explain(
                 vector,
                 with_payload,
                 with_vector,
@@ -172,7 +186,7 @@ impl SegmentEntry for ProxySegment {
                 params,
             )?
         } else {
-            self.wrapped_segment.get().read().search(
+            self.wrapped_segment.get().read().search_explain(
                 vector,
                 with_payload,
                 with_vector,
@@ -182,7 +196,7 @@ impl SegmentEntry for ProxySegment {
             )?
         };
 
-        let mut write_result = self.write_segment.get().read().search(
+        let (mut write_result, write_explain) = self.write_segment.get().read().search_explain(
             vector,
             with_payload,
             with_vector,
@@ -192,7 +206,8 @@ impl SegmentEntry for ProxySegment {
         )?;
 
         wrapped_result.append(&mut write_result);
-        Ok(wrapped_result)
+        explain.merge(write_explain);
+        Ok((wrapped_result, explain))
     }
 
     fn upsert_point(
@@ -253,6 +268,20 @@ impl SegmentEntry for ProxySegment {
             .set_payload(op_num, point_id, payload)
     }
 
+    fn set_payload_by_key(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .set_payload_by_key(op_num, point_id, payload, key)
+    }
+
     fn delete_payload(
         &mut self,
         op_num: SeqNumberType,
@@ -308,6 +337,31 @@ impl SegmentEntry for ProxySegment {
         };
     }
 
+    fn payload_numeric_value(
+        &self,
+        point_id: PointIdType,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<Option<f64>> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment
+                .get()
+                .read()
+                .payload_numeric_value(point_id, key)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.payload_numeric_value(point_id, key);
+                }
+            }
+            self.wrapped_segment
+                .get()
+                .read()
+                .payload_numeric_value(point_id, key)
+        };
+    }
+
     /// Not implemented for proxy
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // iter_points is not available for Proxy implementation
@@ -345,6 +399,104 @@ impl SegmentEntry for ProxySegment {
         read_points
     }
 
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        order_by: &OrderBy,
+    ) -> OperationResult<Vec<(f64, PointIdType)>> {
+        let deleted_points = self.deleted_points.read();
+        let mut read_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_ordered_filtered(offset, limit, filter, order_by)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_ordered_filtered(
+                offset,
+                limit,
+                Some(&wrapped_filter),
+                order_by,
+            )?
+        };
+        let mut write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_ordered_filtered(offset, limit, filter, order_by)?;
+        read_points.append(&mut write_segment_points);
+        read_points.sort_by(|a, b| order_by.compare(*a, *b));
+        read_points.truncate(limit);
+        Ok(read_points)
+    }
+
+    fn facet(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        exact: bool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let deleted_points = self.deleted_points.read();
+        let mut counts = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(key, filter, exact)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(key, Some(&wrapped_filter), exact)?
+        };
+        let write_segment_counts = self.write_segment.get().read().facet(key, filter, exact)?;
+        for (value, count) in write_segment_counts {
+            *counts.entry(value).or_default() += count;
+        }
+        Ok(counts)
+    }
+
+    fn aggregate(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        histogram_interval: Option<f64>,
+    ) -> OperationResult<NumericAggregation> {
+        let deleted_points = self.deleted_points.read();
+        let mut aggregation = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .aggregate(key, filter, histogram_interval)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().aggregate(
+                key,
+                Some(&wrapped_filter),
+                histogram_interval,
+            )?
+        };
+        let write_segment_aggregation =
+            self.write_segment
+                .get()
+                .read()
+                .aggregate(key, filter, histogram_interval)?;
+        aggregation.merge(write_segment_aggregation);
+        Ok(aggregation)
+    }
+
+    fn payload_index_stats(&self) -> OperationResult<HashMap<PayloadKeyType, PayloadIndexStats>> {
+        let mut stats = self.wrapped_segment.get().read().payload_index_stats()?;
+        let write_segment_stats = self.write_segment.get().read().payload_index_stats()?;
+        PayloadIndexStats::merge_fields(&mut stats, write_segment_stats);
+        Ok(stats)
+    }
+
     fn has_point(&self, point_id: PointIdType) -> bool {
         return if self.deleted_points.read().contains(&point_id) {
             self.write_segment.get().read().has_point(point_id)
@@ -469,7 +621,7 @@ impl SegmentEntry for ProxySegment {
         &mut self,
         op_num: u64,
         key: PayloadKeyTypeRef,
-        field_type: &Option<PayloadSchemaType>,
+        field_type: &Option<PayloadFieldSchema>,
     ) -> OperationResult<bool> {
         if self.version() > op_num {
             return Ok(false);
@@ -494,7 +646,7 @@ impl SegmentEntry for ProxySegment {
         Ok(true)
     }
 
-    fn get_indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadSchemaType> {
+    fn get_indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
         let indexed_fields = self.wrapped_segment.get().read().get_indexed_fields();
         indexed_fields
             .into_iter()
@@ -603,7 +755,7 @@ mod tests {
 
         let deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
         let created_indexes = Arc::new(RwLock::new(
-            HashMap::<PayloadKeyType, PayloadSchemaType>::new(),
+            HashMap::<PayloadKeyType, PayloadFieldSchema>::new(),
         ));
 
         let mut proxy_segment = ProxySegment::new(
@@ -679,7 +831,7 @@ mod tests {
 
         let deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
         let created_indexes = Arc::new(RwLock::new(
-            HashMap::<PayloadKeyType, PayloadSchemaType>::new(),
+            HashMap::<PayloadKeyType, PayloadFieldSchema>::new(),
         ));
 
         let mut proxy_segment = ProxySegment::new(
@@ -709,7 +861,7 @@ mod tests {
 
         let deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
         let created_indexes = Arc::new(RwLock::new(
-            HashMap::<PayloadKeyType, PayloadSchemaType>::new(),
+            HashMap::<PayloadKeyType, PayloadFieldSchema>::new(),
         ));
 
         let mut proxy_segment = ProxySegment::new(
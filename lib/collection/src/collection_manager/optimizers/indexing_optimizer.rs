@@ -274,6 +274,7 @@ mod tests {
                 distance: segment_config.distance,
                 shard_number: NonZeroU32::new(1).unwrap(),
                 on_disk_payload: false,
+                payload_schema: None,
             },
             Default::default(),
         );
@@ -318,7 +319,7 @@ mod tests {
             opnum.next().unwrap(),
             &FieldIndexOperations::CreateIndex(CreateIndex {
                 field_name: payload_field.to_owned(),
-                field_type: Some(PayloadSchemaType::Integer),
+                field_type: Some(PayloadSchemaType::Integer.into()),
             }),
         )
         .unwrap();
@@ -10,7 +10,7 @@ use segment::segment::Segment;
 use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::{
-    HnswConfig, Indexes, PayloadKeyType, PayloadSchemaType, PayloadStorageType, PointIdType,
+    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PayloadStorageType, PointIdType,
     SegmentConfig, StorageType, VECTOR_ELEMENT_SIZE,
 };
 
@@ -223,22 +223,26 @@ pub trait SegmentOptimizer {
         optimizing_segments: &[LockedSegment],
         proxy_deleted_points: Arc<RwLock<HashSet<PointIdType>>>,
         proxy_deleted_indexes: Arc<RwLock<HashSet<PayloadKeyType>>>,
-        proxy_created_indexes: Arc<RwLock<HashMap<PayloadKeyType, PayloadSchemaType>>>,
+        proxy_created_indexes: Arc<RwLock<HashMap<PayloadKeyType, PayloadFieldSchema>>>,
         stopped: &AtomicBool,
     ) -> CollectionResult<Segment> {
         let mut segment_builder = self.optimized_segment_builder(optimizing_segments)?;
 
         self.check_cancellation(stopped)?;
 
-        for segment in optimizing_segments {
-            match segment {
-                LockedSegment::Original(segment_arc) => {
-                    let segment_guard = segment_arc.read();
-                    segment_builder.update_from(&segment_guard, stopped)?;
-                }
+        let segment_guards = optimizing_segments
+            .iter()
+            .map(|segment| match segment {
+                LockedSegment::Original(segment_arc) => segment_arc.read(),
                 LockedSegment::Proxy(_) => panic!("Attempt to optimize segment which is already currently under optimization. Should never happen"),
-            }
-        }
+            })
+            .collect_vec();
+        // All segments are copied at once, so that points could be grouped across them
+        segment_builder.update(
+            &segment_guards.iter().map(|guard| &**guard).collect_vec(),
+            stopped,
+        )?;
+        drop(segment_guards);
 
         for field in proxy_deleted_indexes.read().iter() {
             segment_builder.indexed_fields.remove(field);
@@ -277,7 +281,7 @@ pub trait SegmentOptimizer {
             optimized_segment.create_field_index(
                 optimized_segment.version(),
                 create_field_name,
-                &Some(*schema_type),
+                &Some(schema_type.clone()),
             )?;
             self.check_cancellation(stopped)?;
         }
@@ -339,7 +343,7 @@ pub trait SegmentOptimizer {
         let proxy_deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
         let proxy_created_indexes = Arc::new(RwLock::new(HashMap::<
             PayloadKeyType,
-            PayloadSchemaType,
+            PayloadFieldSchema,
         >::new()));
 
         let proxies = optimizing_segments.iter().map(|sg| {
@@ -413,7 +417,7 @@ pub trait SegmentOptimizer {
                 optimized_segment.create_field_index(
                     optimized_segment.version(),
                     created_field_name,
-                    &Some(*schema_type),
+                    &Some(schema_type.clone()),
                 )?;
             }
 
@@ -204,6 +204,7 @@ mod tests {
                 distance: Distance::Dot,
                 shard_number: NonZeroU32::new(1).unwrap(),
                 on_disk_payload: false,
+                payload_schema: None,
             },
             Default::default(),
         );
@@ -6,11 +6,14 @@ use itertools::Itertools;
 use parking_lot::RwLock;
 use segment::entry::entry_point::OperationError;
 use segment::spaces::tools::peek_top_largest_scores_iterable;
-use segment::types::{PointIdType, ScoredPoint, SeqNumberType, WithPayload, WithPayloadInterface};
+use segment::types::{
+    PointIdType, QueryExplain, ScoreType, ScoredPoint, SeqNumberType, WithPayload,
+    WithPayloadInterface,
+};
 use tokio::runtime::Handle;
 
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
-use crate::operations::types::{CollectionResult, Record, SearchRequest};
+use crate::operations::types::{CollectionResult, Record, SearchRequest, SegmentExplain};
 
 /// Simple implementation of segment manager
 ///  - rebuild segment for memory optimization purposes
@@ -23,43 +26,57 @@ impl SegmentsSearcher {
         request: Arc<SearchRequest>,
         runtime_handle: &Handle,
     ) -> CollectionResult<Vec<ScoredPoint>> {
+        let (top_scores, _explain) =
+            Self::search_explain(segments, request, runtime_handle).await?;
+        Ok(top_scores)
+    }
+
+    /// Same as `search`, but also returns explanation of the search in each segment
+    pub async fn search_explain(
+        segments: &RwLock<SegmentHolder>,
+        request: Arc<SearchRequest>,
+        runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Vec<SegmentExplain>)> {
         // Using { } block to ensure segments variable is dropped in the end of it
         // and is not transferred across the all_searches.await? boundary as it
         // does not impl Send trait
-        let searches: Vec<_> = {
+        let (segment_ids, searches): (Vec<_>, Vec<_>) = {
             let segments = segments.read();
 
             let some_segment = segments.iter().next();
 
             if some_segment.is_none() {
-                return Ok(vec![]);
+                return Ok((vec![], vec![]));
             }
 
             segments
                 .iter()
-                .map(|(_id, segment)| search_in_segment(segment.clone(), request.clone()))
-                .map(|f| runtime_handle.spawn(f))
-                .collect()
+                .map(|(id, segment)| {
+                    let search = search_in_segment(segment.clone(), request.clone());
+                    (*id, runtime_handle.spawn(search))
+                })
+                .unzip()
         };
 
         let all_searches = try_join_all(searches);
         let all_search_results = all_searches.await?;
 
-        match all_search_results
-            .iter()
-            .filter_map(|res| res.to_owned().err())
-            .next()
-        {
-            None => {}
-            Some(error) => return Err(error),
+        let mut segments_explain = Vec::with_capacity(segment_ids.len());
+        let mut found_points = Vec::new();
+        for (segment_id, search_result) in segment_ids.into_iter().zip(all_search_results) {
+            let (points, explain) = search_result?;
+            segments_explain.push(SegmentExplain {
+                segment_id,
+                explain,
+            });
+            found_points.extend(points);
         }
 
         let mut seen_idx: HashSet<PointIdType> = HashSet::new();
 
         let top_scores = peek_top_largest_scores_iterable(
-            all_search_results
+            found_points
                 .into_iter()
-                .flat_map(Result::unwrap) // already checked for errors
                 .sorted_by_key(|a| (a.id, 1 - a.version as i64)) // Prefer higher version first
                 .dedup_by(|a, b| a.id == b.id) // Keep only highest version
                 .filter(|scored| {
@@ -70,7 +87,7 @@ impl SegmentsSearcher {
             request.limit + request.offset,
         );
 
-        Ok(top_scores)
+        Ok((top_scores, segments_explain))
     }
 
     pub async fn retrieve(
@@ -106,6 +123,7 @@ impl SegmentsSearcher {
                         } else {
                             None
                         },
+                        order_value: None,
                     },
                 );
                 point_version.insert(id, version);
@@ -119,24 +137,60 @@ impl SegmentsSearcher {
 async fn search_in_segment(
     segment: LockedSegment,
     request: Arc<SearchRequest>,
-) -> CollectionResult<Vec<ScoredPoint>> {
+) -> CollectionResult<(Vec<ScoredPoint>, QueryExplain)> {
     let with_payload_interface = request
         .with_payload
         .as_ref()
         .unwrap_or(&WithPayloadInterface::Bool(false));
     let with_payload = WithPayload::from(with_payload_interface);
     let with_vector = request.with_vector;
+    let top = request.limit + request.offset;
+
+    let segment = segment.get();
+    let segment_guard = segment.read();
+
+    let rescore = match &request.rescore {
+        None => {
+            let res = segment_guard.search_explain(
+                &request.vector,
+                &with_payload,
+                with_vector,
+                request.filter.as_ref(),
+                top,
+                request.params.as_ref(),
+            )?;
+            return Ok(res);
+        }
+        Some(rescore) => rescore,
+    };
 
-    let res = segment.get().read().search(
+    let (candidates, explain) = segment_guard.search_explain(
         &request.vector,
         &with_payload,
         with_vector,
         request.filter.as_ref(),
-        request.limit + request.offset,
+        top * rescore.oversampling(),
         request.params.as_ref(),
     )?;
 
-    Ok(res)
+    let distance = segment_guard.config().distance;
+    let mut rescored = Vec::with_capacity(candidates.len());
+    for mut scored_point in candidates {
+        let similarity = distance.postprocess_score(scored_point.score);
+        if let Some(threshold) = request.score_threshold {
+            if !distance.check_threshold(similarity, threshold) {
+                continue;
+            }
+        }
+        let point_id = scored_point.id;
+        let score = rescore.expression.evaluate(similarity, &|key| {
+            segment_guard.payload_numeric_value(point_id, key)
+        })?;
+        scored_point.score = score as ScoreType;
+        rescored.push(scored_point);
+    }
+
+    Ok((peek_top_largest_scores_iterable(rescored, top), explain))
 }
 
 #[cfg(test)]
@@ -163,6 +217,8 @@ mod tests {
             limit: 5,
             score_threshold: None,
             offset: 0,
+            rescore: None,
+            explain: false,
         });
 
         let result = SegmentsSearcher::search(&segment_holder, req, &Handle::current())
@@ -4,12 +4,14 @@ use itertools::Itertools;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use segment::entry::entry_point::{OperationResult, SegmentEntry};
 use segment::types::{
-    Filter, Payload, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType,
+    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
     SeqNumberType, VectorElementType,
 };
 
+use crate::collection_manager::collection_updater::check_modified_payload;
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
-use crate::operations::payload_ops::PayloadOps;
+use crate::config::PayloadSchema;
+use crate::operations::payload_ops::{PayloadModification, PayloadOps};
 use crate::operations::point_ops::{Batch, PointInsertOperations, PointOperations};
 use crate::operations::types::{CollectionError, CollectionResult, VectorType};
 use crate::operations::FieldIndexOperations;
@@ -54,10 +56,14 @@ pub(crate) fn set_payload(
     op_num: SeqNumberType,
     payload: &Payload,
     points: &[PointIdType],
+    key: Option<PayloadKeyTypeRef>,
 ) -> CollectionResult<usize> {
     let updated_points =
         segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
-            write_segment.set_payload(op_num, id, payload)?;
+            match key {
+                None => write_segment.set_payload(op_num, id, payload)?,
+                Some(key) => write_segment.set_payload_by_key(op_num, id, payload, key)?,
+            };
             Ok(true)
         })?;
 
@@ -65,6 +71,27 @@ pub(crate) fn set_payload(
     Ok(updated_points.len())
 }
 
+/// Set payload to all points in all segments matching the given filter
+pub(crate) fn set_payload_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    payload: &Payload,
+    filter: &Filter,
+    key: Option<PayloadKeyTypeRef>,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    let updated_points =
+        segments.apply_points_to_appendable(op_num, &affected_points, |id, write_segment| {
+            match key {
+                None => write_segment.set_payload(op_num, id, payload)?,
+                Some(key) => write_segment.set_payload_by_key(op_num, id, payload, key)?,
+            };
+            Ok(true)
+        })?;
+
+    Ok(updated_points.len())
+}
+
 pub(crate) fn delete_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -84,6 +111,26 @@ pub(crate) fn delete_payload(
     Ok(updated_points.len())
 }
 
+/// Delete payload keys from all points in all segments matching the given filter
+pub(crate) fn delete_payload_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    filter: &Filter,
+    keys: &[PayloadKeyType],
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    let updated_points =
+        segments.apply_points_to_appendable(op_num, &affected_points, |id, write_segment| {
+            let mut res = true;
+            for key in keys {
+                res = write_segment.delete_payload(op_num, id, key)? && res;
+            }
+            Ok(res)
+        })?;
+
+    Ok(updated_points.len())
+}
+
 pub(crate) fn clear_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -104,13 +151,7 @@ pub(crate) fn clear_payload_by_filter(
     op_num: SeqNumberType,
     filter: &Filter,
 ) -> CollectionResult<usize> {
-    let mut points_to_clear: Vec<PointIdType> = Vec::new();
-
-    segments.apply_segments(|s| {
-        let points = s.read_filtered(None, usize::MAX, Some(filter));
-        points_to_clear.extend_from_slice(points.as_slice());
-        Ok(true)
-    })?;
+    let points_to_clear = points_by_filter(segments, filter)?;
 
     let updated_points = segments.apply_points_to_appendable(
         op_num,
@@ -121,11 +162,102 @@ pub(crate) fn clear_payload_by_filter(
     Ok(updated_points.len())
 }
 
+/// Apply read-modify-write payload modifications to the given points
+pub(crate) fn modify_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    modifications: &[PayloadModification],
+    points: &[PointIdType],
+    payload_schema: Option<&PayloadSchema>,
+) -> CollectionResult<usize> {
+    let checked_points =
+        check_payload_modifications(segments, op_num, modifications, points, payload_schema)?;
+    check_unprocessed_points(points, &checked_points)?;
+
+    let updated_points =
+        segments.apply_points_to_appendable(op_num, points, |id, write_segment| {
+            modify_point_payload(write_segment, op_num, id, modifications, payload_schema)
+        })?;
+
+    check_unprocessed_points(points, &updated_points)?;
+    Ok(updated_points.len())
+}
+
+/// Apply read-modify-write payload modifications to all points matching the given filter
+pub(crate) fn modify_payload_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    modifications: &[PayloadModification],
+    filter: &Filter,
+    payload_schema: Option<&PayloadSchema>,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    check_payload_modifications(
+        segments,
+        op_num,
+        modifications,
+        &affected_points,
+        payload_schema,
+    )?;
+
+    let updated_points =
+        segments.apply_points_to_appendable(op_num, &affected_points, |id, write_segment| {
+            modify_point_payload(write_segment, op_num, id, modifications, payload_schema)
+        })?;
+
+    Ok(updated_points.len())
+}
+
+/// Dry run of the modifications on every point before any of them is changed,
+/// so a failure of a single point, e.g. a type mismatch, rejects the whole operation.
+/// Updates are applied one at a time, so the result can't change before the actual run.
+/// Returns ids of the found points.
+fn check_payload_modifications(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    modifications: &[PayloadModification],
+    points: &[PointIdType],
+    payload_schema: Option<&PayloadSchema>,
+) -> CollectionResult<HashSet<PointIdType>> {
+    let mut checked_points = HashSet::new();
+    segments.read_points(points, |id, segment| {
+        checked_points.insert(id);
+        // Already applied to the point, it is skipped by the actual run
+        if segment
+            .point_version(id)
+            .map_or(false, |version| version >= op_num)
+        {
+            return Ok(true);
+        }
+        let mut payload = segment.payload(id)?;
+        apply_payload_modifications(&mut payload, modifications, payload_schema)?;
+        Ok(true)
+    })?;
+    Ok(checked_points)
+}
+
+/// Collect ids of points from all segments matching the given filter.
+/// Ids never leave the shard, so the selection is resolved right next to the data
+fn points_by_filter(
+    segments: &SegmentHolder,
+    filter: &Filter,
+) -> CollectionResult<Vec<PointIdType>> {
+    let mut affected_points: Vec<PointIdType> = Vec::new();
+
+    segments.apply_segments(|s| {
+        let points = s.read_filtered(None, usize::MAX, Some(filter));
+        affected_points.extend_from_slice(points.as_slice());
+        Ok(true)
+    })?;
+
+    Ok(affected_points)
+}
+
 pub(crate) fn create_field_index(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
     field_name: PayloadKeyTypeRef,
-    field_type: &Option<PayloadSchemaType>,
+    field_type: &Option<PayloadFieldSchema>,
 ) -> CollectionResult<usize> {
     let res = segments.apply_segments(|write_segment| {
         write_segment.create_field_index(op_num, field_name, field_type)
@@ -143,6 +275,38 @@ pub(crate) fn delete_field_index(
     Ok(res)
 }
 
+/// Payload is read and written back while the segment is locked for writing,
+/// so no concurrent update of the point can be lost in between.
+/// Points with version >= `op_num` are skipped by the caller, which makes WAL replay idempotent.
+fn modify_point_payload(
+    segment: &mut RwLockWriteGuard<dyn SegmentEntry>,
+    op_num: SeqNumberType,
+    point_id: PointIdType,
+    modifications: &[PayloadModification],
+    payload_schema: Option<&PayloadSchema>,
+) -> OperationResult<bool> {
+    let mut payload = segment.payload(point_id)?;
+    apply_payload_modifications(&mut payload, modifications, payload_schema)?;
+    segment.set_full_payload(op_num, point_id, &payload)
+}
+
+fn apply_payload_modifications(
+    payload: &mut Payload,
+    modifications: &[PayloadModification],
+    payload_schema: Option<&PayloadSchema>,
+) -> OperationResult<()> {
+    for modification in modifications {
+        modification.apply(payload)?;
+    }
+    if let Some(schema) = payload_schema {
+        let modified_keys = modifications
+            .iter()
+            .map(|modification| modification.key().as_str());
+        check_modified_payload(schema, payload, modified_keys)?;
+    }
+    Ok(())
+}
+
 fn upsert_with_payload(
     segment: &mut RwLockWriteGuard<dyn SegmentEntry>,
     op_num: SeqNumberType,
@@ -264,21 +428,65 @@ pub(crate) fn process_payload_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
     payload_operation: PayloadOps,
+    payload_schema: Option<&PayloadSchema>,
 ) -> CollectionResult<usize> {
     match payload_operation {
         PayloadOps::SetPayload(sp) => {
             let payload: Payload = sp.payload;
-            set_payload(&segments.read(), op_num, &payload, &sp.points)
-        }
-        PayloadOps::DeletePayload(dp) => {
-            delete_payload(&segments.read(), op_num, &dp.points, &dp.keys)
+            match (sp.points, sp.filter) {
+                (Some(points), _) => set_payload(
+                    &segments.read(),
+                    op_num,
+                    &payload,
+                    &points,
+                    sp.key.as_deref(),
+                ),
+                (None, Some(filter)) => set_payload_by_filter(
+                    &segments.read(),
+                    op_num,
+                    &payload,
+                    &filter,
+                    sp.key.as_deref(),
+                ),
+                (None, None) => Err(CollectionError::BadInput {
+                    description: "No points or filter specified".to_string(),
+                }),
+            }
         }
+        PayloadOps::DeletePayload(dp) => match (dp.points, dp.filter) {
+            (Some(points), _) => delete_payload(&segments.read(), op_num, &points, &dp.keys),
+            (None, Some(filter)) => {
+                delete_payload_by_filter(&segments.read(), op_num, &filter, &dp.keys)
+            }
+            (None, None) => Err(CollectionError::BadInput {
+                description: "No points or filter specified".to_string(),
+            }),
+        },
         PayloadOps::ClearPayload { ref points, .. } => {
             clear_payload(&segments.read(), op_num, points)
         }
         PayloadOps::ClearPayloadByFilter(ref filter) => {
             clear_payload_by_filter(&segments.read(), op_num, filter)
         }
+        PayloadOps::ModifyPayload(mp) => match (mp.points, mp.filter) {
+            (Some(points), _) => modify_payload(
+                &segments.read(),
+                op_num,
+                &mp.modifications,
+                &points,
+                payload_schema,
+            ),
+            (None, Some(filter)) => modify_payload_by_filter(
+                &segments.read(),
+                op_num,
+                &mp.modifications,
+                &filter,
+                payload_schema,
+            ),
+            (None, None) => Err(CollectionError::BadInput {
+                description: "No points or filter specified".to_string(),
+            }),
+        },
     }
 }
 
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use segment::types::{Order, PointIdType, ScoreType, ScoredPoint};
+
+use crate::operations::types::Fusion;
+
+/// Smoothing constant of the reciprocal rank fusion.
+/// Lower values give more preference to the top-ranked points of each search.
+pub const RRF_K: usize = 60;
+
+/// Merge results of several searches into a single ranking.
+///
+/// # Arguments
+///
+/// * `results` - pairs of search weight and search result, each result ordered from best to worst
+/// * `fusion` - method of combining the scores
+/// * `order` - order of the scores produced by the searches
+///
+/// # Result
+///
+/// Fused points, ordered by fused score. Fused score is always "larger is better".
+pub fn fuse_search_results(
+    results: Vec<(ScoreType, Vec<ScoredPoint>)>,
+    fusion: Fusion,
+    order: Order,
+) -> Vec<ScoredPoint> {
+    let mut fused: HashMap<PointIdType, ScoredPoint> = HashMap::new();
+
+    for (weight, points) in results {
+        let scores = match fusion {
+            Fusion::Rrf => rrf_scores(&points),
+            Fusion::Weighted => normalized_scores(&points, &order),
+        };
+        for (point, score) in points.into_iter().zip(scores) {
+            let score = weight * score;
+            fused
+                .entry(point.id)
+                .and_modify(|fused_point| fused_point.score += score)
+                .or_insert(ScoredPoint { score, ..point });
+        }
+    }
+
+    let mut result: Vec<_> = fused.into_values().collect();
+    // Resolve ties by id to keep pagination stable across requests
+    result.sort_unstable_by(|a, b| b.cmp(a).then_with(|| a.id.cmp(&b.id)));
+    result
+}
+
+/// Score of each point is `1 / (k + rank)`, where rank starts from 1
+fn rrf_scores(points: &[ScoredPoint]) -> Vec<ScoreType> {
+    (0..points.len())
+        .map(|idx| 1.0 / (RRF_K + idx + 1) as ScoreType)
+        .collect()
+}
+
+/// Min-max normalization of the scores into `[0, 1]` range, where 1 is the best score
+fn normalized_scores(points: &[ScoredPoint], order: &Order) -> Vec<ScoreType> {
+    let min = points
+        .iter()
+        .map(|point| point.score)
+        .fold(ScoreType::INFINITY, ScoreType::min);
+    let max = points
+        .iter()
+        .map(|point| point.score)
+        .fold(ScoreType::NEG_INFINITY, ScoreType::max);
+
+    let range = max - min;
+    points
+        .iter()
+        .map(|point| {
+            if range <= ScoreType::EPSILON {
+                return 1.0;
+            }
+            match order {
+                Order::LargeBetter => (point.score - min) / range,
+                Order::SmallBetter => (max - point.score) / range,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored(id: u64, score: ScoreType) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+        }
+    }
+
+    #[test]
+    fn test_rrf_fusion() {
+        let first = vec![scored(1, 0.9), scored(2, 0.8), scored(3, 0.7)];
+        let second = vec![scored(3, 10.0), scored(4, 5.0)];
+
+        let fused = fuse_search_results(
+            vec![(1.0, first), (1.0, second)],
+            Fusion::Rrf,
+            Order::LargeBetter,
+        );
+
+        let ids: Vec<_> = fused.iter().map(|point| point.id).collect();
+        assert_eq!(ids, vec![3.into(), 1.into(), 2.into(), 4.into()]);
+    }
+
+    #[test]
+    fn test_weighted_fusion() {
+        // Euclid-like scores, smaller is better
+        let first = vec![scored(1, 0.0), scored(2, 1.0), scored(3, 2.0)];
+        let second = vec![scored(3, 1.0), scored(1, 3.0)];
+
+        let fused = fuse_search_results(
+            vec![(1.0, first), (3.0, second)],
+            Fusion::Weighted,
+            Order::SmallBetter,
+        );
+
+        let ids: Vec<_> = fused.iter().map(|point| point.id).collect();
+        assert_eq!(ids, vec![3.into(), 1.into(), 2.into()]);
+        assert!((fused[0].score - 3.0).abs() < 1e-6);
+    }
+}
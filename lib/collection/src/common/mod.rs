@@ -1 +1,2 @@
+pub mod fusion;
 pub mod stoppable_task;
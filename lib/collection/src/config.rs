@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::num::NonZeroU32;
@@ -6,7 +7,7 @@ use std::path::Path;
 use atomicwrites::AtomicFile;
 use atomicwrites::OverwriteBehavior::AllowOverwrite;
 use schemars::JsonSchema;
-use segment::types::{Distance, HnswConfig};
+use segment::types::{Distance, HnswConfig, PayloadKeyType, PayloadSchemaType};
 use serde::{Deserialize, Serialize};
 use wal::WalOptions;
 
@@ -57,6 +58,10 @@ pub struct CollectionParams {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default = "default_on_disk_payload")]
     pub on_disk_payload: bool,
+    /// If set - payloads of updated points are validated against this schema,
+    /// and operations which violate it are rejected
+    #[serde(default)]
+    pub payload_schema: Option<PayloadSchema>,
 }
 
 fn default_shard_number() -> NonZeroU32 {
@@ -66,6 +71,33 @@ fn default_on_disk_payload() -> bool {
     false
 }
 
+/// Declared type of the payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadSchemaField {
+    /// Type of the field value. Arrays are accepted if all of their elements have this type
+    pub r#type: PayloadSchemaType,
+    /// If true - each point must have a non-null value of this field
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Declared structure of point payloads in the collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadSchema {
+    /// Declared fields. Nested fields are addressed by dot-separated paths
+    #[serde(default)]
+    pub fields: BTreeMap<PayloadKeyType, PayloadSchemaField>,
+    /// If false - payloads with fields, which are not declared in `fields`, are rejected
+    #[serde(default = "default_allow_unknown_fields")]
+    pub allow_unknown_fields: bool,
+}
+
+fn default_allow_unknown_fields() -> bool {
+    true
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 pub struct CollectionConfig {
     pub params: CollectionParams,
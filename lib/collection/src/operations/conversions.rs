@@ -1,19 +1,25 @@
 use std::collections::HashMap;
 use std::num::NonZeroU32;
 
-use api::grpc::conversions::{payload_to_proto, proto_to_payloads};
+use api::grpc::conversions::{json_to_proto, payload_to_proto, proto_to_json, proto_to_payloads};
+use api::grpc::qdrant::payload_modification::Modification;
 use itertools::Itertools;
+use segment::types::{PayloadSchemaType, QueryExplain};
 use tonic::Status;
 
-use crate::config::{CollectionConfig, CollectionParams, WalConfig};
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::config::{
+    CollectionConfig, CollectionParams, PayloadSchema, PayloadSchemaField, WalConfig,
+};
 use crate::operations::config_diff::{HnswConfigDiff, OptimizersConfigDiff, WalConfigDiff};
+use crate::operations::payload_ops::PayloadModification;
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
     Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector,
 };
 use crate::operations::types::{
-    CollectionInfo, CollectionStatus, CountResult, OptimizersStatus, Record, UpdateResult,
-    UpdateStatus,
+    CollectionInfo, CollectionScoredPoint, CollectionStatus, CountResult, Fusion, FusionSearch,
+    OptimizersStatus, Record, SegmentExplain, ShardExplain, UpdateResult, UpdateStatus,
 };
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -97,6 +103,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     .into(),
                     shard_number: config.params.shard_number.get(),
                     on_disk_payload: config.params.on_disk_payload,
+                    payload_schema: config.params.payload_schema.map(|v| v.into()),
                 }),
                 hnsw_config: Some(api::grpc::qdrant::HnswConfigDiff {
                     m: Some(config.hnsw_config.m as u64),
@@ -139,6 +146,7 @@ impl From<Record> for api::grpc::qdrant::RetrievedPoint {
             id: Some(record.id.into()),
             payload: record.payload.map(payload_to_proto).unwrap_or_default(),
             vector: record.vector.unwrap_or_default(),
+            order_value: record.order_value,
         }
     }
 }
@@ -151,6 +159,7 @@ impl TryFrom<api::grpc::qdrant::RetrievedPoint> for Record {
             id: retrieved_point.id.unwrap().try_into()?,
             payload: Some(proto_to_payloads(retrieved_point.payload)?),
             vector: Some(retrieved_point.vector),
+            order_value: retrieved_point.order_value,
         })
     }
 }
@@ -197,6 +206,54 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfig {
     }
 }
 
+impl TryFrom<api::grpc::qdrant::PayloadSchema> for PayloadSchema {
+    type Error = Status;
+
+    fn try_from(schema: api::grpc::qdrant::PayloadSchema) -> Result<Self, Self::Error> {
+        let fields = schema
+            .fields
+            .into_iter()
+            .map(|(key, field)| {
+                let r#type = PayloadSchemaType::from_index(field.r#type).ok_or_else(|| {
+                    Status::invalid_argument(format!("Malformed payload schema type of `{}`", key))
+                })?;
+                Ok((
+                    key,
+                    PayloadSchemaField {
+                        r#type,
+                        required: field.required,
+                    },
+                ))
+            })
+            .collect::<Result<_, Status>>()?;
+        Ok(Self {
+            fields,
+            allow_unknown_fields: schema.allow_unknown_fields.unwrap_or(true),
+        })
+    }
+}
+
+impl From<PayloadSchema> for api::grpc::qdrant::PayloadSchema {
+    fn from(schema: PayloadSchema) -> Self {
+        Self {
+            fields: schema
+                .fields
+                .into_iter()
+                .map(|(key, field)| {
+                    (
+                        key,
+                        api::grpc::qdrant::PayloadSchemaField {
+                            r#type: field.r#type.index(),
+                            required: field.required,
+                        },
+                    )
+                })
+                .collect(),
+            allow_unknown_fields: Some(schema.allow_unknown_fields),
+        }
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
     type Error = Status;
 
@@ -216,6 +273,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     },
                     shard_number: NonZeroU32::new(params.shard_number).unwrap(),
                     on_disk_payload: params.on_disk_payload,
+                    payload_schema: params.payload_schema.map(|v| v.try_into()).transpose()?,
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -370,6 +428,71 @@ impl TryFrom<api::grpc::qdrant::PointsSelector> for PointsSelector {
     }
 }
 
+impl TryFrom<api::grpc::qdrant::PayloadModification> for PayloadModification {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PayloadModification) -> Result<Self, Self::Error> {
+        let proto_to_values = |values: Vec<api::grpc::qdrant::Value>| {
+            values
+                .into_iter()
+                .map(proto_to_json)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        match value.modification {
+            Some(Modification::Increment(increment)) => {
+                let value = increment.value.map(proto_to_json).transpose()?;
+                match value {
+                    Some(serde_json::Value::Number(value)) => Ok(PayloadModification::Increment {
+                        key: increment.key,
+                        value,
+                    }),
+                    _ => Err(Status::invalid_argument(
+                        "Increment value should be a number",
+                    )),
+                }
+            }
+            Some(Modification::Append(append)) => Ok(PayloadModification::Append {
+                key: append.key,
+                values: proto_to_values(append.values)?,
+            }),
+            Some(Modification::Remove(remove)) => Ok(PayloadModification::Remove {
+                key: remove.key,
+                values: proto_to_values(remove.values)?,
+            }),
+            None => Err(Status::invalid_argument(
+                "Malformed PayloadModification type",
+            )),
+        }
+    }
+}
+
+impl From<PayloadModification> for api::grpc::qdrant::PayloadModification {
+    fn from(value: PayloadModification) -> Self {
+        let values_to_proto =
+            |key: String, values: Vec<serde_json::Value>| api::grpc::qdrant::PayloadArrayValues {
+                key,
+                values: values.into_iter().map(json_to_proto).collect(),
+            };
+        let modification = match value {
+            PayloadModification::Increment { key, value } => {
+                Modification::Increment(api::grpc::qdrant::PayloadIncrement {
+                    key,
+                    value: Some(json_to_proto(serde_json::Value::Number(value))),
+                })
+            }
+            PayloadModification::Append { key, values } => {
+                Modification::Append(values_to_proto(key, values))
+            }
+            PayloadModification::Remove { key, values } => {
+                Modification::Remove(values_to_proto(key, values))
+            }
+        };
+        Self {
+            modification: Some(modification),
+        }
+    }
+}
+
 impl From<UpdateResult> for api::grpc::qdrant::UpdateResult {
     fn from(value: UpdateResult) -> Self {
         Self {
@@ -416,3 +539,91 @@ impl From<CountResult> for api::grpc::qdrant::CountResult {
         }
     }
 }
+
+impl TryFrom<i32> for Fusion {
+    type Error = Status;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match api::grpc::qdrant::Fusion::from_i32(value) {
+            Some(api::grpc::qdrant::Fusion::Rrf) => Ok(Fusion::Rrf),
+            Some(api::grpc::qdrant::Fusion::Weighted) => Ok(Fusion::Weighted),
+            None => Err(Status::invalid_argument("Malformed Fusion type")),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::FusionSearch> for FusionSearch {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::FusionSearch) -> Result<Self, Self::Error> {
+        Ok(Self {
+            vector: value.vector,
+            filter: value.filter.map(|f| f.try_into()).transpose()?,
+            params: value.params.map(|p| p.into()),
+            limit: value.limit.map(|l| l as usize),
+            score_threshold: value.score_threshold,
+            weight: value.weight,
+        })
+    }
+}
+
+impl From<SegmentExplain> for api::grpc::qdrant::SegmentExplain {
+    fn from(value: SegmentExplain) -> Self {
+        let explain = value.explain;
+        Self {
+            segment_id: value.segment_id as u64,
+            cardinality: explain.cardinality.map(|c| c.into()),
+            strategy: explain
+                .strategy
+                .map(|s| api::grpc::qdrant::QueryStrategy::from(s) as i32),
+            distance_computations: explain.distance_computations as u64,
+            visited_nodes: explain.visited_nodes as u64,
+            time: explain.time,
+            skipped: explain.skipped,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::SegmentExplain> for SegmentExplain {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::SegmentExplain) -> Result<Self, Self::Error> {
+        let strategy = value
+            .strategy
+            .map(|s| {
+                api::grpc::qdrant::QueryStrategy::from_i32(s)
+                    .map(|s| s.into())
+                    .ok_or_else(|| Status::invalid_argument("Malformed QueryStrategy type"))
+            })
+            .transpose()?;
+        Ok(Self {
+            segment_id: value.segment_id as SegmentId,
+            explain: QueryExplain {
+                cardinality: value.cardinality.map(|c| c.into()),
+                strategy,
+                distance_computations: value.distance_computations as usize,
+                visited_nodes: value.visited_nodes as usize,
+                time: value.time,
+                skipped: value.skipped,
+            },
+        })
+    }
+}
+
+impl From<ShardExplain> for api::grpc::qdrant::ShardExplain {
+    fn from(value: ShardExplain) -> Self {
+        Self {
+            shard_id: value.shard_id,
+            segments: value.segments.into_iter().map(|s| s.into()).collect(),
+        }
+    }
+}
+
+impl From<CollectionScoredPoint> for api::grpc::qdrant::CollectionScoredPoint {
+    fn from(value: CollectionScoredPoint) -> Self {
+        Self {
+            collection_name: value.collection_name,
+            point: Some(value.point.into()),
+        }
+    }
+}
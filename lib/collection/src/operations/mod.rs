@@ -9,10 +9,10 @@ pub mod types;
 use std::collections::HashMap;
 
 use schemars::JsonSchema;
-use segment::types::{ExtendedPointId, PayloadSchemaType};
+use segment::types::{ExtendedPointId, PayloadFieldSchema};
 use serde::{Deserialize, Serialize};
 
-use self::types::CollectionResult;
+use self::types::{CollectionError, CollectionResult};
 use crate::hash_ring::HashRing;
 use crate::shard::ShardId;
 
@@ -20,7 +20,7 @@ use crate::shard::ShardId;
 #[serde(rename_all = "snake_case")]
 pub struct CreateIndex {
     pub field_name: String,
-    pub field_type: Option<PayloadSchemaType>,
+    pub field_type: Option<PayloadFieldSchema>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
@@ -80,8 +80,22 @@ impl Validate for CollectionUpdateOperations {
     fn validate(&self) -> CollectionResult<()> {
         match self {
             CollectionUpdateOperations::PointOperation(operation) => operation.validate(),
-            CollectionUpdateOperations::PayloadOperation(_) => Ok(()),
-            CollectionUpdateOperations::FieldIndexOperation(_) => Ok(()),
+            CollectionUpdateOperations::PayloadOperation(operation) => operation.validate(),
+            CollectionUpdateOperations::FieldIndexOperation(operation) => operation.validate(),
+        }
+    }
+}
+
+impl Validate for FieldIndexOperations {
+    fn validate(&self) -> CollectionResult<()> {
+        match self {
+            FieldIndexOperations::CreateIndex(CreateIndex {
+                field_type: Some(field_type),
+                ..
+            }) => field_type
+                .validate()
+                .map_err(|description| CollectionError::BadInput { description }),
+            FieldIndexOperations::CreateIndex(_) | FieldIndexOperations::DeleteIndex(_) => Ok(()),
         }
     }
 }
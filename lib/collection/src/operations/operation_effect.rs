@@ -71,13 +71,27 @@ impl EstimateOperationEffectArea for PayloadOps {
     fn estimate_effect_area(&self) -> OperationEffectArea {
         match self {
             PayloadOps::SetPayload(set_payload) => {
-                OperationEffectArea::Points(set_payload.points.clone())
+                points_or_filter_effect(&set_payload.points, &set_payload.filter)
             }
             PayloadOps::DeletePayload(delete_payload) => {
-                OperationEffectArea::Points(delete_payload.points.clone())
+                points_or_filter_effect(&delete_payload.points, &delete_payload.filter)
             }
             PayloadOps::ClearPayload { points } => OperationEffectArea::Points(points.clone()),
             PayloadOps::ClearPayloadByFilter(filter) => OperationEffectArea::Filter(filter.clone()),
+            PayloadOps::ModifyPayload(modify_payload) => {
+                points_or_filter_effect(&modify_payload.points, &modify_payload.filter)
+            }
         }
     }
 }
+
+fn points_or_filter_effect(
+    points: &Option<Vec<PointIdType>>,
+    filter: &Option<Filter>,
+) -> OperationEffectArea {
+    match (points, filter) {
+        (Some(points), _) => OperationEffectArea::Points(points.clone()),
+        (None, Some(filter)) => OperationEffectArea::Filter(filter.clone()),
+        (None, None) => OperationEffectArea::Empty,
+    }
+}
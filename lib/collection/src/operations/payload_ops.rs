@@ -1,24 +1,172 @@
 use schemars::JsonSchema;
+use segment::common::utils::{strip_array_selectors, update_value_in_json_map};
+use segment::entry::entry_point::{OperationError, OperationResult};
 use segment::types::{Filter, Payload, PayloadKeyType, PointIdType};
 use serde;
 use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
 
-use super::{split_iter_by_shard, OperationToShard, SplitByShard};
+use super::{split_iter_by_shard, OperationToShard, SplitByShard, Validate};
 use crate::hash_ring::HashRing;
+use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shard::ShardId;
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct SetPayload {
     pub payload: Payload,
     /// Assigns payload to each point in this list
-    pub points: Vec<PointIdType>, // ToDo: replace with point selector
+    #[serde(default)]
+    pub points: Option<Vec<PointIdType>>,
+    /// Assigns payload to each point that satisfies this filter
+    #[serde(default)]
+    pub filter: Option<Filter>,
+    /// If set - payload is merged into the nested object located by this path, e.g. `a.b[2].c`.
+    /// Missing objects on the path are created
+    #[serde(default)]
+    pub key: Option<PayloadKeyType>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct DeletePayload {
+    /// Payload paths to delete, e.g. `a.b[2].c`
     pub keys: Vec<PayloadKeyType>,
     /// Deletes values from each point in this list
-    pub points: Vec<PointIdType>, // ToDo: replace with point selector
+    #[serde(default)]
+    pub points: Option<Vec<PointIdType>>,
+    /// Deletes values from each point that satisfies this filter
+    #[serde(default)]
+    pub filter: Option<Filter>,
+}
+
+/// Read-modify-write change of a single payload field.
+/// Nested keys like `a.b` are supported, missing objects on the path are created.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadModification {
+    /// Add `value` to the numeric field, negative `value` decrements it.
+    /// Missing field is treated as 0
+    Increment { key: PayloadKeyType, value: Number },
+    /// Append `values` to the end of the array field.
+    /// Missing field is treated as an empty array, single value - as an array of one element
+    Append {
+        key: PayloadKeyType,
+        values: Vec<Value>,
+    },
+    /// Remove all occurrences of `values` from the array field
+    Remove {
+        key: PayloadKeyType,
+        values: Vec<Value>,
+    },
+}
+
+impl PayloadModification {
+    pub fn key(&self) -> &PayloadKeyType {
+        match self {
+            PayloadModification::Increment { key, .. } => key,
+            PayloadModification::Append { key, .. } => key,
+            PayloadModification::Remove { key, .. } => key,
+        }
+    }
+
+    /// Partial payload with the values written by the modification, enough to check their types.
+    /// `None` if the modification only removes values
+    pub fn written_values(&self) -> Option<Payload> {
+        let value = match self {
+            PayloadModification::Increment { value, .. } => Value::Number(value.clone()),
+            PayloadModification::Append { values, .. } => Value::Array(values.clone()),
+            PayloadModification::Remove { .. } => return None,
+        };
+        // Array elements don't exist in the empty payload, so values are placed by the field path
+        let mut payload = Payload::default();
+        update_value_in_json_map(
+            &strip_array_selectors(self.key()),
+            &mut payload.0,
+            &mut |_| Ok::<_, OperationError>(Some(value.clone())),
+        )
+        .ok()?;
+        Some(payload)
+    }
+
+    /// Apply modification to the payload of a single point.
+    /// Keys with array selectors, like `a[].b` or `a.b[2]`, modify each selected value
+    pub fn apply(&self, payload: &mut Payload) -> OperationResult<()> {
+        update_value_in_json_map(self.key(), &mut payload.0, &mut |current| {
+            self.modify_value(current.filter(|value| !value.is_null()))
+        })
+    }
+
+    /// New value of the field, `None` if it should stay unchanged
+    fn modify_value(&self, current: Option<&Value>) -> OperationResult<Option<Value>> {
+        let new_value = match self {
+            PayloadModification::Increment { key, value } => match current {
+                None => Value::Number(value.clone()),
+                Some(Value::Number(current)) => Value::Number(add_numbers(key, current, value)?),
+                Some(_) => {
+                    return Err(OperationError::TypeError {
+                        field_name: key.clone(),
+                        expected_type: "finite number".to_string(),
+                    })
+                }
+            },
+            PayloadModification::Append { values, .. } => {
+                let mut array = match current {
+                    None => vec![],
+                    Some(Value::Array(array)) => array.clone(),
+                    Some(value) => vec![value.clone()],
+                };
+                array.extend(values.iter().cloned());
+                Value::Array(array)
+            }
+            PayloadModification::Remove { values, .. } => {
+                let array = match current {
+                    None => return Ok(None),
+                    Some(Value::Array(array)) => array.clone(),
+                    Some(value) => vec![value.clone()],
+                };
+                Value::Array(
+                    array
+                        .into_iter()
+                        .filter(|value| !values.contains(value))
+                        .collect(),
+                )
+            }
+        };
+        Ok(Some(new_value))
+    }
+}
+
+/// Integer sum if both numbers are integers, float sum otherwise.
+/// Integer overflow is an error, silent conversion into float would change the type of the field
+fn add_numbers(key: &str, a: &Number, b: &Number) -> OperationResult<Number> {
+    if let (Some(a), Some(b)) = (a.as_i64(), b.as_i64()) {
+        return match a.checked_add(b) {
+            Some(sum) => Ok(sum.into()),
+            None => Err(OperationError::ValidationError {
+                description: format!("increment of `{}` by {} overflows integer {}", key, b, a),
+            }),
+        };
+    }
+    a.as_f64()
+        .zip(b.as_f64())
+        .and_then(|(a, b)| Number::from_f64(a + b))
+        .ok_or_else(|| OperationError::TypeError {
+            field_name: key.to_string(),
+            expected_type: "finite number".to_string(),
+        })
+}
+
+/// Apply payload modifications to the selected points.
+/// Each point is modified under the segment write lock, so concurrent modifications are never lost
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct ModifyPayload {
+    /// Modifications applied to each point in the given order
+    pub modifications: Vec<PayloadModification>,
+    /// Modifies each point in this list
+    #[serde(default)]
+    pub points: Option<Vec<PointIdType>>,
+    /// Modifies each point that satisfies this filter
+    #[serde(default)]
+    pub filter: Option<Filter>,
 }
 
 /// Define operations description for point payloads manipulation
@@ -33,6 +181,8 @@ pub enum PayloadOps {
     ClearPayload { points: Vec<PointIdType> },
     /// Clear all Payload values by given filter criteria.
     ClearPayloadByFilter(Filter),
+    /// Increment numeric values, append to or remove from arrays
+    ModifyPayload(ModifyPayload),
 }
 
 impl SplitByShard for PayloadOps {
@@ -47,32 +197,98 @@ impl SplitByShard for PayloadOps {
             PayloadOps::ClearPayload { points } => split_iter_by_shard(points, |id| *id, ring)
                 .map(|points| PayloadOps::ClearPayload { points }),
             operation @ PayloadOps::ClearPayloadByFilter(_) => OperationToShard::to_all(operation),
+            PayloadOps::ModifyPayload(operation) => operation
+                .split_by_shard(ring)
+                .map(PayloadOps::ModifyPayload),
         }
     }
 }
 
+impl Validate for PayloadOps {
+    fn validate(&self) -> CollectionResult<()> {
+        match self {
+            PayloadOps::SetPayload(operation) => {
+                validate_points_selector(&operation.points, &operation.filter)
+            }
+            PayloadOps::DeletePayload(operation) => {
+                validate_points_selector(&operation.points, &operation.filter)
+            }
+            PayloadOps::ClearPayload { .. } => Ok(()),
+            PayloadOps::ClearPayloadByFilter(_) => Ok(()),
+            PayloadOps::ModifyPayload(operation) => {
+                validate_points_selector(&operation.points, &operation.filter)
+            }
+        }
+    }
+}
+
+/// Exactly one of `points` or `filter` should select the affected points
+fn validate_points_selector(
+    points: &Option<Vec<PointIdType>>,
+    filter: &Option<Filter>,
+) -> CollectionResult<()> {
+    match (points, filter) {
+        (Some(_), None) | (None, Some(_)) => Ok(()),
+        (Some(_), Some(_)) => Err(CollectionError::BadInput {
+            description: "Only one of `points` or `filter` should be specified".to_string(),
+        }),
+        (None, None) => Err(CollectionError::BadInput {
+            description: "Either `points` or `filter` should be specified".to_string(),
+        }),
+    }
+}
+
 impl SplitByShard for DeletePayload {
     fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
-        split_iter_by_shard(self.points, |id| *id, ring).map(|points| DeletePayload {
-            points,
-            keys: self.keys.clone(),
-        })
+        match self.points {
+            Some(points) => {
+                split_iter_by_shard(points, |id| *id, ring).map(|points| DeletePayload {
+                    keys: self.keys.clone(),
+                    points: Some(points),
+                    filter: None,
+                })
+            }
+            // Points selected by filter might be located in any shard
+            None => OperationToShard::to_all(self),
+        }
     }
 }
 
 impl SplitByShard for SetPayload {
     fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
-        split_iter_by_shard(self.points, |id| *id, ring).map(|points| SetPayload {
-            points,
-            payload: self.payload.clone(),
-        })
+        match self.points {
+            Some(points) => split_iter_by_shard(points, |id| *id, ring).map(|points| SetPayload {
+                payload: self.payload.clone(),
+                points: Some(points),
+                filter: None,
+                key: self.key.clone(),
+            }),
+            // Points selected by filter might be located in any shard
+            None => OperationToShard::to_all(self),
+        }
+    }
+}
+
+impl SplitByShard for ModifyPayload {
+    fn split_by_shard(self, ring: &HashRing<ShardId>) -> OperationToShard<Self> {
+        match self.points {
+            Some(points) => {
+                split_iter_by_shard(points, |id| *id, ring).map(|points| ModifyPayload {
+                    modifications: self.modifications.clone(),
+                    points: Some(points),
+                    filter: None,
+                })
+            }
+            // Points selected by filter might be located in any shard
+            None => OperationToShard::to_all(self),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use segment::types::Payload;
-    use serde_json::Value;
+    use serde_json::{json, Value};
 
     use super::*;
 
@@ -114,4 +330,87 @@ mod tests {
             _ => panic!("Wrong operation"),
         }
     }
+
+    #[test]
+    fn test_payload_modifications() {
+        let mut payload: Payload = json!({
+            "views": 10,
+            "rating": 4.5,
+            "tags": ["new", "sale", "new"],
+            "title": "cheese",
+        })
+        .into();
+
+        let modifications: Vec<PayloadModification> = serde_json::from_value(json!([
+            {"increment": {"key": "views", "value": 1}},
+            {"increment": {"key": "rating", "value": -0.5}},
+            {"increment": {"key": "stats.likes", "value": 3}},
+            {"append": {"key": "tags", "values": ["hot"]}},
+            {"remove": {"key": "tags", "values": ["new"]}},
+            {"append": {"key": "title", "values": ["gouda"]}},
+            {"remove": {"key": "missing", "values": [1]}},
+        ]))
+        .unwrap();
+        for modification in &modifications {
+            modification.apply(&mut payload).unwrap();
+        }
+
+        let expected: Payload = json!({
+            "views": 11,
+            "rating": 4.0,
+            "stats": {"likes": 3},
+            "tags": ["sale", "hot"],
+            "title": ["cheese", "gouda"],
+        })
+        .into();
+        assert_eq!(payload, expected);
+
+        let overflow = PayloadModification::Increment {
+            key: "views".to_string(),
+            value: i64::MAX.into(),
+        };
+        assert!(overflow.apply(&mut payload).is_err());
+        assert_eq!(payload.get_value("views"), Some(&json!(11)));
+
+        let not_a_number = PayloadModification::Increment {
+            key: "title".to_string(),
+            value: 1.into(),
+        };
+        assert!(not_a_number.apply(&mut payload).is_err());
+    }
+
+    #[test]
+    fn test_payload_modifications_with_array_selectors() {
+        let mut payload: Payload = json!({
+            "a": {"b": [1, 2, 3]},
+            "items": [{"count": 1}, {"count": 5}, {"name": "cheese"}, "milk"],
+        })
+        .into();
+
+        // Values other than objects on the path, like `a.b` array, are not replaced
+        let modifications: Vec<PayloadModification> = serde_json::from_value(json!([
+            {"increment": {"key": "a.b[2]", "value": 10}},
+            {"increment": {"key": "a.b[5]", "value": 10}},
+            {"increment": {"key": "items[].count", "value": 1}},
+            {"increment": {"key": "a.b.c", "value": 1}},
+        ]))
+        .unwrap();
+        for modification in &modifications {
+            modification.apply(&mut payload).unwrap();
+        }
+
+        let expected: Payload = json!({
+            "a": {"b": [1, 2, 13]},
+            "items": [{"count": 2}, {"count": 6}, {"name": "cheese", "count": 1}, "milk"],
+        })
+        .into();
+        assert_eq!(payload, expected);
+
+        let remove_missing = PayloadModification::Remove {
+            key: "stats.tags".to_string(),
+            values: vec![json!("new")],
+        };
+        remove_missing.apply(&mut payload).unwrap();
+        assert!(!payload.contains_key("stats"));
+    }
 }
@@ -7,8 +7,9 @@ use schemars::JsonSchema;
 use segment::common::file_operations::FileStorageError;
 use segment::entry::entry_point::OperationError;
 use segment::types::{
-    Filter, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType, ScoreType, SearchParams,
-    SeqNumberType, VectorElementType, WithPayloadInterface,
+    FacetValueHit, Filter, OrderBy, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType,
+    QueryExplain, Rescore, ScoreType, ScoredPoint, SearchParams, SeqNumberType, VectorElementType,
+    WithPayloadInterface,
 };
 use serde;
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,7 @@ use tokio::sync::oneshot::error::RecvError as OneshotRecvError;
 use tokio::task::JoinError;
 use tonic::codegen::http::uri::InvalidUri;
 
+use crate::collection_manager::holders::segment_holder::SegmentId;
 use crate::config::CollectionConfig;
 use crate::shard::{PeerId, ShardId};
 use crate::wal::WalError;
@@ -61,6 +63,9 @@ pub struct Record {
     pub payload: Option<Payload>,
     /// Vector of the point
     pub vector: Option<Vec<VectorElementType>>,
+    /// Value of the `order_by` field the point was ordered by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_value: Option<f64>,
 }
 
 /// Current statistics and configuration of the collection
@@ -150,6 +155,10 @@ pub struct ScrollRequest {
     /// Whether to return the point vector with the result?
     #[serde(default)]
     pub with_vector: bool,
+    /// Order the points by the value of the indexed payload field instead of the id.
+    /// `offset` is only applied together with `order_by.start_from`.
+    #[serde(default)]
+    pub order_by: Option<OrderBy>,
 }
 
 impl Default for ScrollRequest {
@@ -160,6 +169,7 @@ impl Default for ScrollRequest {
             filter: None,
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: false,
+            order_by: None,
         }
     }
 }
@@ -172,6 +182,9 @@ pub struct ScrollResult {
     pub points: Vec<Record>,
     /// Offset which should be used to retrieve a next page result
     pub next_page_offset: Option<PointIdType>,
+    /// Value of `order_by.start_from` which should be used to retrieve a next page result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page_start_from: Option<f64>,
 }
 
 /// Search request.
@@ -204,6 +217,99 @@ pub struct SearchRequest {
     /// Score of the returned result might be higher or smaller than the threshold depending on the
     /// Distance function used. E.g. for cosine similarity only higher scores will be returned.
     pub score_threshold: Option<ScoreType>,
+    /// Recompute score of the found points using payload values.
+    /// If defined, results are ordered by the recomputed score, larger is better.
+    /// Score threshold is applied to the similarity score before re-scoring.
+    pub rescore: Option<Rescore>,
+    /// If true, return explanation of how the search was executed in each shard and segment.
+    /// Default: false
+    #[serde(default)]
+    pub explain: bool,
+}
+
+/// Method of merging results of several searches into a single ranking
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Fusion {
+    /// Reciprocal rank fusion. Each search adds `weight / (60 + rank)` to the score of the point.
+    /// Does not depend on the scale of the scores.
+    Rrf,
+    /// Weighted sum of the scores. Scores of each search are normalized into `[0, 1]` range
+    /// before summing, so that 1 is the best score of the search.
+    Weighted,
+}
+
+impl Default for Fusion {
+    fn default() -> Self {
+        Fusion::Rrf
+    }
+}
+
+/// Single search, which is a part of the fusion search request
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct FusionSearch {
+    /// Look for vectors closest to this
+    pub vector: Vec<VectorElementType>,
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+    /// Additional search params
+    pub params: Option<SearchParams>,
+    /// Max number of candidates this search contributes to the fusion.
+    /// Default: `limit + offset` of the fusion request
+    pub limit: Option<usize>,
+    /// Define a minimal score threshold for the candidates of this search.
+    pub score_threshold: Option<ScoreType>,
+    /// Weight of this search in the fused score. Default: 1.0
+    pub weight: Option<ScoreType>,
+}
+
+/// Fusion search request.
+/// Runs several searches in a single request and merges their results into a single ranking.
+/// Merging is performed after results of all shards are collected, so `limit` and `offset`
+/// are applied to the final ranking.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct FusionRequest {
+    /// Searches, results of which should be merged
+    pub searches: Vec<FusionSearch>,
+    /// Method of merging the results. Default: rrf
+    #[serde(default)]
+    pub fusion: Fusion,
+    /// Max number of result to return
+    pub limit: usize,
+    /// Offset of the first result to return.
+    /// May be used to paginate results.
+    /// Note: large offset values may cause performance issues.
+    #[serde(default)]
+    pub offset: usize,
+    /// Select which payload to return with the response. Default: None
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Whether to return the point vector with the result?
+    #[serde(default)]
+    pub with_vector: bool,
+}
+
+/// Search for the most similar points in several collections at once
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionsSearchRequest {
+    /// Names or aliases of the collections to search in.
+    /// All collections must have the same vector size and distance function.
+    pub collections: Vec<String>,
+    /// Search to run in each of the collections
+    #[serde(flatten)]
+    pub search: SearchRequest,
+}
+
+/// Search result, annotated with the collection it was found in
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionScoredPoint {
+    /// Name of the collection the point belongs to
+    pub collection_name: String,
+    #[serde(flatten)]
+    pub point: ScoredPoint,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -254,6 +360,10 @@ pub struct RecommendRequest {
     /// Score of the returned result might be higher or smaller than the threshold depending on the
     /// Distance function used. E.g. for cosine similarity only higher scores will be returned.
     pub score_threshold: Option<ScoreType>,
+    /// If true, return explanation of how the search was executed in each shard and segment.
+    /// Default: false
+    #[serde(default)]
+    pub explain: bool,
 }
 
 /// Count Request
@@ -268,6 +378,10 @@ pub struct CountRequest {
     /// Approximate count might be unreliable during the indexing process. Default: true
     #[serde(default = "default_exact_count")]
     pub exact: bool,
+    /// If true, return explanation of how the points were counted in each shard and segment.
+    /// Default: false
+    #[serde(default)]
+    pub explain: bool,
 }
 
 pub fn default_exact_count() -> bool {
@@ -281,6 +395,90 @@ pub struct CountResult {
     pub count: usize,
 }
 
+/// Facet Request
+/// Counts the number of points for each value of the payload field.
+/// Only points which satisfy the given filter are counted.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequest {
+    /// Payload key to count values of. The field must have a keyword or integer index
+    pub key: PayloadKeyType,
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+    /// Max number of values to return, the most frequent values first. Default: 10
+    #[serde(default = "default_facet_limit")]
+    pub limit: usize,
+    /// If true, count exact number of points for each value.
+    /// If false, counts of the filtered points are estimated faster with the index. Default: false
+    #[serde(default)]
+    pub exact: bool,
+}
+
+pub fn default_facet_limit() -> usize {
+    10
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetResult {
+    /// Values of the field with number of points, the most frequent values first
+    pub hits: Vec<FacetValueHit>,
+}
+
+/// Aggregate Request
+/// Computes statistics of the numeric payload field over points, which satisfy the given filter.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct AggregateRequest {
+    /// Payload key of the integer, float or datetime field to aggregate
+    pub key: PayloadKeyType,
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+    /// If set, also count values in buckets of the fixed width
+    pub histogram: Option<HistogramParams>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramParams {
+    /// Width of the bucket. Datetime values are bucketed by seconds
+    pub interval: f64,
+}
+
+/// Explanation of how the query was executed in a single segment of the shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentExplain {
+    /// Id of the segment within the shard
+    pub segment_id: SegmentId,
+    #[serde(flatten)]
+    pub explain: QueryExplain,
+}
+
+/// Explanation of how the query was executed in a single shard
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardExplain {
+    pub shard_id: ShardId,
+    pub segments: Vec<SegmentExplain>,
+}
+
+/// Search result together with the explanation of the search execution
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchExplainResult {
+    pub result: Vec<ScoredPoint>,
+    pub explain: Vec<ShardExplain>,
+}
+
+/// Count result together with the explanation of the count execution
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CountExplainResult {
+    pub result: CountResult,
+    pub explain: Vec<ShardExplain>,
+}
+
 #[derive(Error, Debug, Clone)]
 #[error("{0}")]
 pub enum CollectionError {
@@ -345,6 +543,7 @@ impl From<OperationError> for CollectionError {
             OperationError::TypeInferenceError { .. } => Self::BadInput {
                 description: format!("{}", err),
             },
+            OperationError::ValidationError { description } => Self::BadInput { description },
         }
     }
 }
@@ -4,13 +4,14 @@ use api::grpc::qdrant::{
     ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
-    DeletePoints, DeletePointsInternal, PointsIdsList, PointsSelector, SetPayloadPoints,
+    DeletePoints, DeletePointsInternal, FieldType, ModifyPayloadPoints,
+    ModifyPayloadPointsInternal, PointsIdsList, PointsSelector, SetPayloadPoints,
     SetPayloadPointsInternal, UpsertPoints, UpsertPointsInternal,
 };
-use segment::types::{Filter, PointIdType};
+use segment::types::{Filter, PayloadFieldSchema, PointIdType};
 use tonic::Status;
 
-use crate::operations::payload_ops::{DeletePayload, SetPayload};
+use crate::operations::payload_ops::{DeletePayload, ModifyPayload, SetPayload};
 use crate::operations::point_ops::PointInsertOperations;
 use crate::operations::types::CollectionResult;
 use crate::operations::CreateIndex;
@@ -84,7 +85,9 @@ pub fn internal_set_payload(
             collection_name: shard.collection_id.clone(),
             wait: Some(wait),
             payload: payload_to_proto(set_payload.payload),
-            points: set_payload.points.into_iter().map(|id| id.into()).collect(),
+            points: vec![],
+            key: set_payload.key,
+            points_selector: points_selector_to_proto(set_payload.points, set_payload.filter),
         }),
     }
 }
@@ -100,15 +103,48 @@ pub fn internal_delete_payload(
             collection_name: shard.collection_id.clone(),
             wait: Some(wait),
             keys: delete_payload.keys,
-            points: delete_payload
-                .points
+            points: vec![],
+            points_selector: points_selector_to_proto(delete_payload.points, delete_payload.filter),
+        }),
+    }
+}
+
+pub fn internal_modify_payload(
+    modify_payload: ModifyPayload,
+    shard: &RemoteShard,
+    wait: bool,
+) -> ModifyPayloadPointsInternal {
+    ModifyPayloadPointsInternal {
+        shard_id: shard.id,
+        modify_payload_points: Some(ModifyPayloadPoints {
+            collection_name: shard.collection_id.clone(),
+            wait: Some(wait),
+            modifications: modify_payload
+                .modifications
                 .into_iter()
-                .map(|id| id.into())
+                .map(|modification| modification.into())
                 .collect(),
+            points: points_selector_to_proto(modify_payload.points, modify_payload.filter),
         }),
     }
 }
 
+fn points_selector_to_proto(
+    points: Option<Vec<PointIdType>>,
+    filter: Option<Filter>,
+) -> Option<PointsSelector> {
+    let points_selector_one_of = match (points, filter) {
+        (Some(points), _) => PointsSelectorOneOf::Points(PointsIdsList {
+            ids: points.into_iter().map(|id| id.into()).collect(),
+        }),
+        (None, Some(filter)) => PointsSelectorOneOf::Filter(filter.into()),
+        (None, None) => return None,
+    };
+    Some(PointsSelector {
+        points_selector_one_of: Some(points_selector_one_of),
+    })
+}
+
 pub fn internal_clear_payload(
     points: Vec<PointIdType>,
     shard: &RemoteShard,
@@ -150,13 +186,24 @@ pub fn internal_create_index(
     shard: &RemoteShard,
     wait: bool,
 ) -> CreateFieldIndexCollectionInternal {
+    let (field_type, field_index_params) = match create_index.field_type {
+        None => (None, None),
+        Some(PayloadFieldSchema::FieldType(field_type)) => {
+            (Some(FieldType::from(field_type) as i32), None)
+        }
+        Some(PayloadFieldSchema::FieldParams(params)) => (
+            Some(FieldType::from(params.kind()) as i32),
+            Some(params.into()),
+        ),
+    };
     CreateFieldIndexCollectionInternal {
         shard_id: shard.id,
         create_field_index_collection: Some(CreateFieldIndexCollection {
             collection_name: shard.collection_id.clone(),
             wait: Some(wait),
             field_name: create_index.field_name,
-            field_type: create_index.field_type.map(|ft| ft.index()),
+            field_type,
+            field_index_params,
         }),
     }
 }
@@ -1,5 +1,5 @@
 use std::cmp::max;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::fs::remove_file;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
@@ -12,9 +12,13 @@ use indicatif::ProgressBar;
 use itertools::Itertools;
 use parking_lot::RwLock;
 use segment::index::field_index::CardinalityEstimation;
+use segment::index::struct_payload_index::check_filter_indexes;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
-use segment::types::{Filter, PayloadStorageType, PointIdType, SegmentConfig};
+use segment::types::{
+    Filter, OrderBy, PayloadKeyTypeRef, PayloadSchemaType, PayloadStorageType, PointIdType,
+    SegmentConfig, SeqNumberType, WithPayload, WithPayloadInterface,
+};
 use tokio::fs::{copy, create_dir_all};
 use tokio::runtime::{self, Runtime};
 use tokio::sync::mpsc::UnboundedSender;
@@ -22,8 +26,9 @@ use tokio::sync::{mpsc, Mutex, RwLock as TokioRwLock};
 
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::config::CollectionConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, Record};
 use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::build_optimizers;
 use crate::shard::shard_config::{ShardConfig, SHARD_CONFIG_FILE};
@@ -87,6 +92,7 @@ impl LocalShard {
             locked_wal.clone(),
             config.optimizer_config.flush_interval_sec,
             config.optimizer_config.max_optimization_threads,
+            config.params.payload_schema.clone(),
         );
 
         let (update_sender, update_receiver) = mpsc::unbounded_channel();
@@ -311,11 +317,12 @@ impl LocalShard {
         let bar = ProgressBar::new(wal.len());
         bar.set_message("Recovering collection");
         let segments = self.segments();
+        let payload_schema = self.config.read().await.params.payload_schema.clone();
         // ToDo: Start from minimal applied version
         for (op_num, update) in wal.read_all() {
             // Panic only in case of internal error. If wrong formatting - skip
             if let Err(CollectionError::ServiceError { error }) =
-                CollectionUpdater::update(segments, op_num, update)
+                CollectionUpdater::update(segments, op_num, update, payload_schema.as_ref())
             {
                 panic!("Can't apply WAL operation: {}", error)
             }
@@ -480,6 +487,143 @@ impl LocalShard {
             .collect();
         Ok(all_points)
     }
+
+    /// Check if the field has an index of one of the given types in any segment of the shard
+    pub fn has_field_index(
+        &self,
+        key: PayloadKeyTypeRef,
+        schema_types: &[PayloadSchemaType],
+    ) -> bool {
+        self.segments().read().iter().any(|(_id, segment)| {
+            let indexed_fields = segment.get().read().get_indexed_fields();
+            indexed_fields
+                .get(key)
+                .map_or(false, |schema| schema_types.contains(&schema.schema_type()))
+        })
+    }
+
+    /// Check that payload indexes of each segment of the shard can serve the filter
+    pub fn check_filter(&self, filter: Option<&Filter>) -> CollectionResult<()> {
+        if let Some(filter) = filter {
+            for (_id, segment) in self.segments().read().iter() {
+                let indexed_fields = segment.get().read().get_indexed_fields();
+                check_filter_indexes(&indexed_fields, filter)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Scroll over points ordered by the value of the `order_by` field.
+    /// If a point is present in multiple segments, only its latest version is considered.
+    pub async fn scroll_by_order(
+        &self,
+        offset: Option<PointIdType>,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: bool,
+        filter: Option<&Filter>,
+        order_by: &OrderBy,
+    ) -> CollectionResult<Vec<Record>> {
+        let is_indexed = self.has_field_index(
+            &order_by.key,
+            &[
+                PayloadSchemaType::Integer,
+                PayloadSchemaType::Float,
+                PayloadSchemaType::Datetime,
+            ],
+        );
+        if !is_indexed {
+            return Err(CollectionError::BadRequest {
+                description: format!(
+                    "Ordering by field '{}' requires an integer, float or datetime payload index",
+                    order_by.key
+                ),
+            });
+        }
+
+        let segments = self.segments();
+        let ordered_points = {
+            let segments = segments.read();
+            // Outdated versions of points take places within the limit of a segment,
+            // so segments are read deeper until `limit` latest versions are found
+            let mut segment_limit = limit;
+            loop {
+                let mut found_points: HashMap<PointIdType, (SeqNumberType, f64)> = HashMap::new();
+                // Last read points of the segments, which might have more points to read
+                let mut boundaries = vec![];
+                for (_id, segment) in segments.iter() {
+                    let segment = segment.get();
+                    let segment_guard = segment.read();
+                    let segment_points = segment_guard.read_ordered_filtered(
+                        offset,
+                        segment_limit,
+                        filter,
+                        order_by,
+                    )?;
+                    if segment_points.len() >= segment_limit {
+                        boundaries.extend(segment_points.last().copied());
+                    }
+                    for (value, point_id) in segment_points {
+                        let version = segment_guard.point_version(point_id).unwrap_or_default();
+                        let entry = found_points.entry(point_id).or_insert((version, value));
+                        if entry.0 < version {
+                            *entry = (version, value);
+                        }
+                    }
+                }
+                // Unread points of a segment might precede the points after its boundary
+                let boundary = boundaries
+                    .into_iter()
+                    .min_by(|a, b| order_by.compare(*a, *b));
+
+                // Outdated version of the point might match, while the latest one does not
+                let latest_version = |point_id: PointIdType| {
+                    segments
+                        .iter()
+                        .filter_map(|(_id, segment)| segment.get().read().point_version(point_id))
+                        .max()
+                        .unwrap_or_default()
+                };
+                let ordered_points = found_points
+                    .into_iter()
+                    .filter(|(point_id, (version, _))| *version >= latest_version(*point_id))
+                    .map(|(point_id, (_, value))| (value, point_id))
+                    .filter(|point| {
+                        boundary
+                            .map_or(true, |boundary| !order_by.compare(*point, boundary).is_gt())
+                    })
+                    .sorted_by(|a, b| order_by.compare(*a, *b))
+                    .take(limit)
+                    .collect_vec();
+
+                if ordered_points.len() >= limit || boundary.is_none() {
+                    break ordered_points;
+                }
+                segment_limit *= 2;
+            }
+        };
+
+        let point_ids = ordered_points
+            .iter()
+            .map(|(_, point_id)| *point_id)
+            .collect_vec();
+        let with_payload = WithPayload::from(with_payload_interface);
+        let mut records: HashMap<PointIdType, Record> =
+            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)
+                .await?
+                .into_iter()
+                .map(|record| (record.id, record))
+                .collect();
+
+        Ok(ordered_points
+            .into_iter()
+            .filter_map(|(value, point_id)| {
+                let mut record = records.remove(&point_id)?;
+                record.order_value = Some(value);
+                Some(record)
+            })
+            .collect())
+    }
 }
 
 impl Drop for LocalShard {
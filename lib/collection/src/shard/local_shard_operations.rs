@@ -1,19 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use itertools::Itertools;
 use segment::types::{
-    ExtendedPointId, Filter, PayloadIndexInfo, PayloadKeyType, ScoredPoint, SegmentType,
-    WithPayload, WithPayloadInterface,
+    ExtendedPointId, FacetValue, FacetValueHit, Filter, FilterCardinality, NumericAggregation,
+    OrderBy, PayloadIndexInfo, PayloadIndexStats, PayloadKeyType, PayloadSchemaType, QueryExplain,
+    QueryStrategy, ScoredPoint, SegmentType, WithPayload, WithPayloadInterface,
 };
 use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::operations::types::{
-    CollectionInfo, CollectionResult, CollectionStatus, CountRequest, CountResult,
-    OptimizersStatus, PointRequest, Record, SearchRequest, UpdateResult, UpdateStatus,
+    AggregateRequest, CollectionError, CollectionInfo, CollectionResult, CollectionStatus,
+    CountRequest, CountResult, FacetRequest, OptimizersStatus, PointRequest, Record, SearchRequest,
+    SegmentExplain, UpdateResult, UpdateStatus,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::shard::local_shard::LocalShard;
@@ -71,7 +74,22 @@ impl ShardOperation for LocalShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: bool,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
     ) -> CollectionResult<Vec<Record>> {
+        self.check_filter(filter)?;
+        if let Some(order_by) = order_by {
+            return self
+                .scroll_by_order(
+                    offset,
+                    limit,
+                    with_payload_interface,
+                    with_vector,
+                    filter,
+                    order_by,
+                )
+                .await;
+        }
+
         // ToDo: Make faster points selection with a set
         let segments = self.segments();
         let point_ids = segments
@@ -143,8 +161,26 @@ impl ShardOperation for LocalShard {
         request: Arc<SearchRequest>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<ScoredPoint>> {
-        let res = SegmentsSearcher::search(self.segments(), request.clone(), search_runtime_handle)
-            .await?;
+        let (res, _explain) = self.search_explain(request, search_runtime_handle).await?;
+        Ok(res)
+    }
+
+    async fn search_explain(
+        &self,
+        request: Arc<SearchRequest>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Vec<SegmentExplain>)> {
+        self.check_filter(request.filter.as_ref())?;
+        let (res, explain) = SegmentsSearcher::search_explain(
+            self.segments(),
+            request.clone(),
+            search_runtime_handle,
+        )
+        .await?;
+        if request.rescore.is_some() {
+            // Re-scored results are already post-processed and filtered by threshold
+            return Ok((res, explain));
+        }
         let distance = self.config.read().await.params.distance;
         let processed_res = res.into_iter().map(|mut scored_point| {
             scored_point.score = distance.postprocess_score(scored_point.score);
@@ -158,10 +194,11 @@ impl ShardOperation for LocalShard {
         } else {
             processed_res.collect()
         };
-        Ok(top_result)
+        Ok((top_result, explain))
     }
 
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
+        self.check_filter(request.filter.as_ref())?;
         let total_count = if request.exact {
             let all_points = self.read_filtered(request.filter.as_ref()).await?;
             all_points.len()
@@ -173,6 +210,142 @@ impl ShardOperation for LocalShard {
         Ok(CountResult { count: total_count })
     }
 
+    async fn count_explain(
+        &self,
+        request: Arc<CountRequest>,
+    ) -> CollectionResult<(CountResult, Vec<SegmentExplain>)> {
+        let filter = request.filter.as_ref();
+        self.check_filter(filter)?;
+        let segments = self.segments().read();
+
+        let mut all_points = BTreeSet::new();
+        let mut estimated_count = 0;
+        let mut segments_explain = Vec::with_capacity(segments.len());
+
+        for (segment_id, segment) in segments.iter() {
+            let timing = Instant::now();
+            let segment = segment.get();
+            let segment_guard = segment.read();
+
+            let cardinality = segment_guard.estimate_points_count(filter);
+            let mut explain = QueryExplain {
+                cardinality: filter.map(|_| FilterCardinality::from(&cardinality)),
+                ..Default::default()
+            };
+
+            if request.exact {
+                explain.strategy = Some(match filter {
+                    None => QueryStrategy::FullScan,
+                    Some(_) => QueryStrategy::FilteredScan,
+                });
+                explain.visited_nodes = segment_guard.points_count();
+                all_points.extend(segment_guard.read_filtered(None, usize::MAX, filter));
+            } else if cardinality.max == 0 {
+                // No points could satisfy the filter, according to the estimation
+                explain.skipped = true;
+            } else {
+                explain.strategy = Some(QueryStrategy::Estimation);
+                estimated_count += cardinality.exp;
+            }
+
+            explain.time = timing.elapsed().as_secs_f64();
+            segments_explain.push(SegmentExplain {
+                segment_id: *segment_id,
+                explain,
+            });
+        }
+
+        let count = if request.exact {
+            all_points.len()
+        } else {
+            estimated_count
+        };
+        Ok((CountResult { count }, segments_explain))
+    }
+
+    async fn facet(&self, request: Arc<FacetRequest>) -> CollectionResult<Vec<FacetValueHit>> {
+        let is_indexed = self.has_field_index(
+            &request.key,
+            &[PayloadSchemaType::Keyword, PayloadSchemaType::Integer],
+        );
+        if !is_indexed {
+            return Err(CollectionError::BadRequest {
+                description: format!(
+                    "Facet by field '{}' requires a keyword or integer payload index",
+                    request.key
+                ),
+            });
+        }
+        self.check_filter(request.filter.as_ref())?;
+
+        let segments = self.segments().read();
+        let mut counts: HashMap<FacetValue, usize> = HashMap::new();
+        for (_id, segment) in segments.iter() {
+            let segment_counts =
+                segment
+                    .get()
+                    .read()
+                    .facet(&request.key, request.filter.as_ref(), request.exact)?;
+            for (value, count) in segment_counts {
+                *counts.entry(value).or_default() += count;
+            }
+        }
+
+        // Values, which are not the most frequent in this shard, might be such in the collection,
+        // so counts of all values are returned and truncated after merging with other shards
+        let hits = counts
+            .into_iter()
+            .map(|(value, count)| FacetValueHit { value, count })
+            .sorted_by(|a, b| a.compare(b))
+            .collect();
+        Ok(hits)
+    }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateRequest>,
+    ) -> CollectionResult<NumericAggregation> {
+        let histogram_interval = request.histogram.as_ref().map(|params| params.interval);
+        if let Some(interval) = histogram_interval {
+            if !(interval > 0.0 && interval.is_finite()) {
+                return Err(CollectionError::BadRequest {
+                    description: format!(
+                        "Histogram interval must be a positive number, got {}",
+                        interval
+                    ),
+                });
+            }
+        }
+        self.check_filter(request.filter.as_ref())?;
+
+        let segments = self.segments().read();
+        let mut aggregation = NumericAggregation {
+            histogram: histogram_interval.map(|_| vec![]),
+            ..Default::default()
+        };
+        for (_id, segment) in segments.iter() {
+            let segment_aggregation = segment.get().read().aggregate(
+                &request.key,
+                request.filter.as_ref(),
+                histogram_interval,
+            )?;
+            aggregation.merge(segment_aggregation);
+        }
+        Ok(aggregation)
+    }
+
+    async fn payload_index_stats(
+        &self,
+    ) -> CollectionResult<HashMap<PayloadKeyType, PayloadIndexStats>> {
+        let segments = self.segments().read();
+        let mut stats = HashMap::new();
+        for (_id, segment) in segments.iter() {
+            let segment_stats = segment.get().read().payload_index_stats()?;
+            PayloadIndexStats::merge_fields(&mut stats, segment_stats);
+        }
+        Ok(stats)
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequest>,
@@ -13,13 +13,16 @@ use std::sync::Arc;
 
 use api::grpc::transport_channel_pool::TransportChannelPool;
 use async_trait::async_trait;
-use segment::types::{ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface};
+use segment::types::{
+    ExtendedPointId, FacetValueHit, Filter, NumericAggregation, OrderBy, PayloadIndexStats,
+    PayloadKeyType, ScoredPoint, WithPayload, WithPayloadInterface,
+};
 use tokio::runtime::Handle;
 use tonic::transport::Uri;
 
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult, PointRequest,
-    Record, SearchRequest, UpdateResult,
+    AggregateRequest, CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult,
+    FacetRequest, PointRequest, Record, SearchRequest, SegmentExplain, UpdateResult,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::shard::local_shard::LocalShard;
@@ -73,6 +76,7 @@ pub trait ShardOperation {
         wait: bool,
     ) -> CollectionResult<UpdateResult>;
 
+    /// Points are ordered by id, or by `order_by` field value with `Record::order_value` set.
     #[allow(clippy::too_many_arguments)]
     async fn scroll_by(
         &self,
@@ -81,6 +85,7 @@ pub trait ShardOperation {
         with_payload_interface: &WithPayloadInterface,
         with_vector: bool,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
     ) -> CollectionResult<Vec<Record>>;
 
     async fn info(&self) -> CollectionResult<CollectionInfo>;
@@ -91,8 +96,34 @@ pub trait ShardOperation {
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<ScoredPoint>>;
 
+    /// Same as `search`, but also returns explanation of the search in each segment of the shard
+    async fn search_explain(
+        &self,
+        request: Arc<SearchRequest>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Vec<SegmentExplain>)>;
+
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult>;
 
+    /// Same as `count`, but also returns explanation of the count in each segment of the shard
+    async fn count_explain(
+        &self,
+        request: Arc<CountRequest>,
+    ) -> CollectionResult<(CountResult, Vec<SegmentExplain>)>;
+
+    /// Most frequent values of the shard, up to `request.limit`
+    async fn facet(&self, request: Arc<FacetRequest>) -> CollectionResult<Vec<FacetValueHit>>;
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateRequest>,
+    ) -> CollectionResult<NumericAggregation>;
+
+    /// Statistics of the payload indexes of the shard, combined over all segments
+    async fn payload_index_stats(
+        &self,
+    ) -> CollectionResult<HashMap<PayloadKeyType, PayloadIndexStats>>;
+
     async fn retrieve(
         &self,
         request: Arc<PointRequest>,
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -6,7 +6,8 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    ExtendedPointId, FacetValueHit, Filter, NumericAggregation, OrderBy, PayloadIndexStats,
+    PayloadKeyType, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
 };
 use tokio::runtime::Handle;
 use tokio::sync::{oneshot, RwLock};
@@ -16,8 +17,8 @@ use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult, PointRequest,
-    Record, SearchRequest, UpdateResult,
+    AggregateRequest, CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult,
+    FacetRequest, PointRequest, Record, SearchRequest, SegmentExplain, UpdateResult,
 };
 use crate::operations::CollectionUpdateOperations;
 use crate::shard::local_shard::LocalShard;
@@ -163,10 +164,18 @@ impl ShardOperation for ProxyShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: bool,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
     ) -> CollectionResult<Vec<Record>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .scroll_by(offset, limit, with_payload_interface, with_vector, filter)
+            .scroll_by(
+                offset,
+                limit,
+                with_payload_interface,
+                with_vector,
+                filter,
+                order_by,
+            )
             .await
     }
 
@@ -186,12 +195,56 @@ impl ShardOperation for ProxyShard {
         local_shard.search(request, search_runtime_handle).await
     }
 
+    /// Forward read-only `search_explain` to `wrapped_shard`
+    async fn search_explain(
+        &self,
+        request: Arc<SearchRequest>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Vec<SegmentExplain>)> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .search_explain(request, search_runtime_handle)
+            .await
+    }
+
     /// Forward read-only `count` to `wrapped_shard`
     async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
         let local_shard = &self.wrapped_shard;
         local_shard.count(request).await
     }
 
+    /// Forward read-only `count_explain` to `wrapped_shard`
+    async fn count_explain(
+        &self,
+        request: Arc<CountRequest>,
+    ) -> CollectionResult<(CountResult, Vec<SegmentExplain>)> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.count_explain(request).await
+    }
+
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(&self, request: Arc<FacetRequest>) -> CollectionResult<Vec<FacetValueHit>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.facet(request).await
+    }
+
+    /// Forward read-only `aggregate` to `wrapped_shard`
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateRequest>,
+    ) -> CollectionResult<NumericAggregation> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.aggregate(request).await
+    }
+
+    /// Forward read-only `payload_index_stats` to `wrapped_shard`
+    async fn payload_index_stats(
+        &self,
+    ) -> CollectionResult<HashMap<PayloadKeyType, PayloadIndexStats>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.payload_index_stats().await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
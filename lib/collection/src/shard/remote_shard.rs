@@ -1,15 +1,20 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use api::grpc::qdrant::collections_internal_client::CollectionsInternalClient;
 use api::grpc::qdrant::points_internal_client::PointsInternalClient;
 use api::grpc::qdrant::{
-    CountPoints, CountPointsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal,
-    GetPoints, GetPointsInternal, ScrollPoints, ScrollPointsInternal, SearchPoints,
-    SearchPointsInternal,
+    AggregatePoints, AggregatePointsInternal, CountPoints, CountPointsInternal, CountResponse,
+    FacetPoints, FacetPointsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal,
+    GetPoints, GetPointsInternal, PayloadIndexStatsInternal, PayloadIndexStatsRequest,
+    ScrollPoints, ScrollPointsInternal, SearchPoints, SearchPointsInternal, SearchResponse,
 };
 use async_trait::async_trait;
-use segment::types::{ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface};
+use segment::types::{
+    ExtendedPointId, FacetValueHit, Filter, NumericAggregation, OrderBy, PayloadIndexStats,
+    PayloadKeyType, ScoredPoint, WithPayload, WithPayloadInterface,
+};
 use tokio::runtime::Handle;
 use tonic::transport::{Channel, Uri};
 use tonic::Status;
@@ -17,14 +22,15 @@ use tonic::Status;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::PointOperations;
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult, PointRequest,
-    Record, SearchRequest, UpdateResult,
+    AggregateRequest, CollectionError, CollectionInfo, CollectionResult, CountRequest, CountResult,
+    FacetRequest, PointRequest, Record, SearchRequest, SegmentExplain, UpdateResult,
 };
 use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
 use crate::shard::conversions::{
     internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
     internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_upsert_points,
+    internal_delete_points_by_filter, internal_modify_payload, internal_set_payload,
+    internal_upsert_points,
 };
 use crate::shard::shard_config::ShardConfig;
 use crate::shard::{ChannelService, CollectionId, PeerId, ShardId, ShardOperation};
@@ -115,6 +121,70 @@ impl RemoteShard {
             .await?;
         Ok(CollectionsInternalClient::new(pooled_channel))
     }
+
+    async fn search_points(
+        &self,
+        request: &SearchRequest,
+        explain: bool,
+    ) -> CollectionResult<SearchResponse> {
+        let mut client = self.points_client().await?;
+
+        let search_points = SearchPoints {
+            collection_name: self.collection_id.clone(),
+            vector: request.vector.clone(),
+            filter: request.filter.clone().map(|f| f.into()),
+            limit: request.limit as u64,
+            with_vector: Some(request.with_vector),
+            with_payload: request.with_payload.clone().map(|wp| wp.into()),
+            params: request.params.map(|sp| sp.into()),
+            score_threshold: request.score_threshold,
+            offset: Some(request.offset as u64),
+            rescore: request.rescore.clone().map(|r| r.into()),
+            explain: Some(explain),
+        };
+        let request = tonic::Request::new(SearchPointsInternal {
+            search_points: Some(search_points),
+            shard_id: self.id,
+        });
+        let response = client.search(request).await?;
+        Ok(response.into_inner())
+    }
+
+    async fn count_points(
+        &self,
+        request: &CountRequest,
+        explain: bool,
+    ) -> CollectionResult<CountResponse> {
+        let mut client = self.points_client().await?;
+
+        let count_points = CountPoints {
+            collection_name: self.collection_id.clone(),
+            filter: request.filter.clone().map(|f| f.into()),
+            exact: Some(request.exact),
+            explain: Some(explain),
+        };
+
+        let request = tonic::Request::new(CountPointsInternal {
+            count_points: Some(count_points),
+            shard_id: self.id,
+        });
+        let response = client.count(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Segments explanation of this shard, returned by the remote peer
+    fn segments_explain(
+        &self,
+        shards_explain: Vec<api::grpc::qdrant::ShardExplain>,
+    ) -> CollectionResult<Vec<SegmentExplain>> {
+        let result: Result<Vec<SegmentExplain>, Status> = shards_explain
+            .into_iter()
+            .filter(|shard_explain| shard_explain.shard_id == self.id)
+            .flat_map(|shard_explain| shard_explain.segments)
+            .map(|segment_explain| segment_explain.try_into())
+            .collect();
+        result.map_err(|e| e.into())
+    }
 }
 
 #[async_trait]
@@ -167,6 +237,11 @@ impl ShardOperation for RemoteShard {
                         tonic::Request::new(internal_clear_payload_by_filter(filter, self, wait));
                     client.clear_payload(request).await?
                 }
+                PayloadOps::ModifyPayload(modify_payload) => {
+                    let request =
+                        tonic::Request::new(internal_modify_payload(modify_payload, self, wait));
+                    client.modify_payload(request).await?
+                }
             },
             CollectionUpdateOperations::FieldIndexOperation(field_index_op) => match field_index_op
             {
@@ -199,6 +274,7 @@ impl ShardOperation for RemoteShard {
         with_payload_interface: &WithPayloadInterface,
         with_vector: bool,
         filter: Option<&Filter>,
+        order_by: Option<&OrderBy>,
     ) -> CollectionResult<Vec<Record>> {
         let mut client = self.points_client().await?;
 
@@ -209,6 +285,7 @@ impl ShardOperation for RemoteShard {
             limit: Some(limit as u32),
             with_vector: Some(with_vector),
             with_payload: Some(with_payload_interface.clone().into()),
+            order_by: order_by.map(|order_by| order_by.clone().into()),
         };
         let request = tonic::Request::new(ScrollPointsInternal {
             scroll_points: Some(scroll_points),
@@ -245,56 +322,120 @@ impl ShardOperation for RemoteShard {
         request: Arc<SearchRequest>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<ScoredPoint>> {
+        let search_response = self.search_points(&request, false).await?;
+        let result: Result<Vec<ScoredPoint>, Status> = search_response
+            .result
+            .into_iter()
+            .map(|scored| scored.try_into())
+            .collect();
+        result.map_err(|e| e.into())
+    }
+
+    async fn search_explain(
+        &self,
+        request: Arc<SearchRequest>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Vec<SegmentExplain>)> {
+        let search_response = self.search_points(&request, true).await?;
+        let result: Result<Vec<ScoredPoint>, Status> = search_response
+            .result
+            .into_iter()
+            .map(|scored| scored.try_into())
+            .collect();
+        let explain = self.segments_explain(search_response.explain)?;
+        Ok((result?, explain))
+    }
+
+    async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
+        let count_response = self.count_points(&request, false).await?;
+        count_response.result.map_or_else(
+            || {
+                Err(CollectionError::service_error(
+                    "Unexpected empty CountResult".to_string(),
+                ))
+            },
+            |count_result| Ok(count_result.into()),
+        )
+    }
+
+    async fn count_explain(
+        &self,
+        request: Arc<CountRequest>,
+    ) -> CollectionResult<(CountResult, Vec<SegmentExplain>)> {
+        let count_response = self.count_points(&request, true).await?;
+        let count_result = count_response.result.ok_or_else(|| {
+            CollectionError::service_error("Unexpected empty CountResult".to_string())
+        })?;
+        let explain = self.segments_explain(count_response.explain)?;
+        Ok((count_result.into(), explain))
+    }
+
+    async fn facet(&self, request: Arc<FacetRequest>) -> CollectionResult<Vec<FacetValueHit>> {
         let mut client = self.points_client().await?;
 
-        let search_points = SearchPoints {
+        let facet_points = FacetPoints {
             collection_name: self.collection_id.clone(),
-            vector: request.vector.clone(),
+            key: request.key.clone(),
             filter: request.filter.clone().map(|f| f.into()),
-            limit: request.limit as u64,
-            with_vector: Some(request.with_vector),
-            with_payload: request.with_payload.clone().map(|wp| wp.into()),
-            params: request.params.map(|sp| sp.into()),
-            score_threshold: request.score_threshold,
-            offset: Some(request.offset as u64),
+            limit: Some(request.limit as u64),
+            exact: Some(request.exact),
         };
-        let request = tonic::Request::new(SearchPointsInternal {
-            search_points: Some(search_points),
+        let request = tonic::Request::new(FacetPointsInternal {
+            facet_points: Some(facet_points),
             shard_id: self.id,
         });
-        let response = client.search(request).await?;
-        let search_response = response.into_inner();
-        let result: Result<Vec<ScoredPoint>, Status> = search_response
-            .result
+        let response = client.facet(request).await?;
+        let facet_response = response.into_inner();
+        let result: Result<Vec<FacetValueHit>, Status> = facet_response
+            .hits
             .into_iter()
-            .map(|scored| scored.try_into())
+            .map(|hit| hit.try_into())
             .collect();
         result.map_err(|e| e.into())
     }
 
-    async fn count(&self, request: Arc<CountRequest>) -> CollectionResult<CountResult> {
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateRequest>,
+    ) -> CollectionResult<NumericAggregation> {
         let mut client = self.points_client().await?;
 
-        let count_points = CountPoints {
+        let aggregate_points = AggregatePoints {
             collection_name: self.collection_id.clone(),
+            key: request.key.clone(),
             filter: request.filter.clone().map(|f| f.into()),
-            exact: Some(request.exact),
+            histogram_interval: request.histogram.as_ref().map(|params| params.interval),
         };
+        let request = tonic::Request::new(AggregatePointsInternal {
+            aggregate_points: Some(aggregate_points),
+            shard_id: self.id,
+        });
+        let response = client.aggregate(request).await?;
+        let aggregation = response.into_inner().result.ok_or_else(|| {
+            CollectionError::service_error("Unexpected empty NumericAggregation".to_string())
+        })?;
+        aggregation.try_into().map_err(|e: Status| e.into())
+    }
 
-        let request = tonic::Request::new(CountPointsInternal {
-            count_points: Some(count_points),
+    async fn payload_index_stats(
+        &self,
+    ) -> CollectionResult<HashMap<PayloadKeyType, PayloadIndexStats>> {
+        let mut client = self.points_client().await?;
+
+        let request = tonic::Request::new(PayloadIndexStatsInternal {
+            payload_index_stats: Some(PayloadIndexStatsRequest {
+                collection_name: self.collection_id.clone(),
+            }),
             shard_id: self.id,
         });
-        let response = client.count(request).await?;
-        let count_response = response.into_inner();
-        count_response.result.map_or_else(
-            || {
-                Err(CollectionError::service_error(
-                    "Unexpected empty CountResult".to_string(),
-                ))
-            },
-            |count_result| Ok(count_result.into()),
-        )
+        let response = client.payload_index_stats(request).await?;
+        let result: Result<HashMap<PayloadKeyType, PayloadIndexStats>, Status> = response
+            .into_inner()
+            .result
+            .into_iter()
+            .map(|(field, stats)| Ok((field, stats.try_into()?)))
+            .collect();
+        result.map_err(|e| e.into())
     }
 
     async fn retrieve(
@@ -136,11 +136,22 @@ impl ShardHolder {
     }
 
     pub fn target_shards(&self, shard_selection: Option<ShardId>) -> CollectionResult<Vec<&Shard>> {
+        let shards = self.target_shards_with_id(shard_selection)?;
+        Ok(shards.into_iter().map(|(_id, shard)| shard).collect())
+    }
+
+    pub fn target_shards_with_id(
+        &self,
+        shard_selection: Option<ShardId>,
+    ) -> CollectionResult<Vec<(ShardId, &Shard)>> {
         match shard_selection {
-            None => Ok(self.all_shards().collect()),
+            None => Ok(self
+                .get_shards()
+                .map(|(shard_id, shard)| (*shard_id, shard))
+                .collect()),
             Some(shard_selection) => {
                 let local_shard = self.local_shard_by_id(shard_selection)?;
-                Ok(vec![local_shard])
+                Ok(vec![(shard_selection, local_shard)])
             }
         }
     }
@@ -31,6 +31,7 @@ async fn test_snapshot_collection() {
         distance: Distance::Dot,
         shard_number: NonZeroU32::new(3).expect("Shard number can not be zero"),
         on_disk_payload: false,
+        payload_schema: None,
     };
 
     let config = CollectionConfig {
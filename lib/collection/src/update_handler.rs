@@ -16,6 +16,7 @@ use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
 use crate::collection_manager::optimizers::segment_optimizer::SegmentOptimizer;
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
+use crate::config::PayloadSchema;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::CollectionUpdateOperations;
 use crate::wal::SerdeWal;
@@ -77,6 +78,8 @@ pub struct UpdateHandler {
     wal: Arc<Mutex<SerdeWal<CollectionUpdateOperations>>>,
     optimization_handles: Arc<Mutex<Vec<StoppableTaskHandle<bool>>>>,
     max_optimization_threads: usize,
+    /// Schema of the collection payloads, checked on payload modifications
+    payload_schema: Option<PayloadSchema>,
 }
 
 impl UpdateHandler {
@@ -87,6 +90,7 @@ impl UpdateHandler {
         wal: Arc<Mutex<SerdeWal<CollectionUpdateOperations>>>,
         flush_interval_sec: u64,
         max_optimization_threads: usize,
+        payload_schema: Option<PayloadSchema>,
     ) -> UpdateHandler {
         UpdateHandler {
             optimizers,
@@ -100,6 +104,7 @@ impl UpdateHandler {
             flush_interval_sec,
             optimization_handles: Arc::new(Mutex::new(vec![])),
             max_optimization_threads,
+            payload_schema,
         }
     }
 
@@ -113,11 +118,13 @@ impl UpdateHandler {
             self.wal.clone(),
             self.optimization_handles.clone(),
             self.max_optimization_threads,
+            self.payload_schema.clone(),
         )));
         self.update_worker = Some(self.runtime_handle.spawn(Self::update_worker_fn(
             update_receiver,
             tx,
             self.segments.clone(),
+            self.payload_schema.clone(),
         )));
         let (flush_tx, flush_rx) = oneshot::channel();
         self.flush_worker = Some(self.runtime_handle.spawn(Self::flush_worker(
@@ -169,6 +176,7 @@ impl UpdateHandler {
     async fn try_recover(
         segments: LockedSegmentHolder,
         wal: Arc<Mutex<SerdeWal<CollectionUpdateOperations>>>,
+        payload_schema: Option<&PayloadSchema>,
     ) -> CollectionResult<usize> {
         // Try to re-apply everything starting from the first failed operation
         let first_failed_operation_option = segments.read().failed_operation.iter().cloned().min();
@@ -177,7 +185,7 @@ impl UpdateHandler {
             Some(first_failed_op) => {
                 let wal_lock = wal.lock().await;
                 for (op_num, operation) in wal_lock.read(first_failed_op) {
-                    CollectionUpdater::update(&segments, op_num, operation)?;
+                    CollectionUpdater::update(&segments, op_num, operation, payload_schema)?;
                 }
             }
         };
@@ -275,6 +283,7 @@ impl UpdateHandler {
         wal: Arc<Mutex<SerdeWal<CollectionUpdateOperations>>>,
         optimization_handles: Arc<Mutex<Vec<StoppableTaskHandle<bool>>>>,
         max_handles: usize,
+        payload_schema: Option<PayloadSchema>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
@@ -286,7 +295,7 @@ impl UpdateHandler {
                     }
                     // We skip the check for number of optimization handles here
                     // Because `Nop` usually means that we need to force the optimization
-                    if Self::try_recover(segments.clone(), wal.clone())
+                    if Self::try_recover(segments.clone(), wal.clone(), payload_schema.as_ref())
                         .await
                         .is_err()
                     {
@@ -309,6 +318,7 @@ impl UpdateHandler {
         mut receiver: UnboundedReceiver<UpdateSignal>,
         optimize_sender: UnboundedSender<OptimizerSignal>,
         segments: LockedSegmentHolder,
+        payload_schema: Option<PayloadSchema>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
@@ -317,7 +327,12 @@ impl UpdateHandler {
                     operation,
                     sender,
                 }) => {
-                    let res = match CollectionUpdater::update(&segments, op_num, operation) {
+                    let res = match CollectionUpdater::update(
+                        &segments,
+                        op_num,
+                        operation,
+                        payload_schema.as_ref(),
+                    ) {
                         Ok(update_res) => optimize_sender
                             .send(OptimizerSignal::Operation(op_num))
                             .and(Ok(update_res))
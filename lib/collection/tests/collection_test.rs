@@ -3,12 +3,16 @@ use std::collections::HashSet;
 use collection::operations::payload_ops::{PayloadOps, SetPayload};
 use collection::operations::point_ops::{Batch, PointOperations, PointStruct};
 use collection::operations::types::{
-    CountRequest, PointRequest, RecommendRequest, ScrollRequest, SearchRequest, UpdateStatus,
+    AggregateRequest, CollectionError, CountRequest, FacetRequest, Fusion, FusionRequest,
+    FusionSearch, HistogramParams, PointRequest, RecommendRequest, ScrollRequest, SearchRequest,
+    UpdateStatus,
 };
-use collection::operations::CollectionUpdateOperations;
+use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use itertools::Itertools;
 use segment::types::{
-    Condition, FieldCondition, Filter, HasIdCondition, Payload, PointIdType, WithPayloadInterface,
+    AggregationValue, Condition, Direction, FacetValue, FacetValueHit, FieldCondition, Filter,
+    HasIdCondition, IntegerIndexParams, OrderBy, Payload, PayloadSchemaParams, PayloadSchemaType,
+    PayloadVariable, PointIdType, QueryStrategy, Rescore, ScoreExpression, WithPayloadInterface,
 };
 use tempdir::TempDir;
 use tokio::runtime::Handle;
@@ -64,6 +68,8 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
         limit: 3,
         offset: 0,
         score_threshold: None,
+        rescore: None,
+        explain: false,
     };
 
     let search_res = collection
@@ -122,6 +128,8 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
         limit: 3,
         offset: 0,
         score_threshold: None,
+        rescore: None,
+        explain: false,
     };
 
     let search_res = collection
@@ -145,9 +153,11 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
             values_count: None,
         }))),
         exact: true,
+        explain: false,
     };
 
     let count_res = collection.count(count_request, None).await.unwrap();
@@ -196,7 +206,9 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
         let assign_payload =
             CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayload {
                 payload,
-                points: vec![2.into(), 3.into()],
+                points: Some(vec![2.into(), 3.into()]),
+                filter: None,
+                key: None,
             }));
 
         collection
@@ -328,6 +340,7 @@ async fn test_recommendation_api_with_shards(shard_number: u32) {
                 with_payload: None,
                 with_vector: false,
                 score_threshold: None,
+                explain: false,
             },
             &Handle::current(),
             None,
@@ -341,6 +354,281 @@ async fn test_recommendation_api_with_shards(shard_number: u32) {
     collection.before_drop().await;
 }
 
+#[tokio::test]
+async fn test_search_fusion_api() {
+    test_search_fusion_api_with_shards(1).await;
+    test_search_fusion_api_with_shards(N_SHARDS).await;
+}
+
+async fn test_search_fusion_api_with_shards(shard_number: u32) {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3].into_iter().map(|x| x.into()).collect_vec(),
+            vectors: vec![
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.6, 0.6, 0.0, 0.0],
+                vec![0.1, 0.1, 0.0, 0.0],
+            ],
+            payloads: None,
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client(insert_points, true)
+        .await
+        .unwrap();
+
+    let fusion_search = |vector: Vec<f32>, weight: Option<f32>| FusionSearch {
+        vector,
+        filter: None,
+        params: None,
+        limit: Some(2),
+        score_threshold: None,
+        weight,
+    };
+
+    let request = FusionRequest {
+        searches: vec![
+            fusion_search(vec![1.0, 0.0, 0.0, 0.0], None),
+            fusion_search(vec![0.0, 1.0, 0.0, 0.0], None),
+        ],
+        fusion: Fusion::Rrf,
+        limit: 3,
+        offset: 0,
+        with_payload: None,
+        with_vector: false,
+    };
+
+    let result = collection
+        .search_fusion(request.clone(), &Handle::current(), None)
+        .await
+        .unwrap();
+    let ids = result.iter().map(|x| x.id).collect_vec();
+    assert_eq!(ids, vec![2.into(), 0.into(), 1.into()]);
+
+    let paginated_request = FusionRequest {
+        limit: 1,
+        offset: 1,
+        ..request.clone()
+    };
+    let result = collection
+        .search_fusion(paginated_request, &Handle::current(), None)
+        .await
+        .unwrap();
+    let ids = result.iter().map(|x| x.id).collect_vec();
+    assert_eq!(ids, vec![0.into()]);
+
+    let weighted_request = FusionRequest {
+        searches: vec![
+            fusion_search(vec![1.0, 0.0, 0.0, 0.0], None),
+            fusion_search(vec![0.0, 1.0, 0.0, 0.0], Some(2.0)),
+        ],
+        ..request
+    };
+    let result = collection
+        .search_fusion(weighted_request, &Handle::current(), None)
+        .await
+        .unwrap();
+    let ids = result.iter().map(|x| x.id).collect_vec();
+    assert_eq!(ids, vec![2.into(), 1.into(), 0.into()]);
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_search_rescore() {
+    test_search_rescore_with_shards(1).await;
+    test_search_rescore_with_shards(N_SHARDS).await;
+}
+
+async fn test_search_rescore_with_shards(shard_number: u32) {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3].into_iter().map(|x| x.into()).collect_vec(),
+            vectors: vec![
+                vec![1.0, 0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0, 0.0],
+                vec![0.6, 0.6, 0.0, 0.0],
+                vec![0.1, 0.1, 0.0, 0.0],
+            ],
+            payloads: serde_json::from_str(
+                r#"[{}, { "boost": 10.0 }, { "boost": 0.5 }, { "boost": [2.0, 5.0] }]"#,
+            )
+            .unwrap(),
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client(insert_points, true)
+        .await
+        .unwrap();
+
+    let search_request = SearchRequest {
+        vector: vec![1.0, 0.0, 0.0, 0.0],
+        filter: None,
+        params: None,
+        limit: 3,
+        offset: 0,
+        with_payload: None,
+        with_vector: false,
+        score_threshold: Some(0.05),
+        rescore: Some(Rescore {
+            expression: ScoreExpression::Sum(vec![
+                ScoreExpression::Score,
+                ScoreExpression::Payload(PayloadVariable {
+                    key: "boost".to_string(),
+                    default: 0.0,
+                }),
+            ]),
+            oversampling: None,
+        }),
+        explain: false,
+    };
+
+    let result = collection
+        .search(search_request, &Handle::current(), None)
+        .await
+        .unwrap();
+
+    // Point 1 has the largest boost, but does not pass the similarity threshold
+    let ids = result.iter().map(|x| x.id).collect_vec();
+    assert_eq!(ids, vec![3.into(), 2.into(), 0.into()]);
+    assert!((result[0].score - 2.1).abs() < 1e-5);
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_search_explain() {
+    test_search_explain_with_shards(1).await;
+    test_search_explain_with_shards(N_SHARDS).await;
+}
+
+async fn test_search_explain_with_shards(shard_number: u32) {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ],
+            payloads: serde_json::from_str(
+                r#"[{ "k": "v1" }, { "k": "v2" }, { "k": "v1" }, { "k": "v1" }, { "k": "v1" }]"#,
+            )
+            .unwrap(),
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client(insert_points, true)
+        .await
+        .unwrap();
+
+    let search_request = SearchRequest {
+        vector: vec![1.0, 1.0, 1.0, 1.0],
+        filter: None,
+        params: None,
+        limit: 3,
+        offset: 0,
+        with_payload: None,
+        with_vector: false,
+        score_threshold: None,
+        rescore: None,
+        explain: true,
+    };
+
+    let expected = collection
+        .search(search_request.clone(), &Handle::current(), None)
+        .await
+        .unwrap();
+    let explained = collection
+        .search_explain(search_request, &Handle::current(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        explained.result.iter().map(|x| x.id).collect_vec(),
+        expected.iter().map(|x| x.id).collect_vec()
+    );
+    assert_eq!(explained.explain.len(), shard_number as usize);
+
+    let segments = explained
+        .explain
+        .iter()
+        .flat_map(|shard| shard.segments.iter())
+        .collect_vec();
+    for segment in &segments {
+        assert!(segment.explain.cardinality.is_none());
+        assert!(
+            segment.explain.skipped || segment.explain.strategy == Some(QueryStrategy::FullScan)
+        );
+    }
+    let distance_computations: usize = segments
+        .iter()
+        .map(|segment| segment.explain.distance_computations)
+        .sum();
+    assert_eq!(distance_computations, 5);
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition {
+        key: "k".to_string(),
+        r#match: Some(serde_json::from_str(r#"{ "value": "v2" }"#).unwrap()),
+        range: None,
+        geo_bounding_box: None,
+        geo_radius: None,
+        geo_polygon: None,
+        values_count: None,
+    }));
+
+    let count_request = CountRequest {
+        filter: Some(filter.clone()),
+        exact: true,
+        explain: true,
+    };
+    let count_res = collection.count_explain(count_request, None).await.unwrap();
+    assert_eq!(count_res.result.count, 1);
+    assert_eq!(count_res.explain.len(), shard_number as usize);
+    for segment in count_res.explain.iter().flat_map(|shard| &shard.segments) {
+        assert!(segment.explain.cardinality.is_some());
+        assert!(
+            segment.explain.skipped
+                || segment.explain.strategy == Some(QueryStrategy::FilteredScan)
+        );
+    }
+
+    let count_request = CountRequest {
+        filter: Some(filter),
+        exact: false,
+        explain: true,
+    };
+    let count_res = collection.count_explain(count_request, None).await.unwrap();
+    for segment in count_res.explain.iter().flat_map(|shard| &shard.segments) {
+        assert!(
+            segment.explain.skipped || segment.explain.strategy == Some(QueryStrategy::Estimation)
+        );
+    }
+
+    collection.before_drop().await;
+}
+
 #[tokio::test]
 async fn test_read_api() {
     test_read_api_with_shards(1).await;
@@ -386,6 +674,7 @@ async fn test_read_api_with_shards(shard_number: u32) {
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: false,
+                order_by: None,
             },
             None,
         )
@@ -397,6 +686,420 @@ async fn test_read_api_with_shards(shard_number: u32) {
     collection.before_drop().await;
 }
 
+#[tokio::test]
+async fn test_scroll_order_by() {
+    test_scroll_order_by_with_shards(1).await;
+    test_scroll_order_by_with_shards(N_SHARDS).await;
+}
+
+async fn test_scroll_order_by_with_shards(shard_number: u32) {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: vec![vec![1.0, 0.0, 0.0, 0.0]; 5],
+            payloads: serde_json::from_str(
+                r#"[{ "price": 30 }, { "price": 10 }, { "price": 20 }, { "price": 10 }, {}]"#,
+            )
+            .unwrap(),
+        }
+        .into(),
+    ));
+
+    collection
+        .update_from_client(insert_points, true)
+        .await
+        .unwrap();
+
+    let scroll_request = |start_from: Option<f64>, offset: Option<PointIdType>| ScrollRequest {
+        offset,
+        limit: Some(2),
+        filter: None,
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: false,
+        order_by: Some(OrderBy {
+            key: "price".to_string(),
+            direction: Direction::Desc,
+            start_from,
+        }),
+    };
+
+    // Ordering requires an index on the field
+    let result = collection.scroll_by(scroll_request(None, None), None).await;
+    assert!(result.is_err());
+
+    // Integer index without both lookup and range is rejected
+    let create_no_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "price".to_string(),
+            field_type: Some(
+                PayloadSchemaParams::Integer(IntegerIndexParams {
+                    lookup: Some(false),
+                    range: Some(false),
+                    ..Default::default()
+                })
+                .into(),
+            ),
+        }),
+    );
+    let result = collection.update_from_client(create_no_index, true).await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "price".to_string(),
+            field_type: Some(PayloadSchemaType::Integer.into()),
+        }),
+    );
+    collection
+        .update_from_client(create_index, true)
+        .await
+        .unwrap();
+
+    let result = collection
+        .scroll_by(scroll_request(None, None), None)
+        .await
+        .unwrap();
+    let ids = result.points.iter().map(|x| x.id).collect_vec();
+    assert_eq!(ids, vec![0.into(), 2.into()]);
+    assert_eq!(result.points[0].order_value, Some(30.0));
+    assert_eq!(result.next_page_offset, Some(1.into()));
+    assert_eq!(result.next_page_start_from, Some(10.0));
+
+    // Points without the field are not returned
+    let result = collection
+        .scroll_by(
+            scroll_request(result.next_page_start_from, result.next_page_offset),
+            None,
+        )
+        .await
+        .unwrap();
+    let ids = result.points.iter().map(|x| x.id).collect_vec();
+    assert_eq!(ids, vec![1.into(), 3.into()]);
+    assert_eq!(result.next_page_offset, None);
+    assert_eq!(result.next_page_start_from, None);
+
+    // Offset is only a part of the cursor together with `start_from`
+    let result = collection
+        .scroll_by(scroll_request(None, Some(1.into())), None)
+        .await;
+    assert!(result.is_err());
+
+    // Match conditions are rejected for integer index without lookup, instead of reading payloads
+    let create_range_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "price".to_string(),
+            field_type: Some(
+                PayloadSchemaParams::Integer(IntegerIndexParams {
+                    lookup: Some(false),
+                    range: Some(true),
+                    ..Default::default()
+                })
+                .into(),
+            ),
+        }),
+    );
+    collection
+        .update_from_client(create_range_index, true)
+        .await
+        .unwrap();
+    let count_request = CountRequest {
+        filter: Some(Filter::new_must(Condition::Field(
+            FieldCondition::new_match("price".to_string(), 10.into()),
+        ))),
+        exact: true,
+        explain: false,
+    };
+    let result = collection.count(count_request, None).await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_facet() {
+    test_facet_with_shards(1).await;
+    test_facet_with_shards(N_SHARDS).await;
+}
+
+async fn test_facet_with_shards(shard_number: u32) {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: vec![vec![1.0, 0.0, 0.0, 0.0]; 5],
+            payloads: serde_json::from_str(
+                r#"[
+                    { "city": "Berlin", "price": 10 },
+                    { "city": ["Berlin", "London"], "price": 20 },
+                    { "city": "London", "price": 30 },
+                    { "city": "Berlin", "price": 40 },
+                    { "city": "Moscow", "price": 50 }
+                ]"#,
+            )
+            .unwrap(),
+        }
+        .into(),
+    ));
+
+    collection
+        .update_from_client(insert_points, true)
+        .await
+        .unwrap();
+
+    let facet_request = |filter: Option<Filter>| FacetRequest {
+        key: "city".to_string(),
+        filter,
+        limit: 2,
+        exact: true,
+    };
+
+    // Facets require an index on the field
+    let result = collection.facet(facet_request(None), None).await;
+    assert!(result.is_err());
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "city".to_string(),
+            field_type: Some(PayloadSchemaType::Keyword.into()),
+        }),
+    );
+    collection
+        .update_from_client(create_index, true)
+        .await
+        .unwrap();
+
+    let result = collection.facet(facet_request(None), None).await.unwrap();
+    assert_eq!(
+        result.hits,
+        vec![
+            FacetValueHit {
+                value: FacetValue::Keyword("Berlin".to_string()),
+                count: 3
+            },
+            FacetValueHit {
+                value: FacetValue::Keyword("London".to_string()),
+                count: 2
+            },
+        ]
+    );
+
+    let filter: Filter =
+        serde_json::from_str(r#"{ "must": [{ "key": "price", "range": { "gte": 30 } }] }"#)
+            .unwrap();
+    let result = collection
+        .facet(facet_request(Some(filter)), None)
+        .await
+        .unwrap();
+    let values = result.hits.into_iter().map(|hit| hit.value).collect_vec();
+    assert_eq!(
+        values,
+        vec![
+            FacetValue::Keyword("Berlin".to_string()),
+            FacetValue::Keyword("London".to_string()),
+        ]
+    );
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_facet_merges_shard_counts() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), 2).await;
+
+    let ids = (0..40).map(|x| x.into()).collect_vec();
+    let upsert_points = |payloads: Option<Vec<Option<Payload>>>| {
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            Batch {
+                ids: ids.clone(),
+                vectors: vec![vec![1.0, 0.0, 0.0, 0.0]; ids.len()],
+                payloads,
+            }
+            .into(),
+        ))
+    };
+    collection
+        .update_from_client(upsert_points(None), true)
+        .await
+        .unwrap();
+
+    let mut shard_points = vec![];
+    for shard_id in 0..2 {
+        let scroll_request = ScrollRequest {
+            offset: None,
+            limit: Some(ids.len()),
+            filter: None,
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: false,
+            order_by: None,
+        };
+        let result = collection
+            .scroll_by(scroll_request, Some(shard_id))
+            .await
+            .unwrap();
+        let point_ids = result.points.into_iter().map(|x| x.id).collect_vec();
+        assert!(point_ids.len() >= 5);
+        shard_points.push(point_ids);
+    }
+
+    // "Berlin" and "London" are the most frequent values of each shard,
+    // while "Moscow" is the most frequent in the collection
+    let mut cities = std::collections::HashMap::new();
+    for (point_ids, city) in shard_points.iter().zip(["Berlin", "London"]) {
+        for point_id in &point_ids[..3] {
+            cities.insert(*point_id, city);
+        }
+        for point_id in &point_ids[3..5] {
+            cities.insert(*point_id, "Moscow");
+        }
+    }
+    let payloads = ids
+        .iter()
+        .map(|point_id| {
+            let payload = match cities.get(point_id) {
+                Some(city) => serde_json::json!({ "city": city }),
+                None => serde_json::json!({}),
+            };
+            Some(serde_json::from_value(payload).unwrap())
+        })
+        .collect();
+    collection
+        .update_from_client(upsert_points(Some(payloads)), true)
+        .await
+        .unwrap();
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "city".to_string(),
+            field_type: Some(PayloadSchemaType::Keyword.into()),
+        }),
+    );
+    collection
+        .update_from_client(create_index, true)
+        .await
+        .unwrap();
+
+    let facet_request = FacetRequest {
+        key: "city".to_string(),
+        filter: None,
+        limit: 1,
+        exact: true,
+    };
+    let result = collection.facet(facet_request, None).await.unwrap();
+    assert_eq!(
+        result.hits,
+        vec![FacetValueHit {
+            value: FacetValue::Keyword("Moscow".to_string()),
+            count: 4
+        }]
+    );
+
+    collection.before_drop().await;
+}
+
+#[tokio::test]
+async fn test_aggregate() {
+    test_aggregate_with_shards(1).await;
+    test_aggregate_with_shards(N_SHARDS).await;
+}
+
+async fn test_aggregate_with_shards(shard_number: u32) {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let mut collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: vec![vec![1.0, 0.0, 0.0, 0.0]; 5],
+            payloads: serde_json::from_str(
+                r#"[
+                    { "city": "Berlin", "price": 10 },
+                    { "city": "Berlin", "price": [15, 25] },
+                    { "city": "London", "price": 30 },
+                    { "city": "Berlin", "price": 40 },
+                    { "city": "Moscow" }
+                ]"#,
+            )
+            .unwrap(),
+        }
+        .into(),
+    ));
+
+    collection
+        .update_from_client(insert_points, true)
+        .await
+        .unwrap();
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "price".to_string(),
+            field_type: Some(PayloadSchemaType::Integer.into()),
+        }),
+    );
+    collection
+        .update_from_client(create_index, true)
+        .await
+        .unwrap();
+
+    let aggregate_request = |filter: Option<Filter>, interval: f64| AggregateRequest {
+        key: "price".to_string(),
+        filter,
+        histogram: Some(HistogramParams { interval }),
+    };
+
+    let result = collection
+        .aggregate(aggregate_request(None, 20.0), None)
+        .await
+        .unwrap();
+    assert_eq!(result.count, 5);
+    assert_eq!(result.min, Some(AggregationValue::Number(10.0)));
+    assert_eq!(result.max, Some(AggregationValue::Number(40.0)));
+    assert_eq!(result.sum, 120.0);
+    assert_eq!(result.avg, Some(AggregationValue::Number(24.0)));
+    let buckets = result
+        .histogram
+        .unwrap()
+        .into_iter()
+        .map(|bucket| (bucket.from.value(), bucket.to.value(), bucket.count))
+        .collect_vec();
+    assert_eq!(
+        buckets,
+        vec![(0.0, 20.0, 2), (20.0, 40.0, 2), (40.0, 60.0, 1)]
+    );
+
+    let filter: Filter =
+        serde_json::from_str(r#"{ "must": [{ "key": "city", "match": { "value": "Berlin" } }] }"#)
+            .unwrap();
+    let result = collection
+        .aggregate(aggregate_request(Some(filter), 20.0), None)
+        .await
+        .unwrap();
+    assert_eq!(result.count, 4);
+    assert_eq!(result.sum, 90.0);
+
+    // Histogram buckets must have a positive width
+    let result = collection
+        .aggregate(aggregate_request(None, 0.0), None)
+        .await;
+    assert!(result.is_err());
+
+    collection.before_drop().await;
+}
+
 #[tokio::test]
 async fn test_collection_delete_points_by_filter() {
     test_collection_delete_points_by_filter_with_shards(1).await;
@@ -441,6 +1144,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
         should: None,
         must: Some(vec![Condition::HasId(HasIdCondition::from(to_be_deleted))]),
         must_not: None,
+        min_should: None,
     };
 
     let delete_points = CollectionUpdateOperations::PointOperation(
@@ -464,6 +1168,7 @@ async fn test_collection_delete_points_by_filter_with_shards(shard_number: u32)
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: false,
+                order_by: None,
             },
             None,
         )
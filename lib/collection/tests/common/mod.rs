@@ -38,6 +38,7 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         distance: Distance::Dot,
         shard_number: NonZeroU32::new(shard_number).expect("Shard number can not be zero"),
         on_disk_payload: false,
+        payload_schema: None,
     };
 
     let collection_config = CollectionConfig {
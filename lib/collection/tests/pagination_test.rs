@@ -47,6 +47,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: false,
         params: None,
         score_threshold: None,
+        rescore: None,
+        explain: false,
     };
 
     let reference_result = collection
@@ -70,6 +72,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: false,
         params: None,
         score_threshold: None,
+        rescore: None,
+        explain: false,
     };
 
     let page_1_result = collection
@@ -92,6 +96,8 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: false,
         params: None,
         score_threshold: None,
+        rescore: None,
+        explain: false,
     };
 
     let page_9_result = collection
@@ -0,0 +1,37 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use memmap::{Mmap, MmapMut, MmapOptions};
+
+use crate::entry::entry_point::OperationResult;
+
+pub fn open_read(path: &Path) -> OperationResult<Mmap> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(false)
+        .append(true)
+        .create(true)
+        .open(path)?;
+
+    Ok(unsafe { MmapOptions::new().map(&file)? })
+}
+
+pub fn open_write(path: &Path) -> OperationResult<MmapMut> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(false)
+        .open(path)?;
+
+    Ok(unsafe { MmapMut::map_mut(&file)? })
+}
+
+pub fn ensure_mmap_file_exists(path: &Path, header: &[u8]) -> OperationResult<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    let mut file = File::create(path)?;
+    file.write_all(header)?;
+    Ok(())
+}
@@ -1,6 +1,7 @@
 pub mod arc_atomic_ref_cell_iterator;
 pub mod error_logging;
 pub mod file_operations;
+pub mod mmap_ops;
 pub mod rocksdb_operations;
 pub mod utils;
 pub mod version;
@@ -80,3 +80,14 @@ pub fn recreate_cf(db: Arc<AtomicRefCell<DB>>, store_cf_name: &str) -> Result<()
     db_mut.create_cf(store_cf_name, &db_options())?;
     Ok(())
 }
+
+/// Size of the persisted files of the column family.
+/// Data, which is not flushed from the memtable yet, is not included.
+pub fn cf_disk_usage_bytes(db: &DB, store_cf_name: &str) -> Result<usize, Error> {
+    let cf_handle = match db.cf_handle(store_cf_name) {
+        Some(cf_handle) => cf_handle,
+        None => return Ok(0),
+    };
+    let size = db.property_int_value_cf(cf_handle, "rocksdb.total-sst-files-size")?;
+    Ok(size.unwrap_or_default() as usize)
+}
@@ -1,42 +1,252 @@
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 pub fn rev_range(a: usize, b: usize) -> impl Iterator<Item = usize> {
     (b + 1..=a).rev()
 }
 
+/// Value of the path, which might contain `key[N]` array selectors.
+/// `key[]` selects several values, so it is only supported by [`get_array_values_from_json_map`].
 pub fn get_value_from_json_map<'a>(
     path: &str,
     value: &'a serde_json::Map<String, Value>,
 ) -> Option<&'a Value> {
-    match path.split_once('.') {
-        Some((element, path)) => match value.get(element) {
-            Some(Value::Object(map)) => get_value_from_json_map(path, map),
-            Some(value) => match path.is_empty() {
-                true => Some(value),
-                false => None,
-            },
-            None => None,
+    let (element, rest) = split_path(path);
+    let (key, selector) = parse_path_element(element);
+    let selected = match (selector, value.get(key)?) {
+        (ArraySelector::Whole, field_value) => field_value,
+        (ArraySelector::Index(index), Value::Array(array)) => array.get(index)?,
+        _ => return None,
+    };
+    match rest {
+        None => Some(selected),
+        Some(rest) => match selected {
+            Value::Object(map) => get_value_from_json_map(rest, map),
+            _ => None,
         },
-        None => value.get(path),
     }
 }
 
+/// Values of the path with `key[]` segments, each of which selects all elements of the array,
+/// and `key[N]` segments, which select a single element.
+/// Elements without the value are skipped.
+pub fn get_array_values_from_json_map<'a>(
+    path: &str,
+    value: &'a serde_json::Map<String, Value>,
+) -> Vec<&'a Value> {
+    let (element, rest) = split_path(path);
+    let (key, selector) = parse_path_element(element);
+    let field_value = match value.get(key) {
+        Some(field_value) => field_value,
+        None => return vec![],
+    };
+    let items: Vec<&Value> = match (selector, field_value) {
+        (ArraySelector::Whole, field_value) => vec![field_value],
+        (ArraySelector::All, Value::Array(array)) => array.iter().collect(),
+        (ArraySelector::Index(index), Value::Array(array)) => {
+            array.get(index).into_iter().collect()
+        }
+        _ => return vec![],
+    };
+    match rest {
+        None => items,
+        Some(rest) => items
+            .into_iter()
+            .flat_map(|item| match item {
+                Value::Object(map) => get_array_values_from_json_map(rest, map),
+                _ => vec![],
+            })
+            .collect(),
+    }
+}
+
+/// Array selector of the path element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArraySelector {
+    /// `key` - the value itself
+    Whole,
+    /// `key[]` - each element of the array
+    All,
+    /// `key[N]` - single element of the array
+    Index(usize),
+}
+
+/// First element of the path and the rest of it, if any
+fn split_path(path: &str) -> (&str, Option<&str>) {
+    match path.split_once('.') {
+        Some((element, rest)) if !rest.is_empty() => (element, Some(rest)),
+        Some((element, _)) => (element, None),
+        None => (path, None),
+    }
+}
+
+fn parse_path_element(element: &str) -> (&str, ArraySelector) {
+    if let Some(key) = element.strip_suffix("[]") {
+        return (key, ArraySelector::All);
+    }
+    if let Some((key, index)) = element
+        .strip_suffix(']')
+        .and_then(|element| element.rsplit_once('['))
+    {
+        if let Ok(index) = index.parse() {
+            return (key, ArraySelector::Index(index));
+        }
+    }
+    (element, ArraySelector::Whole)
+}
+
+/// Values of the map selected by a single path element
+fn select_values_mut<'a>(element: &str, map: &'a mut Map<String, Value>) -> Vec<&'a mut Value> {
+    let (key, selector) = parse_path_element(element);
+    match (selector, map.get_mut(key)) {
+        (_, None) => vec![],
+        (ArraySelector::Whole, Some(value)) => vec![value],
+        (ArraySelector::All, Some(Value::Array(array))) => array.iter_mut().collect(),
+        (ArraySelector::Index(index), Some(Value::Array(array))) => {
+            array.get_mut(index).into_iter().collect()
+        }
+        (_, Some(_)) => vec![],
+    }
+}
+
+/// Remove value by the path, which might contain `key[]` and `key[N]` array selectors.
+/// Values removed from several array elements are combined into a single array.
 pub fn remove_value_from_json_map(
     path: &str,
     value: &mut serde_json::Map<String, Value>,
 ) -> Option<Value> {
-    match path.split_once('.') {
-        Some((element, new_path)) => {
-            if new_path.is_empty() {
-                value.remove(element)
-            } else {
-                match value.get_mut(element) {
-                    None => None,
-                    Some(Value::Object(map)) => remove_value_from_json_map(new_path, map),
-                    Some(_value) => None,
+    let (element, rest) = split_path(path);
+    let (key, selector) = parse_path_element(element);
+    match rest {
+        None => match (selector, value.get_mut(key)) {
+            (ArraySelector::Whole, _) => value.remove(key),
+            (ArraySelector::All, Some(Value::Array(array))) => {
+                Some(Value::Array(std::mem::take(array)))
+            }
+            (ArraySelector::Index(index), Some(Value::Array(array))) if index < array.len() => {
+                Some(array.remove(index))
+            }
+            _ => None,
+        },
+        Some(rest) => {
+            let mut removed: Vec<_> = select_values_mut(element, value)
+                .into_iter()
+                .filter_map(|item| match item {
+                    Value::Object(map) => remove_value_from_json_map(rest, map),
+                    _ => None,
+                })
+                .collect();
+            if removed.is_empty() {
+                return None;
+            }
+            match selector {
+                ArraySelector::All => Some(Value::Array(removed)),
+                ArraySelector::Whole | ArraySelector::Index(_) => removed.pop(),
+            }
+        }
+    }
+}
+
+/// Merge `source` into the object located by the path.
+/// Missing objects on the way are created, `key[]` and `key[N]` selectors
+/// only match existing arrays. `Null` values of the `source` remove the keys.
+/// Existing values other than objects are kept as is, nothing is merged into them.
+pub fn merge_value_into_json_map(
+    path: &str,
+    value: &mut serde_json::Map<String, Value>,
+    source: &serde_json::Map<String, Value>,
+) {
+    let (element, rest) = split_path(path);
+    let (key, selector) = parse_path_element(element);
+    if selector == ArraySelector::Whole {
+        let entry = value.entry(key).or_insert(Value::Null);
+        if entry.is_null() {
+            *entry = Value::Object(Map::new());
+        }
+    }
+    for target in select_values_mut(element, value) {
+        if let Value::Object(map) = target {
+            match rest {
+                Some(rest) => merge_value_into_json_map(rest, map, source),
+                None => merge_json_maps(map, source),
+            }
+        }
+    }
+}
+
+/// Replace values by the path, which might contain `key[]` and `key[N]` array selectors,
+/// with the result of `f` applied to the current value. `None` result keeps the value as is.
+/// Missing objects on the way are created only if some value is written into them,
+/// `key[]` and `key[N]` selectors only match existing array elements.
+/// Existing values other than objects on the way are kept as is.
+pub fn update_value_in_json_map<E>(
+    path: &str,
+    value: &mut serde_json::Map<String, Value>,
+    f: &mut impl FnMut(Option<&Value>) -> Result<Option<Value>, E>,
+) -> Result<(), E> {
+    let (element, rest) = split_path(path);
+    let (key, selector) = parse_path_element(element);
+    match rest {
+        None if selector == ArraySelector::Whole => {
+            if let Some(new_value) = f(value.get(key))? {
+                value.insert(key.to_owned(), new_value);
+            }
+        }
+        None => {
+            for item in select_values_mut(element, value) {
+                if let Some(new_value) = f(Some(item))? {
+                    *item = new_value;
+                }
+            }
+        }
+        Some(rest)
+            if selector == ArraySelector::Whole && value.get(key).map_or(true, Value::is_null) =>
+        {
+            let mut nested = Map::new();
+            update_value_in_json_map(rest, &mut nested, f)?;
+            if !nested.is_empty() {
+                value.insert(key.to_owned(), Value::Object(nested));
+            }
+        }
+        Some(rest) => {
+            for item in select_values_mut(element, value) {
+                if let Value::Object(nested) = item {
+                    update_value_in_json_map(rest, nested, f)?;
                 }
             }
         }
-        None => value.remove(path),
     }
+    Ok(())
+}
+
+/// Path with array selectors removed, e.g. `a[].b[2]` -> `a.b`
+pub fn strip_array_selectors(path: &str) -> String {
+    path.split('.')
+        .map(|element| parse_path_element(element).0)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Top-level merge of the maps, `Null` values of the `source` remove the keys
+pub fn merge_json_maps(value: &mut Map<String, Value>, source: &Map<String, Value>) {
+    for (key, source_value) in source {
+        match source_value {
+            Value::Null => value.remove(key),
+            _ => value.insert(key.to_owned(), source_value.to_owned()),
+        };
+    }
+}
+
+/// Check if modification of the value by one path might change the value by the other one.
+/// Array selectors are ignored, so the check is conservative.
+pub fn paths_overlap(path: &str, other: &str) -> bool {
+    let keys = |path: &str| -> Vec<String> {
+        path.split('.')
+            .filter(|element| !element.is_empty())
+            .map(|element| parse_path_element(element).0.to_owned())
+            .collect()
+    };
+    let (keys, other_keys) = (keys(path), keys(other));
+    keys.iter()
+        .zip(other_keys.iter())
+        .all(|(key, other)| key == other)
 }
@@ -11,9 +11,10 @@ use thiserror::Error;
 use crate::common::file_operations::FileStorageError;
 use crate::index::field_index::CardinalityEstimation;
 use crate::types::{
-    Filter, Payload, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType,
-    VectorElementType, WithPayload,
+    FacetValue, Filter, NumericAggregation, OrderBy, Payload, PayloadFieldSchema,
+    PayloadIndexStats, PayloadKeyType, PayloadKeyTypeRef, PointIdType, QueryExplain, ScoredPoint,
+    SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, VectorElementType,
+    WithPayload,
 };
 
 #[derive(Error, Debug, Clone)]
@@ -33,6 +34,8 @@ pub enum OperationError {
     },
     #[error("Unable to infer type for the field '{field_name}'. Please specify `field_type`")]
     TypeInferenceError { field_name: PayloadKeyType },
+    #[error("Validation error: {description}")]
+    ValidationError { description: String },
     /// Service Error prevents further update of the collection until it is fixed.
     /// Should only be used for hardware, data corruption, IO, or other unexpected internal errors.
     #[error("Service runtime error: {description}")]
@@ -154,6 +157,17 @@ pub trait SegmentEntry {
         params: Option<&SearchParams>,
     ) -> OperationResult<Vec<ScoredPoint>>;
 
+    /// Same as `search`, additionally explains how the search was executed in the segment
+    fn search_explain(
+        &self,
+        vector: &[VectorElementType],
+        with_payload: &WithPayload,
+        with_vector: bool,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+    ) -> OperationResult<(Vec<ScoredPoint>, QueryExplain)>;
+
     fn upsert_point(
         &mut self,
         op_num: SeqNumberType,
@@ -174,6 +188,15 @@ pub trait SegmentEntry {
         payload: &Payload,
     ) -> OperationResult<bool>;
 
+    /// Merge payload into the nested object located by the `key` path, e.g. `a.b[2].c`
+    fn set_payload_by_key(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<bool>;
+
     fn set_full_payload(
         &mut self,
         op_num: SeqNumberType,
@@ -198,6 +221,15 @@ pub trait SegmentEntry {
 
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload>;
 
+    /// Numeric value of the payload field of the point.
+    /// Read from the field index, if the field is indexed as a number.
+    /// Datetime values are represented as seconds since epoch.
+    fn payload_numeric_value(
+        &self,
+        point_id: PointIdType,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<Option<f64>>;
+
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_>;
 
     /// Paginate over points which satisfies filtering condition starting with `offset` id including.
@@ -208,6 +240,38 @@ pub trait SegmentEntry {
         filter: Option<&'a Filter>,
     ) -> Vec<PointIdType>;
 
+    /// Paginate over points which satisfies filtering condition, ordered by the indexed payload field.
+    /// Starts with the `(order_by.start_from, offset)` cursor including.
+    /// Returns pairs of the order value and point id.
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        order_by: &OrderBy,
+    ) -> OperationResult<Vec<(f64, PointIdType)>>;
+
+    /// Count points which satisfy filtering condition for each value of the payload field.
+    /// If not `exact`, counts of the filtered points might be estimated.
+    fn facet(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        exact: bool,
+    ) -> OperationResult<HashMap<FacetValue, usize>>;
+
+    /// Aggregate numeric values of the payload field among the points which satisfy filtering
+    /// condition. If `histogram_interval` is given, values are also counted in buckets of this width.
+    fn aggregate(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        histogram_interval: Option<f64>,
+    ) -> OperationResult<NumericAggregation>;
+
+    /// Statistics of the payload indexes of each indexed field
+    fn payload_index_stats(&self) -> OperationResult<HashMap<PayloadKeyType, PayloadIndexStats>>;
+
     /// Check if there is point with `point_id` in this segment.
     fn has_point(&self, point_id: PointIdType) -> bool;
 
@@ -256,11 +320,11 @@ pub trait SegmentEntry {
         &mut self,
         op_num: SeqNumberType,
         key: PayloadKeyTypeRef,
-        field_type: &Option<PayloadSchemaType>,
+        field_type: &Option<PayloadFieldSchema>,
     ) -> OperationResult<bool>;
 
     /// Get indexed fields
-    fn get_indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadSchemaType>;
+    fn get_indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema>;
 
     /// Checks if segment errored during last operations
     fn check_error(&self) -> Option<SegmentFailedState>;
@@ -172,6 +172,7 @@ pub fn random_must_filter<R: Rng + ?Sized>(rnd_gen: &mut R, num_conditions: usiz
         should: None,
         must: Some(must_conditions),
         must_not: None,
+        min_should: None,
     }
 }
 
@@ -203,6 +204,7 @@ pub fn random_filter<R: Rng + ?Sized>(rnd_gen: &mut R, total_conditions: usize)
         should: should_conditions_opt,
         must: must_conditions_opt,
         must_not: None,
+        min_should: None,
     }
 }
 
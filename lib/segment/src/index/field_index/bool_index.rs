@@ -0,0 +1,339 @@
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::BitVec;
+use rocksdb::{IteratorMode, DB};
+use serde_json::Value;
+
+use crate::common::rocksdb_operations::{cf_disk_usage_bytes, db_write_options, recreate_cf};
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::{
+    CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
+};
+use crate::types::{
+    FieldCondition, Match, MatchValue, PayloadKeyType, PointOffsetType, ValueVariants,
+};
+
+const TRUE_FLAG: u8 = 0b01;
+const FALSE_FLAG: u8 = 0b10;
+
+/// Bitmap-based index for boolean values.
+/// Point might be present in both bitmaps, if it has an array with both values.
+pub struct BoolIndex {
+    trues: BitVec,
+    falses: BitVec,
+    trues_count: usize,
+    falses_count: usize,
+    /// Amount of point which have at least one indexed payload value
+    indexed_points: usize,
+    store_cf_name: String,
+    db: Arc<AtomicRefCell<DB>>,
+}
+
+impl BoolIndex {
+    pub fn new(db: Arc<AtomicRefCell<DB>>, field_name: &str) -> BoolIndex {
+        BoolIndex {
+            trues: BitVec::new(),
+            falses: BitVec::new(),
+            trues_count: 0,
+            falses_count: 0,
+            indexed_points: 0,
+            store_cf_name: Self::storage_cf_name(field_name),
+            db,
+        }
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_bool")
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        Ok(recreate_cf(self.db.clone(), &self.store_cf_name)?)
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        let db = self.db.clone();
+        let db_ref = db.borrow();
+        let cf_handle = if let Some(cf_handle) = db_ref.cf_handle(&self.store_cf_name) {
+            cf_handle
+        } else {
+            return Ok(false);
+        };
+        for (key, value) in db_ref.iterator_cf(cf_handle, IteratorMode::Start) {
+            const DECODE_ERR: &str = "Index db parsing error: wrong data format";
+            let idx = PointOffsetType::from_be_bytes(
+                key.as_ref()
+                    .try_into()
+                    .map_err(|_| OperationError::service_error(DECODE_ERR))?,
+            );
+            let flags = *value
+                .first()
+                .ok_or_else(|| OperationError::service_error(DECODE_ERR))?;
+            self.set_flags(idx, flags);
+        }
+        Ok(true)
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "Index flush error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        Ok(store_ref.flush_cf(cf_handle)?)
+    }
+
+    fn get_flags(&self, idx: PointOffsetType) -> u8 {
+        let idx = idx as usize;
+        let mut flags = 0;
+        if self.trues.get(idx).map_or(false, |bit| *bit) {
+            flags |= TRUE_FLAG;
+        }
+        if self.falses.get(idx).map_or(false, |bit| *bit) {
+            flags |= FALSE_FLAG;
+        }
+        flags
+    }
+
+    /// Replace in-memory values of the point, keeping counters consistent
+    fn set_flags(&mut self, idx: PointOffsetType, flags: u8) {
+        let old_flags = self.get_flags(idx);
+        let idx = idx as usize;
+        if self.trues.len() <= idx {
+            self.trues.resize(idx + 1, false);
+            self.falses.resize(idx + 1, false);
+        }
+        self.trues.set(idx, flags & TRUE_FLAG != 0);
+        self.falses.set(idx, flags & FALSE_FLAG != 0);
+
+        self.trues_count += (flags & TRUE_FLAG != 0) as usize;
+        self.trues_count -= (old_flags & TRUE_FLAG != 0) as usize;
+        self.falses_count += (flags & FALSE_FLAG != 0) as usize;
+        self.falses_count -= (old_flags & FALSE_FLAG != 0) as usize;
+        self.indexed_points += (flags != 0) as usize;
+        self.indexed_points -= (old_flags != 0) as usize;
+    }
+
+    /// Values of the point, in the order `true`, `false`
+    pub fn get_values(&self, idx: PointOffsetType) -> Vec<bool> {
+        let flags = self.get_flags(idx);
+        [(TRUE_FLAG, true), (FALSE_FLAG, false)]
+            .into_iter()
+            .filter(|(flag, _)| flags & flag != 0)
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    pub fn check_value(&self, idx: PointOffsetType, value: bool) -> bool {
+        let bitmap = if value { &self.trues } else { &self.falses };
+        bitmap.get(idx as usize).map_or(false, |bit| *bit)
+    }
+
+    /// Number of distinct values, which are present in the index
+    pub fn distinct_values(&self) -> usize {
+        (self.trues_count > 0) as usize + (self.falses_count > 0) as usize
+    }
+
+    /// Memory, used by the bitmaps
+    pub fn ram_usage_bytes(&self) -> usize {
+        (self.trues.capacity() + self.falses.capacity()) / 8
+    }
+
+    pub fn disk_usage_bytes(&self) -> OperationResult<usize> {
+        Ok(cf_disk_usage_bytes(&self.db.borrow(), &self.store_cf_name)?)
+    }
+
+    fn value_count(&self, value: bool) -> usize {
+        if value {
+            self.trues_count
+        } else {
+            self.falses_count
+        }
+    }
+
+    fn get_iterator(&self, value: bool) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let bitmap = if value { &self.trues } else { &self.falses };
+        Box::new(bitmap.iter_ones().map(|idx| idx as PointOffsetType))
+    }
+
+    fn add_many_to_map(&mut self, idx: PointOffsetType, values: Vec<bool>) -> OperationResult<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "Index add error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+
+        let flags = values.iter().fold(0, |flags, value| {
+            flags | if *value { TRUE_FLAG } else { FALSE_FLAG }
+        });
+        store_ref
+            .put_cf_opt(cf_handle, idx.to_be_bytes(), [flags], &db_write_options())
+            .map_err(|e| OperationError::service_error(&format!("Index db update error: {}", e)))?;
+        drop(store_ref);
+
+        self.set_flags(idx, flags);
+        Ok(())
+    }
+
+    fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        if self.get_flags(idx) == 0 {
+            return Ok(());
+        }
+
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "point remove error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        store_ref.delete_cf(cf_handle, idx.to_be_bytes())?;
+        drop(store_ref);
+
+        self.set_flags(idx, 0);
+        Ok(())
+    }
+}
+
+impl PayloadFieldIndex for BoolIndex {
+    fn indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        BoolIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        Ok(self.db.borrow_mut().drop_cf(&self.store_cf_name)?)
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        BoolIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Bool(value),
+            })) => Some(self.get_iterator(*value)),
+            _ => None,
+        }
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Bool(value),
+            })) => {
+                let mut estimation = CardinalityEstimation::exact(self.value_count(*value));
+                estimation
+                    .primary_clauses
+                    .push(PrimaryCondition::Condition(condition.clone()));
+                Some(estimation)
+            }
+            _ => None,
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = [true, false]
+            .into_iter()
+            .map(|value| (value, self.value_count(value)))
+            .filter(move |(_value, count)| *count >= threshold)
+            .map(move |(value, count)| PayloadBlockCondition {
+                condition: FieldCondition::new_match(key.clone(), value.into()),
+                cardinality: count,
+            });
+        Box::new(iter)
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+}
+
+impl ValueIndexer<bool> for BoolIndex {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<bool>) -> OperationResult<()> {
+        self.add_many_to_map(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<bool> {
+        value.as_bool()
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.remove_point(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use serde_json::json;
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::common::rocksdb_operations::open_db_with_existing_cf;
+
+    const FIELD_NAME: &str = "test";
+
+    #[test]
+    fn test_bool_index() {
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        let mut index = BoolIndex::new(
+            open_db_with_existing_cf(tmp_dir.path()).unwrap(),
+            FIELD_NAME,
+        );
+        index.recreate().unwrap();
+
+        let payloads = vec![
+            json!(true),
+            json!(false),
+            json!([true, false]),
+            json!("true"),
+            json!(true),
+        ];
+        for (idx, payload) in payloads.iter().enumerate() {
+            index.add_point(idx as PointOffsetType, payload).unwrap();
+        }
+        index.remove_point(4).unwrap();
+        index.flush().unwrap();
+        drop(index);
+
+        let mut index = BoolIndex::new(
+            open_db_with_existing_cf(tmp_dir.path()).unwrap(),
+            FIELD_NAME,
+        );
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 3);
+        assert_eq!(index.get_values(2), vec![true, false]);
+        assert!(index.get_values(3).is_empty());
+
+        let true_condition = FieldCondition::new_match(FIELD_NAME.to_string(), true.into());
+        let found = index.filter(&true_condition).unwrap().collect_vec();
+        assert_eq!(found, vec![0, 2]);
+        assert_eq!(index.estimate_cardinality(&true_condition).unwrap().exp, 2);
+
+        let blocks = index
+            .payload_blocks(2, FIELD_NAME.to_string())
+            .map(|block| block.cardinality)
+            .collect_vec();
+        assert_eq!(blocks, vec![2, 2]);
+    }
+}
@@ -1,12 +1,24 @@
+use std::collections::BTreeMap;
+use std::mem::size_of;
+
+use itertools::Itertools;
 use serde_json::Value;
 
 use crate::entry::entry_point::OperationResult;
+use crate::index::field_index::bool_index::BoolIndex;
+use crate::index::field_index::full_text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::map_index::MapIndex;
+use crate::index::field_index::mmap_geo_index::MmapGeoMapIndex;
+use crate::index::field_index::mmap_map_index::MmapMapIndex;
+use crate::index::field_index::mmap_numeric_index::MmapNumericIndex;
+use crate::index::field_index::null_index::NullIndex;
 use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::types::{
-    FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, PointOffsetType,
+    DateTimePayloadType, FacetValue, FacetValueHit, FieldCondition, FloatPayloadType,
+    IntPayloadType, PayloadIndexStats, PayloadKeyType, PayloadSchemaType, PointOffsetType,
+    UuidIntType, INDEX_STATS_TOP_VALUES,
 };
 
 pub trait PayloadFieldIndex {
@@ -80,7 +92,19 @@ pub enum FieldIndex {
     IntMapIndex(MapIndex<IntPayloadType>),
     KeywordIndex(MapIndex<String>),
     FloatIndex(NumericIndex<FloatPayloadType>),
+    DatetimeIndex(NumericIndex<DateTimePayloadType>),
     GeoIndex(GeoMapIndex),
+    FullTextIndex(FullTextIndex),
+    BoolIndex(BoolIndex),
+    UuidIndex(MapIndex<UuidIntType>),
+    IntMmapIndex(MmapNumericIndex<IntPayloadType>),
+    IntMapMmapIndex(MmapMapIndex<IntPayloadType>),
+    KeywordMmapIndex(MmapMapIndex<String>),
+    FloatMmapIndex(MmapNumericIndex<FloatPayloadType>),
+    DatetimeMmapIndex(MmapNumericIndex<DateTimePayloadType>),
+    GeoMmapIndex(MmapGeoMapIndex),
+    UuidMmapIndex(MmapMapIndex<UuidIntType>),
+    NullIndex(NullIndex),
 }
 
 impl FieldIndex {
@@ -90,7 +114,19 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
+            FieldIndex::DatetimeIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
+            FieldIndex::FullTextIndex(payload_field_index) => payload_field_index,
+            FieldIndex::BoolIndex(payload_field_index) => payload_field_index,
+            FieldIndex::UuidIndex(payload_field_index) => payload_field_index,
+            FieldIndex::IntMmapIndex(payload_field_index) => payload_field_index,
+            FieldIndex::IntMapMmapIndex(payload_field_index) => payload_field_index,
+            FieldIndex::KeywordMmapIndex(payload_field_index) => payload_field_index,
+            FieldIndex::FloatMmapIndex(payload_field_index) => payload_field_index,
+            FieldIndex::DatetimeMmapIndex(payload_field_index) => payload_field_index,
+            FieldIndex::GeoMmapIndex(payload_field_index) => payload_field_index,
+            FieldIndex::UuidMmapIndex(payload_field_index) => payload_field_index,
+            FieldIndex::NullIndex(payload_field_index) => payload_field_index,
         }
     }
 
@@ -101,7 +137,19 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::BoolIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::UuidIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::IntMmapIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::IntMapMmapIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::KeywordMmapIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::FloatMmapIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::DatetimeMmapIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::GeoMmapIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::UuidMmapIndex(ref mut payload_field_index) => payload_field_index,
+            FieldIndex::NullIndex(ref mut payload_field_index) => payload_field_index,
         }
     }
 
@@ -111,7 +159,21 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::BoolIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::UuidIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::IntMmapIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::IntMapMmapIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::KeywordMmapIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::FloatMmapIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::DatetimeMmapIndex(ref mut payload_field_index) => {
+                payload_field_index.load()
+            }
+            FieldIndex::GeoMmapIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::UuidMmapIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::NullIndex(ref mut payload_field_index) => payload_field_index.load(),
         }
     }
 
@@ -121,7 +183,19 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.clear(),
             FieldIndex::KeywordIndex(index) => index.clear(),
             FieldIndex::FloatIndex(index) => index.clear(),
+            FieldIndex::DatetimeIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
+            FieldIndex::FullTextIndex(index) => index.clear(),
+            FieldIndex::BoolIndex(index) => index.clear(),
+            FieldIndex::UuidIndex(index) => index.clear(),
+            FieldIndex::IntMmapIndex(index) => index.clear(),
+            FieldIndex::IntMapMmapIndex(index) => index.clear(),
+            FieldIndex::KeywordMmapIndex(index) => index.clear(),
+            FieldIndex::FloatMmapIndex(index) => index.clear(),
+            FieldIndex::DatetimeMmapIndex(index) => index.clear(),
+            FieldIndex::GeoMmapIndex(index) => index.clear(),
+            FieldIndex::UuidMmapIndex(index) => index.clear(),
+            FieldIndex::NullIndex(index) => index.clear(),
         }
     }
 
@@ -131,7 +205,43 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.recreate(),
             FieldIndex::KeywordIndex(index) => index.recreate(),
             FieldIndex::FloatIndex(index) => index.recreate(),
+            FieldIndex::DatetimeIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
+            FieldIndex::FullTextIndex(index) => index.recreate(),
+            FieldIndex::BoolIndex(index) => index.recreate(),
+            FieldIndex::UuidIndex(index) => index.recreate(),
+            FieldIndex::IntMmapIndex(index) => index.recreate(),
+            FieldIndex::IntMapMmapIndex(index) => index.recreate(),
+            FieldIndex::KeywordMmapIndex(index) => index.recreate(),
+            FieldIndex::FloatMmapIndex(index) => index.recreate(),
+            FieldIndex::DatetimeMmapIndex(index) => index.recreate(),
+            FieldIndex::GeoMmapIndex(index) => index.recreate(),
+            FieldIndex::UuidMmapIndex(index) => index.recreate(),
+            FieldIndex::NullIndex(index) => index.recreate(),
+        }
+    }
+
+    /// Complete building of the index, called after all points of a new segment are added.
+    /// On-disk indexes are stored at this point and could not be extended after that.
+    pub fn finalize(&mut self) -> OperationResult<()> {
+        match self {
+            FieldIndex::IntIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BoolIndex(_)
+            | FieldIndex::UuidIndex(_)
+            | FieldIndex::NullIndex(_) => Ok(()),
+            FieldIndex::IntMmapIndex(index) => index.finalize(),
+            FieldIndex::IntMapMmapIndex(index) => index.finalize(),
+            FieldIndex::KeywordMmapIndex(index) => index.finalize(),
+            FieldIndex::FloatMmapIndex(index) => index.finalize(),
+            FieldIndex::DatetimeMmapIndex(index) => index.finalize(),
+            FieldIndex::GeoMmapIndex(index) => index.finalize(),
+            FieldIndex::UuidMmapIndex(index) => index.finalize(),
         }
     }
 
@@ -171,6 +281,231 @@ impl FieldIndex {
         self.get_payload_field_index().count_indexed_points()
     }
 
+    /// The smallest keyword of the point, `None` if the index is not a keyword one
+    pub fn min_keyword(&self, point_id: PointOffsetType) -> Option<String> {
+        match self {
+            FieldIndex::KeywordIndex(index) => index
+                .get_values(point_id)
+                .and_then(|values| values.iter().min().cloned()),
+            FieldIndex::KeywordMmapIndex(index) => index
+                .get_values(point_id)
+                .and_then(|values| values.into_iter().min()),
+            _ => None,
+        }
+    }
+
+    /// Index of the presence of the field, `None` if the index is not a null index
+    pub fn as_null_index(&self) -> Option<&NullIndex> {
+        match self {
+            FieldIndex::NullIndex(index) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Number of points for each indexed value.
+    /// Return `None` if the index does not support facets
+    pub fn facet_counts(&self) -> Option<Box<dyn Iterator<Item = (FacetValue, usize)> + '_>> {
+        match self {
+            FieldIndex::IntMapIndex(index) => Some(Box::new(
+                index
+                    .values_with_counts()
+                    .map(|(value, count)| (FacetValue::Integer(*value), count)),
+            )),
+            FieldIndex::KeywordIndex(index) => {
+                Some(Box::new(index.values_with_counts().map(
+                    |(value, count)| (FacetValue::Keyword(value.clone()), count),
+                )))
+            }
+            FieldIndex::IntMapMmapIndex(index) => Some(Box::new(
+                index
+                    .values_with_counts()
+                    .map(|(value, count)| (FacetValue::Integer(value), count)),
+            )),
+            FieldIndex::KeywordMmapIndex(index) => Some(Box::new(
+                index
+                    .values_with_counts()
+                    .map(|(value, count)| (FacetValue::Keyword(value), count)),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Indexed values of the point.
+    /// Return `None` if the index does not support facets
+    pub fn facet_values(&self, point_id: PointOffsetType) -> Option<Vec<FacetValue>> {
+        match self {
+            FieldIndex::IntMapIndex(index) => Some(
+                index
+                    .get_values(point_id)
+                    .map(|values| values.iter().copied().map(FacetValue::Integer).collect())
+                    .unwrap_or_default(),
+            ),
+            FieldIndex::KeywordIndex(index) => Some(
+                index
+                    .get_values(point_id)
+                    .map(|values| values.iter().cloned().map(FacetValue::Keyword).collect())
+                    .unwrap_or_default(),
+            ),
+            FieldIndex::IntMapMmapIndex(index) => Some(
+                index
+                    .get_values(point_id)
+                    .map(|values| values.into_iter().map(FacetValue::Integer).collect())
+                    .unwrap_or_default(),
+            ),
+            FieldIndex::KeywordMmapIndex(index) => Some(
+                index
+                    .get_values(point_id)
+                    .map(|values| values.into_iter().map(FacetValue::Keyword).collect())
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// All indexed values as numbers, datetime values are represented as seconds since epoch.
+    /// Return `None` if the index is not numeric
+    pub fn numeric_values(&self) -> Option<Box<dyn Iterator<Item = f64> + '_>> {
+        match self {
+            FieldIndex::IntIndex(index) => Some(Box::new(index.values())),
+            FieldIndex::FloatIndex(index) => Some(Box::new(index.values())),
+            FieldIndex::DatetimeIndex(index) => Some(Box::new(index.values())),
+            FieldIndex::IntMmapIndex(index) => Some(Box::new(index.values())),
+            FieldIndex::FloatMmapIndex(index) => Some(Box::new(index.values())),
+            FieldIndex::DatetimeMmapIndex(index) => Some(Box::new(index.values())),
+            _ => None,
+        }
+    }
+
+    /// Count indexed values in the buckets of the `interval` width.
+    /// Return `None` if the index can't count them without reading all values
+    pub fn count_by_interval(&self, interval: f64) -> Option<BTreeMap<i64, usize>> {
+        match self {
+            FieldIndex::IntIndex(index) => Some(index.count_by_interval(interval)),
+            FieldIndex::FloatIndex(index) => Some(index.count_by_interval(interval)),
+            FieldIndex::DatetimeIndex(index) => Some(index.count_by_interval(interval)),
+            // Histogram of the on-disk index is not updated on removal
+            _ => None,
+        }
+    }
+
+    /// Indexed values of the point as numbers.
+    /// Return `None` if the index is not numeric
+    pub fn point_numeric_values(&self, point_id: PointOffsetType) -> Option<Vec<f64>> {
+        let values = match self {
+            FieldIndex::IntIndex(index) => index
+                .get_values(point_id)
+                .map(|values| values.iter().map(|value| *value as f64).collect()),
+            FieldIndex::FloatIndex(index) => index.get_values(point_id).cloned(),
+            FieldIndex::DatetimeIndex(index) => index
+                .get_values(point_id)
+                .map(|values| values.iter().map(|value| value.0).collect()),
+            FieldIndex::IntMmapIndex(index) => index
+                .get_values(point_id)
+                .map(|values| values.into_iter().map(|value| value as f64).collect()),
+            FieldIndex::FloatMmapIndex(index) => index.get_values(point_id),
+            FieldIndex::DatetimeMmapIndex(index) => index
+                .get_values(point_id)
+                .map(|values| values.into_iter().map(|value| value.0).collect()),
+            _ => return None,
+        };
+        Some(values.unwrap_or_default())
+    }
+
+    /// Statistics of the index, built from the in-memory structures
+    pub fn stats(&self, data_type: PayloadSchemaType) -> OperationResult<PayloadIndexStats> {
+        let distinct_values = match self {
+            FieldIndex::IntIndex(_) | FieldIndex::FloatIndex(_) | FieldIndex::DatetimeIndex(_) => {
+                self.numeric_values().map(|values| values.dedup().count())
+            }
+            FieldIndex::IntMapIndex(index) => Some(index.values_with_counts().count()),
+            FieldIndex::KeywordIndex(index) => Some(index.values_with_counts().count()),
+            FieldIndex::UuidIndex(index) => Some(index.values_with_counts().count()),
+            FieldIndex::IntMmapIndex(_)
+            | FieldIndex::FloatMmapIndex(_)
+            | FieldIndex::DatetimeMmapIndex(_) => {
+                self.numeric_values().map(|values| values.dedup().count())
+            }
+            FieldIndex::IntMapMmapIndex(index) => Some(index.values_with_counts().count()),
+            FieldIndex::KeywordMmapIndex(index) => Some(index.values_with_counts().count()),
+            FieldIndex::UuidMmapIndex(index) => Some(index.values_with_counts().count()),
+            FieldIndex::FullTextIndex(index) => Some(index.tokens_count()),
+            FieldIndex::BoolIndex(index) => Some(index.distinct_values()),
+            FieldIndex::GeoIndex(_) | FieldIndex::GeoMmapIndex(_) | FieldIndex::NullIndex(_) => {
+                None
+            }
+        };
+        let top_values = self.facet_counts().map(|counts| {
+            counts
+                .map(|(value, count)| FacetValueHit { value, count })
+                .sorted_by(|a, b| a.compare(b))
+                .take(INDEX_STATS_TOP_VALUES)
+                .collect()
+        });
+        let numeric = match self {
+            FieldIndex::IntIndex(index) => index.numeric_stats(),
+            FieldIndex::FloatIndex(index) => index.numeric_stats(),
+            FieldIndex::DatetimeIndex(index) => index.numeric_stats(),
+            FieldIndex::IntMmapIndex(index) => index.numeric_stats(),
+            FieldIndex::FloatMmapIndex(index) => index.numeric_stats(),
+            FieldIndex::DatetimeMmapIndex(index) => index.numeric_stats(),
+            _ => None,
+        };
+        let (ram_usage_bytes, disk_usage_bytes) = match self {
+            FieldIndex::IntIndex(index) => (index.ram_usage_bytes(), index.disk_usage_bytes()?),
+            FieldIndex::IntMapIndex(index) => (
+                index.ram_usage_bytes(|_| size_of::<IntPayloadType>()),
+                index.disk_usage_bytes()?,
+            ),
+            FieldIndex::KeywordIndex(index) => (
+                index.ram_usage_bytes(|value| size_of::<String>() + value.len()),
+                index.disk_usage_bytes()?,
+            ),
+            FieldIndex::FloatIndex(index) => (index.ram_usage_bytes(), index.disk_usage_bytes()?),
+            FieldIndex::DatetimeIndex(index) => {
+                (index.ram_usage_bytes(), index.disk_usage_bytes()?)
+            }
+            FieldIndex::GeoIndex(index) => (index.ram_usage_bytes(), index.disk_usage_bytes()?),
+            FieldIndex::FullTextIndex(index) => {
+                (index.ram_usage_bytes(), index.disk_usage_bytes()?)
+            }
+            FieldIndex::BoolIndex(index) => (index.ram_usage_bytes(), index.disk_usage_bytes()?),
+            FieldIndex::UuidIndex(index) => (
+                index.ram_usage_bytes(|_| size_of::<UuidIntType>()),
+                index.disk_usage_bytes()?,
+            ),
+            FieldIndex::IntMmapIndex(index) => (index.ram_usage_bytes(), index.disk_usage_bytes()),
+            FieldIndex::IntMapMmapIndex(index) => {
+                (index.ram_usage_bytes(), index.disk_usage_bytes())
+            }
+            FieldIndex::KeywordMmapIndex(index) => {
+                (index.ram_usage_bytes(), index.disk_usage_bytes())
+            }
+            FieldIndex::FloatMmapIndex(index) => {
+                (index.ram_usage_bytes(), index.disk_usage_bytes())
+            }
+            FieldIndex::DatetimeMmapIndex(index) => {
+                (index.ram_usage_bytes(), index.disk_usage_bytes())
+            }
+            FieldIndex::GeoMmapIndex(index) => (index.ram_usage_bytes(), index.disk_usage_bytes()),
+            FieldIndex::UuidMmapIndex(index) => (index.ram_usage_bytes(), index.disk_usage_bytes()),
+            FieldIndex::NullIndex(index) => (index.ram_usage_bytes(), index.disk_usage_bytes()?),
+        };
+        let points_count = match self {
+            // Values are counted by the other indexes of the field
+            FieldIndex::NullIndex(_) => 0,
+            _ => self.count_indexed_points(),
+        };
+        Ok(PayloadIndexStats {
+            data_type,
+            points_count,
+            distinct_values,
+            top_values,
+            numeric,
+            ram_usage_bytes,
+            disk_usage_bytes,
+        })
+    }
+
     pub fn add_point(&mut self, id: PointOffsetType, payload: &Value) -> OperationResult<()> {
         match self {
             FieldIndex::IntIndex(ref mut payload_field_index) => {
@@ -185,9 +520,45 @@ impl FieldIndex {
             FieldIndex::FloatIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::DatetimeIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::GeoIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::FullTextIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::BoolIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::UuidIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::IntMmapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::IntMapMmapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::KeywordMmapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::FloatMmapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::DatetimeMmapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::GeoMmapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::UuidMmapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
+            FieldIndex::NullIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
         }
     }
 
@@ -197,7 +568,19 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(index) => index.remove_point(point_id),
             FieldIndex::KeywordIndex(index) => index.remove_point(point_id),
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
+            FieldIndex::DatetimeIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
+            FieldIndex::FullTextIndex(index) => index.remove_point(point_id),
+            FieldIndex::BoolIndex(index) => index.remove_point(point_id),
+            FieldIndex::UuidIndex(index) => index.remove_point(point_id),
+            FieldIndex::IntMmapIndex(index) => index.remove_point(point_id),
+            FieldIndex::IntMapMmapIndex(index) => index.remove_point(point_id),
+            FieldIndex::KeywordMmapIndex(index) => index.remove_point(point_id),
+            FieldIndex::FloatMmapIndex(index) => index.remove_point(point_id),
+            FieldIndex::DatetimeMmapIndex(index) => index.remove_point(point_id),
+            FieldIndex::GeoMmapIndex(index) => index.remove_point(point_id),
+            FieldIndex::UuidMmapIndex(index) => index.remove_point(point_id),
+            FieldIndex::NullIndex(index) => index.remove_point(point_id),
         }
     }
 }
@@ -0,0 +1,523 @@
+use std::collections::{BTreeSet, HashMap};
+use std::iter;
+use std::mem::size_of;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use rocksdb::{IteratorMode, DB};
+use serde_json::Value;
+
+use crate::common::rocksdb_operations::{cf_disk_usage_bytes, db_write_options, recreate_cf};
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::full_text_index::tokenizers::Tokenizer;
+use crate::index::field_index::{
+    CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
+};
+use crate::types::{
+    FieldCondition, Match, MatchText, PayloadKeyType, PointOffsetType, TextIndexParams,
+};
+
+pub mod tokenizers;
+
+/// Set of tokens, extracted from all text values of a single point
+type Document = BTreeSet<String>;
+
+/// Tokens of a full-text query. Point matches if its document contains all of them.
+#[derive(Debug, Clone)]
+pub struct ParsedQuery {
+    pub tokens: Vec<String>,
+}
+
+impl ParsedQuery {
+    pub fn parse(text: &str, config: &TextIndexParams) -> Self {
+        let mut tokens = vec![];
+        Tokenizer::tokenize_query(text, config, |token| tokens.push(token));
+        tokens.sort_unstable();
+        tokens.dedup();
+        ParsedQuery { tokens }
+    }
+
+    pub fn check_match(&self, document: &Document) -> bool {
+        !self.tokens.is_empty() && self.tokens.iter().all(|token| document.contains(token))
+    }
+}
+
+/// Check the text condition against the payload value without the full-text index.
+/// Text values are tokenized with the default parameters of the index and, like in the index,
+/// form a single document, which must contain all tokens of the query.
+pub fn check_text_match(text: &str, payload: &Value) -> bool {
+    let config = TextIndexParams::default();
+    let values = match payload {
+        Value::Array(values) => values.iter().collect(),
+        value => vec![value],
+    };
+    let mut document = Document::new();
+    for value in values.into_iter().filter_map(Value::as_str) {
+        Tokenizer::tokenize_doc(value, &config, |token| {
+            document.insert(token);
+        });
+    }
+    ParsedQuery::parse(text, &config).check_match(&document)
+}
+
+/// Inverted index over tokens of text payload values
+pub struct FullTextIndex {
+    postings: HashMap<String, BTreeSet<PointOffsetType>>,
+    point_to_document: Vec<Document>,
+    /// Amount of point which have at least one indexed token
+    indexed_points: usize,
+    config: TextIndexParams,
+    store_cf_name: String,
+    db: Arc<AtomicRefCell<DB>>,
+}
+
+impl FullTextIndex {
+    pub fn new(db: Arc<AtomicRefCell<DB>>, config: TextIndexParams, field: &str) -> Self {
+        FullTextIndex {
+            postings: Default::default(),
+            point_to_document: vec![],
+            indexed_points: 0,
+            config,
+            store_cf_name: Self::storage_cf_name(field),
+            db,
+        }
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_fts")
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        Ok(recreate_cf(self.db.clone(), &self.store_cf_name)?)
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        let db_ref = self.db.borrow();
+        let cf_handle = if let Some(cf_handle) = db_ref.cf_handle(&self.store_cf_name) {
+            cf_handle
+        } else {
+            return Ok(false);
+        };
+        self.indexed_points = 0;
+        for (key, value) in db_ref.iterator_cf(cf_handle, IteratorMode::Start) {
+            let idx = Self::decode_db_key(&key)?;
+            let document: Document = serde_cbor::from_slice(&value)?;
+            if self.point_to_document.len() <= idx as usize {
+                self.point_to_document
+                    .resize(idx as usize + 1, Document::new())
+            }
+            for token in &document {
+                self.postings.entry(token.clone()).or_default().insert(idx);
+            }
+            if !document.is_empty() {
+                self.indexed_points += 1;
+            }
+            self.point_to_document[idx as usize] = document;
+        }
+        Ok(true)
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "Index flush error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        Ok(store_ref.flush_cf(cf_handle)?)
+    }
+
+    fn encode_db_key(idx: PointOffsetType) -> String {
+        idx.to_string()
+    }
+
+    fn decode_db_key(key: &[u8]) -> OperationResult<PointOffsetType> {
+        const DECODE_ERR: &str = "Index db parsing error: wrong data format";
+        let key_str =
+            std::str::from_utf8(key).map_err(|_| OperationError::service_error(DECODE_ERR))?;
+        PointOffsetType::from_str(key_str).map_err(|_| OperationError::service_error(DECODE_ERR))
+    }
+
+    pub fn get_document(&self, idx: PointOffsetType) -> Option<&Document> {
+        self.point_to_document.get(idx as usize)
+    }
+
+    /// Number of distinct tokens in the index
+    pub fn tokens_count(&self) -> usize {
+        self.postings.len()
+    }
+
+    /// Rough estimation of memory, used by the index structures
+    pub fn ram_usage_bytes(&self) -> usize {
+        let token_size = |token: &String| size_of::<String>() + token.len();
+        let postings_usage = self
+            .postings
+            .iter()
+            .map(|(token, points)| token_size(token) + points.len() * size_of::<PointOffsetType>())
+            .sum::<usize>();
+        let documents_usage = self
+            .point_to_document
+            .iter()
+            .map(|document| size_of::<Document>() + document.iter().map(token_size).sum::<usize>())
+            .sum::<usize>();
+        postings_usage + documents_usage
+    }
+
+    pub fn disk_usage_bytes(&self) -> OperationResult<usize> {
+        Ok(cf_disk_usage_bytes(&self.db.borrow(), &self.store_cf_name)?)
+    }
+
+    pub fn parse_query(&self, text: &str) -> ParsedQuery {
+        ParsedQuery::parse(text, &self.config)
+    }
+
+    /// Check if the point matches parsed full-text query
+    pub fn check_match(&self, query: &ParsedQuery, idx: PointOffsetType) -> bool {
+        self.get_document(idx)
+            .map(|document| query.check_match(document))
+            .unwrap_or(false)
+    }
+
+    /// Posting lists of all query tokens, ordered by their size.
+    /// `None` if some token is not present in the index at all.
+    fn query_postings(&self, query: &ParsedQuery) -> Option<Vec<&BTreeSet<PointOffsetType>>> {
+        let mut postings = query
+            .tokens
+            .iter()
+            .map(|token| self.postings.get(token))
+            .collect::<Option<Vec<_>>>()?;
+        postings.sort_by_key(|posting| posting.len());
+        Some(postings)
+    }
+
+    fn get_iterator(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self.query_postings(query) {
+            Some(postings) if !postings.is_empty() => {
+                let mut postings = postings.into_iter();
+                let smallest = postings.next().unwrap();
+                let rest: Vec<_> = postings.collect();
+                Box::new(
+                    smallest
+                        .iter()
+                        .copied()
+                        .filter(move |idx| rest.iter().all(|posting| posting.contains(idx))),
+                )
+            }
+            _ => Box::new(iter::empty::<PointOffsetType>()),
+        }
+    }
+
+    pub fn match_cardinality(&self, query: &ParsedQuery) -> CardinalityEstimation {
+        let postings = match self.query_postings(query) {
+            Some(postings) if !postings.is_empty() => postings,
+            _ => return CardinalityEstimation::exact(0),
+        };
+
+        let smallest = postings[0].len();
+        if postings.len() == 1 {
+            return CardinalityEstimation::exact(smallest);
+        }
+
+        // Assume tokens are distributed independently of each other
+        let total = self.indexed_points.max(1) as f64;
+        let expected = postings
+            .iter()
+            .fold(total, |acc, posting| acc * posting.len() as f64 / total);
+
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp: (expected.round() as usize).min(smallest),
+            max: smallest,
+        }
+    }
+
+    fn add_document(&mut self, idx: PointOffsetType, document: Document) -> OperationResult<()> {
+        self.remove_point(idx)?;
+
+        if document.is_empty() {
+            return Ok(());
+        }
+
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "Index add error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+
+        store_ref
+            .put_cf_opt(
+                cf_handle,
+                Self::encode_db_key(idx),
+                serde_cbor::to_vec(&document)?,
+                &db_write_options(),
+            )
+            .map_err(|e| OperationError::service_error(&format!("Index db update error: {}", e)))?;
+
+        for token in &document {
+            self.postings.entry(token.clone()).or_default().insert(idx);
+        }
+
+        if self.point_to_document.len() <= idx as usize {
+            self.point_to_document
+                .resize(idx as usize + 1, Document::new())
+        }
+        self.point_to_document[idx as usize] = document;
+        self.indexed_points += 1;
+        Ok(())
+    }
+
+    fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        if self.point_to_document.len() <= idx as usize {
+            return Ok(());
+        }
+
+        let removed_document = std::mem::take(&mut self.point_to_document[idx as usize]);
+        if removed_document.is_empty() {
+            return Ok(());
+        }
+        self.indexed_points -= 1;
+
+        for token in &removed_document {
+            if let Some(posting) = self.postings.get_mut(token) {
+                posting.remove(&idx);
+                if posting.is_empty() {
+                    self.postings.remove(token);
+                }
+            }
+        }
+
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "point remove error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        store_ref.delete_cf(cf_handle, Self::encode_db_key(idx))?;
+
+        Ok(())
+    }
+}
+
+impl PayloadFieldIndex for FullTextIndex {
+    fn indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        FullTextIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        Ok(self.db.borrow_mut().drop_cf(&self.store_cf_name)?)
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        FullTextIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.r#match {
+            Some(Match::Text(MatchText { text })) => {
+                Some(self.get_iterator(&self.parse_query(text)))
+            }
+            _ => None,
+        }
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        match &condition.r#match {
+            Some(Match::Text(MatchText { text })) => {
+                let mut estimation = self.match_cardinality(&self.parse_query(text));
+                estimation
+                    .primary_clauses
+                    .push(PrimaryCondition::Condition(condition.clone()));
+                Some(estimation)
+            }
+            _ => None,
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = self
+            .postings
+            .iter()
+            .filter(move |(_token, point_ids)| point_ids.len() >= threshold)
+            .map(move |(token, point_ids)| PayloadBlockCondition {
+                condition: FieldCondition::new_match(
+                    key.clone(),
+                    Match::Text(MatchText {
+                        text: token.clone(),
+                    }),
+                ),
+                cardinality: point_ids.len(),
+            });
+        Box::new(iter)
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+}
+
+impl ValueIndexer<String> for FullTextIndex {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
+        let mut document = Document::new();
+        for value in &values {
+            Tokenizer::tokenize_doc(value, &self.config, |token| {
+                document.insert(token);
+            });
+        }
+        self.add_document(id, document)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<String> {
+        if let Value::String(text) = value {
+            return Some(text.to_owned());
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.remove_point(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use serde_json::json;
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::common::rocksdb_operations::open_db_with_existing_cf;
+    use crate::types::TokenizerType;
+
+    const FIELD_NAME: &str = "description";
+
+    fn texts() -> Vec<Value> {
+        vec![
+            json!("The quick brown fox"),
+            json!("Quick vector search"),
+            json!(["Brown bear", "lazy dog"]),
+            json!("Fox and the hound"),
+            json!(42),
+        ]
+    }
+
+    fn build_index(db: Arc<AtomicRefCell<DB>>, config: TextIndexParams) -> FullTextIndex {
+        let mut index = FullTextIndex::new(db, config, FIELD_NAME);
+        index.recreate().unwrap();
+        for (idx, payload) in texts().iter().enumerate() {
+            index.add_point(idx as PointOffsetType, payload).unwrap();
+        }
+        index
+    }
+
+    fn filter_text(index: &FullTextIndex, text: &str) -> Vec<PointOffsetType> {
+        let condition = FieldCondition::new_match(
+            FIELD_NAME.to_string(),
+            Match::Text(MatchText {
+                text: text.to_string(),
+            }),
+        );
+        index.filter(&condition).unwrap().collect()
+    }
+
+    #[test]
+    fn test_full_text_filtering() {
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let mut index = build_index(db, TextIndexParams::default());
+
+        assert_eq!(index.indexed_points(), 4);
+        assert_eq!(filter_text(&index, "quick"), vec![0, 1]);
+        assert_eq!(filter_text(&index, "Brown FOX"), vec![0]);
+        assert_eq!(filter_text(&index, "brown"), vec![0, 2]);
+        assert!(filter_text(&index, "fox cat").is_empty());
+        assert!(filter_text(&index, "qui").is_empty());
+
+        let estimation = index
+            .estimate_cardinality(&FieldCondition::new_match(
+                FIELD_NAME.to_string(),
+                Match::Text(MatchText {
+                    text: "fox".to_string(),
+                }),
+            ))
+            .unwrap();
+        assert_eq!(estimation.exp, 2);
+        assert_eq!(estimation.primary_clauses.len(), 1);
+
+        let blocks = index.payload_blocks(2, FIELD_NAME.to_string()).count();
+        // "the", "quick", "brown", "fox"
+        assert_eq!(blocks, 4);
+
+        // Payload without the index is matched the same way
+        for text in [
+            "quick",
+            "Brown FOX",
+            "brown",
+            "bear dog",
+            "fox cat",
+            "qui",
+            "",
+        ] {
+            let checked = texts()
+                .iter()
+                .positions(|payload| check_text_match(text, payload))
+                .map(|idx| idx as PointOffsetType)
+                .collect_vec();
+            assert_eq!(checked, filter_text(&index, text), "{text}");
+        }
+
+        index.remove_point(0).unwrap();
+        assert_eq!(filter_text(&index, "quick"), vec![1]);
+        assert_eq!(index.indexed_points(), 3);
+    }
+
+    #[test]
+    fn test_prefix_filtering() {
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let config = TextIndexParams {
+            tokenizer: TokenizerType::Prefix,
+            ..Default::default()
+        };
+        let index = build_index(db, config);
+
+        assert_eq!(filter_text(&index, "qui"), vec![0, 1]);
+        assert!(filter_text(&index, "bro ho").is_empty());
+        assert_eq!(filter_text(&index, "fo ho"), vec![3]);
+    }
+
+    #[test]
+    fn test_full_text_load_from_disk() {
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        {
+            let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+            let index = build_index(db, TextIndexParams::default());
+            index.flush().unwrap();
+        }
+
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let mut index = FullTextIndex::new(db, TextIndexParams::default(), FIELD_NAME);
+        assert!(index.load().unwrap());
+
+        assert_eq!(index.indexed_points(), 4);
+        assert_eq!(filter_text(&index, "lazy dog"), vec![2]);
+        assert!(index.check_match(&index.parse_query("hound"), 3));
+        assert!(!index.check_match(&index.parse_query("hound"), 4));
+    }
+}
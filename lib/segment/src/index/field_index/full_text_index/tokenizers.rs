@@ -0,0 +1,129 @@
+use crate::types::{TextIndexParams, TokenizerType};
+
+/// Splits text into tokens according to the full-text index configuration
+pub struct Tokenizer;
+
+impl Tokenizer {
+    fn split<'a>(
+        text: &'a str,
+        tokenizer: TokenizerType,
+    ) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        match tokenizer {
+            TokenizerType::Whitespace => Box::new(text.split_whitespace()),
+            TokenizerType::Word | TokenizerType::Prefix => Box::new(
+                text.split(|c: char| !c.is_alphanumeric())
+                    .filter(|token| !token.is_empty()),
+            ),
+        }
+    }
+
+    fn normalize(token: &str, config: &TextIndexParams) -> String {
+        if config.lowercase.unwrap_or(true) {
+            token.to_lowercase()
+        } else {
+            token.to_string()
+        }
+    }
+
+    fn token_len_fits(token: &str, config: &TextIndexParams) -> bool {
+        let len = token.chars().count();
+        config.min_token_len.map_or(true, |min_len| len >= min_len)
+            && config.max_token_len.map_or(true, |max_len| len <= max_len)
+    }
+
+    /// Tokens to be stored in the index for a given payload text.
+    /// Prefix tokenizer additionally produces every prefix of each word.
+    pub fn tokenize_doc<C: FnMut(String)>(text: &str, config: &TextIndexParams, mut callback: C) {
+        for word in Self::split(text, config.tokenizer) {
+            let token = Self::normalize(word, config);
+            match config.tokenizer {
+                TokenizerType::Prefix => {
+                    let min_len = config.min_token_len.unwrap_or(1).max(1);
+                    let max_len = config.max_token_len.unwrap_or(usize::MAX);
+                    for (prefix_len, (char_start, char)) in token.char_indices().enumerate() {
+                        let prefix_len = prefix_len + 1;
+                        if prefix_len > max_len {
+                            break;
+                        }
+                        if prefix_len >= min_len {
+                            callback(token[..char_start + char.len_utf8()].to_string());
+                        }
+                    }
+                }
+                TokenizerType::Whitespace | TokenizerType::Word => {
+                    if Self::token_len_fits(&token, config) {
+                        callback(token);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Tokens of the text used in a `match: {text: ...}` condition.
+    /// All of them must be present in the document for it to match.
+    pub fn tokenize_query<C: FnMut(String)>(text: &str, config: &TextIndexParams, mut callback: C) {
+        for word in Self::split(text, config.tokenizer) {
+            let token = Self::normalize(word, config);
+            match (config.tokenizer, config.max_token_len) {
+                // Longest indexed prefix is still a valid filter for a longer word
+                (TokenizerType::Prefix, Some(max_len)) if token.chars().count() > max_len => {
+                    callback(token.chars().take(max_len).collect())
+                }
+                _ => {
+                    if Self::token_len_fits(&token, config) {
+                        callback(token);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc_tokens(text: &str, config: &TextIndexParams) -> Vec<String> {
+        let mut tokens = vec![];
+        Tokenizer::tokenize_doc(text, config, |token| tokens.push(token));
+        tokens
+    }
+
+    #[test]
+    fn test_whitespace_tokenizer() {
+        let config = TextIndexParams {
+            tokenizer: TokenizerType::Whitespace,
+            ..Default::default()
+        };
+        let tokens = doc_tokens("Hello, World!  qdrant", &config);
+        assert_eq!(tokens, vec!["hello,", "world!", "qdrant"]);
+    }
+
+    #[test]
+    fn test_word_tokenizer() {
+        let config = TextIndexParams {
+            tokenizer: TokenizerType::Word,
+            lowercase: Some(false),
+            min_token_len: Some(2),
+            ..Default::default()
+        };
+        let tokens = doc_tokens("Hello, World! a vector-search", &config);
+        assert_eq!(tokens, vec!["Hello", "World", "vector", "search"]);
+    }
+
+    #[test]
+    fn test_prefix_tokenizer() {
+        let config = TextIndexParams {
+            tokenizer: TokenizerType::Prefix,
+            min_token_len: Some(2),
+            max_token_len: Some(4),
+            ..Default::default()
+        };
+        let tokens = doc_tokens("Qdrant über", &config);
+        assert_eq!(tokens, vec!["qd", "qdr", "qdra", "üb", "übe", "über"]);
+
+        let mut query_tokens = vec![];
+        Tokenizer::tokenize_query("QDRANTS", &config, |token| query_tokens.push(token));
+        assert_eq!(query_tokens, vec!["qdra"]);
+    }
+}
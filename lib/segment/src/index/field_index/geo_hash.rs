@@ -1,11 +1,13 @@
 use std::ops::Range;
 
+use geo::algorithm::contains::Contains;
 use geo::algorithm::haversine_distance::HaversineDistance;
-use geo::{Coordinate, Point};
+use geo::algorithm::intersects::Intersects;
+use geo::{Coordinate, Point, Rect};
 use geohash::{decode, decode_bbox, encode, Direction, GeohashError};
 use itertools::Itertools;
 
-use crate::types::{GeoBoundingBox, GeoPoint, GeoRadius};
+use crate::types::{GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, PolygonWrapper};
 
 pub type GeoHash = String;
 
@@ -233,6 +235,102 @@ pub fn rectangle_hashes(rectangle: &GeoBoundingBox, max_regions: usize) -> Vec<G
         .expect("geo-hash coverage is empty")
 }
 
+/// Geohash tile as a geometry with `x` as longitude and `y` as latitude.
+/// Empty geohash covers the whole world.
+pub fn geo_hash_to_rect(geo_hash: &str) -> Rect<f64> {
+    if geo_hash.is_empty() {
+        return Rect::new(
+            Coordinate {
+                x: LON_RANGE.start,
+                y: LAT_RANGE.start,
+            },
+            Coordinate {
+                x: LON_RANGE.end,
+                y: LAT_RANGE.end,
+            },
+        );
+    }
+    decode_bbox(geo_hash).unwrap()
+}
+
+/// Check if geohash tile intersects the polygon
+fn check_polygon_intersection(geohash: &str, polygon: &PolygonWrapper) -> bool {
+    if geohash.is_empty() {
+        return true;
+    }
+    polygon.polygon.intersects(&geo_hash_to_rect(geohash))
+}
+
+/// Check if geohash tile lies completely within the polygon,
+/// so points of the tile don't require individual checks.
+pub fn check_polygon_covers(geohash: &str, polygon: &PolygonWrapper) -> bool {
+    if geohash.is_empty() {
+        return false;
+    }
+    let rect = geo_hash_to_rect(geohash);
+    // If none of the polygon borders crosses the tile, the tile is either inside or outside
+    let crosses_border = polygon.polygon.exterior().intersects(&rect)
+        || polygon
+            .polygon
+            .interiors()
+            .iter()
+            .any(|interior| interior.intersects(&rect));
+    !crosses_border && polygon.polygon.contains(&Point::from(rect.center()))
+}
+
+/// Return as-high-as-possible with maximum of `max_regions`
+/// number of geo-hash guaranteed to contain the whole polygon.
+pub fn polygon_hashes(polygon: &GeoPolygon, max_regions: usize) -> Vec<GeoHash> {
+    assert_ne!(max_regions, 0, "max_regions cannot be equal to zero");
+    let polygon_wrapper = polygon.convert();
+    let geo_bounding_box = minimum_bounding_rectangle_for_polygon(polygon);
+    let full_geohash_bounding_box: GeohashBoundingBox = geo_bounding_box.into();
+
+    (0..=GEOHASH_MAX_LENGTH)
+        .map(|precision| {
+            full_geohash_bounding_box
+                .geohash_regions(precision, max_regions)
+                .map(|hashes| {
+                    hashes
+                        .into_iter()
+                        .filter(|hash| check_polygon_intersection(hash, &polygon_wrapper))
+                        .collect_vec()
+                })
+        })
+        .take_while(|hashes| hashes.is_some())
+        .last()
+        .expect("no hash coverage for any precision")
+        .expect("geo-hash coverage is empty")
+}
+
+/// Returns the GeoBoundingBox of the polygon exterior
+fn minimum_bounding_rectangle_for_polygon(polygon: &GeoPolygon) -> GeoBoundingBox {
+    let points = &polygon.exterior.points;
+    let (min_lon, max_lon) = points
+        .iter()
+        .map(|point| point.lon)
+        .minmax()
+        .into_option()
+        .unwrap_or((LON_RANGE.start, LON_RANGE.end));
+    let (min_lat, max_lat) = points
+        .iter()
+        .map(|point| point.lat)
+        .minmax()
+        .into_option()
+        .unwrap_or((LAT_RANGE.start, LAT_RANGE.end));
+
+    GeoBoundingBox {
+        top_left: GeoPoint {
+            lat: max_lat,
+            lon: min_lon,
+        },
+        bottom_right: GeoPoint {
+            lat: min_lat,
+            lon: max_lon,
+        },
+    }
+}
+
 /// A globally-average value is usually considered to be 6,371 kilometres (3,959 mi) with a 0.3% variability (±10 km).
 /// https://en.wikipedia.org/wiki/Earth_radius.
 const EARTH_RADIUS_METERS: f64 = 6371.0 * 1000.;
@@ -289,6 +387,7 @@ mod tests {
     use rand::{Rng, SeedableRng};
 
     use super::*;
+    use crate::types::GeoLineString;
 
     const BERLIN: GeoPoint = GeoPoint {
         lat: 52.52437,
@@ -461,6 +560,69 @@ mod tests {
         }
     }
 
+    fn build_polygon(points: &[(f64, f64)], interiors: Vec<Vec<(f64, f64)>>) -> GeoPolygon {
+        let line = |points: &[(f64, f64)]| GeoLineString {
+            points: points
+                .iter()
+                .map(|&(lon, lat)| GeoPoint { lon, lat })
+                .collect(),
+        };
+        GeoPolygon {
+            exterior: line(points),
+            interiors: Some(interiors.iter().map(|interior| line(interior)).collect()),
+        }
+    }
+
+    #[test]
+    fn polygon_hashes_cover_polygon() {
+        let polygon = build_polygon(
+            &[
+                (-74.02, 40.70),
+                (-73.93, 40.71),
+                (-73.97, 40.80),
+                (-74.02, 40.70),
+            ],
+            vec![vec![
+                (-73.98, 40.73),
+                (-73.96, 40.73),
+                (-73.97, 40.75),
+                (-73.98, 40.73),
+            ]],
+        );
+        let polygon_wrapper = polygon.convert();
+
+        let max_hashes = 16;
+        let hashes = polygon_hashes(&polygon, max_hashes);
+        assert!(!hashes.is_empty());
+        assert!(hashes.len() <= max_hashes);
+
+        let mut rnd = StdRng::seed_from_u64(42);
+        for _ in 0..1000 {
+            let lon = rnd.gen_range(-74.05..-73.90);
+            let lat = rnd.gen_range(40.65..40.85);
+            let point_hash = encode_max_precision(lon, lat).unwrap();
+            let is_covered = hashes.iter().any(|hash| point_hash.starts_with(hash));
+            let is_inner = hashes.iter().any(|hash| {
+                point_hash.starts_with(hash) && check_polygon_covers(hash, &polygon_wrapper)
+            });
+            if polygon.check_point(lon, lat) {
+                assert!(is_covered);
+            } else {
+                assert!(!is_inner);
+            }
+        }
+
+        let mut interior_hole = 0;
+        for _ in 0..100 {
+            let lon = rnd.gen_range(-73.975..-73.965);
+            let lat = rnd.gen_range(40.735..40.74);
+            if !polygon.check_point(lon, lat) {
+                interior_hole += 1;
+            }
+        }
+        assert_eq!(interior_hole, 100);
+    }
+
     #[test]
     fn test_lon_threshold() {
         let query = GeoRadius {
@@ -1,30 +1,33 @@
 use std::cmp::{max, min};
 use std::collections::{BTreeMap, HashSet};
+use std::mem::size_of;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use geo::algorithm::area::Area;
 use itertools::Itertools;
 use rocksdb::{IteratorMode, DB};
 use serde_json::Value;
 
-use crate::common::rocksdb_operations::{db_write_options, recreate_cf};
+use crate::common::rocksdb_operations::{cf_disk_usage_bytes, db_write_options, recreate_cf};
 use crate::entry::entry_point::{OperationError, OperationResult};
 use crate::index::field_index::geo_hash::{
-    circle_hashes, common_hash_prefix, encode_max_precision, geo_hash_to_box, rectangle_hashes,
-    GeoHash,
+    check_polygon_covers, circle_hashes, common_hash_prefix, encode_max_precision, geo_hash_to_box,
+    geo_hash_to_rect, polygon_hashes, rectangle_hashes, GeoHash,
 };
 use crate::index::field_index::stat_tools::estimate_multi_value_selection_cardinality;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
 };
 use crate::types::{
-    FieldCondition, GeoBoundingBox, GeoPoint, GeoRadius, PayloadKeyType, PointOffsetType,
+    FieldCondition, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, PayloadKeyType,
+    PointOffsetType, PolygonWrapper,
 };
 
 /// Max number of sub-regions computed for an input geo query
 // TODO discuss value, should it be dynamically computed?
-const GEO_QUERY_MAX_REGION: usize = 12;
+pub(super) const GEO_QUERY_MAX_REGION: usize = 12;
 
 pub struct GeoMapIndex {
     /**
@@ -165,6 +168,27 @@ impl GeoMapIndex {
         self.point_to_values.get(idx as usize)
     }
 
+    /// Rough estimation of memory, used by the index structures
+    pub fn ram_usage_bytes(&self) -> usize {
+        let counts_usage = (self.points_per_hash.len() + self.values_per_hash.len())
+            * (size_of::<GeoHash>() + size_of::<usize>());
+        let map_usage = self
+            .points_map
+            .values()
+            .map(|points| size_of::<GeoHash>() + points.len() * size_of::<PointOffsetType>())
+            .sum::<usize>();
+        let values_usage = self
+            .point_to_values
+            .iter()
+            .map(|values| size_of::<Vec<GeoPoint>>() + values.len() * size_of::<GeoPoint>())
+            .sum::<usize>();
+        counts_usage + map_usage + values_usage
+    }
+
+    pub fn disk_usage_bytes(&self) -> OperationResult<usize> {
+        Ok(cf_disk_usage_bytes(&self.db.borrow(), &self.store_cf_name)?)
+    }
+
     pub fn check_radius(&self, idx: PointOffsetType, radius: &GeoRadius) -> bool {
         self.get_values(idx)
             .map(|values| values.iter().any(|x| radius.check_point(x.lon, x.lat)))
@@ -177,45 +201,19 @@ impl GeoMapIndex {
             .unwrap_or(false)
     }
 
-    pub fn match_cardinality(&self, values: &[GeoHash]) -> CardinalityEstimation {
-        let common_hash = common_hash_prefix(values);
-
-        let total_points = self.points_per_hash.get(&common_hash).copied().unwrap_or(0);
-        let total_values = self.values_per_hash.get(&common_hash).copied().unwrap_or(0);
+    pub fn check_polygon(&self, idx: PointOffsetType, polygon: &PolygonWrapper) -> bool {
+        self.get_values(idx)
+            .map(|values| values.iter().any(|x| polygon.check_point(x.lon, x.lat)))
+            .unwrap_or(false)
+    }
 
-        let (sum, maximum_per_hash) = values
-            .iter()
-            .map(|region| self.points_per_hash.get(region).cloned().unwrap_or(0))
-            .fold((0, 0), |(sum, maximum), count| {
-                (sum + count, max(maximum, count))
-            });
-
-        // Assume all selected points have `max_values_per_point` value hits.
-        // Therefore number of points can't be less than `total_hits / max_values_per_point`
-        let min_hits_by_value_groups = sum / self.max_values_per_point;
-
-        // Assume that we have selected all possible duplications of the points
-        let point_duplications = total_values - total_points;
-        let possible_non_duplicated = sum.saturating_sub(point_duplications);
-
-        let estimation_min = max(
-            max(min_hits_by_value_groups, possible_non_duplicated),
-            maximum_per_hash,
-        );
-        let estimation_max = min(sum, total_points);
-
-        // estimate_multi_value_selection_cardinality might overflow at some corner cases
-        // so it is better to limit its value with min and max
-        let estimation_exp =
-            estimate_multi_value_selection_cardinality(total_points, total_values, sum).round()
-                as usize;
-
-        CardinalityEstimation {
-            primary_clauses: vec![],
-            min: estimation_min,
-            exp: min(estimation_max, max(estimation_min, estimation_exp)),
-            max: estimation_max,
-        }
+    pub fn match_cardinality(&self, values: &[GeoHash]) -> CardinalityEstimation {
+        match_cardinality(
+            values,
+            |hash| self.points_per_hash.get(hash).copied().unwrap_or(0),
+            |hash| self.values_per_hash.get(hash).copied().unwrap_or(0),
+            self.max_values_per_point,
+        )
     }
 
     fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
@@ -393,34 +391,42 @@ impl GeoMapIndex {
         )
     }
 
+    /// Points of the inner tiles are matched as is,
+    /// only points of the edge tiles are checked against the polygon
+    fn get_polygon_iterator(
+        &self,
+        polygon: &GeoPolygon,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let (polygon_wrapper, inner_hashes, edge_hashes) = polygon_regions(polygon);
+        let inner_points = self.get_iterator(inner_hashes);
+        let edge_points = self
+            .get_iterator(edge_hashes)
+            .filter(move |point| self.check_polygon(*point, &polygon_wrapper));
+        Box::new(inner_points.chain(edge_points).unique())
+    }
+
+    fn polygon_cardinality(&self, polygon: &GeoPolygon) -> CardinalityEstimation {
+        polygon_cardinality(
+            polygon,
+            |hashes| self.match_cardinality(hashes),
+            self.points_count,
+        )
+    }
+
     /// Get iterator over smallest geo-hash regions larger than `threshold` points
     fn get_large_hashes(
         &self,
         threshold: usize,
     ) -> Box<dyn Iterator<Item = (&GeoHash, usize)> + '_> {
-        let mut large_regions = self
-            .points_per_hash
-            .iter()
-            .filter(|(hash, size)| **size > threshold && !hash.is_empty())
-            .collect_vec();
-
-        // smallest regions first
-        large_regions.sort_by(|a, b| b.cmp(a));
-
-        let mut edge_region = vec![];
-
-        let mut current_region = "";
-
-        for (region, size) in large_regions.into_iter() {
-            if current_region.starts_with(region) {
-                continue;
-            } else {
-                current_region = region;
-                edge_region.push((region, *size));
-            }
-        }
-
-        Box::new(edge_region.into_iter())
+        Box::new(
+            large_hashes(
+                self.points_per_hash
+                    .iter()
+                    .map(|(hash, size)| (hash, *size)),
+                threshold,
+            )
+            .into_iter(),
+        )
     }
 }
 
@@ -498,6 +504,10 @@ impl PayloadFieldIndex for GeoMapIndex {
             )));
         }
 
+        if let Some(geo_polygon) = &condition.geo_polygon {
+            return Some(self.get_polygon_iterator(geo_polygon));
+        }
+
         None
     }
 
@@ -520,6 +530,14 @@ impl PayloadFieldIndex for GeoMapIndex {
             return Some(estimation);
         }
 
+        if let Some(geo_polygon) = &condition.geo_polygon {
+            let mut estimation = self.polygon_cardinality(geo_polygon);
+            estimation
+                .primary_clauses
+                .push(PrimaryCondition::Condition(condition.clone()));
+            return Some(estimation);
+        }
+
         None
     }
 
@@ -545,6 +563,133 @@ impl PayloadFieldIndex for GeoMapIndex {
     }
 }
 
+/// Estimate amount of points within the geo-hash regions,
+/// using counts of points and values per geo-hash prefix
+pub(super) fn match_cardinality(
+    values: &[GeoHash],
+    points_per_hash: impl Fn(&str) -> usize,
+    values_per_hash: impl Fn(&str) -> usize,
+    max_values_per_point: usize,
+) -> CardinalityEstimation {
+    if values.is_empty() {
+        return CardinalityEstimation::exact(0);
+    }
+    let common_hash = common_hash_prefix(values);
+
+    let total_points = points_per_hash(&common_hash);
+    let total_values = values_per_hash(&common_hash);
+
+    let (sum, maximum_per_hash) = values
+        .iter()
+        .map(|region| points_per_hash(region))
+        .fold((0, 0), |(sum, maximum), count| {
+            (sum + count, max(maximum, count))
+        });
+
+    // Assume all selected points have `max_values_per_point` value hits.
+    // Therefore number of points can't be less than `total_hits / max_values_per_point`
+    let min_hits_by_value_groups = sum / max_values_per_point;
+
+    // Assume that we have selected all possible duplications of the points
+    let point_duplications = total_values - total_points;
+    let possible_non_duplicated = sum.saturating_sub(point_duplications);
+
+    let estimation_min = max(
+        max(min_hits_by_value_groups, possible_non_duplicated),
+        maximum_per_hash,
+    );
+    let estimation_max = min(sum, total_points);
+
+    // estimate_multi_value_selection_cardinality might overflow at some corner cases
+    // so it is better to limit its value with min and max
+    let estimation_exp = estimate_multi_value_selection_cardinality(total_points, total_values, sum)
+        .round() as usize;
+
+    CardinalityEstimation {
+        primary_clauses: vec![],
+        min: estimation_min,
+        exp: min(estimation_max, max(estimation_min, estimation_exp)),
+        max: estimation_max,
+    }
+}
+
+/// Split the polygon cover into tiles inside of the polygon and tiles crossed by its borders
+pub(super) fn polygon_regions(
+    polygon: &GeoPolygon,
+) -> (PolygonWrapper, Vec<GeoHash>, Vec<GeoHash>) {
+    let polygon_wrapper = polygon.convert();
+    let (inner_hashes, edge_hashes) = polygon_hashes(polygon, GEO_QUERY_MAX_REGION)
+        .into_iter()
+        .partition(|geo_hash| check_polygon_covers(geo_hash, &polygon_wrapper));
+    (polygon_wrapper, inner_hashes, edge_hashes)
+}
+
+/// Estimate amount of points within the polygon by the cardinalities of its cover tiles
+pub(super) fn polygon_cardinality(
+    polygon: &GeoPolygon,
+    match_cardinality: impl Fn(&[GeoHash]) -> CardinalityEstimation,
+    points_count: usize,
+) -> CardinalityEstimation {
+    let (polygon_wrapper, inner_hashes, edge_hashes) = polygon_regions(polygon);
+    let inner_estimation = match_cardinality(&inner_hashes);
+    let edge_estimation = match_cardinality(&edge_hashes);
+
+    // Tiles of the same precision don't overlap, so the polygon area outside of the inner
+    // tiles is the part of the edge tiles covered by the polygon.
+    // Assume points of the edge tiles match proportionally to this covered share.
+    let hashes_area = |hashes: &[GeoHash]| -> f64 {
+        hashes
+            .iter()
+            .map(|geo_hash| geo_hash_to_rect(geo_hash).unsigned_area())
+            .sum()
+    };
+    let edge_area = hashes_area(&edge_hashes);
+    let covered_share = if edge_area > 0.0 {
+        let covered_area = polygon_wrapper.polygon.unsigned_area() - hashes_area(&inner_hashes);
+        (covered_area / edge_area).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    let estimation_min = inner_estimation.min;
+    let estimation_max = min(inner_estimation.max + edge_estimation.max, points_count);
+    let estimation_exp =
+        inner_estimation.exp + (edge_estimation.exp as f64 * covered_share).round() as usize;
+
+    CardinalityEstimation {
+        primary_clauses: vec![],
+        min: estimation_min,
+        exp: min(estimation_max, max(estimation_min, estimation_exp)),
+        max: estimation_max,
+    }
+}
+
+/// Smallest geo-hash regions larger than `threshold` points
+pub(super) fn large_hashes<H: AsRef<str>>(
+    points_per_hash: impl Iterator<Item = (H, usize)>,
+    threshold: usize,
+) -> Vec<(H, usize)> {
+    let mut large_regions = points_per_hash
+        .filter(|(hash, size)| *size > threshold && !hash.as_ref().is_empty())
+        .collect_vec();
+
+    // smallest regions first
+    large_regions.sort_by(|a, b| b.0.as_ref().cmp(a.0.as_ref()));
+
+    let mut edge_region: Vec<(H, usize)> = vec![];
+
+    for (region, size) in large_regions.into_iter() {
+        let is_covered = edge_region.last().map_or(false, |(current_region, _)| {
+            current_region.as_ref().starts_with(region.as_ref())
+        });
+        if !is_covered {
+            edge_region.push((region, size));
+        }
+    }
+
+    edge_region
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -556,7 +701,7 @@ mod tests {
     use super::*;
     use crate::common::rocksdb_operations::open_db_with_existing_cf;
     use crate::fixtures::payload_fixtures::random_geo_payload;
-    use crate::types::GeoRadius;
+    use crate::types::{GeoLineString, GeoRadius};
 
     const NYC: GeoPoint = GeoPoint {
         lat: 40.75798,
@@ -669,6 +814,71 @@ mod tests {
         assert_eq!(matched_points, indexed_matched_points);
     }
 
+    fn build_polygon(exterior: &[(f64, f64)], interiors: &[&[(f64, f64)]]) -> GeoPolygon {
+        let line = |points: &[(f64, f64)]| GeoLineString {
+            points: points
+                .iter()
+                .map(|&(lon, lat)| GeoPoint { lon, lat })
+                .collect(),
+        };
+        GeoPolygon {
+            exterior: line(exterior),
+            interiors: Some(interiors.iter().map(|interior| line(interior)).collect()),
+        }
+    }
+
+    #[test]
+    fn geo_polygon_filtering_and_cardinality() {
+        // Rough outline of Europe with a hole in the middle
+        let geo_polygon = build_polygon(
+            &[
+                (-10.0, 36.0),
+                (30.0, 34.0),
+                (40.0, 55.0),
+                (25.0, 70.0),
+                (-10.0, 60.0),
+                (-10.0, 36.0),
+            ],
+            &[&[(5.0, 45.0), (15.0, 45.0), (10.0, 52.0), (5.0, 45.0)]],
+        );
+        let polygon_wrapper = geo_polygon.convert();
+
+        let field_index = build_random_index(1000, 5);
+
+        let mut matched_points = field_index
+            .point_to_values
+            .iter()
+            .enumerate()
+            .filter(|(_idx, geo_points)| {
+                geo_points
+                    .iter()
+                    .any(|geo_point| polygon_wrapper.check_point(geo_point.lon, geo_point.lat))
+            })
+            .map(|(idx, _geo_points)| idx as PointOffsetType)
+            .collect_vec();
+
+        assert!(!matched_points.is_empty());
+
+        let field_condition = FieldCondition::new_geo_polygon("test".to_string(), geo_polygon);
+
+        let mut indexed_matched_points =
+            field_index.filter(&field_condition).unwrap().collect_vec();
+
+        matched_points.sort_unstable();
+        indexed_matched_points.sort_unstable();
+
+        assert_eq!(matched_points, indexed_matched_points);
+
+        let card = field_index.estimate_cardinality(&field_condition).unwrap();
+        let real_cardinality = matched_points.len();
+
+        assert!(card.min <= real_cardinality);
+        assert!(card.max >= real_cardinality);
+
+        assert!(card.exp >= card.min);
+        assert!(card.exp <= card.max);
+    }
+
     #[test]
     fn test_payload_blocks() {
         let field_index = build_random_index(1000, 5);
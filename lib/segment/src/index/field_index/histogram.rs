@@ -7,6 +7,23 @@ use itertools::Itertools;
 
 const MIN_BUCKET_SIZE: usize = 10;
 
+/// Bucket of the fixed-width histogram, which contains the value: `[k * interval, (k + 1) * interval)`
+pub fn interval_bucket(value: f64, interval: f64) -> i64 {
+    (value / interval).floor() as i64
+}
+
+/// Count values in the buckets of the `interval` width
+pub fn count_by_interval(
+    values: impl IntoIterator<Item = f64>,
+    interval: f64,
+) -> BTreeMap<i64, usize> {
+    let mut counts = BTreeMap::new();
+    for value in values {
+        *counts.entry(interval_bucket(value, interval)).or_default() += 1;
+    }
+    counts
+}
+
 #[derive(Debug, Clone)]
 struct Counts {
     pub left: usize,
@@ -55,6 +72,56 @@ impl Histogram {
         min(max(MIN_BUCKET_SIZE, bucket_size), self.max_bucket_size)
     }
 
+    /// Estimates value at the given quantile `q` in `[0, 1]`.
+    /// Values between borders are assumed to be distributed uniformly.
+    ///
+    /// Returns None if there are no points stored
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.total_count == 0 {
+            return None;
+        }
+        let rank = q.clamp(0.0, 1.0) * (self.total_count - 1) as f64;
+        // Rank of the current border among all values
+        let mut position = 0;
+        for ((border, counts), (next_border, _)) in self.borders.iter().tuple_windows() {
+            let next_position = position + counts.right + 1;
+            if rank < next_position as f64 {
+                let fraction = (rank - position as f64) / (next_position - position) as f64;
+                return Some(border.val + (next_border.val - border.val) * fraction);
+            }
+            position = next_position;
+        }
+        self.borders.keys().next_back().map(|border| border.val)
+    }
+
+    /// Count values in the buckets of the `interval` width, same as `count_by_interval`.
+    /// Values between borders within the same bucket are counted without reading them,
+    /// `values_between` is used to read values between borders in different buckets.
+    pub fn count_by_interval<F, I>(&self, interval: f64, values_between: F) -> BTreeMap<i64, usize>
+    where
+        F: Fn(&Point, &Point) -> I,
+        I: Iterator<Item = f64>,
+    {
+        let mut counts = BTreeMap::new();
+        let mut borders = self.borders.iter().peekable();
+        while let Some((border, border_counts)) = borders.next() {
+            let bucket = interval_bucket(border.val, interval);
+            *counts.entry(bucket).or_default() += 1;
+            let next_border = match borders.peek() {
+                Some((next_border, _)) if border_counts.right > 0 => next_border,
+                _ => continue,
+            };
+            if bucket == interval_bucket(next_border.val, interval) {
+                *counts.entry(bucket).or_default() += border_counts.right;
+            } else {
+                for value in values_between(border, next_border) {
+                    *counts.entry(interval_bucket(value, interval)).or_default() += 1;
+                }
+            }
+        }
+        counts
+    }
+
     /// Infers boundaries for bucket of given size and staring point.
     /// Returns `to` range of values starting provided `from`value which is expected to contain
     /// `range_size` values
@@ -701,6 +768,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_count_by_interval() {
+        let mut rnd = StdRng::seed_from_u64(42);
+        let points = (0..2000)
+            .map(|i| Point {
+                val: f64::round(rnd.sample::<f64, _>(StandardNormal) * 100.0),
+                idx: i,
+            })
+            .collect_vec();
+
+        let mut points_index: BTreeSet<Point> = Default::default();
+        let mut histogram = Histogram::new(100, 0.01);
+        for point in &points {
+            points_index.insert(point.clone());
+            histogram.insert(
+                point.clone(),
+                |x| {
+                    points_index
+                        .range((Unbounded, Excluded(x)))
+                        .next_back()
+                        .cloned()
+                },
+                |x| points_index.range((Excluded(x), Unbounded)).next().cloned(),
+            );
+        }
+        for point in points.iter().step_by(3) {
+            points_index.remove(point);
+            histogram.remove(
+                point,
+                |x| {
+                    points_index
+                        .range((Unbounded, Excluded(x)))
+                        .next_back()
+                        .cloned()
+                },
+                |x| points_index.range((Excluded(x), Unbounded)).next().cloned(),
+            );
+        }
+
+        for interval in [0.5, 7.0, 30.0, 1000.0] {
+            let read_values = Cell::new(0);
+            let counts = histogram.count_by_interval(interval, |from, to| {
+                let values = points_index
+                    .range((Excluded(from), Excluded(to)))
+                    .map(|point| point.val)
+                    .collect_vec();
+                read_values.set(read_values.get() + values.len());
+                values.into_iter()
+            });
+            let expected = count_by_interval(points_index.iter().map(|point| point.val), interval);
+            assert_eq!(counts, expected);
+            if interval >= 1000.0 {
+                assert!(read_values.get() < points_index.len() / 2);
+            }
+        }
+    }
+
     fn test_range_by_cardinality(histogram: &Histogram) {
         let from = Unbounded;
         let range_size = 100;
@@ -896,6 +1020,38 @@ mod tests {
         request_histogram(&histogram, &points_index);
     }
 
+    #[test]
+    fn test_histogram_quantile() {
+        let mut rnd = StdRng::seed_from_u64(42);
+        let points = (0..10_000)
+            .map(|i| Point {
+                val: rnd.gen_range(0.0..1000.0),
+                idx: i,
+            })
+            .collect_vec();
+        let (histogram, points_index) = build_histogram(100, 0.01, points);
+
+        let min = points_index.iter().next().unwrap().val;
+        let max = points_index.iter().next_back().unwrap().val;
+        assert_eq!(histogram.quantile(0.0), Some(min));
+        assert_eq!(histogram.quantile(1.0), Some(max));
+
+        for q in [0.1, 0.5, 0.9] {
+            let real = points_index
+                .iter()
+                .nth((q * (points_index.len() - 1) as f64) as usize)
+                .unwrap()
+                .val;
+            let estimation = histogram.quantile(q).unwrap();
+            assert!(
+                (real - estimation).abs() < 20.0,
+                "{q}: {real} vs {estimation}"
+            );
+        }
+
+        assert_eq!(Histogram::new(100, 0.01).quantile(0.5), None);
+    }
+
     #[test]
     fn test_build_histogram() {
         let max_bucket_size = 1000;
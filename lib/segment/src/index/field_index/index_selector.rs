@@ -1,16 +1,120 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use rocksdb::DB;
 
+use crate::index::field_index::bool_index::BoolIndex;
+use crate::index::field_index::full_text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::map_index::MapIndex;
+use crate::index::field_index::mmap_geo_index::MmapGeoMapIndex;
+use crate::index::field_index::mmap_map_index::MmapMapIndex;
+use crate::index::field_index::mmap_numeric_index::MmapNumericIndex;
+use crate::index::field_index::null_index::NullIndex;
 use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::FieldIndex;
-use crate::types::{FloatPayloadType, IntPayloadType, PayloadSchemaType};
+use crate::types::{
+    DateTimePayloadType, FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams,
+    PayloadSchemaType, UuidIntType,
+};
 
-/// Selects index types based on field type
+/// Selects index types based on field type.
+/// On-disk indexes are only used in non-appendable segments, stored in the `path` directory.
+/// Presence of the field is tracked by the null index, which goes after the indexes of the values.
 pub fn index_selector(
+    field: &str,
+    payload_schema: &PayloadFieldSchema,
+    db: Arc<AtomicRefCell<DB>>,
+    path: &Path,
+    is_appendable: bool,
+) -> Vec<FieldIndex> {
+    let mut indexes = value_index_selector(field, payload_schema, db.clone(), path, is_appendable);
+    indexes.push(FieldIndex::NullIndex(NullIndex::new(db, field)));
+    indexes
+}
+
+/// Selects indexes of the values
+fn value_index_selector(
+    field: &str,
+    payload_schema: &PayloadFieldSchema,
+    db: Arc<AtomicRefCell<DB>>,
+    path: &Path,
+    is_appendable: bool,
+) -> Vec<FieldIndex> {
+    match payload_schema {
+        PayloadFieldSchema::FieldType(payload_type) => type_index_selector(field, payload_type, db),
+        PayloadFieldSchema::FieldParams(payload_params)
+            if !is_appendable && payload_params.on_disk() =>
+        {
+            mmap_index_selector(field, payload_params, path)
+        }
+        PayloadFieldSchema::FieldParams(payload_params) => match payload_params {
+            PayloadSchemaParams::Text(text_index_params) => vec![FieldIndex::FullTextIndex(
+                FullTextIndex::new(db, text_index_params.clone(), field),
+            )],
+            PayloadSchemaParams::Integer(integer_params) => {
+                let mut indexes = vec![];
+                if integer_params.lookup() {
+                    indexes.push(FieldIndex::IntMapIndex(MapIndex::<IntPayloadType>::new(
+                        db.clone(),
+                        field,
+                    )));
+                }
+                if integer_params.range() {
+                    indexes.push(FieldIndex::IntIndex(NumericIndex::<IntPayloadType>::new(
+                        db, field,
+                    )));
+                }
+                indexes
+            }
+            _ => type_index_selector(field, &payload_params.kind(), db),
+        },
+    }
+}
+
+/// Selects on-disk index types, stored as memory-mapped files in the `path` directory
+fn mmap_index_selector(
+    field: &str,
+    payload_params: &PayloadSchemaParams,
+    path: &Path,
+) -> Vec<FieldIndex> {
+    match payload_params {
+        PayloadSchemaParams::Keyword(_) => {
+            vec![FieldIndex::KeywordMmapIndex(MmapMapIndex::new(path, field))]
+        }
+        PayloadSchemaParams::Integer(integer_params) => {
+            let mut indexes = vec![];
+            if integer_params.lookup() {
+                indexes.push(FieldIndex::IntMapMmapIndex(MmapMapIndex::new(path, field)));
+            }
+            if integer_params.range() {
+                indexes.push(FieldIndex::IntMmapIndex(MmapNumericIndex::new(path, field)));
+            }
+            indexes
+        }
+        PayloadSchemaParams::Float(_) => {
+            vec![FieldIndex::FloatMmapIndex(MmapNumericIndex::new(
+                path, field,
+            ))]
+        }
+        PayloadSchemaParams::Geo(_) => {
+            vec![FieldIndex::GeoMmapIndex(MmapGeoMapIndex::new(path, field))]
+        }
+        PayloadSchemaParams::Datetime(_) => {
+            vec![FieldIndex::DatetimeMmapIndex(MmapNumericIndex::new(
+                path, field,
+            ))]
+        }
+        PayloadSchemaParams::Uuid(_) => {
+            vec![FieldIndex::UuidMmapIndex(MmapMapIndex::new(path, field))]
+        }
+        PayloadSchemaParams::Text(_) => unreachable!("full-text index is never stored on disk"),
+    }
+}
+
+/// Selects index types for a field type with default parameters
+fn type_index_selector(
     field: &str,
     payload_type: &PayloadSchemaType,
     db: Arc<AtomicRefCell<DB>>,
@@ -29,5 +133,17 @@ pub fn index_selector(
             )]
         }
         PayloadSchemaType::Geo => vec![FieldIndex::GeoIndex(GeoMapIndex::new(db, field))],
+        PayloadSchemaType::Text => vec![FieldIndex::FullTextIndex(FullTextIndex::new(
+            db,
+            Default::default(),
+            field,
+        ))],
+        PayloadSchemaType::Datetime => vec![FieldIndex::DatetimeIndex(NumericIndex::<
+            DateTimePayloadType,
+        >::new(db, field))],
+        PayloadSchemaType::Bool => vec![FieldIndex::BoolIndex(BoolIndex::new(db, field))],
+        PayloadSchemaType::Uuid => vec![FieldIndex::UuidIndex(MapIndex::<UuidIntType>::new(
+            db, field,
+        ))],
     }
 }
@@ -1,40 +1,117 @@
-use std::collections::{BTreeSet, HashMap};
+use std::cmp::{max, min};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Display;
 use std::hash::Hash;
 use std::iter;
+use std::mem::size_of;
+use std::ops::Bound;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use itertools::Itertools;
 use rocksdb::{IteratorMode, DB};
 use serde_json::Value;
+use uuid::Uuid;
 
-use crate::common::rocksdb_operations::{db_write_options, recreate_cf};
+use crate::common::rocksdb_operations::{cf_disk_usage_bytes, db_write_options, recreate_cf};
 use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::stat_tools::estimate_multi_value_selection_cardinality;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
 };
 use crate::types::{
-    FieldCondition, IntPayloadType, Match, MatchValue, PayloadKeyType, PointOffsetType,
-    ValueVariants,
+    parse_uuid, AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept,
+    MatchKeywordRange, MatchValue, PayloadKeyType, PointOffsetType, UuidIntType, ValueVariants,
 };
 
-/// HashMap-based type of index
-pub struct MapIndex<N: Hash + Eq + Clone + Display> {
-    map: HashMap<N, BTreeSet<PointOffsetType>>,
+/// Max amount of keywords, visited to estimate the cardinality of a keyword pattern
+const PATTERN_ESTIMATION_SAMPLE: usize = 1000;
+
+/// Points of each value of the index
+pub trait PostingsMap<N>: Default {
+    fn postings(&self, value: &N) -> Option<&BTreeSet<PointOffsetType>>;
+
+    fn postings_mut(&mut self, value: &N) -> Option<&mut BTreeSet<PointOffsetType>>;
+
+    fn postings_or_default(&mut self, value: N) -> &mut BTreeSet<PointOffsetType>;
+
+    fn iter_postings(&self) -> Box<dyn Iterator<Item = (&N, &BTreeSet<PointOffsetType>)> + '_>;
+}
+
+impl<N: Hash + Eq> PostingsMap<N> for HashMap<N, BTreeSet<PointOffsetType>> {
+    fn postings(&self, value: &N) -> Option<&BTreeSet<PointOffsetType>> {
+        self.get(value)
+    }
+
+    fn postings_mut(&mut self, value: &N) -> Option<&mut BTreeSet<PointOffsetType>> {
+        self.get_mut(value)
+    }
+
+    fn postings_or_default(&mut self, value: N) -> &mut BTreeSet<PointOffsetType> {
+        self.entry(value).or_default()
+    }
+
+    fn iter_postings(&self) -> Box<dyn Iterator<Item = (&N, &BTreeSet<PointOffsetType>)> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+impl<N: Ord> PostingsMap<N> for BTreeMap<N, BTreeSet<PointOffsetType>> {
+    fn postings(&self, value: &N) -> Option<&BTreeSet<PointOffsetType>> {
+        self.get(value)
+    }
+
+    fn postings_mut(&mut self, value: &N) -> Option<&mut BTreeSet<PointOffsetType>> {
+        self.get_mut(value)
+    }
+
+    fn postings_or_default(&mut self, value: N) -> &mut BTreeSet<PointOffsetType> {
+        self.entry(value).or_default()
+    }
+
+    fn iter_postings(&self) -> Box<dyn Iterator<Item = (&N, &BTreeSet<PointOffsetType>)> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+/// Value, which could be indexed by `MapIndex`
+pub trait MapIndexKey: Hash + Ord + Clone + Display + FromStr {
+    type Map: PostingsMap<Self>;
+}
+
+/// Keywords are ordered, so they could be selected by prefix or range
+impl MapIndexKey for String {
+    type Map = BTreeMap<String, BTreeSet<PointOffsetType>>;
+}
+
+impl MapIndexKey for IntPayloadType {
+    type Map = HashMap<IntPayloadType, BTreeSet<PointOffsetType>>;
+}
+
+impl MapIndexKey for UuidIntType {
+    type Map = HashMap<UuidIntType, BTreeSet<PointOffsetType>>;
+}
+
+/// Map-based type of index
+pub struct MapIndex<N: MapIndexKey> {
+    map: N::Map,
     point_to_values: Vec<Vec<N>>,
     /// Amount of point which have at least one indexed payload value
     indexed_points: usize,
+    /// Total amount of unique (point, value) pairs in the index
+    values_count: usize,
     store_cf_name: String,
     db: Arc<AtomicRefCell<DB>>,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
+impl<N: MapIndexKey> MapIndex<N> {
     pub fn new(db: Arc<AtomicRefCell<DB>>, field_name: &str) -> MapIndex<N> {
         MapIndex {
             map: Default::default(),
             point_to_values: Vec::new(),
             indexed_points: 0,
+            values_count: 0,
             store_cf_name: Self::storage_cf_name(field_name),
             db,
         }
@@ -56,6 +133,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
             return Ok(false);
         };
         self.indexed_points = 0;
+        self.values_count = 0;
         for (record, _) in db_ref.iterator_cf(cf_handle, IteratorMode::Start) {
             let record = std::str::from_utf8(&record).map_err(|_| {
                 OperationError::service_error("Index load error: UTF8 error while DB parsing")
@@ -68,7 +146,9 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
                 self.indexed_points += 1;
             }
             self.point_to_values[idx as usize].push(value.clone());
-            self.map.entry(value).or_default().insert(idx);
+            if self.map.postings_or_default(value).insert(idx) {
+                self.values_count += 1;
+            }
         }
         Ok(true)
     }
@@ -85,7 +165,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
     }
 
     pub fn match_cardinality(&self, value: &N) -> CardinalityEstimation {
-        let values_count = match self.map.get(value) {
+        let values_count = match self.map.postings(value) {
             None => 0,
             Some(points) => points.len(),
         };
@@ -98,10 +178,56 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
         }
     }
 
+    /// Estimate amount of points, which have at least one of the given values
+    pub fn values_cardinality<'a>(
+        &self,
+        values: impl Iterator<Item = &'a N>,
+    ) -> CardinalityEstimation
+    where
+        N: 'a,
+    {
+        self.postings_cardinality(
+            values.map(|value| self.map.postings(value).map_or(0, |points| points.len())),
+        )
+    }
+
+    /// Estimate amount of points, which have at least one of the values with given posting sizes
+    fn postings_cardinality(&self, sizes: impl Iterator<Item = usize>) -> CardinalityEstimation {
+        postings_cardinality(sizes, self.indexed_points, self.values_count)
+    }
+
     pub fn get_values(&self, idx: PointOffsetType) -> Option<&Vec<N>> {
         self.point_to_values.get(idx as usize)
     }
 
+    /// Iterate over indexed values with the number of points which have each of them
+    pub fn values_with_counts(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
+        self.map
+            .iter_postings()
+            .map(|(value, points)| (value, points.len()))
+            .filter(|(_value, count)| *count > 0)
+    }
+
+    /// Rough estimation of memory, used by the index structures.
+    /// `value_size` is the memory, occupied by a single value, including its heap allocations.
+    pub fn ram_usage_bytes(&self, value_size: impl Fn(&N) -> usize) -> usize {
+        let map_usage = self
+            .map
+            .iter_postings()
+            .map(|(value, points)| value_size(value) + points.len() * size_of::<PointOffsetType>())
+            .sum::<usize>();
+        let values_usage = self
+            .point_to_values
+            .iter()
+            .map(|values| size_of::<Vec<N>>() + values.iter().map(&value_size).sum::<usize>())
+            .sum::<usize>();
+        map_usage + values_usage
+    }
+
+    pub fn disk_usage_bytes(&self) -> OperationResult<usize> {
+        Ok(cf_disk_usage_bytes(&self.db.borrow(), &self.store_cf_name)?)
+    }
+
     fn add_many_to_map(&mut self, idx: PointOffsetType, values: Vec<N>) -> OperationResult<()> {
         if values.is_empty() {
             return Ok(());
@@ -120,8 +246,10 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
         }
         self.point_to_values[idx as usize] = values.into_iter().collect();
         for value in &self.point_to_values[idx as usize] {
-            let entry = self.map.entry(value.clone()).or_default();
-            entry.insert(idx);
+            let entry = self.map.postings_or_default(value.clone());
+            if entry.insert(idx) {
+                self.values_count += 1;
+            }
 
             let db_record = Self::encode_db_record(value, idx);
             store_ref
@@ -136,11 +264,52 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
 
     fn get_iterator(&self, value: &N) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         self.map
-            .get(value)
+            .postings(value)
             .map(|ids| Box::new(ids.iter().copied()) as Box<dyn Iterator<Item = PointOffsetType>>)
             .unwrap_or_else(|| Box::new(iter::empty::<PointOffsetType>()))
     }
 
+    /// Points which have at least one of the given values
+    fn get_any_iterator(
+        &self,
+        values: &BTreeSet<N>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let postings: Vec<_> = values
+            .iter()
+            .filter_map(|value| self.map.postings(value))
+            .collect();
+        Box::new(
+            postings
+                .into_iter()
+                .flat_map(|point_ids| point_ids.iter().copied())
+                .unique(),
+        )
+    }
+
+    /// Points which have at least one value, other than the excluded ones
+    fn get_except_iterator(
+        &self,
+        excluded: &BTreeSet<N>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let excluded = excluded.clone();
+        Box::new(
+            self.map
+                .iter_postings()
+                .filter(move |(value, _)| !excluded.contains(*value))
+                .flat_map(|(_, point_ids)| point_ids.iter().copied())
+                .unique(),
+        )
+    }
+
+    fn except_cardinality(&self, excluded: &BTreeSet<N>) -> CardinalityEstimation {
+        self.values_cardinality(
+            self.map
+                .iter_postings()
+                .map(|(value, _)| value)
+                .filter(|value| !excluded.contains(value)),
+        )
+    }
+
     fn encode_db_record(value: &N, idx: PointOffsetType) -> String {
         format!("{}/{}", value, idx)
     }
@@ -183,8 +352,10 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
         }
 
         for value in &removed_values {
-            if let Some(vals) = self.map.get_mut(value) {
-                vals.remove(&idx);
+            if let Some(vals) = self.map.postings_mut(value) {
+                if vals.remove(&idx) {
+                    self.values_count -= 1;
+                }
             }
             let key = MapIndex::encode_db_record(value, idx);
             store_ref.delete_cf(cf_handle, key)?;
@@ -194,6 +365,97 @@ impl<N: Hash + Eq + Clone + Display + FromStr> MapIndex<N> {
     }
 }
 
+impl MapIndex<String> {
+    /// Keywords within the range, which could satisfy the keyword pattern, in ascending order.
+    /// Each keyword is given by its points and the flag, whether it actually satisfies the pattern.
+    fn pattern_candidates(
+        &self,
+        condition_match: &Match,
+    ) -> Option<Box<dyn Iterator<Item = (bool, &BTreeSet<PointOffsetType>)> + '_>> {
+        let starting_from = |from: Bound<&str>| self.map.range::<str, _>((from, Bound::Unbounded));
+        let candidates: Box<dyn Iterator<Item = (bool, &BTreeSet<PointOffsetType>)> + '_> =
+            match condition_match {
+                Match::Prefix(prefix) => {
+                    let prefix = prefix.clone();
+                    Box::new(
+                        starting_from(Bound::Included(prefix.prefix.as_str()))
+                            .take_while(move |(keyword, _)| prefix.check(keyword))
+                            .map(|(_, points)| (true, points)),
+                    )
+                }
+                Match::KeywordRange(MatchKeywordRange { keyword_range }) => {
+                    let keyword_range = keyword_range.clone();
+                    let upper_range = keyword_range.clone();
+                    Box::new(
+                        starting_from(keyword_range.lower_bound())
+                            .take_while(move |(keyword, _)| upper_range.check_upper(keyword))
+                            .map(move |(keyword, points)| (keyword_range.check(keyword), points)),
+                    )
+                }
+                Match::Wildcard(wildcard) => {
+                    let wildcard = wildcard.clone();
+                    let literal_prefix = wildcard.literal_prefix().to_owned();
+                    Box::new(
+                        starting_from(Bound::Included(literal_prefix.as_str()))
+                            .take_while(move |(keyword, _)| keyword.starts_with(&literal_prefix))
+                            .map(move |(keyword, points)| (wildcard.check(keyword), points)),
+                    )
+                }
+                _ => return None,
+            };
+        Some(candidates)
+    }
+
+    /// Keywords with their points in ascending order, which satisfy the keyword pattern.
+    /// Only keywords within the range, which could match the pattern, are visited.
+    fn pattern_postings(
+        &self,
+        condition_match: &Match,
+    ) -> Option<Box<dyn Iterator<Item = &BTreeSet<PointOffsetType>> + '_>> {
+        let candidates = self.pattern_candidates(condition_match)?;
+        Some(Box::new(
+            candidates
+                .filter(|(matches, _)| *matches)
+                .map(|(_, points)| points),
+        ))
+    }
+
+    /// Estimate amount of points, which satisfy the keyword pattern.
+    /// At most `PATTERN_ESTIMATION_SAMPLE` keywords are visited, e.g. patterns with a leading `*`
+    /// would have to check every keyword otherwise.
+    /// If there are more candidates, the share of matching values in the sample is extrapolated.
+    fn pattern_cardinality(&self, condition_match: &Match) -> Option<CardinalityEstimation> {
+        let mut candidates = self.pattern_candidates(condition_match)?;
+        let sample: Vec<_> = candidates
+            .by_ref()
+            .take(PATTERN_ESTIMATION_SAMPLE)
+            .collect();
+        let matched_sizes = || {
+            sample
+                .iter()
+                .filter(|(matches, _)| *matches)
+                .map(|(_, points)| points.len())
+        };
+        let sample_estimation = self.postings_cardinality(matched_sizes());
+        if candidates.next().is_none() {
+            return Some(sample_estimation);
+        }
+
+        let sampled_values: usize = sample.iter().map(|(_, points)| points.len()).sum();
+        let matched_values: usize = matched_sizes().sum();
+        let extrapolated = self.indexed_points * matched_values / max(sampled_values, 1);
+        Some(CardinalityEstimation {
+            primary_clauses: vec![],
+            min: sample_estimation.min,
+            exp: min(
+                max(extrapolated, sample_estimation.exp),
+                self.indexed_points,
+            ),
+            max: self.indexed_points,
+        })
+    }
+}
+
 impl PayloadFieldIndex for MapIndex<String> {
     fn indexed_points(&self) -> usize {
         self.indexed_points
@@ -219,23 +481,38 @@ impl PayloadFieldIndex for MapIndex<String> {
             Some(Match::Value(MatchValue {
                 value: ValueVariants::Keyword(keyword),
             })) => Some(self.get_iterator(keyword)),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Keywords(keywords),
+            })) => Some(self.get_any_iterator(keywords)),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => Some(self.get_except_iterator(keywords)),
+            Some(pattern) => self.pattern_postings(pattern).map(|postings| {
+                Box::new(postings.flat_map(|points| points.iter().copied()).unique())
+                    as Box<dyn Iterator<Item = PointOffsetType>>
+            }),
             _ => None,
         }
     }
 
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
-        match &condition.r#match {
+        let mut estimation = match &condition.r#match {
             Some(Match::Value(MatchValue {
                 value: ValueVariants::Keyword(keyword),
-            })) => {
-                let mut estimation = self.match_cardinality(keyword);
-                estimation
-                    .primary_clauses
-                    .push(PrimaryCondition::Condition(condition.clone()));
-                Some(estimation)
-            }
-            _ => None,
-        }
+            })) => self.match_cardinality(keyword),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Keywords(keywords),
+            })) => self.values_cardinality(keywords.iter()),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => self.except_cardinality(keywords),
+            Some(pattern) => self.pattern_cardinality(pattern)?,
+            _ => return None,
+        };
+        estimation
+            .primary_clauses
+            .push(PrimaryCondition::Condition(condition.clone()));
+        Some(estimation)
     }
 
     fn payload_blocks(
@@ -284,25 +561,116 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
             Some(Match::Value(MatchValue {
                 value: ValueVariants::Integer(integer),
             })) => Some(self.get_iterator(integer)),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Integers(integers),
+            })) => Some(self.get_any_iterator(integers)),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Integers(integers),
+            })) => Some(self.get_except_iterator(integers)),
             _ => None,
         }
     }
 
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
-        match &condition.r#match {
+        let mut estimation = match &condition.r#match {
             Some(Match::Value(MatchValue {
                 value: ValueVariants::Integer(integer),
-            })) => {
-                let mut estimation = self.match_cardinality(integer);
-                estimation
-                    .primary_clauses
-                    .push(PrimaryCondition::Condition(condition.clone()));
-                Some(estimation)
-            }
+            })) => self.match_cardinality(integer),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Integers(integers),
+            })) => self.values_cardinality(integers.iter()),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Integers(integers),
+            })) => self.except_cardinality(integers),
+            _ => return None,
+        };
+        estimation
+            .primary_clauses
+            .push(PrimaryCondition::Condition(condition.clone()));
+        Some(estimation)
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = self
+            .map
+            .iter()
+            .filter(move |(_value, point_ids)| point_ids.len() >= threshold)
+            .map(move |(value, point_ids)| PayloadBlockCondition {
+                condition: FieldCondition::new_match(key.clone(), (*value).into()),
+                cardinality: point_ids.len(),
+            });
+        Box::new(iter)
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+}
+
+impl PayloadFieldIndex for MapIndex<UuidIntType> {
+    fn indexed_points(&self) -> usize {
+        self.indexed_points
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        MapIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        Ok(self.db.borrow_mut().drop_cf(&self.store_cf_name)?)
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        MapIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => Some(match parse_uuid(keyword) {
+                Some(uuid) => self.get_iterator(&uuid),
+                None => Box::new(iter::empty()),
+            }),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Keywords(keywords),
+            })) => Some(self.get_any_iterator(&parse_uuids(keywords))),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => Some(self.get_except_iterator(&parse_uuids(keywords))),
             _ => None,
         }
     }
 
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        let mut estimation = match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => match parse_uuid(keyword) {
+                Some(uuid) => self.match_cardinality(&uuid),
+                None => CardinalityEstimation::exact(0),
+            },
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Keywords(keywords),
+            })) => self.values_cardinality(parse_uuids(keywords).iter()),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => self.except_cardinality(&parse_uuids(keywords)),
+            _ => return None,
+        };
+        estimation
+            .primary_clauses
+            .push(PrimaryCondition::Condition(condition.clone()));
+        Some(estimation)
+    }
+
     fn payload_blocks(
         &self,
         threshold: usize,
@@ -313,7 +681,10 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
             .iter()
             .filter(move |(_value, point_ids)| point_ids.len() >= threshold)
             .map(move |(value, point_ids)| PayloadBlockCondition {
-                condition: FieldCondition::new_match(key.clone(), (*value).into()),
+                condition: FieldCondition::new_match(
+                    key.clone(),
+                    Uuid::from_u128(*value).to_string().into(),
+                ),
                 cardinality: point_ids.len(),
             });
         Box::new(iter)
@@ -324,6 +695,49 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 }
 
+/// Estimate amount of points, which have at least one of the values with given posting sizes.
+/// `total_points` and `total_values` are the amounts of indexed points and (point, value) pairs.
+pub(super) fn postings_cardinality(
+    sizes: impl Iterator<Item = usize>,
+    total_points: usize,
+    total_values: usize,
+) -> CardinalityEstimation {
+    let (sum, maximum) = sizes.fold((0, 0), |(sum, maximum), count| {
+        (sum + count, max(maximum, count))
+    });
+
+    if sum == 0 || total_points == 0 {
+        return CardinalityEstimation::exact(0);
+    }
+
+    // Assume that we have selected all possible duplications of the points
+    let point_duplications = total_values - total_points;
+    let possible_non_duplicated = sum.saturating_sub(point_duplications);
+
+    let estimation_min = max(maximum, possible_non_duplicated);
+    let estimation_max = min(sum, total_points);
+
+    // estimate_multi_value_selection_cardinality might overflow at some corner cases
+    // so it is better to limit its value with min and max
+    let estimation_exp = estimate_multi_value_selection_cardinality(total_points, total_values, sum)
+        .round() as usize;
+
+    CardinalityEstimation {
+        primary_clauses: vec![],
+        min: estimation_min,
+        exp: min(estimation_max, max(estimation_min, estimation_exp)),
+        max: estimation_max,
+    }
+}
+
+/// Integer representations of the valid UUIDs, others can't match anything
+fn parse_uuids(keywords: &BTreeSet<String>) -> BTreeSet<UuidIntType> {
+    keywords
+        .iter()
+        .filter_map(|keyword| parse_uuid(keyword))
+        .collect()
+}
+
 impl ValueIndexer<String> for MapIndex<String> {
     fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
         self.add_many_to_map(id, values)
@@ -362,6 +776,23 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
     }
 }
 
+impl ValueIndexer<UuidIntType> for MapIndex<UuidIntType> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<UuidIntType>) -> OperationResult<()> {
+        self.add_many_to_map(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<UuidIntType> {
+        if let Value::String(keyword) = value {
+            return parse_uuid(keyword);
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.remove_point(id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -373,13 +804,11 @@ mod tests {
 
     use super::*;
     use crate::common::rocksdb_operations::open_db_with_existing_cf;
+    use crate::types::{KeywordRange, MatchPrefix, MatchWildcard};
 
     const FIELD_NAME: &str = "test";
 
-    fn save_map_index<N: Hash + Eq + Clone + Display + FromStr + Debug>(
-        data: &[Vec<N>],
-        path: &Path,
-    ) {
+    fn save_map_index<N: MapIndexKey + Debug>(data: &[Vec<N>], path: &Path) {
         let mut index = MapIndex::<N>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME);
         index.recreate().unwrap();
         for (idx, values) in data.iter().enumerate() {
@@ -390,10 +819,7 @@ mod tests {
         index.flush().unwrap();
     }
 
-    fn load_map_index<N: Hash + Eq + Clone + Display + FromStr + Debug>(
-        data: &[Vec<N>],
-        path: &Path,
-    ) {
+    fn load_map_index<N: MapIndexKey + Debug>(data: &[Vec<N>], path: &Path) {
         let mut index = MapIndex::<N>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME);
         index.load().unwrap();
         for (idx, values) in data.iter().enumerate() {
@@ -411,7 +837,7 @@ mod tests {
 
     #[test]
     fn test_int_disk_map_index() {
-        let data = vec![
+        let data: Vec<Vec<IntPayloadType>> = vec![
             vec![1, 2, 3, 4, 5, 6],
             vec![1, 2, 3, 4, 5, 6],
             vec![13, 14, 15, 16, 17, 18],
@@ -454,4 +880,186 @@ mod tests {
         save_map_index(&data, tmp_dir.path());
         load_map_index(&data, tmp_dir.path());
     }
+
+    #[test]
+    fn test_match_any_and_except() {
+        let data = vec![
+            vec![String::from("red")],
+            vec![String::from("red"), String::from("blue")],
+            vec![String::from("green")],
+            vec![String::from("blue")],
+            vec![],
+        ];
+
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        save_map_index(&data, tmp_dir.path());
+        let mut index = MapIndex::<String>::new(
+            open_db_with_existing_cf(tmp_dir.path()).unwrap(),
+            FIELD_NAME,
+        );
+        index.load().unwrap();
+
+        let any_condition = FieldCondition::new_match(
+            FIELD_NAME.to_string(),
+            Match::Any(MatchAny {
+                any: AnyVariants::Keywords(BTreeSet::from([
+                    String::from("red"),
+                    String::from("blue"),
+                ])),
+            }),
+        );
+        let found: Vec<_> = index.filter(&any_condition).unwrap().sorted().collect();
+        assert_eq!(found, vec![0, 1, 3]);
+
+        let estimation = index.estimate_cardinality(&any_condition).unwrap();
+        assert!(estimation.min <= 3 && 3 <= estimation.max);
+        assert_eq!(estimation.max, 4);
+
+        let except_condition = FieldCondition::new_match(
+            FIELD_NAME.to_string(),
+            Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(BTreeSet::from([String::from("red")])),
+            }),
+        );
+        let found: Vec<_> = index.filter(&except_condition).unwrap().sorted().collect();
+        assert_eq!(found, vec![1, 2, 3]);
+
+        let estimation = index.estimate_cardinality(&except_condition).unwrap();
+        assert!(estimation.min <= 3 && 3 <= estimation.max);
+    }
+
+    #[test]
+    fn test_keyword_patterns() {
+        let data = vec![
+            vec![String::from("docs/api.md")],
+            vec![String::from("docs/guide.md"), String::from("docs/api.md")],
+            vec![String::from("doc.md")],
+            vec![String::from("src/main.rs")],
+            vec![String::from("docs/guide.rs")],
+        ];
+
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        save_map_index(&data, tmp_dir.path());
+        let mut index = MapIndex::<String>::new(
+            open_db_with_existing_cf(tmp_dir.path()).unwrap(),
+            FIELD_NAME,
+        );
+        index.load().unwrap();
+
+        let check = |condition_match: Match, expected: Vec<PointOffsetType>| {
+            let condition = FieldCondition::new_match(FIELD_NAME.to_string(), condition_match);
+            let found: Vec<_> = index.filter(&condition).unwrap().sorted().collect();
+            assert_eq!(found, expected);
+            let estimation = index.estimate_cardinality(&condition).unwrap();
+            assert!(estimation.min <= expected.len() && expected.len() <= estimation.max);
+        };
+
+        check(
+            Match::Prefix(MatchPrefix {
+                prefix: String::from("docs/"),
+            }),
+            vec![0, 1, 4],
+        );
+        check(
+            Match::KeywordRange(MatchKeywordRange {
+                keyword_range: KeywordRange {
+                    gt: Some(String::from("doc.md")),
+                    lt: Some(String::from("docs/h")),
+                    ..Default::default()
+                },
+            }),
+            vec![0, 1, 4],
+        );
+        check(
+            Match::Wildcard(MatchWildcard {
+                wildcard: String::from("doc*.md"),
+            }),
+            vec![0, 1, 2],
+        );
+        check(
+            Match::Wildcard(MatchWildcard {
+                wildcard: String::from("*.rs"),
+            }),
+            vec![3, 4],
+        );
+
+        // Keywords, which can't match the prefix, are not counted
+        let condition = FieldCondition::new_match(
+            FIELD_NAME.to_string(),
+            Match::Prefix(MatchPrefix {
+                prefix: String::from("src/"),
+            }),
+        );
+        assert_eq!(index.estimate_cardinality(&condition).unwrap().max, 1);
+    }
+
+    #[test]
+    fn test_keyword_pattern_estimation_sample() {
+        let data: Vec<_> = (0..PATTERN_ESTIMATION_SAMPLE * 3)
+            .map(|idx| {
+                let extension = if idx % 3 == 0 { "rs" } else { "md" };
+                vec![format!("file_{idx}.{extension}")]
+            })
+            .collect();
+
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        save_map_index(&data, tmp_dir.path());
+        let mut index = MapIndex::<String>::new(
+            open_db_with_existing_cf(tmp_dir.path()).unwrap(),
+            FIELD_NAME,
+        );
+        index.load().unwrap();
+
+        let condition = FieldCondition::new_match(
+            FIELD_NAME.to_string(),
+            Match::Wildcard(MatchWildcard {
+                wildcard: String::from("*.rs"),
+            }),
+        );
+        let expected = index.filter(&condition).unwrap().count();
+        assert_eq!(expected, PATTERN_ESTIMATION_SAMPLE);
+
+        let estimation = index.estimate_cardinality(&condition).unwrap();
+        assert!(estimation.min <= expected && expected <= estimation.max);
+        assert!(estimation.exp.abs_diff(expected) < expected / 10);
+    }
+
+    #[test]
+    fn test_uuid_map_index() {
+        let uuid = "550e8400-e29b-41d4-a716-446655440000";
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let mut index = MapIndex::<UuidIntType>::new(db, FIELD_NAME);
+        index.recreate().unwrap();
+        index
+            .add_point(0, &Value::String(uuid.to_uppercase()))
+            .unwrap();
+        index
+            .add_point(1, &Value::String("not-a-uuid".to_string()))
+            .unwrap();
+        index.flush().unwrap();
+        drop(index);
+
+        let mut index = MapIndex::<UuidIntType>::new(
+            open_db_with_existing_cf(tmp_dir.path()).unwrap(),
+            FIELD_NAME,
+        );
+        index.load().unwrap();
+        assert_eq!(index.count_indexed_points(), 1);
+
+        let condition = FieldCondition::new_match(FIELD_NAME.to_string(), uuid.to_string().into());
+        let found: Vec<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(found, vec![0]);
+        assert_eq!(index.estimate_cardinality(&condition).unwrap().exp, 1);
+
+        let invalid = FieldCondition::new_match(FIELD_NAME.to_string(), "x".to_string().into());
+        assert_eq!(index.filter(&invalid).unwrap().count(), 0);
+
+        let blocks: Vec<_> = index.payload_blocks(1, FIELD_NAME.to_string()).collect();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(
+            blocks[0].condition.r#match,
+            Some(Match::from(uuid.to_string()))
+        );
+    }
 }
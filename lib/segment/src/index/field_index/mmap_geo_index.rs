@@ -0,0 +1,598 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{create_dir_all, remove_dir_all};
+use std::mem::size_of;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+
+use itertools::Itertools;
+use serde_json::Value;
+
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::geo_hash::{
+    circle_hashes, encode_max_precision, geo_hash_to_box, rectangle_hashes, GeoHash,
+};
+use crate::index::field_index::geo_index::{
+    large_hashes, match_cardinality, polygon_cardinality, polygon_regions, GEO_QUERY_MAX_REGION,
+};
+use crate::index::field_index::mmap_postings::{MmapPostings, MmapRecords};
+use crate::index::field_index::{
+    CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
+};
+use crate::types::{
+    FieldCondition, GeoPoint, GeoPolygon, PayloadKeyType, PointOffsetType, PolygonWrapper,
+};
+
+const POINT_VALUES_FILE: &str = "point_values.dat";
+const HASH_PREFIXES_FILE: &str = "hash_prefixes.dat";
+const HASH_COUNTS_FILE: &str = "hash_counts.dat";
+
+const GEO_POINT_SIZE: usize = 2 * size_of::<f64>();
+
+/// Memory-mapped files of the stored geo index
+struct MmapGeoStorage {
+    /// Points of each max precision geo-hash
+    postings: MmapPostings,
+    /// Encoded geo values of each point
+    point_values: MmapRecords,
+    /// Sorted prefixes of the stored geo-hashes, including the empty one
+    hash_prefixes: MmapRecords,
+    /// Number of points and values of each prefix
+    hash_counts: MmapRecords,
+}
+
+/// On-disk variant of `GeoMapIndex`, used in non-appendable segments.
+/// Points and values counts per geo-hash prefix are stored on disk as well,
+/// counts of the removed points are subtracted in memory.
+pub struct MmapGeoMapIndex {
+    path: PathBuf,
+    /// Values of the points, added before the index is stored
+    pending: Vec<Vec<GeoPoint>>,
+    storage: Option<MmapGeoStorage>,
+    /// Number of removed points and values of each geo-hash prefix
+    removed_per_hash: HashMap<GeoHash, (usize, usize)>,
+    points_count: usize,
+    values_count: usize,
+    max_values_per_point: usize,
+}
+
+impl MmapGeoMapIndex {
+    pub fn new(path: &Path, field: &str) -> Self {
+        MmapGeoMapIndex {
+            path: path.join(format!("{field}_geo")),
+            pending: vec![],
+            storage: None,
+            removed_per_hash: Default::default(),
+            points_count: 0,
+            values_count: 0,
+            max_values_per_point: 1,
+        }
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    fn encode_geo_points(values: &[GeoPoint]) -> Vec<u8> {
+        let mut record = Vec::with_capacity(values.len() * GEO_POINT_SIZE);
+        for value in values {
+            record.extend_from_slice(&value.lat.to_le_bytes());
+            record.extend_from_slice(&value.lon.to_le_bytes());
+        }
+        record
+    }
+
+    fn decode_geo_points(record: &[u8]) -> Vec<GeoPoint> {
+        record
+            .chunks_exact(GEO_POINT_SIZE)
+            .map(|chunk| {
+                let (lat, lon) = chunk.split_at(size_of::<f64>());
+                GeoPoint {
+                    lat: f64::from_le_bytes(lat.try_into().unwrap()),
+                    lon: f64::from_le_bytes(lon.try_into().unwrap()),
+                }
+            })
+            .collect()
+    }
+
+    fn geo_hashes(values: &[GeoPoint]) -> Vec<GeoHash> {
+        values
+            .iter()
+            .map(|value| encode_max_precision(value.lon, value.lat).unwrap())
+            .collect()
+    }
+
+    /// Call `f` with each prefix of the point geo-hashes and `true` if the point was not
+    /// counted in this prefix yet
+    fn for_each_hash_prefix(geo_hashes: &[GeoHash], mut f: impl FnMut(&str, bool)) {
+        let mut seen_hashes: HashSet<&str> = Default::default();
+        for geo_hash in geo_hashes {
+            for i in 0..=geo_hash.len() {
+                let sub_geo_hash = &geo_hash[0..i];
+                f(sub_geo_hash, seen_hashes.insert(sub_geo_hash));
+            }
+        }
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        let postings = match MmapPostings::open(&self.path)? {
+            Some(postings) => postings,
+            None => return Ok(false),
+        };
+        let storage = MmapGeoStorage {
+            postings,
+            point_values: MmapRecords::open(&self.path.join(POINT_VALUES_FILE))?,
+            hash_prefixes: MmapRecords::open(&self.path.join(HASH_PREFIXES_FILE))?,
+            hash_counts: MmapRecords::open(&self.path.join(HASH_COUNTS_FILE))?,
+        };
+
+        self.points_count = 0;
+        self.values_count = 0;
+        self.max_values_per_point = 1;
+        self.removed_per_hash.clear();
+        for idx in 0..storage.point_values.len() {
+            let values = Self::decode_geo_points(storage.point_values.get(idx));
+            if values.is_empty() {
+                continue;
+            }
+            if storage.postings.is_deleted(idx as PointOffsetType) {
+                let removed_per_hash = &mut self.removed_per_hash;
+                Self::for_each_hash_prefix(&Self::geo_hashes(&values), |hash, is_new_point| {
+                    let removed = removed_per_hash.entry(hash.to_owned()).or_default();
+                    removed.0 += is_new_point as usize;
+                    removed.1 += 1;
+                });
+            } else {
+                self.points_count += 1;
+                self.values_count += values.len();
+                self.max_values_per_point = self.max_values_per_point.max(values.len());
+            }
+        }
+        self.storage = Some(storage);
+        Ok(true)
+    }
+
+    /// Store values, added so far, on disk. The index could not be extended after that.
+    pub fn finalize(&mut self) -> OperationResult<()> {
+        let point_values = std::mem::take(&mut self.pending);
+
+        let mut postings: BTreeMap<Vec<u8>, Vec<PointOffsetType>> = Default::default();
+        let mut counts_per_hash: BTreeMap<GeoHash, (u64, u64)> = Default::default();
+        for (idx, values) in point_values.iter().enumerate() {
+            let geo_hashes = Self::geo_hashes(values);
+            for geo_hash in &geo_hashes {
+                let points = postings.entry(geo_hash.as_bytes().to_vec()).or_default();
+                if points.last() != Some(&(idx as PointOffsetType)) {
+                    points.push(idx as PointOffsetType);
+                }
+            }
+            Self::for_each_hash_prefix(&geo_hashes, |hash, is_new_point| {
+                let counts = counts_per_hash.entry(hash.to_owned()).or_default();
+                counts.0 += is_new_point as u64;
+                counts.1 += 1;
+            });
+        }
+
+        create_dir_all(&self.path)?;
+        let value_records = point_values
+            .iter()
+            .map(|values| Self::encode_geo_points(values))
+            .collect_vec();
+        let count_records = counts_per_hash
+            .values()
+            .map(|(points, values)| [points.to_le_bytes(), values.to_le_bytes()].concat())
+            .collect_vec();
+        MmapRecords::create(&self.path.join(POINT_VALUES_FILE), &value_records)?;
+        MmapRecords::create(
+            &self.path.join(HASH_PREFIXES_FILE),
+            &counts_per_hash.keys().collect_vec(),
+        )?;
+        MmapRecords::create(&self.path.join(HASH_COUNTS_FILE), &count_records)?;
+        // Postings are written last, they mark the complete index
+        MmapPostings::create(&self.path, &postings)?;
+        if !self.load()? {
+            return Err(OperationError::service_error(&format!(
+                "Failed to create index in {}",
+                self.path.display()
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        match &self.storage {
+            Some(storage) => storage.postings.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn add_many_geo_points(
+        &mut self,
+        idx: PointOffsetType,
+        values: Vec<GeoPoint>,
+    ) -> OperationResult<()> {
+        if self.storage.is_some() {
+            return Err(OperationError::service_error(
+                "Index add error: on-disk index could not be updated",
+            ));
+        }
+        if values.is_empty() {
+            return Ok(());
+        }
+        if self.pending.len() <= idx as usize {
+            self.pending.resize(idx as usize + 1, vec![]);
+        }
+        self.pending[idx as usize] = values;
+        Ok(())
+    }
+
+    fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let storage = match &mut self.storage {
+            Some(storage) => storage,
+            None => {
+                if let Some(values) = self.pending.get_mut(idx as usize) {
+                    values.clear();
+                }
+                return Ok(());
+            }
+        };
+        if storage.postings.remove_point(idx).is_empty() {
+            return Ok(()); // Already removed or never actually existed
+        }
+
+        let removed_values = Self::decode_geo_points(storage.point_values.get(idx as usize));
+        self.points_count -= 1;
+        self.values_count -= removed_values.len();
+
+        let removed_per_hash = &mut self.removed_per_hash;
+        Self::for_each_hash_prefix(&Self::geo_hashes(&removed_values), |hash, is_new_point| {
+            let removed = removed_per_hash.entry(hash.to_owned()).or_default();
+            removed.0 += is_new_point as usize;
+            removed.1 += 1;
+        });
+        Ok(())
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<Vec<GeoPoint>> {
+        let storage = self.storage.as_ref()?;
+        if idx as usize >= storage.point_values.len() || storage.postings.is_deleted(idx) {
+            return None;
+        }
+        Some(Self::decode_geo_points(
+            storage.point_values.get(idx as usize),
+        ))
+    }
+
+    /// Number of not removed points and values within the geo-hash region
+    fn hash_counts(&self, geo_hash: &str) -> (usize, usize) {
+        let storage = match &self.storage {
+            Some(storage) => storage,
+            None => return (0, 0),
+        };
+        let (points, values) = match storage.hash_prefixes.find(geo_hash.as_bytes()) {
+            Some(position) => {
+                let record = storage.hash_counts.get(position);
+                let (points, values) = record.split_at(size_of::<u64>());
+                (
+                    u64::from_le_bytes(points.try_into().unwrap()) as usize,
+                    u64::from_le_bytes(values.try_into().unwrap()) as usize,
+                )
+            }
+            None => return (0, 0),
+        };
+        let (removed_points, removed_values) = self
+            .removed_per_hash
+            .get(geo_hash)
+            .copied()
+            .unwrap_or_default();
+        (points - removed_points, values - removed_values)
+    }
+
+    pub fn match_cardinality(&self, values: &[GeoHash]) -> CardinalityEstimation {
+        match_cardinality(
+            values,
+            |hash| self.hash_counts(hash).0,
+            |hash| self.hash_counts(hash).1,
+            self.max_values_per_point,
+        )
+    }
+
+    /// Points of the stored geo-hashes, which start with any of the given ones
+    fn get_iterator(&self, values: Vec<GeoHash>) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let postings = match &self.storage {
+            Some(storage) => &storage.postings,
+            None => return Box::new(std::iter::empty()),
+        };
+        Box::new(
+            values
+                .into_iter()
+                .flat_map(move |top_geo_hash| {
+                    let start = postings.lower_bound(Bound::Included(top_geo_hash.as_bytes()));
+                    (start..postings.keys_count())
+                        .take_while(move |key_idx| {
+                            postings.key(*key_idx).starts_with(top_geo_hash.as_bytes())
+                        })
+                        .flat_map(|key_idx| postings.points(key_idx))
+                })
+                .unique(),
+        )
+    }
+
+    fn check_values(&self, idx: PointOffsetType, check: impl Fn(&GeoPoint) -> bool) -> bool {
+        self.get_values(idx)
+            .map_or(false, |values| values.iter().any(check))
+    }
+
+    pub fn check_polygon(&self, idx: PointOffsetType, polygon: &PolygonWrapper) -> bool {
+        self.check_values(idx, |value| polygon.check_point(value.lon, value.lat))
+    }
+
+    /// Points of the inner tiles are matched as is,
+    /// only points of the edge tiles are checked against the polygon
+    fn get_polygon_iterator(
+        &self,
+        polygon: &GeoPolygon,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let (polygon_wrapper, inner_hashes, edge_hashes) = polygon_regions(polygon);
+        let inner_points = self.get_iterator(inner_hashes);
+        let edge_points = self
+            .get_iterator(edge_hashes)
+            .filter(move |point| self.check_polygon(*point, &polygon_wrapper));
+        Box::new(inner_points.chain(edge_points).unique())
+    }
+
+    fn polygon_cardinality(&self, polygon: &GeoPolygon) -> CardinalityEstimation {
+        polygon_cardinality(
+            polygon,
+            |hashes| self.match_cardinality(hashes),
+            self.points_count,
+        )
+    }
+
+    /// Rough estimation of memory, used by the index structures
+    pub fn ram_usage_bytes(&self) -> usize {
+        let pending_usage = self
+            .pending
+            .iter()
+            .map(|values| size_of::<Vec<GeoPoint>>() + values.len() * size_of::<GeoPoint>())
+            .sum::<usize>();
+        let removed_usage =
+            self.removed_per_hash.len() * (size_of::<GeoHash>() + size_of::<(usize, usize)>());
+        let postings_usage = self
+            .storage
+            .as_ref()
+            .map_or(0, |storage| storage.postings.ram_usage_bytes());
+        pending_usage + removed_usage + postings_usage
+    }
+
+    pub fn disk_usage_bytes(&self) -> usize {
+        self.storage.as_ref().map_or(0, |storage| {
+            storage.postings.disk_usage_bytes()
+                + storage.point_values.disk_usage_bytes()
+                + storage.hash_prefixes.disk_usage_bytes()
+                + storage.hash_counts.disk_usage_bytes()
+        })
+    }
+}
+
+impl ValueIndexer<GeoPoint> for MmapGeoMapIndex {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<GeoPoint>) -> OperationResult<()> {
+        self.add_many_geo_points(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<GeoPoint> {
+        match value {
+            Value::Object(obj) => {
+                let lon_op = obj.get("lon").and_then(|x| x.as_f64());
+                let lat_op = obj.get("lat").and_then(|x| x.as_f64());
+
+                if let (Some(lon), Some(lat)) = (lon_op, lat_op) {
+                    return Some(GeoPoint { lon, lat });
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.remove_point(id)
+    }
+}
+
+impl PayloadFieldIndex for MmapGeoMapIndex {
+    fn indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        MmapGeoMapIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.recreate()
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        MmapGeoMapIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        if let Some(geo_bounding_box) = &condition.geo_bounding_box {
+            let geo_hashes = rectangle_hashes(geo_bounding_box, GEO_QUERY_MAX_REGION);
+            let geo_condition_copy = geo_bounding_box.clone();
+            return Some(Box::new(self.get_iterator(geo_hashes).filter(
+                move |point| {
+                    self.check_values(*point, |value| {
+                        geo_condition_copy.check_point(value.lon, value.lat)
+                    })
+                },
+            )));
+        }
+
+        if let Some(geo_radius) = &condition.geo_radius {
+            let geo_hashes = circle_hashes(geo_radius, GEO_QUERY_MAX_REGION);
+            let geo_condition_copy = geo_radius.clone();
+            return Some(Box::new(self.get_iterator(geo_hashes).filter(
+                move |point| {
+                    self.check_values(*point, |value| {
+                        geo_condition_copy.check_point(value.lon, value.lat)
+                    })
+                },
+            )));
+        }
+
+        if let Some(geo_polygon) = &condition.geo_polygon {
+            return Some(self.get_polygon_iterator(geo_polygon));
+        }
+
+        None
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        if let Some(geo_bounding_box) = &condition.geo_bounding_box {
+            let geo_hashes = rectangle_hashes(geo_bounding_box, GEO_QUERY_MAX_REGION);
+            let mut estimation = self.match_cardinality(&geo_hashes);
+            estimation
+                .primary_clauses
+                .push(PrimaryCondition::Condition(condition.clone()));
+            return Some(estimation);
+        }
+
+        if let Some(geo_radius) = &condition.geo_radius {
+            let geo_hashes = circle_hashes(geo_radius, GEO_QUERY_MAX_REGION);
+            let mut estimation = self.match_cardinality(&geo_hashes);
+            estimation
+                .primary_clauses
+                .push(PrimaryCondition::Condition(condition.clone()));
+            return Some(estimation);
+        }
+
+        if let Some(geo_polygon) = &condition.geo_polygon {
+            let mut estimation = self.polygon_cardinality(geo_polygon);
+            estimation
+                .primary_clauses
+                .push(PrimaryCondition::Condition(condition.clone()));
+            return Some(estimation);
+        }
+
+        None
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let hash_prefixes = match &self.storage {
+            Some(storage) => &storage.hash_prefixes,
+            None => return Box::new(std::iter::empty()),
+        };
+        let points_per_hash = (0..hash_prefixes.len()).map(|position| {
+            let geo_hash = String::from_utf8_lossy(hash_prefixes.get(position)).into_owned();
+            let points = self.hash_counts(&geo_hash).0;
+            (geo_hash, points)
+        });
+        Box::new(large_hashes(points_per_hash, threshold).into_iter().map(
+            move |(geo_hash, size)| PayloadBlockCondition {
+                condition: FieldCondition::new_geo_bounding_box(
+                    key.clone(),
+                    geo_hash_to_box(&geo_hash),
+                ),
+                cardinality: size,
+            },
+        ))
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::prelude::StdRng;
+    use rand::SeedableRng;
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::fixtures::payload_fixtures::random_geo_payload;
+    use crate::types::GeoRadius;
+
+    const BERLIN: GeoPoint = GeoPoint {
+        lat: 52.52437,
+        lon: 13.41053,
+    };
+
+    const FIELD_NAME: &str = "test";
+
+    #[test]
+    fn test_mmap_geo_index() {
+        let mut rnd = StdRng::seed_from_u64(42);
+        let tmp_dir = TempDir::new("test_mmap_geo_index").unwrap();
+        let mut index = MmapGeoMapIndex::new(tmp_dir.path(), FIELD_NAME);
+        index.recreate().unwrap();
+
+        let values: Vec<Vec<GeoPoint>> = (0..1000)
+            .map(|_| {
+                random_geo_payload(&mut rnd, 1..=3)
+                    .iter()
+                    .filter_map(|value| index.get_value(value))
+                    .collect()
+            })
+            .collect();
+        for (idx, point_values) in values.iter().enumerate() {
+            index
+                .add_many_geo_points(idx as PointOffsetType, point_values.clone())
+                .unwrap();
+        }
+        index.finalize().unwrap();
+        assert_eq!(index.count_indexed_points(), 1000);
+
+        let geo_radius = GeoRadius {
+            center: BERLIN,
+            radius: 2_000_000.0,
+        };
+        let condition = FieldCondition::new_geo_radius(FIELD_NAME.to_owned(), geo_radius.clone());
+        let expected = |index: &MmapGeoMapIndex| {
+            (0..values.len() as PointOffsetType)
+                .filter(|idx| {
+                    index.check_values(*idx, |value| geo_radius.check_point(value.lon, value.lat))
+                })
+                .sorted()
+                .collect_vec()
+        };
+        let points = index.filter(&condition).unwrap().sorted().collect_vec();
+        assert!(!points.is_empty());
+        assert_eq!(points, expected(&index));
+        let hash_points = index
+            .get_iterator(circle_hashes(&geo_radius, GEO_QUERY_MAX_REGION))
+            .count();
+        let estimation = index.estimate_cardinality(&condition).unwrap();
+        assert!(estimation.min <= hash_points);
+        assert!(estimation.max >= hash_points);
+
+        for idx in &points[..points.len() / 2] {
+            index.remove_point(*idx).unwrap();
+        }
+        let remaining_points = index.filter(&condition).unwrap().sorted().collect_vec();
+        assert_eq!(remaining_points, points[points.len() / 2..].to_vec());
+        assert_eq!(index.hash_counts("").0, 1000 - points.len() / 2);
+        index.flush().unwrap();
+        drop(index);
+
+        let mut index = MmapGeoMapIndex::new(tmp_dir.path(), FIELD_NAME);
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 1000 - points.len() / 2);
+        assert_eq!(index.hash_counts("").0, 1000 - points.len() / 2);
+        let reloaded_points = index.filter(&condition).unwrap().sorted().collect_vec();
+        assert_eq!(reloaded_points, remaining_points);
+        assert_eq!(reloaded_points, expected(&index));
+        assert_eq!(index.get_values(999).unwrap(), values[999]);
+    }
+}
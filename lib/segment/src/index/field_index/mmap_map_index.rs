@@ -0,0 +1,743 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs::remove_dir_all;
+use std::iter;
+use std::mem::size_of;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+
+use itertools::Itertools;
+use log::warn;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::map_index::postings_cardinality;
+use crate::index::field_index::mmap_postings::{MmapKey, MmapPostings};
+use crate::index::field_index::{
+    CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
+};
+use crate::types::{
+    parse_uuid, AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept,
+    MatchKeywordRange, MatchValue, PayloadKeyType, PointOffsetType, UuidIntType, ValueVariants,
+};
+
+/// On-disk variant of `MapIndex`, used in non-appendable segments.
+/// Values are collected in memory while the index is built, then stored as memory-mapped
+/// sorted keys and posting lists. After that points could only be removed.
+pub struct MmapMapIndex<N: MmapKey> {
+    path: PathBuf,
+    /// Points of the values, added before the postings are stored
+    pending: BTreeMap<Vec<u8>, Vec<PointOffsetType>>,
+    postings: Option<MmapPostings>,
+    _value: std::marker::PhantomData<N>,
+}
+
+impl<N: MmapKey> MmapMapIndex<N> {
+    pub fn new(path: &Path, field: &str) -> Self {
+        MmapMapIndex {
+            path: path.join(format!("{field}_map")),
+            pending: Default::default(),
+            postings: None,
+            _value: Default::default(),
+        }
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        self.postings = MmapPostings::open(&self.path)?;
+        Ok(self.postings.is_some())
+    }
+
+    /// Amount of point which have at least one indexed payload value
+    fn indexed_points(&self) -> usize {
+        self.postings
+            .as_ref()
+            .map_or(0, |postings| postings.indexed_points())
+    }
+
+    /// Total amount of unique (point, value) pairs in the index
+    fn values_count(&self) -> usize {
+        self.postings
+            .as_ref()
+            .map_or(0, |postings| postings.values_count())
+    }
+
+    /// Store values, added so far, on disk. The index could not be extended after that.
+    pub fn finalize(&mut self) -> OperationResult<()> {
+        let mut pending = std::mem::take(&mut self.pending);
+        for points in pending.values_mut() {
+            points.sort_unstable();
+            points.dedup();
+        }
+        MmapPostings::create(&self.path, &pending)?;
+        self.load()?;
+        Ok(())
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        match &self.postings {
+            Some(postings) => postings.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.recreate()
+    }
+
+    fn add_many_to_map(&mut self, idx: PointOffsetType, values: Vec<N>) -> OperationResult<()> {
+        if self.postings.is_some() {
+            return Err(OperationError::service_error(
+                "Index add error: on-disk index could not be updated",
+            ));
+        }
+        for value in values {
+            self.pending
+                .entry(value.encode_mmap_key())
+                .or_default()
+                .push(idx);
+        }
+        Ok(())
+    }
+
+    fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        match &mut self.postings {
+            Some(postings) => {
+                postings.remove_point(idx);
+            }
+            None => {
+                for points in self.pending.values_mut() {
+                    points.retain(|point| *point != idx);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<Vec<N>> {
+        let postings = self.postings.as_ref()?;
+        let keys = postings.point_keys(idx)?;
+        Some(
+            keys.filter_map(|key_idx| Self::decode_key(postings, key_idx))
+                .collect(),
+        )
+    }
+
+    /// Decode the stored key. Keys of the corrupted index file are skipped with a warning,
+    /// as the iterators over the values can't return errors
+    fn decode_key(postings: &MmapPostings, key_idx: usize) -> Option<N> {
+        N::decode_mmap_key(postings.key(key_idx))
+            .map_err(|err| {
+                warn!(
+                    "Skipping key {} of the index {}: {}",
+                    key_idx,
+                    postings.path().display(),
+                    err
+                )
+            })
+            .ok()
+    }
+
+    /// Iterate over indexed values with the number of points which have each of them
+    pub fn values_with_counts(&self) -> impl Iterator<Item = (N, usize)> + '_ {
+        self.keys_from(Bound::Unbounded)
+            .map(|(value, key_idx)| (value, self.key_points_count(key_idx)))
+            .filter(|(_value, count)| *count > 0)
+    }
+
+    /// Stored values with their key positions, starting from the `from` encoded key
+    fn keys_from(&self, from: Bound<&[u8]>) -> Box<dyn Iterator<Item = (N, usize)> + '_> {
+        match &self.postings {
+            Some(postings) => Box::new(
+                (postings.lower_bound(from)..postings.keys_count()).filter_map(|key_idx| {
+                    Self::decode_key(postings, key_idx).map(|value| (value, key_idx))
+                }),
+            ),
+            None => Box::new(iter::empty()),
+        }
+    }
+
+    fn key_position(&self, value: &N) -> Option<usize> {
+        self.postings.as_ref()?.find(&value.encode_mmap_key())
+    }
+
+    fn key_points(&self, key_idx: usize) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match &self.postings {
+            Some(postings) => Box::new(postings.points(key_idx)),
+            None => Box::new(iter::empty()),
+        }
+    }
+
+    fn key_points_count(&self, key_idx: usize) -> usize {
+        self.postings
+            .as_ref()
+            .map_or(0, |postings| postings.points_count(key_idx))
+    }
+
+    pub fn match_cardinality(&self, value: &N) -> CardinalityEstimation {
+        let values_count = self
+            .key_position(value)
+            .map_or(0, |key_idx| self.key_points_count(key_idx));
+        CardinalityEstimation::exact(values_count)
+    }
+
+    /// Estimate amount of points, which have at least one of the given values
+    pub fn values_cardinality<'a>(
+        &self,
+        values: impl Iterator<Item = &'a N>,
+    ) -> CardinalityEstimation
+    where
+        N: 'a,
+    {
+        self.postings_cardinality(values.map(|value| {
+            self.key_position(value)
+                .map_or(0, |key_idx| self.key_points_count(key_idx))
+        }))
+    }
+
+    fn postings_cardinality(&self, sizes: impl Iterator<Item = usize>) -> CardinalityEstimation {
+        postings_cardinality(sizes, self.indexed_points(), self.values_count())
+    }
+
+    fn get_iterator(&self, value: &N) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self.key_position(value) {
+            Some(key_idx) => self.key_points(key_idx),
+            None => Box::new(iter::empty()),
+        }
+    }
+
+    /// Points which have at least one of the given values
+    fn get_any_iterator(
+        &self,
+        values: &BTreeSet<N>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let key_indexes: Vec<_> = values
+            .iter()
+            .filter_map(|value| self.key_position(value))
+            .collect();
+        Box::new(
+            key_indexes
+                .into_iter()
+                .flat_map(|key_idx| self.key_points(key_idx))
+                .unique(),
+        )
+    }
+
+    /// Points which have at least one value, other than the excluded ones
+    fn get_except_iterator(
+        &self,
+        excluded: &BTreeSet<N>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let excluded = self.excluded_positions(excluded);
+        Box::new(
+            self.keys_from(Bound::Unbounded)
+                .filter(move |(_, key_idx)| !excluded.contains(key_idx))
+                .flat_map(|(_, key_idx)| self.key_points(key_idx))
+                .unique(),
+        )
+    }
+
+    fn except_cardinality(&self, excluded: &BTreeSet<N>) -> CardinalityEstimation {
+        let excluded = self.excluded_positions(excluded);
+        self.postings_cardinality(
+            self.keys_from(Bound::Unbounded)
+                .filter(|(_, key_idx)| !excluded.contains(key_idx))
+                .map(|(_, key_idx)| self.key_points_count(key_idx)),
+        )
+    }
+
+    fn excluded_positions(&self, excluded: &BTreeSet<N>) -> HashSet<usize> {
+        excluded
+            .iter()
+            .filter_map(|value| self.key_position(value))
+            .collect()
+    }
+
+    fn value_blocks(&self, threshold: usize) -> impl Iterator<Item = (N, usize)> + '_ {
+        self.keys_from(Bound::Unbounded)
+            .map(|(value, key_idx)| (value, self.key_points_count(key_idx)))
+            .filter(move |(_value, count)| *count >= threshold)
+    }
+
+    /// Rough estimation of memory, used by the index structures
+    pub fn ram_usage_bytes(&self) -> usize {
+        let pending_usage = self
+            .pending
+            .iter()
+            .map(|(key, points)| key.len() + points.len() * size_of::<PointOffsetType>())
+            .sum::<usize>();
+        let postings_usage = self
+            .postings
+            .as_ref()
+            .map_or(0, |postings| postings.ram_usage_bytes());
+        pending_usage + postings_usage
+    }
+
+    pub fn disk_usage_bytes(&self) -> usize {
+        self.postings
+            .as_ref()
+            .map_or(0, |postings| postings.disk_usage_bytes())
+    }
+}
+
+impl MmapMapIndex<String> {
+    /// Key positions of the keywords, which satisfy the keyword pattern.
+    /// Only keywords within the range, which could match the pattern, are visited.
+    fn pattern_postings(
+        &self,
+        condition_match: &Match,
+    ) -> Option<Box<dyn Iterator<Item = usize> + '_>> {
+        let starting_from = |from: Bound<&str>| {
+            self.keys_from(match from {
+                Bound::Included(from) => Bound::Included(from.as_bytes()),
+                Bound::Excluded(from) => Bound::Excluded(from.as_bytes()),
+                Bound::Unbounded => Bound::Unbounded,
+            })
+        };
+        let postings: Box<dyn Iterator<Item = usize> + '_> = match condition_match {
+            Match::Prefix(prefix) => {
+                let prefix = prefix.clone();
+                Box::new(
+                    starting_from(Bound::Included(prefix.prefix.as_str()))
+                        .take_while(move |(keyword, _)| prefix.check(keyword))
+                        .map(|(_, key_idx)| key_idx),
+                )
+            }
+            Match::KeywordRange(MatchKeywordRange { keyword_range }) => {
+                let keyword_range = keyword_range.clone();
+                let upper_range = keyword_range.clone();
+                Box::new(
+                    starting_from(keyword_range.lower_bound())
+                        .take_while(move |(keyword, _)| upper_range.check_upper(keyword))
+                        .filter(move |(keyword, _)| keyword_range.check(keyword))
+                        .map(|(_, key_idx)| key_idx),
+                )
+            }
+            Match::Wildcard(wildcard) => {
+                let wildcard = wildcard.clone();
+                let literal_prefix = wildcard.literal_prefix().to_owned();
+                Box::new(
+                    starting_from(Bound::Included(literal_prefix.as_str()))
+                        .take_while(move |(keyword, _)| keyword.starts_with(&literal_prefix))
+                        .filter(move |(keyword, _)| wildcard.check(keyword))
+                        .map(|(_, key_idx)| key_idx),
+                )
+            }
+            _ => return None,
+        };
+        Some(postings)
+    }
+}
+
+impl PayloadFieldIndex for MmapMapIndex<String> {
+    fn indexed_points(&self) -> usize {
+        MmapMapIndex::indexed_points(self)
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        MmapMapIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        MmapMapIndex::clear(self)
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        MmapMapIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => Some(self.get_iterator(keyword)),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Keywords(keywords),
+            })) => Some(self.get_any_iterator(keywords)),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => Some(self.get_except_iterator(keywords)),
+            Some(pattern) => self.pattern_postings(pattern).map(|postings| {
+                Box::new(
+                    postings
+                        .flat_map(|key_idx| self.key_points(key_idx))
+                        .unique(),
+                ) as Box<dyn Iterator<Item = PointOffsetType>>
+            }),
+            _ => None,
+        }
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        let mut estimation = match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => self.match_cardinality(keyword),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Keywords(keywords),
+            })) => self.values_cardinality(keywords.iter()),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => self.except_cardinality(keywords),
+            Some(pattern) => {
+                let postings = self.pattern_postings(pattern)?;
+                self.postings_cardinality(postings.map(|key_idx| self.key_points_count(key_idx)))
+            }
+            _ => return None,
+        };
+        estimation
+            .primary_clauses
+            .push(PrimaryCondition::Condition(condition.clone()));
+        Some(estimation)
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter =
+            self.value_blocks(threshold + 1)
+                .map(move |(value, count)| PayloadBlockCondition {
+                    condition: FieldCondition::new_match(key.clone(), value.into()),
+                    cardinality: count,
+                });
+        Box::new(iter)
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        MmapMapIndex::indexed_points(self)
+    }
+}
+
+impl PayloadFieldIndex for MmapMapIndex<IntPayloadType> {
+    fn indexed_points(&self) -> usize {
+        MmapMapIndex::indexed_points(self)
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        MmapMapIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        MmapMapIndex::clear(self)
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        MmapMapIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Integer(integer),
+            })) => Some(self.get_iterator(integer)),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Integers(integers),
+            })) => Some(self.get_any_iterator(integers)),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Integers(integers),
+            })) => Some(self.get_except_iterator(integers)),
+            _ => None,
+        }
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        let mut estimation = match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Integer(integer),
+            })) => self.match_cardinality(integer),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Integers(integers),
+            })) => self.values_cardinality(integers.iter()),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Integers(integers),
+            })) => self.except_cardinality(integers),
+            _ => return None,
+        };
+        estimation
+            .primary_clauses
+            .push(PrimaryCondition::Condition(condition.clone()));
+        Some(estimation)
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = self
+            .value_blocks(threshold)
+            .map(move |(value, count)| PayloadBlockCondition {
+                condition: FieldCondition::new_match(key.clone(), value.into()),
+                cardinality: count,
+            });
+        Box::new(iter)
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        MmapMapIndex::indexed_points(self)
+    }
+}
+
+impl PayloadFieldIndex for MmapMapIndex<UuidIntType> {
+    fn indexed_points(&self) -> usize {
+        MmapMapIndex::indexed_points(self)
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        MmapMapIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        MmapMapIndex::clear(self)
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        MmapMapIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => Some(match parse_uuid(keyword) {
+                Some(uuid) => self.get_iterator(&uuid),
+                None => Box::new(iter::empty()),
+            }),
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Keywords(keywords),
+            })) => Some(self.get_any_iterator(&parse_uuids(keywords))),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => Some(self.get_except_iterator(&parse_uuids(keywords))),
+            _ => None,
+        }
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        let mut estimation = match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => match parse_uuid(keyword) {
+                Some(uuid) => self.match_cardinality(&uuid),
+                None => CardinalityEstimation::exact(0),
+            },
+            Some(Match::Any(MatchAny {
+                any: AnyVariants::Keywords(keywords),
+            })) => self.values_cardinality(parse_uuids(keywords).iter()),
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => self.except_cardinality(&parse_uuids(keywords)),
+            _ => return None,
+        };
+        estimation
+            .primary_clauses
+            .push(PrimaryCondition::Condition(condition.clone()));
+        Some(estimation)
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = self
+            .value_blocks(threshold)
+            .map(move |(value, count)| PayloadBlockCondition {
+                condition: FieldCondition::new_match(
+                    key.clone(),
+                    Uuid::from_u128(value).to_string().into(),
+                ),
+                cardinality: count,
+            });
+        Box::new(iter)
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        MmapMapIndex::indexed_points(self)
+    }
+}
+
+/// Integer representations of the valid UUIDs, others can't match anything
+fn parse_uuids(keywords: &BTreeSet<String>) -> BTreeSet<UuidIntType> {
+    keywords
+        .iter()
+        .filter_map(|keyword| parse_uuid(keyword))
+        .collect()
+}
+
+impl ValueIndexer<String> for MmapMapIndex<String> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
+        self.add_many_to_map(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<String> {
+        if let Value::String(keyword) = value {
+            return Some(keyword.to_owned());
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.remove_point(id)
+    }
+}
+
+impl ValueIndexer<IntPayloadType> for MmapMapIndex<IntPayloadType> {
+    fn add_many(
+        &mut self,
+        id: PointOffsetType,
+        values: Vec<IntPayloadType>,
+    ) -> OperationResult<()> {
+        self.add_many_to_map(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<IntPayloadType> {
+        if let Value::Number(num) = value {
+            return num.as_i64();
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.remove_point(id)
+    }
+}
+
+impl ValueIndexer<UuidIntType> for MmapMapIndex<UuidIntType> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<UuidIntType>) -> OperationResult<()> {
+        self.add_many_to_map(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<UuidIntType> {
+        if let Value::String(keyword) = value {
+            return parse_uuid(keyword);
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.remove_point(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::types::MatchPrefix;
+
+    const FIELD_NAME: &str = "test";
+
+    fn build_index<N: MmapKey>(data: &[Vec<N>], path: &Path) -> MmapMapIndex<N>
+    where
+        N: Clone,
+    {
+        let mut index = MmapMapIndex::<N>::new(path, FIELD_NAME);
+        index.recreate().unwrap();
+        for (idx, values) in data.iter().enumerate() {
+            index
+                .add_many_to_map(idx as PointOffsetType, values.clone())
+                .unwrap();
+        }
+        index.finalize().unwrap();
+        index
+    }
+
+    #[test]
+    fn test_mmap_keyword_index() {
+        let data = vec![
+            vec!["AABB".to_string(), "UUFF".to_string()],
+            vec!["AACC".to_string()],
+            vec![],
+            vec!["UUFF".to_string(), "AABB".to_string(), "AABB".to_string()],
+        ];
+        let tmp_dir = TempDir::new("mmap_map_index").unwrap();
+        let mut index = build_index(&data, tmp_dir.path());
+        assert_eq!(index.count_indexed_points(), 3);
+
+        let points = |index: &MmapMapIndex<String>, condition: FieldCondition| {
+            index.filter(&condition).unwrap().collect::<HashSet<_>>()
+        };
+
+        let condition = FieldCondition::new_match(FIELD_NAME.to_owned(), "AABB".to_owned().into());
+        assert_eq!(points(&index, condition.clone()), HashSet::from([0, 3]));
+        assert_eq!(index.estimate_cardinality(&condition).unwrap().exp, 2);
+
+        let prefix_condition = FieldCondition::new_match(
+            FIELD_NAME.to_owned(),
+            Match::Prefix(MatchPrefix {
+                prefix: "AA".to_owned(),
+            }),
+        );
+        assert_eq!(
+            points(&index, prefix_condition.clone()),
+            HashSet::from([0, 1, 3])
+        );
+
+        let except_condition = FieldCondition::new_match(
+            FIELD_NAME.to_owned(),
+            Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(BTreeSet::from([
+                    "AABB".to_owned(),
+                    "UUFF".to_owned(),
+                ])),
+            }),
+        );
+        assert_eq!(points(&index, except_condition), HashSet::from([1]));
+
+        index.remove_point(0).unwrap();
+        assert!(index.get_values(0).is_none());
+        assert_eq!(points(&index, condition.clone()), HashSet::from([3]));
+        assert_eq!(index.count_indexed_points(), 2);
+        index.flush().unwrap();
+        drop(index);
+
+        let mut index = MmapMapIndex::<String>::new(tmp_dir.path(), FIELD_NAME);
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 2);
+        assert_eq!(points(&index, condition), HashSet::from([3]));
+        assert_eq!(points(&index, prefix_condition), HashSet::from([1, 3]));
+        let values: HashSet<_> = index.get_values(3).unwrap().into_iter().collect();
+        assert_eq!(
+            values,
+            HashSet::from(["AABB".to_string(), "UUFF".to_string()])
+        );
+        assert!(index.add_many_to_map(4, vec!["AABB".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn test_mmap_int_map_index() {
+        let data = vec![vec![-1, 2], vec![2], vec![3, 5, 7], vec![-1]];
+        let tmp_dir = TempDir::new("mmap_map_index").unwrap();
+        let index = build_index::<IntPayloadType>(&data, tmp_dir.path());
+
+        let condition = FieldCondition::new_match(FIELD_NAME.to_owned(), 2.into());
+        let points: HashSet<_> = index.filter(&condition).unwrap().collect();
+        assert_eq!(points, HashSet::from([0, 1]));
+        assert_eq!(
+            index.values_with_counts().collect::<Vec<_>>(),
+            vec![(-1, 2), (2, 2), (3, 1), (5, 1), (7, 1)]
+        );
+        assert_eq!(index.get_values(2).unwrap(), vec![3, 5, 7]);
+    }
+}
@@ -0,0 +1,519 @@
+use std::collections::BTreeMap;
+use std::fs::remove_dir_all;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::histogram::{Histogram, Point};
+use crate::index::field_index::mmap_postings::MmapPostings;
+use crate::index::field_index::numeric_index::{
+    encoded_range_bounds, payload_blocks, range_cardinality, FromRangeValue, KeyDecoder,
+    KeyEncoder, ToRangeValue, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION,
+};
+use crate::index::field_index::{
+    CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
+};
+use crate::types::{
+    parse_datetime_seconds, DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType,
+    NumericIndexStats, PayloadKeyType, PointOffsetType, QuantileValue, INDEX_STATS_QUANTILES,
+};
+
+/// On-disk variant of `NumericIndex`, used in non-appendable segments.
+/// Each (value, point) pair is stored as a separate key, same as in the in-memory map.
+/// The histogram is kept in memory and built on load.
+/// Removed points are not subtracted from the histogram, so estimations could be a bit higher.
+pub struct MmapNumericIndex<T: KeyEncoder + KeyDecoder + FromRangeValue + Clone> {
+    path: PathBuf,
+    /// Keys of the values, added before the postings are stored
+    pending: BTreeMap<Vec<u8>, Vec<PointOffsetType>>,
+    postings: Option<MmapPostings>,
+    histogram: Histogram,
+    points_count: usize,
+    values_count: usize,
+    max_values_per_point: usize,
+    _value: PhantomData<T>,
+}
+
+impl<T: KeyEncoder + KeyDecoder + FromRangeValue + ToRangeValue + Clone> MmapNumericIndex<T> {
+    pub fn new(path: &Path, field: &str) -> Self {
+        Self {
+            path: path.join(format!("{field}_numeric")),
+            pending: Default::default(),
+            postings: None,
+            histogram: Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION),
+            points_count: 0,
+            values_count: 0,
+            max_values_per_point: 1,
+            _value: Default::default(),
+        }
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        if self.path.exists() {
+            remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    pub fn add_many_to_list(
+        &mut self,
+        idx: PointOffsetType,
+        values: impl IntoIterator<Item = T>,
+    ) -> OperationResult<()> {
+        if self.postings.is_some() {
+            return Err(OperationError::service_error(
+                "Index add error: on-disk index could not be updated",
+            ));
+        }
+        for value in values {
+            self.pending.insert(value.encode_key(idx), vec![idx]);
+        }
+        Ok(())
+    }
+
+    pub fn load(&mut self) -> OperationResult<bool> {
+        self.postings = MmapPostings::open(&self.path)?;
+        let postings = match &self.postings {
+            Some(postings) => postings,
+            None => return Ok(false),
+        };
+
+        self.points_count = 0;
+        self.values_count = 0;
+        self.max_values_per_point = 1;
+        for idx in 0..postings.total_points() as PointOffsetType {
+            let values_count = postings.point_keys(idx).map_or(0, |keys| keys.count());
+            if values_count > 0 {
+                self.points_count += 1;
+                self.values_count += values_count;
+                self.max_values_per_point = self.max_values_per_point.max(values_count);
+            }
+        }
+
+        // Keys are sorted, so the neighbors of the inserted points are among the previous ones
+        let points: Vec<Point> = (0..postings.keys_count())
+            .filter(|key_idx| postings.points_count(*key_idx) > 0)
+            .map(|key_idx| Self::key_to_histogram_point(postings.key(key_idx)))
+            .collect();
+        self.histogram = Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION);
+        for (position, point) in points.iter().enumerate() {
+            let inserted = &points[..=position];
+            self.histogram.insert(
+                point.clone(),
+                |x| {
+                    let left = inserted.partition_point(|point| point < x);
+                    left.checked_sub(1).map(|left| inserted[left].clone())
+                },
+                |x| {
+                    let right = inserted.partition_point(|point| point <= x);
+                    inserted.get(right).cloned()
+                },
+            );
+        }
+        Ok(true)
+    }
+
+    /// Store values, added so far, on disk. The index could not be extended after that.
+    pub fn finalize(&mut self) -> OperationResult<()> {
+        let pending = std::mem::take(&mut self.pending);
+        MmapPostings::create(&self.path, &pending)?;
+        self.load()?;
+        Ok(())
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        match &self.postings {
+            Some(postings) => postings.flush(),
+            None => Ok(()),
+        }
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        match &mut self.postings {
+            Some(postings) => {
+                let removed_keys = postings.remove_point(idx);
+                if !removed_keys.is_empty() {
+                    self.points_count -= 1;
+                    self.values_count -= removed_keys.len();
+                }
+            }
+            None => self.pending.retain(|_key, points| points[0] != idx),
+        }
+        Ok(())
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<Vec<T>> {
+        let postings = self.postings.as_ref()?;
+        let keys = postings.point_keys(idx)?;
+        Some(
+            keys.map(|key_idx| T::decode_key(postings.key(key_idx)).1)
+                .collect(),
+        )
+    }
+
+    /// Distribution of the indexed values, `None` if the index is empty
+    pub fn numeric_stats(&self) -> Option<NumericIndexStats> {
+        let min = self.values().next()?;
+        let max = self.values().next_back()?;
+        let quantiles = INDEX_STATS_QUANTILES
+            .iter()
+            .filter_map(|&quantile| {
+                let value = self.histogram.quantile(quantile)?;
+                Some(QuantileValue { quantile, value })
+            })
+            .collect();
+        Some(NumericIndexStats {
+            values_count: self.values_count,
+            min,
+            max,
+            quantiles,
+        })
+    }
+
+    /// Rough estimation of memory, used by the index structures
+    pub fn ram_usage_bytes(&self) -> usize {
+        let pending_usage = self
+            .pending
+            .keys()
+            .map(|key| size_of::<Vec<u8>>() + key.len() + size_of::<PointOffsetType>())
+            .sum::<usize>();
+        let postings_usage = self
+            .postings
+            .as_ref()
+            .map_or(0, |postings| postings.ram_usage_bytes());
+        pending_usage + postings_usage
+    }
+
+    pub fn disk_usage_bytes(&self) -> usize {
+        self.postings
+            .as_ref()
+            .map_or(0, |postings| postings.disk_usage_bytes())
+    }
+
+    /// Positions of the keys within the bounds
+    fn key_positions(&self, from: Bound<&[u8]>, to: Bound<&[u8]>) -> std::ops::Range<usize> {
+        match &self.postings {
+            Some(postings) => postings.lower_bound(from)..postings.upper_bound(to),
+            None => 0..0,
+        }
+    }
+
+    /// Not deleted (value, point) pairs of the key positions in ascending order
+    fn pairs(
+        &self,
+        positions: std::ops::Range<usize>,
+    ) -> impl DoubleEndedIterator<Item = (f64, PointOffsetType)> + '_ {
+        positions.filter_map(move |key_idx| {
+            let postings = self.postings.as_ref()?;
+            let (idx, value) = T::decode_key(postings.key(key_idx));
+            if postings.is_deleted(idx) {
+                None
+            } else {
+                Some((T::to_range(value), idx))
+            }
+        })
+    }
+
+    /// Iterate over all indexed values in ascending order
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = f64> + '_ {
+        self.pairs(self.key_positions(Bound::Unbounded, Bound::Unbounded))
+            .map(|(value, _idx)| value)
+    }
+
+    /// Iterate over points ordered by value, starting from the `start_from` value.
+    /// Each point is listed once: by its smallest value in ascending order
+    /// and by its largest value in descending order.
+    pub fn ordered_points(
+        &self,
+        start_from: Option<f64>,
+        descending: bool,
+    ) -> Box<dyn Iterator<Item = (f64, PointOffsetType)> + '_> {
+        let is_point_order_value = move |(value, idx): &(f64, PointOffsetType)| {
+            let values = match self.get_values(*idx) {
+                Some(values) => values.into_iter().map(T::to_range),
+                None => return false,
+            };
+            let order_value = if descending {
+                values.fold(f64::NEG_INFINITY, f64::max)
+            } else {
+                values.fold(f64::INFINITY, f64::min)
+            };
+            order_value == *value
+        };
+        match (start_from, descending) {
+            (None, false) => Box::new(
+                self.pairs(self.key_positions(Bound::Unbounded, Bound::Unbounded))
+                    .filter(is_point_order_value),
+            ),
+            (None, true) => Box::new(
+                self.pairs(self.key_positions(Bound::Unbounded, Bound::Unbounded))
+                    .rev()
+                    .filter(is_point_order_value),
+            ),
+            (Some(start_from), false) => {
+                let start_key = T::from_range(start_from).encode_key(PointOffsetType::MIN);
+                Box::new(
+                    self.pairs(self.key_positions(Bound::Included(&start_key), Bound::Unbounded))
+                        .filter(is_point_order_value),
+                )
+            }
+            (Some(start_from), true) => {
+                let start_key = T::from_range(start_from).encode_key(PointOffsetType::MAX);
+                Box::new(
+                    self.pairs(self.key_positions(Bound::Unbounded, Bound::Included(&start_key)))
+                        .rev()
+                        .filter(is_point_order_value),
+                )
+            }
+        }
+    }
+
+    fn key_to_histogram_point(key: &[u8]) -> Point {
+        let (decoded_idx, decoded_val) = T::decode_key(key);
+        Point {
+            val: T::to_range(decoded_val),
+            idx: decoded_idx as usize,
+        }
+    }
+}
+
+fn as_slice_bound(bound: &Bound<Vec<u8>>) -> Bound<&[u8]> {
+    match bound {
+        Bound::Included(key) => Bound::Included(key.as_slice()),
+        Bound::Excluded(key) => Bound::Excluded(key.as_slice()),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+impl<T: KeyEncoder + KeyDecoder + FromRangeValue + ToRangeValue + Clone> PayloadFieldIndex
+    for MmapNumericIndex<T>
+{
+    fn indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        MmapNumericIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.recreate()
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        MmapNumericIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        let cond_range = condition.range.as_ref()?;
+        let (start_bound, end_bound) = encoded_range_bounds::<T>(cond_range);
+        let positions =
+            self.key_positions(as_slice_bound(&start_bound), as_slice_bound(&end_bound));
+        Some(Box::new(self.pairs(positions).map(|(_value, idx)| idx)))
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        condition.range.as_ref().map(|range| {
+            let mut cardinality = range_cardinality(
+                &self.histogram,
+                range,
+                self.points_count,
+                self.values_count,
+                self.max_values_per_point,
+            );
+            cardinality
+                .primary_clauses
+                .push(PrimaryCondition::Condition(condition.clone()));
+            cardinality
+        })
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(
+            payload_blocks(
+                &self.histogram,
+                self.points_count,
+                self.values_count,
+                self.max_values_per_point,
+                threshold,
+                key,
+            )
+            .into_iter(),
+        )
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+}
+
+impl ValueIndexer<IntPayloadType> for MmapNumericIndex<IntPayloadType> {
+    fn add_many(
+        &mut self,
+        id: PointOffsetType,
+        values: Vec<IntPayloadType>,
+    ) -> OperationResult<()> {
+        self.add_many_to_list(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<IntPayloadType> {
+        if let Value::Number(num) = value {
+            return num.as_i64();
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        MmapNumericIndex::remove_point(self, id)
+    }
+}
+
+impl ValueIndexer<FloatPayloadType> for MmapNumericIndex<FloatPayloadType> {
+    fn add_many(
+        &mut self,
+        id: PointOffsetType,
+        values: Vec<FloatPayloadType>,
+    ) -> OperationResult<()> {
+        self.add_many_to_list(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<FloatPayloadType> {
+        if let Value::Number(num) = value {
+            return num.as_f64();
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        MmapNumericIndex::remove_point(self, id)
+    }
+}
+
+impl ValueIndexer<DateTimePayloadType> for MmapNumericIndex<DateTimePayloadType> {
+    fn add_many(
+        &mut self,
+        id: PointOffsetType,
+        values: Vec<DateTimePayloadType>,
+    ) -> OperationResult<()> {
+        self.add_many_to_list(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<DateTimePayloadType> {
+        if let Value::String(string) = value {
+            return parse_datetime_seconds(string).map(DateTimePayloadType);
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        MmapNumericIndex::remove_point(self, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::prelude::StdRng;
+    use rand::{Rng, SeedableRng};
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::types::Range;
+
+    const COLUMN_NAME: &str = "test";
+
+    #[test]
+    fn test_mmap_numeric_index() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let tmp_dir = TempDir::new("test_mmap_numeric_index").unwrap();
+        let mut index: MmapNumericIndex<FloatPayloadType> =
+            MmapNumericIndex::new(tmp_dir.path(), COLUMN_NAME);
+        index.recreate().unwrap();
+
+        let values: Vec<Vec<f64>> = (0..1000)
+            .map(|_| (0..2).map(|_| rng.gen_range(0.0..100.0)).collect())
+            .collect();
+        for (idx, point_values) in values.iter().enumerate() {
+            index
+                .add_many_to_list(idx as PointOffsetType, point_values.clone())
+                .unwrap();
+        }
+        index.finalize().unwrap();
+        assert_eq!(index.count_indexed_points(), 1000);
+
+        let range = Range {
+            lt: Some(30.0),
+            gt: None,
+            gte: Some(20.0),
+            lte: None,
+        };
+        let condition = FieldCondition::new_range(COLUMN_NAME.to_owned(), range);
+        let expected = |removed: &[PointOffsetType]| {
+            values
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| !removed.contains(&(*idx as PointOffsetType)))
+                .filter(|(_, point_values)| {
+                    point_values
+                        .iter()
+                        .any(|value| (20.0..30.0).contains(value))
+                })
+                .map(|(idx, _)| idx as PointOffsetType)
+                .collect_vec()
+        };
+
+        let points = index
+            .filter(&condition)
+            .unwrap()
+            .unique()
+            .sorted()
+            .collect_vec();
+        assert_eq!(points, expected(&[]));
+        let estimation = index.estimate_cardinality(&condition).unwrap();
+        assert!(estimation.min <= points.len());
+        assert!(estimation.max >= points.len());
+
+        let removed = points[..10].to_vec();
+        for idx in &removed {
+            index.remove_point(*idx).unwrap();
+        }
+        assert!(index.get_values(removed[0]).is_none());
+        assert_eq!(index.count_indexed_points(), 990);
+        index.flush().unwrap();
+        drop(index);
+
+        let mut index: MmapNumericIndex<FloatPayloadType> =
+            MmapNumericIndex::new(tmp_dir.path(), COLUMN_NAME);
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 990);
+        let points = index
+            .filter(&condition)
+            .unwrap()
+            .unique()
+            .sorted()
+            .collect_vec();
+        assert_eq!(points, expected(&removed));
+        let sorted_values = |values: Vec<f64>| {
+            values
+                .into_iter()
+                .sorted_by(|a, b| a.partial_cmp(b).unwrap())
+                .collect_vec()
+        };
+        assert_eq!(
+            sorted_values(index.get_values(999).unwrap()),
+            sorted_values(values[999].clone())
+        );
+        assert_eq!(index.numeric_stats().unwrap().values_count, 1980);
+    }
+}
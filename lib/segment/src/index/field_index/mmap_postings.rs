@@ -0,0 +1,583 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+
+use atomicwrites::{AllowOverwrite, AtomicFile};
+use memmap::{Mmap, MmapMut};
+
+use crate::common::error_logging::LogError;
+use crate::common::mmap_ops::{open_read, open_write};
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::key_encoding::{
+    decode_f64_ascending, decode_i64_ascending, encode_f64_ascending, encode_i64_ascending,
+};
+use crate::types::{
+    DateTimePayloadType, FloatPayloadType, IntPayloadType, PointOffsetType, UuidIntType,
+};
+
+const RECORDS_HEADER: &[u8; 4] = b"recs";
+const DELETED_HEADER: &[u8; 4] = b"drop";
+const HEADER_SIZE: usize = 4;
+/// Header and the number of records
+const RECORDS_OFFSETS_START: usize = HEADER_SIZE + size_of::<u64>();
+/// Header, the number of indexed points and the number of values
+const DELETED_FLAGS_START: usize = HEADER_SIZE + 2 * size_of::<u64>();
+
+const KEYS_FILE: &str = "keys.dat";
+const POSTINGS_FILE: &str = "postings.dat";
+const POINT_KEYS_FILE: &str = "point_keys.dat";
+const DELETED_FILE: &str = "deleted.dat";
+
+/// Value, which could be used as a key of the memory-mapped postings.
+/// Encoded keys are compared as bytes, so the encoding must preserve the order of values.
+pub trait MmapKey: Sized {
+    fn encode_mmap_key(&self) -> Vec<u8>;
+
+    fn decode_mmap_key(bytes: &[u8]) -> OperationResult<Self>;
+}
+
+/// Bytes of the fixed-size key, error if the stored key has another size
+fn fixed_size_key<const SIZE: usize>(bytes: &[u8]) -> OperationResult<[u8; SIZE]> {
+    bytes.try_into().map_err(|_| {
+        OperationError::service_error(&format!(
+            "Index key decoding error: expected {} bytes, got {}",
+            SIZE,
+            bytes.len()
+        ))
+    })
+}
+
+impl MmapKey for String {
+    fn encode_mmap_key(&self) -> Vec<u8> {
+        // Byte order of UTF-8 strings is the same as the order of `String`
+        self.as_bytes().to_vec()
+    }
+
+    fn decode_mmap_key(bytes: &[u8]) -> OperationResult<Self> {
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+impl MmapKey for IntPayloadType {
+    fn encode_mmap_key(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(size_of::<IntPayloadType>());
+        encode_i64_ascending(*self, &mut buf);
+        buf
+    }
+
+    fn decode_mmap_key(bytes: &[u8]) -> OperationResult<Self> {
+        let bytes = fixed_size_key::<{ size_of::<IntPayloadType>() }>(bytes)?;
+        Ok(decode_i64_ascending(&bytes))
+    }
+}
+
+impl MmapKey for UuidIntType {
+    fn encode_mmap_key(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+
+    fn decode_mmap_key(bytes: &[u8]) -> OperationResult<Self> {
+        Ok(UuidIntType::from_be_bytes(fixed_size_key(bytes)?))
+    }
+}
+
+impl MmapKey for FloatPayloadType {
+    fn encode_mmap_key(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(size_of::<FloatPayloadType>() + 1);
+        encode_f64_ascending(*self, &mut buf);
+        buf
+    }
+
+    fn decode_mmap_key(bytes: &[u8]) -> OperationResult<Self> {
+        // Type tag and the value
+        let bytes = fixed_size_key::<{ size_of::<FloatPayloadType>() + 1 }>(bytes)?;
+        Ok(decode_f64_ascending(&bytes))
+    }
+}
+
+impl MmapKey for DateTimePayloadType {
+    fn encode_mmap_key(&self) -> Vec<u8> {
+        self.0.encode_mmap_key()
+    }
+
+    fn decode_mmap_key(bytes: &[u8]) -> OperationResult<Self> {
+        FloatPayloadType::decode_mmap_key(bytes).map(DateTimePayloadType)
+    }
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + size_of::<u64>()].try_into().unwrap())
+}
+
+/// Decode a record of little-endian `u32` numbers
+pub fn read_u32_record(
+    record: &[u8],
+) -> impl DoubleEndedIterator<Item = u32> + ExactSizeIterator + '_ {
+    record
+        .chunks_exact(size_of::<u32>())
+        .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+}
+
+/// Encode a record of little-endian `u32` numbers
+pub fn u32_record(values: impl IntoIterator<Item = u32>) -> Vec<u8> {
+    values.into_iter().flat_map(u32::to_le_bytes).collect()
+}
+
+/// Immutable sequence of variable-length byte records in a memory-mapped file.
+///
+/// ```text
+/// ┌────────┬───────────┬──────────────────────────┬──────────────┐
+/// │ header │ count: u64│ offsets: (count + 1) u64 │ records data │
+/// └────────┴───────────┴──────────────────────────┴──────────────┘
+/// ```
+pub struct MmapRecords {
+    mmap: Mmap,
+    len: usize,
+}
+
+impl MmapRecords {
+    pub fn create<R: AsRef<[u8]>>(path: &Path, records: &[R]) -> OperationResult<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(RECORDS_HEADER)?;
+        writer.write_all(&(records.len() as u64).to_le_bytes())?;
+        let mut offset = 0u64;
+        writer.write_all(&offset.to_le_bytes())?;
+        for record in records {
+            offset += record.as_ref().len() as u64;
+            writer.write_all(&offset.to_le_bytes())?;
+        }
+        for record in records {
+            writer.write_all(record.as_ref())?;
+        }
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        Ok(())
+    }
+
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let mmap = open_read(path).describe("Open mmap for reading")?;
+        if mmap.len() < RECORDS_OFFSETS_START || &mmap[..HEADER_SIZE] != RECORDS_HEADER {
+            return Err(OperationError::service_error(&format!(
+                "Index file {} is corrupted",
+                path.display()
+            )));
+        }
+        let len = read_u64(&mmap, HEADER_SIZE) as usize;
+        Ok(MmapRecords { mmap, len })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, idx: usize) -> &[u8] {
+        let data_start = RECORDS_OFFSETS_START + (self.len + 1) * size_of::<u64>();
+        let offset_pos = RECORDS_OFFSETS_START + idx * size_of::<u64>();
+        let start = read_u64(&self.mmap, offset_pos) as usize;
+        let end = read_u64(&self.mmap, offset_pos + size_of::<u64>()) as usize;
+        &self.mmap[data_start + start..data_start + end]
+    }
+
+    /// Index of the first record, for which `pred` is false.
+    /// Records must be partitioned by `pred`, e.g. sorted records by "less than".
+    pub fn partition_point(&self, pred: impl Fn(&[u8]) -> bool) -> usize {
+        let (mut left, mut right) = (0, self.len);
+        while left < right {
+            let mid = left + (right - left) / 2;
+            if pred(self.get(mid)) {
+                left = mid + 1;
+            } else {
+                right = mid;
+            }
+        }
+        left
+    }
+
+    /// Position of the `record` among the sorted records
+    pub fn find(&self, record: &[u8]) -> Option<usize> {
+        let idx = self.partition_point(|current| current < record);
+        if idx < self.len && self.get(idx) == record {
+            Some(idx)
+        } else {
+            None
+        }
+    }
+
+    pub fn disk_usage_bytes(&self) -> usize {
+        self.mmap.len()
+    }
+}
+
+/// Mem-mapped file with a soft-delete flag for each point.
+/// Counters of the not deleted points are stored in the header,
+/// so that they are not recounted on load.
+///
+/// ```text
+/// ┌────────┬──────────────────────┬───────────────────┬──────────────────────┐
+/// │ header │ indexed_points: u64  │ values_count: u64 │ flags: u8 per point  │
+/// └────────┴──────────────────────┴───────────────────┴──────────────────────┘
+/// ```
+pub struct MmapDeletedFlags {
+    mmap: MmapMut,
+}
+
+impl MmapDeletedFlags {
+    /// File is written atomically, so it is either missing or complete after a crash
+    pub fn create(
+        path: &Path,
+        points_count: usize,
+        indexed_points: usize,
+        values_count: usize,
+    ) -> OperationResult<()> {
+        let af = AtomicFile::new(path, AllowOverwrite);
+        af.write(|f| {
+            let mut writer = BufWriter::new(f);
+            writer.write_all(DELETED_HEADER)?;
+            writer.write_all(&(indexed_points as u64).to_le_bytes())?;
+            writer.write_all(&(values_count as u64).to_le_bytes())?;
+            writer.write_all(&vec![0u8; points_count])?;
+            writer.flush()
+        })?;
+        Ok(())
+    }
+
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let mmap = open_write(path).describe("Open mmap for writing")?;
+        if mmap.len() < DELETED_FLAGS_START || &mmap[..HEADER_SIZE] != DELETED_HEADER {
+            return Err(OperationError::service_error(&format!(
+                "Index file {} is corrupted",
+                path.display()
+            )));
+        }
+        Ok(MmapDeletedFlags { mmap })
+    }
+
+    /// Number of not deleted points, which have at least one value
+    pub fn indexed_points(&self) -> usize {
+        read_u64(&self.mmap, HEADER_SIZE) as usize
+    }
+
+    /// Number of (point, value) pairs of not deleted points
+    pub fn values_count(&self) -> usize {
+        read_u64(&self.mmap, HEADER_SIZE + size_of::<u64>()) as usize
+    }
+
+    fn write_u64(&mut self, offset: usize, value: usize) {
+        self.mmap[offset..offset + size_of::<u64>()].copy_from_slice(&(value as u64).to_le_bytes());
+    }
+
+    pub fn deleted(&self, idx: PointOffsetType) -> bool {
+        self.mmap
+            .get(DELETED_FLAGS_START + idx as usize)
+            .map_or(false, |flag| *flag > 0)
+    }
+
+    /// Mark the point with `values` number of values as deleted, updating the counters.
+    /// Returns `true` if the point was not deleted before
+    pub fn delete(&mut self, idx: PointOffsetType, values: usize) -> bool {
+        match self.mmap.get_mut(DELETED_FLAGS_START + idx as usize) {
+            Some(flag) if *flag == 0 => *flag = 1,
+            _ => return false,
+        }
+        if values > 0 {
+            self.write_u64(HEADER_SIZE, self.indexed_points().saturating_sub(1));
+            self.write_u64(
+                HEADER_SIZE + size_of::<u64>(),
+                self.values_count().saturating_sub(values),
+            );
+        }
+        true
+    }
+
+    /// Iterate over deleted points
+    pub fn iter_deleted(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.mmap[DELETED_FLAGS_START..]
+            .iter()
+            .enumerate()
+            .filter(|(_, flag)| **flag > 0)
+            .map(|(idx, _)| idx as PointOffsetType)
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        self.mmap.flush()?;
+        Ok(())
+    }
+
+    pub fn disk_usage_bytes(&self) -> usize {
+        self.mmap.len()
+    }
+}
+
+/// Immutable mapping of the sorted keys to the sorted lists of points, stored in memory-mapped
+/// files of the `path` directory. Keys are encoded with `MmapKey`.
+/// Removed points are only marked as deleted and skipped by the iterators.
+pub struct MmapPostings {
+    path: PathBuf,
+    /// Sorted encoded keys
+    keys: MmapRecords,
+    /// Points of each key in ascending order
+    postings: MmapRecords,
+    /// Positions of the keys of each point
+    point_keys: MmapRecords,
+    deleted: MmapDeletedFlags,
+    /// Number of deleted points in the posting of each key position
+    deleted_per_key: HashMap<usize, usize>,
+}
+
+impl MmapPostings {
+    /// Write postings into the `path` directory and open them.
+    /// Points of each posting must be sorted and unique.
+    pub fn create(
+        path: &Path,
+        postings: &BTreeMap<Vec<u8>, Vec<PointOffsetType>>,
+    ) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let mut point_keys: Vec<Vec<u8>> = vec![];
+        for (key_idx, points) in postings.values().enumerate() {
+            for point in points {
+                let point = *point as usize;
+                if point_keys.len() <= point {
+                    point_keys.resize(point + 1, vec![]);
+                }
+                point_keys[point].extend((key_idx as u32).to_le_bytes());
+            }
+        }
+        let keys = postings.keys().collect::<Vec<_>>();
+        let posting_records = postings
+            .values()
+            .map(|points| u32_record(points.iter().copied()))
+            .collect::<Vec<_>>();
+
+        MmapRecords::create(&path.join(KEYS_FILE), &keys)?;
+        MmapRecords::create(&path.join(POSTINGS_FILE), &posting_records)?;
+        MmapRecords::create(&path.join(POINT_KEYS_FILE), &point_keys)?;
+        // Data files and their directory entries must be durable before the deleted flags,
+        // which are written last and mark complete postings
+        File::open(path)?.sync_all()?;
+        let indexed_points = point_keys.iter().filter(|keys| !keys.is_empty()).count();
+        let values_count = postings.values().map(Vec::len).sum();
+        MmapDeletedFlags::create(
+            &path.join(DELETED_FILE),
+            point_keys.len(),
+            indexed_points,
+            values_count,
+        )?;
+
+        Self::open(path)?.ok_or_else(|| {
+            OperationError::service_error(&format!("Failed to create index in {}", path.display()))
+        })
+    }
+
+    /// Open postings of the `path` directory, `None` if they were not created
+    pub fn open(path: &Path) -> OperationResult<Option<Self>> {
+        let deleted_path = path.join(DELETED_FILE);
+        if !deleted_path.exists() {
+            return Ok(None);
+        }
+        let deleted = MmapDeletedFlags::open(&deleted_path)?;
+        let mut postings = MmapPostings {
+            path: path.to_owned(),
+            keys: MmapRecords::open(&path.join(KEYS_FILE))?,
+            postings: MmapRecords::open(&path.join(POSTINGS_FILE))?,
+            point_keys: MmapRecords::open(&path.join(POINT_KEYS_FILE))?,
+            deleted,
+            deleted_per_key: Default::default(),
+        };
+        let deleted_keys = postings
+            .deleted
+            .iter_deleted()
+            .flat_map(|idx| postings.raw_point_keys(idx))
+            .collect::<Vec<_>>();
+        for key_idx in deleted_keys {
+            *postings.deleted_per_key.entry(key_idx).or_default() += 1;
+        }
+        Ok(Some(postings))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of the stored keys
+    pub fn keys_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn key(&self, key_idx: usize) -> &[u8] {
+        self.keys.get(key_idx)
+    }
+
+    pub fn find(&self, key: &[u8]) -> Option<usize> {
+        self.keys.find(key)
+    }
+
+    /// Position of the first key, which satisfies the lower `bound`
+    pub fn lower_bound(&self, bound: Bound<&[u8]>) -> usize {
+        match bound {
+            Bound::Included(key) => self.keys.partition_point(|current| current < key),
+            Bound::Excluded(key) => self.keys.partition_point(|current| current <= key),
+            Bound::Unbounded => 0,
+        }
+    }
+
+    /// Position after the last key, which satisfies the upper `bound`
+    pub fn upper_bound(&self, bound: Bound<&[u8]>) -> usize {
+        match bound {
+            Bound::Included(key) => self.keys.partition_point(|current| current <= key),
+            Bound::Excluded(key) => self.keys.partition_point(|current| current < key),
+            Bound::Unbounded => self.keys.len(),
+        }
+    }
+
+    /// Not deleted points of the key in ascending order
+    pub fn points(&self, key_idx: usize) -> impl DoubleEndedIterator<Item = PointOffsetType> + '_ {
+        read_u32_record(self.postings.get(key_idx)).filter(|idx| !self.deleted.deleted(*idx))
+    }
+
+    /// Number of not deleted points of the key
+    pub fn points_count(&self, key_idx: usize) -> usize {
+        let raw_count = self.postings.get(key_idx).len() / size_of::<PointOffsetType>();
+        raw_count - self.deleted_per_key.get(&key_idx).copied().unwrap_or(0)
+    }
+
+    /// Number of points, including deleted and points without keys
+    pub fn total_points(&self) -> usize {
+        self.point_keys.len()
+    }
+
+    /// Number of not deleted points, which have at least one key
+    pub fn indexed_points(&self) -> usize {
+        self.deleted.indexed_points()
+    }
+
+    /// Number of (point, key) pairs of not deleted points
+    pub fn values_count(&self) -> usize {
+        self.deleted.values_count()
+    }
+
+    fn raw_point_keys(&self, idx: PointOffsetType) -> impl Iterator<Item = usize> + '_ {
+        let record = if (idx as usize) < self.point_keys.len() {
+            self.point_keys.get(idx as usize)
+        } else {
+            &[]
+        };
+        read_u32_record(record).map(|key_idx| key_idx as usize)
+    }
+
+    /// Key positions of the point, `None` if the point is deleted or was not indexed
+    pub fn point_keys(&self, idx: PointOffsetType) -> Option<impl Iterator<Item = usize> + '_> {
+        if (idx as usize) >= self.point_keys.len() || self.deleted.deleted(idx) {
+            return None;
+        }
+        Some(self.raw_point_keys(idx))
+    }
+
+    pub fn is_deleted(&self, idx: PointOffsetType) -> bool {
+        self.deleted.deleted(idx)
+    }
+
+    /// Mark the point as deleted.
+    /// Returns key positions of the point, empty if it is already deleted.
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> Vec<usize> {
+        if (idx as usize) >= self.point_keys.len() || self.deleted.deleted(idx) {
+            return vec![];
+        }
+        let key_indexes = self.raw_point_keys(idx).collect::<Vec<_>>();
+        self.deleted.delete(idx, key_indexes.len());
+        for key_idx in &key_indexes {
+            *self.deleted_per_key.entry(*key_idx).or_default() += 1;
+        }
+        key_indexes
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        self.deleted.flush()
+    }
+
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.deleted_per_key.len() * 2 * size_of::<usize>()
+    }
+
+    pub fn disk_usage_bytes(&self) -> usize {
+        self.keys.disk_usage_bytes()
+            + self.postings.disk_usage_bytes()
+            + self.point_keys.disk_usage_bytes()
+            + self.deleted.disk_usage_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use tempdir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_mmap_postings() {
+        let tmp_dir = TempDir::new("mmap_postings").unwrap();
+        let postings: BTreeMap<Vec<u8>, Vec<PointOffsetType>> = [
+            (-5i64, vec![1, 4]),
+            (0, vec![0]),
+            (3, vec![1, 2, 5]),
+            (100, vec![3]),
+        ]
+        .into_iter()
+        .map(|(key, points)| (key.encode_mmap_key(), points))
+        .collect();
+
+        let mut index = MmapPostings::create(tmp_dir.path(), &postings).unwrap();
+        assert_eq!(index.keys_count(), 4);
+        assert_eq!(index.total_points(), 6);
+        assert_eq!(index.indexed_points(), 6);
+        assert_eq!(index.values_count(), 7);
+
+        let key_3 = index.find(&3i64.encode_mmap_key()).unwrap();
+        assert_eq!(
+            IntPayloadType::decode_mmap_key(index.key(key_3)).unwrap(),
+            3
+        );
+        assert_eq!(index.points(key_3).collect_vec(), vec![1, 2, 5]);
+        assert!(index.find(&4i64.encode_mmap_key()).is_none());
+
+        let from = 0i64.encode_mmap_key();
+        let to = 100i64.encode_mmap_key();
+        let range_keys =
+            index.lower_bound(Bound::Excluded(&from))..index.upper_bound(Bound::Included(&to));
+        let range_values = range_keys
+            .map(|key_idx| IntPayloadType::decode_mmap_key(index.key(key_idx)).unwrap())
+            .collect_vec();
+        assert_eq!(range_values, vec![3, 100]);
+
+        let point_values = index
+            .point_keys(1)
+            .unwrap()
+            .map(|key_idx| IntPayloadType::decode_mmap_key(index.key(key_idx)).unwrap())
+            .collect_vec();
+        assert_eq!(point_values, vec![-5, 3]);
+
+        assert_eq!(index.remove_point(1).len(), 2);
+        assert!(index.remove_point(1).is_empty());
+        assert!(index.point_keys(1).is_none());
+        assert_eq!(index.points(key_3).collect_vec(), vec![2, 5]);
+        assert_eq!(index.points_count(key_3), 2);
+        index.flush().unwrap();
+        drop(index);
+
+        let index = MmapPostings::open(tmp_dir.path()).unwrap().unwrap();
+        assert!(index.is_deleted(1));
+        assert_eq!(index.indexed_points(), 5);
+        assert_eq!(index.values_count(), 5);
+        assert!(UuidIntType::decode_mmap_key(index.key(key_3)).is_err());
+        assert_eq!(index.points_count(key_3), 2);
+        assert_eq!(index.points_count(0), 1);
+    }
+}
@@ -1,13 +1,23 @@
 use std::collections::HashSet;
 
-use crate::types::{FieldCondition, IsEmptyCondition, PointOffsetType};
+use crate::types::{
+    FieldCondition, FilterCardinality, HasFieldCondition, IsEmptyCondition, IsNullCondition,
+    PointOffsetType,
+};
 
+pub mod bool_index;
 mod field_index_base;
+pub mod full_text_index;
 pub mod geo_hash;
 pub mod geo_index;
-mod histogram;
+pub mod histogram;
 pub mod index_selector;
 pub mod map_index;
+pub mod mmap_geo_index;
+pub mod mmap_map_index;
+pub mod mmap_numeric_index;
+pub mod mmap_postings;
+pub mod null_index;
 pub mod numeric_index;
 mod stat_tools;
 
@@ -18,6 +28,8 @@ pub use field_index_base::*;
 pub enum PrimaryCondition {
     Condition(FieldCondition),
     IsEmpty(IsEmptyCondition),
+    IsNull(IsNullCondition),
+    HasField(HasFieldCondition),
     Ids(HashSet<PointOffsetType>),
 }
 
@@ -60,3 +72,13 @@ impl CardinalityEstimation {
         }
     }
 }
+
+impl From<&CardinalityEstimation> for FilterCardinality {
+    fn from(estimation: &CardinalityEstimation) -> Self {
+        FilterCardinality {
+            min: estimation.min,
+            exp: estimation.exp,
+            max: estimation.max,
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::BitVec;
+use rocksdb::{IteratorMode, DB};
+use serde_json::Value;
+
+use crate::common::rocksdb_operations::{cf_disk_usage_bytes, db_write_options, recreate_cf};
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex};
+use crate::types::{FieldCondition, PayloadKeyType, PointOffsetType};
+
+/// The field is present in the payload
+const FIELD_FLAG: u8 = 0b001;
+/// The value of the field is `NULL`
+const NULL_FLAG: u8 = 0b010;
+/// The value of the field is `NULL` or an empty array
+const EMPTY_FLAG: u8 = 0b100;
+
+/// Bitmap-based index of the presence of the field and its empty values.
+/// It is built for each indexed field along with the indexes of the values,
+/// so that `is_null`, `is_empty` and `has_field` conditions are checked without the payload.
+/// Values of other indexes can't tell about points, which have the field without indexed values.
+pub struct NullIndex {
+    fields: BitVec,
+    nulls: BitVec,
+    empties: BitVec,
+    fields_count: usize,
+    nulls_count: usize,
+    empties_count: usize,
+    store_cf_name: String,
+    db: Arc<AtomicRefCell<DB>>,
+}
+
+impl NullIndex {
+    pub fn new(db: Arc<AtomicRefCell<DB>>, field_name: &str) -> NullIndex {
+        NullIndex {
+            fields: BitVec::new(),
+            nulls: BitVec::new(),
+            empties: BitVec::new(),
+            fields_count: 0,
+            nulls_count: 0,
+            empties_count: 0,
+            store_cf_name: Self::storage_cf_name(field_name),
+            db,
+        }
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_null")
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        Ok(recreate_cf(self.db.clone(), &self.store_cf_name)?)
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        let db = self.db.clone();
+        let db_ref = db.borrow();
+        let cf_handle = if let Some(cf_handle) = db_ref.cf_handle(&self.store_cf_name) {
+            cf_handle
+        } else {
+            return Ok(false);
+        };
+        for (key, value) in db_ref.iterator_cf(cf_handle, IteratorMode::Start) {
+            const DECODE_ERR: &str = "Index db parsing error: wrong data format";
+            let idx = PointOffsetType::from_be_bytes(
+                key.as_ref()
+                    .try_into()
+                    .map_err(|_| OperationError::service_error(DECODE_ERR))?,
+            );
+            let flags = *value
+                .first()
+                .ok_or_else(|| OperationError::service_error(DECODE_ERR))?;
+            self.set_flags(idx, flags);
+        }
+        Ok(true)
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "Index flush error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        Ok(store_ref.flush_cf(cf_handle)?)
+    }
+
+    fn get_flags(&self, idx: PointOffsetType) -> u8 {
+        let idx = idx as usize;
+        let mut flags = 0;
+        if self.fields.get(idx).map_or(false, |bit| *bit) {
+            flags |= FIELD_FLAG;
+        }
+        if self.nulls.get(idx).map_or(false, |bit| *bit) {
+            flags |= NULL_FLAG;
+        }
+        if self.empties.get(idx).map_or(false, |bit| *bit) {
+            flags |= EMPTY_FLAG;
+        }
+        flags
+    }
+
+    /// Replace in-memory flags of the point, keeping counters consistent
+    fn set_flags(&mut self, idx: PointOffsetType, flags: u8) {
+        let old_flags = self.get_flags(idx);
+        let idx = idx as usize;
+        if self.fields.len() <= idx {
+            self.fields.resize(idx + 1, false);
+            self.nulls.resize(idx + 1, false);
+            self.empties.resize(idx + 1, false);
+        }
+        self.fields.set(idx, flags & FIELD_FLAG != 0);
+        self.nulls.set(idx, flags & NULL_FLAG != 0);
+        self.empties.set(idx, flags & EMPTY_FLAG != 0);
+
+        self.fields_count += (flags & FIELD_FLAG != 0) as usize;
+        self.fields_count -= (old_flags & FIELD_FLAG != 0) as usize;
+        self.nulls_count += (flags & NULL_FLAG != 0) as usize;
+        self.nulls_count -= (old_flags & NULL_FLAG != 0) as usize;
+        self.empties_count += (flags & EMPTY_FLAG != 0) as usize;
+        self.empties_count -= (old_flags & EMPTY_FLAG != 0) as usize;
+    }
+
+    /// The field is present in the payload of the point, `NULL` values included
+    pub fn has_field(&self, idx: PointOffsetType) -> bool {
+        self.get_flags(idx) & FIELD_FLAG != 0
+    }
+
+    /// The value of the field is `NULL`
+    pub fn is_null(&self, idx: PointOffsetType) -> bool {
+        self.get_flags(idx) & NULL_FLAG != 0
+    }
+
+    /// The field is missing, `NULL` or an empty array
+    pub fn is_empty(&self, idx: PointOffsetType) -> bool {
+        let flags = self.get_flags(idx);
+        flags & FIELD_FLAG == 0 || flags & EMPTY_FLAG != 0
+    }
+
+    /// Number of points, which have the field
+    pub fn fields_count(&self) -> usize {
+        self.fields_count
+    }
+
+    /// Number of points with the `NULL` value of the field
+    pub fn nulls_count(&self) -> usize {
+        self.nulls_count
+    }
+
+    /// Number of points, which have the field with a value other than `NULL` or an empty array
+    pub fn non_empty_count(&self) -> usize {
+        self.fields_count - self.empties_count
+    }
+
+    /// Points, which have the field
+    pub fn iter_fields(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(self.fields.iter_ones().map(|idx| idx as PointOffsetType))
+    }
+
+    /// Points with the `NULL` value of the field
+    pub fn iter_nulls(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(self.nulls.iter_ones().map(|idx| idx as PointOffsetType))
+    }
+
+    /// Memory, used by the bitmaps
+    pub fn ram_usage_bytes(&self) -> usize {
+        (self.fields.capacity() + self.nulls.capacity() + self.empties.capacity()) / 8
+    }
+
+    pub fn disk_usage_bytes(&self) -> OperationResult<usize> {
+        Ok(cf_disk_usage_bytes(&self.db.borrow(), &self.store_cf_name)?)
+    }
+
+    /// Mark the field of the point as present with the given value.
+    /// Points without the field are not added.
+    pub fn add_point(&mut self, idx: PointOffsetType, value: &Value) -> OperationResult<()> {
+        let flags = match value {
+            Value::Null => FIELD_FLAG | NULL_FLAG | EMPTY_FLAG,
+            Value::Array(array) if array.is_empty() => FIELD_FLAG | EMPTY_FLAG,
+            _ => FIELD_FLAG,
+        };
+
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "Index add error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        store_ref
+            .put_cf_opt(cf_handle, idx.to_be_bytes(), [flags], &db_write_options())
+            .map_err(|e| OperationError::service_error(&format!("Index db update error: {}", e)))?;
+        drop(store_ref);
+
+        self.set_flags(idx, flags);
+        Ok(())
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        if self.get_flags(idx) == 0 {
+            return Ok(());
+        }
+
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "point remove error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        store_ref.delete_cf(cf_handle, idx.to_be_bytes())?;
+        drop(store_ref);
+
+        self.set_flags(idx, 0);
+        Ok(())
+    }
+}
+
+impl PayloadFieldIndex for NullIndex {
+    fn indexed_points(&self) -> usize {
+        self.non_empty_count()
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        NullIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        Ok(self.db.borrow_mut().drop_cf(&self.store_cf_name)?)
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        NullIndex::flush(self)
+    }
+
+    fn filter(
+        &self,
+        _condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        None
+    }
+
+    fn estimate_cardinality(&self, _condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        None
+    }
+
+    fn payload_blocks(
+        &self,
+        _threshold: usize,
+        _key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(std::iter::empty())
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.non_empty_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use serde_json::json;
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::common::rocksdb_operations::open_db_with_existing_cf;
+
+    const FIELD_NAME: &str = "test";
+
+    #[test]
+    fn test_null_index() {
+        let tmp_dir = TempDir::new("store_dir").unwrap();
+        let mut index = NullIndex::new(
+            open_db_with_existing_cf(tmp_dir.path()).unwrap(),
+            FIELD_NAME,
+        );
+        index.recreate().unwrap();
+
+        let payloads = vec![
+            json!(null),
+            json!([]),
+            json!([null]),
+            json!("a"),
+            json!(null),
+        ];
+        for (idx, payload) in payloads.iter().enumerate() {
+            index.add_point(idx as PointOffsetType, payload).unwrap();
+        }
+        index.remove_point(4).unwrap();
+        index.flush().unwrap();
+        drop(index);
+
+        let mut index = NullIndex::new(
+            open_db_with_existing_cf(tmp_dir.path()).unwrap(),
+            FIELD_NAME,
+        );
+        assert!(index.load().unwrap());
+        assert_eq!(index.fields_count(), 4);
+        assert_eq!(index.nulls_count(), 1);
+        assert_eq!(index.count_indexed_points(), 2);
+
+        assert_eq!(index.iter_nulls().collect_vec(), vec![0]);
+        assert_eq!(index.iter_fields().collect_vec(), vec![0, 1, 2, 3]);
+        assert!(index.is_empty(1));
+        assert!(!index.is_empty(2));
+        assert!(index.is_empty(4));
+        assert!(!index.has_field(4));
+    }
+}
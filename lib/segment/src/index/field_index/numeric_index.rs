@@ -1,5 +1,7 @@
 use std::cmp::{max, min};
 use std::collections::BTreeMap;
+use std::mem::size_of;
+use std::ops::Bound;
 use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::sync::Arc;
 
@@ -7,7 +9,7 @@ use atomic_refcell::AtomicRefCell;
 use rocksdb::DB;
 use serde_json::Value;
 
-use crate::common::rocksdb_operations::{db_write_options, recreate_cf};
+use crate::common::rocksdb_operations::{cf_disk_usage_bytes, db_write_options, recreate_cf};
 use crate::entry::entry_point::{OperationError, OperationResult};
 use crate::index::field_index::histogram::{Histogram, Point};
 use crate::index::field_index::stat_tools::estimate_multi_value_selection_cardinality;
@@ -19,11 +21,13 @@ use crate::index::key_encoding::{
     encode_i64_key_ascending,
 };
 use crate::types::{
-    FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, PointOffsetType, Range,
+    parse_datetime_seconds, DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType,
+    NumericIndexStats, PayloadKeyType, PointOffsetType, QuantileValue, Range,
+    INDEX_STATS_QUANTILES,
 };
 
-const HISTOGRAM_MAX_BUCKET_SIZE: usize = 10_000;
-const HISTOGRAM_PRECISION: f64 = 0.01;
+pub(super) const HISTOGRAM_MAX_BUCKET_SIZE: usize = 10_000;
+pub(super) const HISTOGRAM_PRECISION: f64 = 0.01;
 
 pub trait KeyEncoder: Clone {
     fn encode_key(&self, id: PointOffsetType) -> Vec<u8>;
@@ -41,6 +45,12 @@ impl KeyEncoder for FloatPayloadType {
     }
 }
 
+impl KeyEncoder for DateTimePayloadType {
+    fn encode_key(&self, id: PointOffsetType) -> Vec<u8> {
+        encode_f64_key_ascending(self.0, id)
+    }
+}
+
 pub trait KeyDecoder {
     fn decode_key(key: &[u8]) -> (PointOffsetType, Self);
 }
@@ -57,6 +67,13 @@ impl KeyDecoder for FloatPayloadType {
     }
 }
 
+impl KeyDecoder for DateTimePayloadType {
+    fn decode_key(key: &[u8]) -> (PointOffsetType, Self) {
+        let (id, seconds) = decode_f64_key_ascending(key);
+        (id, DateTimePayloadType(seconds))
+    }
+}
+
 pub trait FromRangeValue {
     fn from_range(range_value: f64) -> Self;
 }
@@ -73,6 +90,12 @@ impl FromRangeValue for IntPayloadType {
     }
 }
 
+impl FromRangeValue for DateTimePayloadType {
+    fn from_range(range_value: f64) -> Self {
+        DateTimePayloadType(range_value)
+    }
+}
+
 pub trait ToRangeValue {
     fn to_range(value: Self) -> f64;
 }
@@ -89,6 +112,12 @@ impl ToRangeValue for IntPayloadType {
     }
 }
 
+impl ToRangeValue for DateTimePayloadType {
+    fn to_range(value: Self) -> f64 {
+        value.0
+    }
+}
+
 pub struct NumericIndex<T: KeyEncoder + KeyDecoder + FromRangeValue + Clone> {
     map: BTreeMap<Vec<u8>, u32>,
     db: Arc<AtomicRefCell<DB>>,
@@ -244,59 +273,126 @@ impl<T: KeyEncoder + KeyDecoder + FromRangeValue + ToRangeValue + Clone> Numeric
         self.point_to_values.get(idx as usize)
     }
 
-    fn range_cardinality(&self, range: &Range) -> CardinalityEstimation {
-        let lbound = if let Some(lte) = range.lte {
-            Included(lte)
-        } else if let Some(lt) = range.lt {
-            Excluded(lt)
-        } else {
-            Unbounded
-        };
+    /// Distribution of the indexed values, `None` if the index is empty
+    pub fn numeric_stats(&self) -> Option<NumericIndexStats> {
+        let min = self.values().next()?;
+        let max = self.values().next_back()?;
+        let quantiles = INDEX_STATS_QUANTILES
+            .iter()
+            .filter_map(|&quantile| {
+                let value = self.histogram.quantile(quantile)?;
+                Some(QuantileValue { quantile, value })
+            })
+            .collect();
+        Some(NumericIndexStats {
+            values_count: self.map.len(),
+            min,
+            max,
+            quantiles,
+        })
+    }
 
-        let gbound = if let Some(gte) = range.gte {
-            Included(gte)
-        } else if let Some(gt) = range.gt {
-            Excluded(gt)
-        } else {
-            Unbounded
-        };
+    /// Rough estimation of memory, used by the index structures
+    pub fn ram_usage_bytes(&self) -> usize {
+        let map_usage = self
+            .map
+            .keys()
+            .map(|key| size_of::<Vec<u8>>() + key.len() + size_of::<PointOffsetType>())
+            .sum::<usize>();
+        let values_usage = self
+            .point_to_values
+            .iter()
+            .map(|values| size_of::<Vec<T>>() + values.len() * size_of::<T>())
+            .sum::<usize>();
+        map_usage + values_usage
+    }
 
-        let histogram_estimation = self.histogram.estimate(gbound, lbound);
-        let min_estimation = histogram_estimation.0;
-        let max_estimation = histogram_estimation.2;
-
-        let total_values = self.map.len();
-        // Example: points_count = 1000, total values = 2000, values_count = 500
-        // min = max(1, 500 - (2000 - 1000)) = 1
-        // exp = 500 / (2000 / 1000) = 250
-        // max = min(1000, 500) = 500
-
-        // Example: points_count = 1000, total values = 1200, values_count = 500
-        // min = max(1, 500 - (1200 - 1000)) = 300
-        // exp = 500 / (1200 / 1000) = 416
-        // max = min(1000, 500) = 500
-        let expected_min = max(
-            min_estimation / self.max_values_per_point,
-            max(
-                min(1, min_estimation),
-                min_estimation.saturating_sub(total_values - self.points_count),
+    pub fn disk_usage_bytes(&self) -> OperationResult<usize> {
+        Ok(cf_disk_usage_bytes(&self.db.borrow(), &self.store_cf_name)?)
+    }
+
+    /// Iterate over all indexed values in ascending order
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = f64> + '_ {
+        self.map.keys().map(|key| {
+            let (_, value) = T::decode_key(key);
+            T::to_range(value)
+        })
+    }
+
+    /// Count values in the buckets of the `interval` width, using the histogram
+    pub fn count_by_interval(&self, interval: f64) -> BTreeMap<i64, usize> {
+        self.histogram.count_by_interval(interval, |from, to| {
+            let from_key = T::from_range(from.val).encode_key(from.idx as PointOffsetType);
+            let to_key = T::from_range(to.val).encode_key(to.idx as PointOffsetType);
+            self.map
+                .range((Excluded(from_key), Excluded(to_key)))
+                .map(|(key, _)| Self::key_to_histogram_point(key).val)
+        })
+    }
+
+    /// Iterate over points ordered by value, starting from the `start_from` value.
+    /// Each point is listed once: by its smallest value in ascending order
+    /// and by its largest value in descending order.
+    pub fn ordered_points(
+        &self,
+        start_from: Option<f64>,
+        descending: bool,
+    ) -> Box<dyn Iterator<Item = (f64, PointOffsetType)> + '_> {
+        let decode = |(key, idx): (&Vec<u8>, &PointOffsetType)| {
+            let (_, value) = T::decode_key(key);
+            (T::to_range(value), *idx)
+        };
+        let is_point_order_value = move |(value, idx): &(f64, PointOffsetType)| {
+            let values = match self.point_to_values.get(*idx as usize) {
+                Some(values) => values.iter().map(|value| T::to_range(value.clone())),
+                None => return false,
+            };
+            let order_value = if descending {
+                values.fold(f64::NEG_INFINITY, f64::max)
+            } else {
+                values.fold(f64::INFINITY, f64::min)
+            };
+            order_value == *value
+        };
+        match (start_from, descending) {
+            (None, false) => Box::new(self.map.iter().map(decode).filter(is_point_order_value)),
+            (None, true) => Box::new(
+                self.map
+                    .iter()
+                    .rev()
+                    .map(decode)
+                    .filter(is_point_order_value),
             ),
-        );
-        let expected_max = min(self.points_count, max_estimation);
+            (Some(start_from), false) => {
+                let start_key = T::from_range(start_from).encode_key(PointOffsetType::MIN);
+                Box::new(
+                    self.map
+                        .range(start_key..)
+                        .map(decode)
+                        .filter(is_point_order_value),
+                )
+            }
+            (Some(start_from), true) => {
+                let start_key = T::from_range(start_from).encode_key(PointOffsetType::MAX);
+                Box::new(
+                    self.map
+                        .range(..=start_key)
+                        .rev()
+                        .map(decode)
+                        .filter(is_point_order_value),
+                )
+            }
+        }
+    }
 
-        let estimation = estimate_multi_value_selection_cardinality(
+    fn range_cardinality(&self, range: &Range) -> CardinalityEstimation {
+        range_cardinality(
+            &self.histogram,
+            range,
             self.points_count,
-            total_values,
-            histogram_estimation.1,
+            self.map.len(),
+            self.max_values_per_point,
         )
-        .round() as usize;
-
-        CardinalityEstimation {
-            primary_clauses: vec![],
-            min: expected_min,
-            exp: min(expected_max, max(estimation, expected_min)),
-            max: expected_max,
-        }
     }
 
     fn add_to_map(
@@ -379,30 +475,7 @@ impl<T: KeyEncoder + KeyDecoder + FromRangeValue + ToRangeValue + Clone> Payload
         condition: &FieldCondition,
     ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
         let cond_range = condition.range.as_ref()?;
-
-        let start_bound = match cond_range {
-            Range { gt: Some(gt), .. } => {
-                let v: T = T::from_range(gt.to_owned());
-                Excluded(v.encode_key(PointOffsetType::MAX))
-            }
-            Range { gte: Some(gte), .. } => {
-                let v: T = T::from_range(gte.to_owned());
-                Included(v.encode_key(PointOffsetType::MIN))
-            }
-            _ => Unbounded,
-        };
-
-        let end_bound = match cond_range {
-            Range { lt: Some(lt), .. } => {
-                let v: T = T::from_range(lt.to_owned());
-                Excluded(v.encode_key(PointOffsetType::MIN))
-            }
-            Range { lte: Some(lte), .. } => {
-                let v: T = T::from_range(lte.to_owned());
-                Included(v.encode_key(PointOffsetType::MAX))
-            }
-            _ => Unbounded,
-        };
+        let (start_bound, end_bound) = encoded_range_bounds::<T>(cond_range);
 
         // map.range
         // Panics if range start > end. Panics if range start == end and both bounds are Excluded.
@@ -438,74 +511,192 @@ impl<T: KeyEncoder + KeyDecoder + FromRangeValue + ToRangeValue + Clone> Payload
         threshold: usize,
         key: PayloadKeyType,
     ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
-        let mut lower_bound = Unbounded;
-        let mut pre_lower_bound = None;
-        let mut payload_conditions = Vec::new();
-
-        let value_per_point = self.map.len() as f64 / self.points_count as f64;
-        let effective_threshold = (threshold as f64 * value_per_point) as usize;
-
-        loop {
-            let upper_bound = self
-                .histogram
-                .get_range_by_size(lower_bound, effective_threshold / 2);
-
-            if let Some(pre_lower_bound) = pre_lower_bound {
-                let range = Range {
-                    lt: match upper_bound {
-                        Excluded(val) => Some(val),
-                        _ => None,
-                    },
-                    gt: match pre_lower_bound {
-                        Excluded(val) => Some(val),
-                        _ => None,
-                    },
-                    gte: match pre_lower_bound {
-                        Included(val) => Some(val),
-                        _ => None,
-                    },
-                    lte: match upper_bound {
-                        Included(val) => Some(val),
-                        _ => None,
-                    },
-                };
-                let cardinality = self.range_cardinality(&range);
-                let condition = PayloadBlockCondition {
-                    condition: FieldCondition::new_range(key.clone(), range),
-                    cardinality: cardinality.exp,
-                };
-
-                payload_conditions.push(condition);
-            } else if upper_bound == Unbounded {
-                // One block covers all points
-                payload_conditions.push(PayloadBlockCondition {
-                    condition: FieldCondition::new_range(
-                        key.clone(),
-                        Range {
-                            gte: None,
-                            lte: None,
-                            lt: None,
-                            gt: None,
-                        },
-                    ),
-                    cardinality: self.points_count,
-                });
-            }
+        Box::new(
+            payload_blocks(
+                &self.histogram,
+                self.points_count,
+                self.map.len(),
+                self.max_values_per_point,
+                threshold,
+                key,
+            )
+            .into_iter(),
+        )
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+}
 
-            pre_lower_bound = Some(lower_bound);
+/// Bounds of the encoded keys, which have values within the range
+pub(super) fn encoded_range_bounds<T: KeyEncoder + FromRangeValue>(
+    cond_range: &Range,
+) -> (Bound<Vec<u8>>, Bound<Vec<u8>>) {
+    let start_bound = match cond_range {
+        Range { gt: Some(gt), .. } => {
+            let v: T = T::from_range(gt.to_owned());
+            Excluded(v.encode_key(PointOffsetType::MAX))
+        }
+        Range { gte: Some(gte), .. } => {
+            let v: T = T::from_range(gte.to_owned());
+            Included(v.encode_key(PointOffsetType::MIN))
+        }
+        _ => Unbounded,
+    };
 
-            lower_bound = match upper_bound {
-                Included(val) => Excluded(val),
-                Excluded(val) => Excluded(val),
-                Unbounded => break,
-            };
+    let end_bound = match cond_range {
+        Range { lt: Some(lt), .. } => {
+            let v: T = T::from_range(lt.to_owned());
+            Excluded(v.encode_key(PointOffsetType::MIN))
+        }
+        Range { lte: Some(lte), .. } => {
+            let v: T = T::from_range(lte.to_owned());
+            Included(v.encode_key(PointOffsetType::MAX))
         }
-        Box::new(payload_conditions.into_iter())
+        _ => Unbounded,
+    };
+
+    (start_bound, end_bound)
+}
+
+/// Estimate amount of points, which have values in the range, using the values histogram
+pub(super) fn range_cardinality(
+    histogram: &Histogram,
+    range: &Range,
+    points_count: usize,
+    total_values: usize,
+    max_values_per_point: usize,
+) -> CardinalityEstimation {
+    let lbound = if let Some(lte) = range.lte {
+        Included(lte)
+    } else if let Some(lt) = range.lt {
+        Excluded(lt)
+    } else {
+        Unbounded
+    };
+
+    let gbound = if let Some(gte) = range.gte {
+        Included(gte)
+    } else if let Some(gt) = range.gt {
+        Excluded(gt)
+    } else {
+        Unbounded
+    };
+
+    let histogram_estimation = histogram.estimate(gbound, lbound);
+    let min_estimation = histogram_estimation.0;
+    let max_estimation = histogram_estimation.2;
+
+    // Example: points_count = 1000, total values = 2000, values_count = 500
+    // min = max(1, 500 - (2000 - 1000)) = 1
+    // exp = 500 / (2000 / 1000) = 250
+    // max = min(1000, 500) = 500
+
+    // Example: points_count = 1000, total values = 1200, values_count = 500
+    // min = max(1, 500 - (1200 - 1000)) = 300
+    // exp = 500 / (1200 / 1000) = 416
+    // max = min(1000, 500) = 500
+    let expected_min = max(
+        min_estimation / max_values_per_point,
+        max(
+            min(1, min_estimation),
+            min_estimation.saturating_sub(total_values - points_count),
+        ),
+    );
+    let expected_max = min(points_count, max_estimation);
+
+    let estimation = estimate_multi_value_selection_cardinality(
+        points_count,
+        total_values,
+        histogram_estimation.1,
+    )
+    .round() as usize;
+
+    CardinalityEstimation {
+        primary_clauses: vec![],
+        min: expected_min,
+        exp: min(expected_max, max(estimation, expected_min)),
+        max: expected_max,
     }
+}
 
-    fn count_indexed_points(&self) -> usize {
-        self.points_count
+/// Split the indexed values into ranges, covering around `threshold` points each
+pub(super) fn payload_blocks(
+    histogram: &Histogram,
+    points_count: usize,
+    total_values: usize,
+    max_values_per_point: usize,
+    threshold: usize,
+    key: PayloadKeyType,
+) -> Vec<PayloadBlockCondition> {
+    let mut lower_bound = Unbounded;
+    let mut pre_lower_bound = None;
+    let mut payload_conditions = Vec::new();
+
+    let value_per_point = total_values as f64 / points_count as f64;
+    let effective_threshold = (threshold as f64 * value_per_point) as usize;
+
+    loop {
+        let upper_bound = histogram.get_range_by_size(lower_bound, effective_threshold / 2);
+
+        if let Some(pre_lower_bound) = pre_lower_bound {
+            let range = Range {
+                lt: match upper_bound {
+                    Excluded(val) => Some(val),
+                    _ => None,
+                },
+                gt: match pre_lower_bound {
+                    Excluded(val) => Some(val),
+                    _ => None,
+                },
+                gte: match pre_lower_bound {
+                    Included(val) => Some(val),
+                    _ => None,
+                },
+                lte: match upper_bound {
+                    Included(val) => Some(val),
+                    _ => None,
+                },
+            };
+            let cardinality = range_cardinality(
+                histogram,
+                &range,
+                points_count,
+                total_values,
+                max_values_per_point,
+            );
+            let condition = PayloadBlockCondition {
+                condition: FieldCondition::new_range(key.clone(), range),
+                cardinality: cardinality.exp,
+            };
+
+            payload_conditions.push(condition);
+        } else if upper_bound == Unbounded {
+            // One block covers all points
+            payload_conditions.push(PayloadBlockCondition {
+                condition: FieldCondition::new_range(
+                    key.clone(),
+                    Range {
+                        gte: None,
+                        lte: None,
+                        lt: None,
+                        gt: None,
+                    },
+                ),
+                cardinality: points_count,
+            });
+        }
+
+        pre_lower_bound = Some(lower_bound);
+
+        lower_bound = match upper_bound {
+            Included(val) => Excluded(val),
+            Excluded(val) => Excluded(val),
+            Unbounded => break,
+        };
     }
+    payload_conditions
 }
 
 impl ValueIndexer<IntPayloadType> for NumericIndex<IntPayloadType> {
@@ -550,11 +741,33 @@ impl ValueIndexer<FloatPayloadType> for NumericIndex<FloatPayloadType> {
     }
 }
 
+impl ValueIndexer<DateTimePayloadType> for NumericIndex<DateTimePayloadType> {
+    fn add_many(
+        &mut self,
+        id: PointOffsetType,
+        values: Vec<DateTimePayloadType>,
+    ) -> OperationResult<()> {
+        self.add_many_to_list(id, values)
+    }
+
+    fn get_value(&self, value: &Value) -> Option<DateTimePayloadType> {
+        if let Value::String(string) = value {
+            return parse_datetime_seconds(string).map(DateTimePayloadType);
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        NumericIndex::remove_point(self, id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
     use rand::prelude::StdRng;
     use rand::{Rng, SeedableRng};
+    use serde_json::json;
     use tempdir::TempDir;
 
     use super::*;
@@ -842,6 +1055,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_datetime_index() {
+        let tmp_dir = TempDir::new("test_numeric_index").unwrap();
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let mut index: NumericIndex<DateTimePayloadType> = NumericIndex::new(db, COLUMN_NAME);
+        index.recreate().unwrap();
+
+        let values = [
+            json!("2022-01-01T00:00:00Z"),
+            json!("2022-06-15T12:30:00+02:00"),
+            json!("not a datetime"),
+            json!(["2023-03-01T00:00:00Z", "2021-12-31T23:59:59Z"]),
+        ];
+        for (idx, value) in values.iter().enumerate() {
+            index.add_point(idx as PointOffsetType, value).unwrap();
+        }
+
+        assert!(index.get_values(2).unwrap().is_empty());
+        assert_eq!(index.get_values(3).unwrap().len(), 2);
+
+        let range: Range = serde_json::from_value(json!({
+            "gte": "2022-01-01T00:00:00Z",
+            "lt": "2022-12-31T00:00:00Z"
+        }))
+        .unwrap();
+        test_cond(&index, range, vec![0, 1]);
+
+        let range: Range = serde_json::from_value(json!({
+            "gt": "2022-06-15T10:30:00Z"
+        }))
+        .unwrap();
+        test_cond(&index, range, vec![3]);
+    }
+
+    #[test]
+    fn test_ordered_points() {
+        let (_tmp_dir, mut index) = get_index();
+        index.add_many_to_list(0, vec![3.0]).unwrap();
+        index.add_many_to_list(1, vec![1.0, 5.0]).unwrap();
+        index.add_many_to_list(2, vec![3.0]).unwrap();
+        index.add_many_to_list(3, vec![]).unwrap();
+
+        let ascending = index.ordered_points(None, false).collect_vec();
+        assert_eq!(ascending, vec![(1.0, 1), (3.0, 0), (3.0, 2)]);
+
+        let descending = index.ordered_points(None, true).collect_vec();
+        assert_eq!(descending, vec![(5.0, 1), (3.0, 2), (3.0, 0)]);
+
+        // Point 1 is ordered by its smallest value, which is before the start
+        let from_start = index.ordered_points(Some(3.0), false).collect_vec();
+        assert_eq!(from_start, vec![(3.0, 0), (3.0, 2)]);
+
+        let from_start = index.ordered_points(Some(3.0), true).collect_vec();
+        assert_eq!(from_start, vec![(3.0, 2), (3.0, 0)]);
+    }
+
     fn test_cond<T: KeyEncoder + KeyDecoder + FromRangeValue + ToRangeValue + Clone>(
         index: &NumericIndex<T>,
         rng: Range,
@@ -853,6 +1122,7 @@ mod tests {
             range: Some(rng),
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
             values_count: None,
         };
 
@@ -266,9 +266,21 @@ impl GraphLayers {
             Some(ep) => ep,
         };
 
+        self.search_from(top, ef, points_scorer, entry_point.point_id)
+    }
+
+    /// Same as `search`, but starts from the given entry point instead of the global ones.
+    /// Entry point must satisfy the filter of the `points_scorer`, e.g. point of the requested tenant.
+    pub fn search_from(
+        &self,
+        top: usize,
+        ef: usize,
+        mut points_scorer: FilteredScorer,
+        entry_point: PointOffsetType,
+    ) -> Vec<ScoredPointOffset> {
         let zero_level_entry = self.search_entry(
-            entry_point.point_id,
-            entry_point.level,
+            entry_point,
+            self.point_level(entry_point),
             0,
             &mut points_scorer,
         );
@@ -1,4 +1,5 @@
 use std::cmp::max;
+use std::collections::HashMap;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -10,24 +11,32 @@ use rand::thread_rng;
 use rayon::prelude::*;
 use rayon::ThreadPool;
 
+use crate::common::file_operations::{atomic_save_json, read_json};
 use crate::entry::entry_point::{OperationError, OperationResult};
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
-use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::point_scorer::{FilteredScorer, ScorerStats};
+use crate::index::plain_payload_index::search_plain_filtered;
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::VisitedList;
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::types::Condition::Field;
 use crate::types::{
-    FieldCondition, Filter, HnswConfig, SearchParams, VectorElementType, VECTOR_ELEMENT_SIZE,
+    FieldCondition, Filter, HnswConfig, Match, MatchValue, PayloadKeyType, PointOffsetType,
+    QueryExplain, QueryStrategy, SearchParams, ValueVariants, VectorElementType,
+    VECTOR_ELEMENT_SIZE,
 };
 use crate::vector_storage::{ScoredPointOffset, VectorStorageSS};
 
 const HNSW_USE_HEURISTIC: bool = true;
 const BYTES_IN_KB: usize = 1024;
+const TENANT_ENTRY_POINTS_FILE: &str = "tenant_entry_points.json";
+
+/// Search entry point of each tenant, by the tenant field and the keyword of the tenant
+type TenantEntryPoints = HashMap<PayloadKeyType, HashMap<String, PointOffsetType>>;
 
 pub struct HNSWIndex {
     vector_storage: Arc<AtomicRefCell<VectorStorageSS>>,
@@ -35,6 +44,7 @@ pub struct HNSWIndex {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: GraphLayers,
+    tenant_entry_points: TenantEntryPoints,
 }
 
 impl HNSWIndex {
@@ -78,12 +88,20 @@ impl HNSWIndex {
             )
         };
 
+        let tenant_entry_points_path = path.join(TENANT_ENTRY_POINTS_FILE);
+        let tenant_entry_points = if tenant_entry_points_path.exists() {
+            read_json(&tenant_entry_points_path)?
+        } else {
+            Default::default()
+        };
+
         Ok(HNSWIndex {
             vector_storage,
             payload_index,
             config,
             path: path.to_owned(),
             graph,
+            tenant_entry_points,
         })
     }
 
@@ -97,9 +115,18 @@ impl HNSWIndex {
         self.graph.save(&graph_path)
     }
 
+    fn save_tenant_entry_points(&self) -> OperationResult<()> {
+        let tenant_entry_points_path = self.path.join(TENANT_ENTRY_POINTS_FILE);
+        Ok(atomic_save_json(
+            &tenant_entry_points_path,
+            &self.tenant_entry_points,
+        )?)
+    }
+
     pub fn save(&self) -> OperationResult<()> {
         self.save_config()?;
         self.save_graph()?;
+        self.save_tenant_entry_points()?;
         Ok(())
     }
 
@@ -160,6 +187,17 @@ impl HNSWIndex {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_with_graph_explain(vector, filter, top, params, &mut QueryExplain::default())
+    }
+
+    fn search_with_graph_explain(
+        &self,
+        vector: &[VectorElementType],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        explain: &mut QueryExplain,
     ) -> Vec<ScoredPointOffset> {
         let req_ef = params
             .and_then(|params| params.hnsw_ef)
@@ -174,22 +212,50 @@ impl HNSWIndex {
 
         let filter_context = filter.map(|f| payload_index.filter_context(f));
 
-        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
+        let stats = ScorerStats::default();
+        let points_scorer =
+            FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref()).with_stats(&stats);
+
+        // Tenant points are linked with each other, so search starts from the tenant itself.
+        // Stored entry point might be deleted or moved to another tenant since the index was built,
+        // regular entry points are used in this case.
+        let tenant_entry_point = filter
+            .and_then(|filter| payload_index.tenant_condition(filter))
+            .and_then(|condition| {
+                self.tenant_entry_points
+                    .get(&condition.key)?
+                    .get(tenant_keyword(condition)?)
+                    .copied()
+            })
+            .filter(|entry_point| points_scorer.check_point(*entry_point));
+
+        let result = match tenant_entry_point {
+            Some(entry_point) => self.graph.search_from(top, ef, points_scorer, entry_point),
+            None => self.graph.search(top, ef, points_scorer),
+        };
 
-        self.graph.search(top, ef, points_scorer)
+        explain.strategy = Some(if filter.is_some() {
+            QueryStrategy::FilteredHnsw
+        } else {
+            QueryStrategy::Hnsw
+        });
+        explain.visited_nodes += stats.visited_nodes.get();
+        explain.distance_computations += stats.distance_computations.get();
+        result
     }
 }
 
 impl VectorIndex for HNSWIndex {
-    fn search(
+    fn search_explain(
         &self,
         vector: &[VectorElementType],
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        explain: &mut QueryExplain,
     ) -> Vec<ScoredPointOffset> {
         match filter {
-            None => self.search_with_graph(vector, None, top, params),
+            None => self.search_with_graph_explain(vector, None, top, params, explain),
             Some(query_filter) => {
                 // depending on the amount of filtered-out points the optimal strategy could be
                 // - to retrieve possible points and score them after
@@ -197,6 +263,7 @@ impl VectorIndex for HNSWIndex {
 
                 let payload_index = self.payload_index.borrow();
                 let query_cardinality = payload_index.estimate_cardinality(query_filter);
+                explain.cardinality = Some((&query_cardinality).into());
 
                 // debug!("query_cardinality: {:#?}", query_cardinality);
 
@@ -204,13 +271,20 @@ impl VectorIndex for HNSWIndex {
 
                 if query_cardinality.max < self.config.indexing_threshold {
                     // if cardinality is small - use plain index
-                    let mut filtered_ids = payload_index.query_points(query_filter);
-                    return vector_storage.score_points(vector, &mut filtered_ids, top);
+                    return search_plain_filtered(
+                        &*vector_storage,
+                        &payload_index,
+                        vector,
+                        query_filter,
+                        &query_cardinality,
+                        top,
+                        explain,
+                    );
                 }
 
                 if query_cardinality.min > self.config.indexing_threshold {
                     // if cardinality is high enough - use HNSW index
-                    return self.search_with_graph(vector, filter, top, params);
+                    return self.search_with_graph_explain(vector, filter, top, params, explain);
                 }
 
                 let filter_context = payload_index.filter_context(query_filter);
@@ -224,11 +298,18 @@ impl VectorIndex for HNSWIndex {
                     vector_storage.vector_count(),
                 ) {
                     // if cardinality is high enough - use HNSW index
-                    self.search_with_graph(vector, filter, top, params)
+                    self.search_with_graph_explain(vector, filter, top, params, explain)
                 } else {
                     // if cardinality is small - use plain index
-                    let mut filtered_ids = payload_index.query_points(query_filter);
-                    vector_storage.score_points(vector, &mut filtered_ids, top)
+                    search_plain_filtered(
+                        &*vector_storage,
+                        &payload_index,
+                        vector,
+                        query_filter,
+                        &query_cardinality,
+                        top,
+                        explain,
+                    )
                 };
             }
         }
@@ -286,9 +367,16 @@ impl VectorIndex for HNSWIndex {
         let mut block_filter_list = VisitedList::new(total_vectors_count);
 
         let payload_index = self.payload_index.borrow();
+        self.tenant_entry_points.clear();
 
-        for (field, _) in payload_index.indexed_fields() {
+        for (field, payload_schema) in payload_index.indexed_fields() {
             debug!("building additional index for field {}", &field);
+            // Each tenant is searched separately, so it needs its own links regardless of the size.
+            // Like any payload block, the graph of the tenant allocates links of all the points
+            // and is merged over all of them, which costs O(points) per tenant on top of linking.
+            // Only tenants of at least `indexing_threshold` points get their graphs,
+            // so there are at most `points / indexing_threshold` of them.
+            let is_tenant = payload_schema.is_tenant();
 
             // It is expected, that graph will become disconnected less than
             // $1/m$ points left.
@@ -304,9 +392,24 @@ impl VectorIndex for HNSWIndex {
                         description: "Cancelled by external thread".to_string(),
                     });
                 }
-                if payload_block.cardinality > max_block_size {
+                if payload_block.cardinality > max_block_size && !is_tenant {
                     continue;
                 }
+                if is_tenant {
+                    // Search could start from any point of the tenant.
+                    // The highest one is taken to get through the upper levels of the graph as well.
+                    let entry_point = payload_index
+                        .query_points(&Filter::new_must(Field(payload_block.condition.clone())))
+                        .max_by_key(|point_id| self.graph.point_level(*point_id));
+                    if let (Some(keyword), Some(entry_point)) =
+                        (tenant_keyword(&payload_block.condition), entry_point)
+                    {
+                        self.tenant_entry_points
+                            .entry(field.clone())
+                            .or_default()
+                            .insert(keyword.to_owned(), entry_point);
+                    }
+                }
                 // ToDo: re-use graph layer for same payload
                 let mut additional_graph = GraphLayersBuilder::new_with_params(
                     self.vector_storage.borrow().total_vector_count(),
@@ -332,3 +435,13 @@ impl VectorIndex for HNSWIndex {
         self.save()
     }
 }
+
+/// Keyword of the tenant, required by the condition
+fn tenant_keyword(condition: &FieldCondition) -> Option<&str> {
+    match &condition.r#match {
+        Some(Match::Value(MatchValue {
+            value: ValueVariants::Keyword(keyword),
+        })) => Some(keyword),
+        _ => None,
+    }
+}
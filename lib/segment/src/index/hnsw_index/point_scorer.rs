@@ -1,11 +1,32 @@
+use std::cell::Cell;
+
 use crate::payload_storage::FilterContext;
 use crate::types::{PointOffsetType, ScoreType};
 use crate::vector_storage::{RawScorer, ScoredPointOffset};
 
+/// Counters of the work done by the scorer
+#[derive(Debug, Default)]
+pub struct ScorerStats {
+    /// Number of points, passed to the scorer
+    pub visited_nodes: Cell<usize>,
+    /// Number of computed scores
+    pub distance_computations: Cell<usize>,
+}
+
+impl ScorerStats {
+    fn record(&self, visited_nodes: usize, distance_computations: usize) {
+        self.visited_nodes
+            .set(self.visited_nodes.get() + visited_nodes);
+        self.distance_computations
+            .set(self.distance_computations.get() + distance_computations);
+    }
+}
+
 pub struct FilteredScorer<'a> {
     pub raw_scorer: &'a dyn RawScorer,
     pub filter_context: Option<&'a dyn FilterContext>,
     points_buffer: Vec<ScoredPointOffset>,
+    stats: Option<&'a ScorerStats>,
 }
 
 impl<'a> FilteredScorer<'a> {
@@ -17,9 +38,16 @@ impl<'a> FilteredScorer<'a> {
             raw_scorer,
             filter_context,
             points_buffer: Vec::new(),
+            stats: None,
         }
     }
 
+    /// Count visited points and computed scores in the given `stats`
+    pub fn with_stats(mut self, stats: &'a ScorerStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
     pub fn check_point(&self, point_id: PointOffsetType) -> bool {
         match self.filter_context {
             None => self.raw_scorer.check_point(point_id),
@@ -42,6 +70,7 @@ impl<'a> FilteredScorer<'a> {
         point_ids: &mut [PointOffsetType],
         limit: usize,
     ) -> &[ScoredPointOffset] {
+        let visited_nodes = point_ids.len();
         // apply filter and store filtered ids to source slice memory
         let filtered_point_ids = match self.filter_context {
             None => point_ids,
@@ -64,10 +93,16 @@ impl<'a> FilteredScorer<'a> {
         let count = self
             .raw_scorer
             .score_points(filtered_point_ids, &mut self.points_buffer);
+        if let Some(stats) = self.stats {
+            stats.record(visited_nodes, count);
+        }
         &self.points_buffer[0..count]
     }
 
     pub fn score_point(&self, point_id: PointOffsetType) -> ScoreType {
+        if let Some(stats) = self.stats {
+            stats.record(1, 1);
+        }
         self.raw_scorer.score_point(point_id)
     }
 
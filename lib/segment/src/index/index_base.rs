@@ -7,8 +7,8 @@ use crate::entry::entry_point::OperationResult;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::payload_storage::FilterContext;
 use crate::types::{
-    Filter, Payload, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointOffsetType,
-    SearchParams, VectorElementType,
+    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    PointOffsetType, QueryExplain, SearchParams, VectorElementType,
 };
 use crate::vector_storage::ScoredPointOffset;
 
@@ -21,6 +21,18 @@ pub trait VectorIndex {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_explain(vector, filter, top, params, &mut QueryExplain::default())
+    }
+
+    /// Same as `search`, additionally records how the search was executed into `explain`
+    fn search_explain(
+        &self,
+        vector: &[VectorElementType],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        explain: &mut QueryExplain,
     ) -> Vec<ScoredPointOffset>;
 
     /// Force internal index rebuild.
@@ -29,13 +41,13 @@ pub trait VectorIndex {
 
 pub trait PayloadIndex {
     /// Get indexed fields
-    fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadSchemaType>;
+    fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema>;
 
     /// Mark field as one which should be indexed
     fn set_indexed(
         &mut self,
         field: PayloadKeyTypeRef,
-        payload_type: PayloadSchemaType,
+        payload_schema: PayloadFieldSchema,
     ) -> OperationResult<()>;
 
     /// Remove index
@@ -70,6 +82,15 @@ pub trait PayloadIndex {
     /// Assign payload to a concrete point with a concrete payload value
     fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()>;
 
+    /// Merge payload into the nested object of the point payload, located by the `key` path.
+    /// Missing objects on the path are created
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()>;
+
     /// Get payload for point
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
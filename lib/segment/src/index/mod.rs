@@ -2,6 +2,7 @@ pub mod field_index;
 pub mod hnsw_index;
 mod index_base;
 mod key_encoding;
+pub mod nested_index;
 mod payload_config;
 pub mod plain_payload_index;
 pub mod query_estimator;
@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use rocksdb::{IteratorMode, DB};
+use serde_json::{Map, Value};
+
+use crate::common::rocksdb_operations::{db_write_options, recreate_cf};
+use crate::common::utils::get_value_from_json_map;
+use crate::entry::entry_point::{OperationError, OperationResult};
+use crate::index::field_index::index_selector::index_selector;
+use crate::index::query_optimization::condition_converter::field_condition_index;
+use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
+use crate::index::query_optimization::optimizer::IndexesMap;
+use crate::payload_storage::query_checker::check_filter;
+use crate::types::{
+    Condition, FieldCondition, Filter, HasIdCondition, MinShould, Payload, PayloadFieldSchema,
+    PayloadKeyType, PayloadKeyTypeRef, PointOffsetType,
+};
+
+pub type NestedIndexesMap = HashMap<PayloadKeyType, NestedIndex>;
+
+/// Split path of the array element field `key[].field` into the array `key` and the `field`.
+/// Only the fields of the outermost arrays are supported.
+pub fn split_element_path(path: PayloadKeyTypeRef) -> Option<(&str, &str)> {
+    let (key, field) = path.split_once("[].")?;
+    if key.is_empty() || field.is_empty() || field.contains("[]") {
+        return None;
+    }
+    Some((key, field))
+}
+
+/// Index of the objects of an array field, which refers to each element by its position.
+///
+/// Each element gets an id, which identifies the point and the position of the element in the array.
+/// Indexes of the element fields are keyed by element ids, so that conditions of a nested filter
+/// could be checked to be satisfied by the same element without reading the payload.
+/// Element ids of the points are persisted along with the element indexes.
+pub struct NestedIndex {
+    /// Path to the array of objects
+    key: PayloadKeyType,
+    /// Ids of the elements of each point, in the order of their positions in the array
+    point_elements: Vec<Vec<PointOffsetType>>,
+    /// Ids of the removed elements are not reused
+    next_element_id: PointOffsetType,
+    /// Indexes of the element fields, keyed by element ids. Fields are relative to the element
+    field_indexes: IndexesMap,
+    store_cf_name: String,
+    db: Arc<AtomicRefCell<DB>>,
+}
+
+impl NestedIndex {
+    /// Indexes of the element fields are stored under the `key[].field@elements` name,
+    /// element ids of the points under the `key[]@elements` name
+    pub fn new(
+        key: PayloadKeyTypeRef,
+        fields: &[(PayloadKeyType, PayloadFieldSchema)],
+        db: Arc<AtomicRefCell<DB>>,
+    ) -> Self {
+        let mut field_indexes = IndexesMap::new();
+        for (field, payload_schema) in fields {
+            let indexes = index_selector(
+                &format!("{}[].{}@elements", key, field),
+                payload_schema,
+                db.clone(),
+                // Element indexes are stored in RocksDB, on-disk indexes are not used
+                Path::new(""),
+                true,
+            );
+            field_indexes.insert(field.clone(), indexes);
+        }
+        NestedIndex {
+            key: key.to_owned(),
+            point_elements: vec![],
+            next_element_id: 0,
+            field_indexes,
+            store_cf_name: format!("{}[]@elements", key),
+            db,
+        }
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        recreate_cf(self.db.clone(), &self.store_cf_name)?;
+        for indexes in self.field_indexes.values() {
+            for index in indexes {
+                index.recreate()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load element ids of the points and the element indexes.
+    /// Return `false` if some of them are not stored, the index has to be rebuilt then.
+    pub fn load(&mut self) -> OperationResult<bool> {
+        {
+            let db_ref = self.db.borrow();
+            let cf_handle = match db_ref.cf_handle(&self.store_cf_name) {
+                Some(cf_handle) => cf_handle,
+                None => return Ok(false),
+            };
+            for (key, value) in db_ref.iterator_cf(cf_handle, IteratorMode::Start) {
+                let (point_id, elements) = Self::decode_db_record(&key, &value)?;
+                if let Some(&max_element_id) = elements.iter().max() {
+                    self.next_element_id = self.next_element_id.max(max_element_id + 1);
+                }
+                if self.point_elements.len() <= point_id as usize {
+                    self.point_elements
+                        .resize_with(point_id as usize + 1, Default::default);
+                }
+                self.point_elements[point_id as usize] = elements;
+            }
+        }
+        for indexes in self.field_indexes.values_mut() {
+            for index in indexes.iter_mut() {
+                if !index.load()? {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    fn decode_db_record(
+        key: &[u8],
+        value: &[u8],
+    ) -> OperationResult<(PointOffsetType, Vec<PointOffsetType>)> {
+        const DECODE_ERR: &str = "Index db parsing error: wrong data format";
+        const ID_SIZE: usize = std::mem::size_of::<PointOffsetType>();
+        let point_id = PointOffsetType::from_be_bytes(
+            key.try_into()
+                .map_err(|_| OperationError::service_error(DECODE_ERR))?,
+        );
+        if value.len() % ID_SIZE != 0 {
+            return Err(OperationError::service_error(DECODE_ERR));
+        }
+        let elements = value
+            .chunks_exact(ID_SIZE)
+            .map(|bytes| PointOffsetType::from_be_bytes(bytes.try_into().unwrap()))
+            .collect();
+        Ok((point_id, elements))
+    }
+
+    fn store_point_elements(&self, point_id: PointOffsetType) -> OperationResult<()> {
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "Index add error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        let elements = self
+            .point_elements
+            .get(point_id as usize)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if elements.is_empty() {
+            store_ref.delete_cf(cf_handle, point_id.to_be_bytes())?;
+        } else {
+            let value: Vec<u8> = elements
+                .iter()
+                .flat_map(|element_id| element_id.to_be_bytes())
+                .collect();
+            store_ref
+                .put_cf_opt(
+                    cf_handle,
+                    point_id.to_be_bytes(),
+                    value,
+                    &db_write_options(),
+                )
+                .map_err(|e| {
+                    OperationError::service_error(&format!("Index db update error: {}", e))
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Replace indexed elements of the point with the elements of the array in the payload
+    pub fn update_point(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+    ) -> OperationResult<()> {
+        self.remove_point(point_id)?;
+        let elements: Vec<&Map<String, Value>> = match payload.get_value(&self.key) {
+            Some(Value::Array(array)) => array.iter().filter_map(|x| x.as_object()).collect(),
+            Some(Value::Object(object)) => vec![object],
+            _ => return Ok(()),
+        };
+        if self.point_elements.len() <= point_id as usize {
+            self.point_elements
+                .resize_with(point_id as usize + 1, Default::default);
+        }
+        for element in elements {
+            let element_id = self.next_element_id;
+            self.next_element_id += 1;
+            for (field, indexes) in self.field_indexes.iter_mut() {
+                if let Some(value) = get_value_from_json_map(field, element) {
+                    for index in indexes.iter_mut() {
+                        index.add_point(element_id, value)?;
+                    }
+                }
+            }
+            self.point_elements[point_id as usize].push(element_id);
+        }
+        self.store_point_elements(point_id)
+    }
+
+    pub fn remove_point(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
+        let elements = match self.point_elements.get_mut(point_id as usize) {
+            Some(elements) if !elements.is_empty() => std::mem::take(elements),
+            _ => return Ok(()),
+        };
+        for element_id in elements {
+            for indexes in self.field_indexes.values_mut() {
+                for index in indexes.iter_mut() {
+                    index.remove_point(element_id)?;
+                }
+            }
+        }
+        self.store_point_elements(point_id)
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        for indexes in self.field_indexes.into_values() {
+            for index in indexes {
+                index.clear()?;
+            }
+        }
+        let mut db_mut = self.db.borrow_mut();
+        if db_mut.cf_handle(&self.store_cf_name).is_some() {
+            db_mut.drop_cf(&self.store_cf_name)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        for indexes in self.field_indexes.values() {
+            for index in indexes {
+                index.flush()?;
+            }
+        }
+        let store_ref = self.db.borrow();
+        let cf_handle = store_ref.cf_handle(&self.store_cf_name).ok_or_else(|| {
+            OperationError::service_error(&format!(
+                "Index flush error: column family {} not found",
+                self.store_cf_name
+            ))
+        })?;
+        Ok(store_ref.flush_cf(cf_handle)?)
+    }
+
+    /// Checker of the nested filter, which uses only element indexes.
+    /// Return `None` if some condition of the filter can't be checked with the indexes,
+    /// such conditions have to be checked with the payload.
+    pub fn filter_checker<'a, F>(
+        &'a self,
+        filter: &'a Filter,
+        check_has_id: F,
+    ) -> Option<ConditionCheckerFn<'a>>
+    where
+        F: Fn(&HasIdCondition, PointOffsetType) -> bool + 'a,
+    {
+        let mut field_conditions = vec![];
+        collect_field_conditions(filter, &mut field_conditions)?;
+        let field_checkers = field_conditions
+            .into_iter()
+            .map(|field_condition| {
+                let checker = self
+                    .field_indexes
+                    .get(&field_condition.key)?
+                    .iter()
+                    .find_map(|index| field_condition_index(index, field_condition))?;
+                Some((field_condition, checker))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Box::new(move |point_id| {
+            let elements = match self.point_elements.get(point_id as usize) {
+                Some(elements) => elements,
+                None => return false,
+            };
+            elements.iter().any(|&element_id| {
+                let checker = |condition: &Condition| match condition {
+                    Condition::Field(field_condition) => field_checkers
+                        .iter()
+                        .find(|(condition, _)| std::ptr::eq(*condition, field_condition))
+                        .map_or(false, |(_, checker)| checker(element_id)),
+                    Condition::HasId(has_id) => check_has_id(has_id, point_id),
+                    _ => unreachable!("only field and has_id conditions are collected"),
+                };
+                check_filter(&checker, filter)
+            })
+        }))
+    }
+}
+
+/// Field conditions of the filter. Return `None` if there are conditions,
+/// which depend on the element, but can't be checked with field indexes
+fn collect_field_conditions<'a>(
+    filter: &'a Filter,
+    conditions: &mut Vec<&'a FieldCondition>,
+) -> Option<()> {
+    let all_conditions = filter
+        .should
+        .iter()
+        .chain(filter.must.iter())
+        .chain(filter.must_not.iter())
+        .flatten()
+        .chain(
+            filter
+                .min_should
+                .iter()
+                .flat_map(|MinShould { conditions, .. }| conditions),
+        );
+    for condition in all_conditions {
+        match condition {
+            Condition::Field(field_condition) => conditions.push(field_condition),
+            Condition::Filter(filter) => collect_field_conditions(filter, conditions)?,
+            Condition::HasId(_) => {}
+            Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasField(_)
+            | Condition::Nested(_) => return None,
+        }
+    }
+    Some(())
+}
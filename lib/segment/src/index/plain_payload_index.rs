@@ -16,8 +16,8 @@ use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::payload_storage::{ConditionCheckerSS, FilterContext};
 use crate::types::{
-    Filter, Payload, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointOffsetType,
-    SearchParams, VectorElementType,
+    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    PointOffsetType, QueryExplain, QueryStrategy, SearchParams, VectorElementType,
 };
 use crate::vector_storage::{ScoredPointOffset, VectorStorageSS};
 
@@ -71,21 +71,20 @@ impl PlainPayloadIndex {
 }
 
 impl PayloadIndex for PlainPayloadIndex {
-    fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadSchemaType> {
+    fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
         self.config.indexed_fields.clone()
     }
 
     fn set_indexed(
         &mut self,
         field: PayloadKeyTypeRef,
-        payload_type: PayloadSchemaType,
+        payload_schema: PayloadFieldSchema,
     ) -> OperationResult<()> {
-        if self
+        let previous_schema = self
             .config
             .indexed_fields
-            .insert(field.to_owned(), payload_type)
-            .is_none()
-        {
+            .insert(field.to_owned(), payload_schema.clone());
+        if previous_schema.as_ref() != Some(&payload_schema) {
             return self.save_config();
         }
 
@@ -142,6 +141,15 @@ impl PayloadIndex for PlainPayloadIndex {
         todo!()
     }
 
+    fn assign_by_key(
+        &mut self,
+        _point_id: PointOffsetType,
+        _payload: &Payload,
+        _key: PayloadKeyTypeRef,
+    ) -> OperationResult<()> {
+        todo!()
+    }
+
     fn payload(&self, _point_id: PointOffsetType) -> OperationResult<Payload> {
         todo!()
     }
@@ -191,23 +199,67 @@ impl PlainIndex {
     }
 }
 
+/// Score points, which satisfy the filter, without the use of vector index.
+/// Points are selected with the payload index if possible.
+pub fn search_plain_filtered(
+    vector_storage: &VectorStorageSS,
+    payload_index: &StructPayloadIndex,
+    vector: &[VectorElementType],
+    filter: &Filter,
+    query_cardinality: &CardinalityEstimation,
+    top: usize,
+    explain: &mut QueryExplain,
+) -> Vec<ScoredPointOffset> {
+    let mut matched_points = 0;
+    let result = {
+        let mut filtered_ids = payload_index
+            .query_points(filter)
+            .inspect(|_| matched_points += 1);
+        vector_storage.score_points(vector, &mut filtered_ids, top)
+    };
+
+    if query_cardinality.primary_clauses.is_empty() {
+        explain.strategy = Some(QueryStrategy::FilteredScan);
+        explain.visited_nodes += payload_index.total_points();
+    } else {
+        explain.strategy = Some(QueryStrategy::PayloadIndex);
+        explain.visited_nodes += matched_points;
+    }
+    explain.distance_computations += matched_points;
+    result
+}
+
 impl VectorIndex for PlainIndex {
-    fn search(
+    fn search_explain(
         &self,
         vector: &[VectorElementType],
         filter: Option<&Filter>,
         top: usize,
         _params: Option<&SearchParams>,
+        explain: &mut QueryExplain,
     ) -> Vec<ScoredPointOffset> {
         match filter {
             Some(filter) => {
                 let borrowed_payload_index = self.payload_index.borrow();
-                let mut filtered_ids = borrowed_payload_index.query_points(filter);
-                self.vector_storage
-                    .borrow()
-                    .score_points(vector, &mut filtered_ids, top)
+                let query_cardinality = borrowed_payload_index.estimate_cardinality(filter);
+                explain.cardinality = Some((&query_cardinality).into());
+                search_plain_filtered(
+                    &*self.vector_storage.borrow(),
+                    &borrowed_payload_index,
+                    vector,
+                    filter,
+                    &query_cardinality,
+                    top,
+                    explain,
+                )
+            }
+            None => {
+                let vector_storage = self.vector_storage.borrow();
+                explain.strategy = Some(QueryStrategy::FullScan);
+                explain.visited_nodes += vector_storage.vector_count();
+                explain.distance_computations += vector_storage.vector_count();
+                vector_storage.score_all(vector, top)
             }
-            None => self.vector_storage.borrow().score_all(vector, top),
         }
     }
 
@@ -8,7 +8,7 @@ use std::cmp::{max, min};
 use itertools::Itertools;
 
 use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
-use crate::types::{Condition, Filter};
+use crate::types::{Condition, Filter, MinShould};
 
 pub fn combine_should_estimations(
     estimations: &[CardinalityEstimation],
@@ -38,6 +38,66 @@ pub fn combine_should_estimations(
     }
 }
 
+/// Estimate number of points, which satisfy at least `min_count` of conditions.
+///
+/// Expected value assumes conditions to be independent.
+/// Bounds are based on the total amount of condition hits: each matching point gives
+/// at least `min_count` hits, while any other point gives at most `min_count - 1` hits.
+pub fn combine_min_should_estimations(
+    estimations: &[CardinalityEstimation],
+    min_count: usize,
+    total: usize,
+) -> CardinalityEstimation {
+    if min_count == 0 {
+        return CardinalityEstimation::exact(total);
+    }
+    if min_count > estimations.len() {
+        return CardinalityEstimation::exact(0);
+    }
+    if min_count == 1 {
+        return combine_should_estimations(estimations, total);
+    }
+
+    // Every matching point satisfies at least one condition, so `should` clauses are valid
+    let clauses = combine_should_estimations(estimations, total).primary_clauses;
+
+    // Probability of having exactly `n` hits, the last element accumulates `min_count` or more
+    let mut hits_prob = vec![0.0; min_count + 1];
+    hits_prob[0] = 1.0;
+    for estimation in estimations {
+        let hit_prob = estimation.exp as f64 / total as f64;
+        for hits in (0..=min_count).rev() {
+            let keep_prob = if hits == min_count {
+                hits_prob[hits]
+            } else {
+                hits_prob[hits] * (1.0 - hit_prob)
+            };
+            let gain_prob = if hits > 0 {
+                hits_prob[hits - 1] * hit_prob
+            } else {
+                0.0
+            };
+            hits_prob[hits] = keep_prob + gain_prob;
+        }
+    }
+    let expected_count = (hits_prob[min_count] * (total as f64)).round() as usize;
+
+    let min_hits: usize = estimations.iter().map(|x| x.min).sum();
+    let max_hits: usize = estimations.iter().map(|x| x.max).sum();
+    let max_estimation = min(max_hits / min_count, total);
+    let min_estimation = min(
+        min_hits.saturating_sub((min_count - 1) * total) / (estimations.len() - min_count + 1),
+        max_estimation,
+    );
+
+    CardinalityEstimation {
+        primary_clauses: clauses,
+        min: min_estimation,
+        exp: expected_count.clamp(min_estimation, max_estimation),
+        max: max_estimation,
+    }
+}
+
 pub fn combine_must_estimations(
     estimations: &[CardinalityEstimation],
     total: usize,
@@ -117,6 +177,17 @@ where
             }
         }
     }
+    match &filter.min_should {
+        None => {}
+        Some(MinShould {
+            conditions,
+            min_count,
+        }) => {
+            filter_estimations.push(estimate_min_should(
+                estimator, conditions, *min_count, total,
+            ));
+        }
+    }
 
     combine_must_estimations(&filter_estimations, total)
 }
@@ -134,6 +205,20 @@ where
     combine_should_estimations(&should_estimations, total)
 }
 
+fn estimate_min_should<F>(
+    estimator: &F,
+    conditions: &[Condition],
+    min_count: usize,
+    total: usize,
+) -> CardinalityEstimation
+where
+    F: Fn(&Condition) -> CardinalityEstimation,
+{
+    let estimate = |x| estimate_condition(estimator, x, total);
+    let min_should_estimations = conditions.iter().map(estimate).collect_vec();
+    combine_min_should_estimations(&min_should_estimations, min_count, total)
+}
+
 fn estimate_must<F>(estimator: &F, conditions: &[Condition], total: usize) -> CardinalityEstimation
 where
     F: Fn(&Condition) -> CardinalityEstimation,
@@ -186,6 +271,7 @@ mod tests {
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
             values_count: None,
         })
     }
@@ -193,6 +279,8 @@ mod tests {
     fn test_estimator(condition: &Condition) -> CardinalityEstimation {
         match condition {
             Condition::Filter(_) => panic!("unexpected Filter"),
+            Condition::Nested(_) => panic!("unexpected Nested"),
+            Condition::IsNull(_) | Condition::HasField(_) => CardinalityEstimation::unknown(TOTAL),
             Condition::Field(field) => match field.key.as_str() {
                 "color" => CardinalityEstimation {
                     primary_clauses: vec![PrimaryCondition::Condition(field.clone())],
@@ -253,6 +341,7 @@ mod tests {
                 test_condition("un-indexed".to_owned()),
             ]),
             must_not: None,
+            min_should: None,
         };
 
         let estimation = estimate_filter(&test_estimator, &query, TOTAL);
@@ -261,6 +350,8 @@ mod tests {
             PrimaryCondition::Condition(field) => assert_eq!(&field.key, "size"),
             PrimaryCondition::Ids(_) => panic!(),
             PrimaryCondition::IsEmpty(_) => panic!(),
+            PrimaryCondition::IsNull(_) => panic!(),
+            PrimaryCondition::HasField(_) => panic!(),
         }
         assert!(estimation.max <= TOTAL);
         assert!(estimation.exp <= estimation.max);
@@ -276,6 +367,7 @@ mod tests {
             ]),
             must: None,
             must_not: None,
+            min_should: None,
         };
 
         let estimation = estimate_filter(&test_estimator, &query, TOTAL);
@@ -295,6 +387,7 @@ mod tests {
             ]),
             must: None,
             must_not: None,
+            min_should: None,
         };
 
         let estimation = estimate_filter(&test_estimator, &query, TOTAL);
@@ -316,6 +409,7 @@ mod tests {
                         test_condition("size".to_owned()),
                     ]),
                     must_not: None,
+                    min_should: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
@@ -324,12 +418,14 @@ mod tests {
                         test_condition("size".to_owned()),
                     ]),
                     must_not: None,
+                    min_should: None,
                 }),
             ]),
             must: None,
             must_not: Some(vec![Condition::HasId(HasIdCondition {
                 has_id: HashSet::from_iter([1, 2, 3, 4, 5].into_iter().map(|x| x.into())),
             })]),
+            min_should: None,
         };
 
         let estimation = estimate_filter(&test_estimator, &query, TOTAL);
@@ -351,6 +447,7 @@ mod tests {
                         test_condition("size".to_owned()),
                     ]),
                     must_not: None,
+                    min_should: None,
                 }),
                 Condition::Filter(Filter {
                     must: None,
@@ -359,11 +456,13 @@ mod tests {
                         test_condition("size".to_owned()),
                     ]),
                     must_not: None,
+                    min_should: None,
                 }),
             ]),
             must_not: Some(vec![Condition::HasId(HasIdCondition {
                 has_id: HashSet::from_iter([1, 2, 3, 4, 5].into_iter().map(|x| x.into())),
             })]),
+            min_should: None,
         };
 
         let estimation = estimate_filter(&test_estimator, &query, TOTAL);
@@ -374,11 +473,39 @@ mod tests {
             }
             PrimaryCondition::Ids(_) => panic!("Should not go here"),
             PrimaryCondition::IsEmpty(_) => panic!("Should not go here"),
+            PrimaryCondition::IsNull(_) => panic!("Should not go here"),
+            PrimaryCondition::HasField(_) => panic!("Should not go here"),
         });
         assert!(estimation.max <= TOTAL);
         assert!(estimation.exp <= estimation.max);
         assert!(estimation.min <= estimation.exp);
     }
+    #[test]
+    fn min_should_estimation_query_test() {
+        let query = |min_count| Filter {
+            should: None,
+            must: None,
+            must_not: None,
+            min_should: Some(MinShould {
+                conditions: vec![
+                    test_condition("color".to_owned()),
+                    test_condition("size".to_owned()),
+                    test_condition("price".to_owned()),
+                ],
+                min_count,
+            }),
+        };
+
+        let estimation = estimate_filter(&test_estimator, &query(2), TOTAL);
+        assert_eq!(estimation.primary_clauses.len(), 3);
+        assert_eq!(estimation.min, 0);
+        assert_eq!(estimation.exp, 24);
+        assert_eq!(estimation.max, 210);
+
+        let estimation = estimate_filter(&test_estimator, &query(4), TOTAL);
+        assert_eq!(estimation.max, 0);
+    }
+
     #[test]
     fn test_combine_must_estimations() {
         let estimations = vec![CardinalityEstimation {
@@ -1,19 +1,26 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::null_index::NullIndex;
 use crate::index::field_index::FieldIndex;
+use crate::index::nested_index::NestedIndexesMap;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::optimizer::IndexesMap;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
-use crate::payload_storage::query_checker::{check_field_condition, check_is_empty_condition};
+use crate::payload_storage::query_checker::{
+    check_has_field_condition, check_is_empty_condition, check_is_null_condition,
+    check_nested_condition, FieldConditionChecker,
+};
 use crate::types::{
-    Condition, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoRadius, Match, MatchValue,
-    PointOffsetType, Range, ValueVariants,
+    parse_uuid, AnyVariants, Condition, FieldCondition, Filter, FloatPayloadType, GeoBoundingBox,
+    GeoPolygon, GeoRadius, HasIdCondition, Match, MatchAny, MatchExcept, MatchKeywordRange,
+    MatchText, MatchValue, PointOffsetType, Range, UuidIntType, ValueVariants,
 };
 
 pub fn condition_converter<'a>(
     condition: &'a Condition,
     field_indexes: &'a IndexesMap,
+    nested_indexes: &'a NestedIndexesMap,
     payload_provider: PayloadProvider,
     id_tracker: &IdTrackerSS,
 ) -> ConditionCheckerFn<'a> {
@@ -27,20 +34,38 @@ pub fn condition_converter<'a>(
                     .next()
             })
             .unwrap_or_else(|| {
+                let checker = FieldConditionChecker::new(field_condition);
                 Box::new(move |point_id| {
-                    payload_provider.with_payload(point_id, |payload| {
-                        check_field_condition(field_condition, &payload)
-                    })
+                    payload_provider.with_payload(point_id, |payload| checker.check(&payload))
                 })
             }),
-        // ToDo: It might be possible to make this condition faster by using index to check
-        //       if there is any value. But if value if not found,
-        //       it does not mean that there are no values in payload
-        Condition::IsEmpty(is_empty) => Box::new(move |point_id| {
-            payload_provider.with_payload(point_id, |payload| {
-                check_is_empty_condition(is_empty, &payload)
-            })
-        }),
+        // Presence of the indexed fields is tracked by the null index
+        Condition::IsEmpty(is_empty) => match null_index(field_indexes, &is_empty.is_empty.key) {
+            Some(null_index) => Box::new(move |point_id| null_index.is_empty(point_id)),
+            None => Box::new(move |point_id| {
+                payload_provider.with_payload(point_id, |payload| {
+                    check_is_empty_condition(is_empty, &payload)
+                })
+            }),
+        },
+        Condition::IsNull(is_null) => match null_index(field_indexes, &is_null.is_null.key) {
+            Some(null_index) => Box::new(move |point_id| null_index.is_null(point_id)),
+            None => Box::new(move |point_id| {
+                payload_provider.with_payload(point_id, |payload| {
+                    check_is_null_condition(is_null, &payload)
+                })
+            }),
+        },
+        Condition::HasField(has_field) => {
+            match null_index(field_indexes, &has_field.has_field.key) {
+                Some(null_index) => Box::new(move |point_id| null_index.has_field(point_id)),
+                None => Box::new(move |point_id| {
+                    payload_provider.with_payload(point_id, |payload| {
+                        check_has_field_condition(has_field, &payload)
+                    })
+                }),
+            }
+        }
         // ToDo: It might be possible to make this condition faster by using `VisitedPool` instead of HashSet
         Condition::HasId(has_id) => {
             let segment_ids: HashSet<_> = has_id
@@ -50,10 +75,102 @@ pub fn condition_converter<'a>(
                 .collect();
             Box::new(move |point_id| segment_ids.contains(&point_id))
         }
+        // Elements of the array are matched with the element indexes, if all conditions
+        // of the nested filter could be checked with them. Otherwise the payload is used
+        Condition::Nested(nested) => {
+            let index_checker = nested_indexes
+                .get(&nested.nested.key)
+                .and_then(|nested_index| {
+                    let segment_ids = nested_has_id_segment_ids(&nested.nested.filter, id_tracker);
+                    nested_index.filter_checker(&nested.nested.filter, move |has_id, point_id| {
+                        check_nested_has_id(&segment_ids, has_id, point_id)
+                    })
+                });
+            if let Some(index_checker) = index_checker {
+                return index_checker;
+            }
+            let segment_ids = nested_has_id_segment_ids(&nested.nested.filter, id_tracker);
+            Box::new(move |point_id| {
+                let check_has_id =
+                    |has_id: &HasIdCondition| check_nested_has_id(&segment_ids, has_id, point_id);
+                payload_provider.with_payload(point_id, |payload| {
+                    check_nested_condition(nested, &payload.0, &check_has_id)
+                })
+            })
+        }
         Condition::Filter(_) => unreachable!(),
     }
 }
 
+/// Index of the presence of the field, if the field is indexed
+pub fn null_index<'a>(field_indexes: &'a IndexesMap, key: &str) -> Option<&'a NullIndex> {
+    field_indexes
+        .get(key)?
+        .iter()
+        .find_map(|index| index.as_null_index())
+}
+
+/// Segment ids of the points, selected by each `HasId` condition of the nested filter
+fn nested_has_id_segment_ids<'a>(
+    filter: &'a Filter,
+    id_tracker: &IdTrackerSS,
+) -> Vec<(&'a HasIdCondition, HashSet<PointOffsetType>)> {
+    let mut has_id_conditions = vec![];
+    collect_has_id_conditions(filter, &mut has_id_conditions);
+    has_id_conditions
+        .into_iter()
+        .map(|has_id| {
+            let segment_ids = has_id
+                .has_id
+                .iter()
+                .filter_map(|external_id| id_tracker.internal_id(*external_id))
+                .collect();
+            (has_id, segment_ids)
+        })
+        .collect()
+}
+
+fn check_nested_has_id(
+    segment_ids: &[(&HasIdCondition, HashSet<PointOffsetType>)],
+    has_id: &HasIdCondition,
+    point_id: PointOffsetType,
+) -> bool {
+    segment_ids
+        .iter()
+        .find(|(condition, _)| std::ptr::eq(*condition, has_id))
+        .map_or(false, |(_, ids)| ids.contains(&point_id))
+}
+
+/// `HasId` conditions of the nested filter refer to the point itself,
+/// so external ids have to be converted into segment ids beforehand
+fn collect_has_id_conditions<'a>(filter: &'a Filter, conditions: &mut Vec<&'a HasIdCondition>) {
+    let all_conditions = filter
+        .should
+        .iter()
+        .chain(filter.must.iter())
+        .chain(filter.must_not.iter())
+        .chain(
+            filter
+                .min_should
+                .iter()
+                .map(|min_should| &min_should.conditions),
+        )
+        .flatten();
+    for condition in all_conditions {
+        match condition {
+            Condition::HasId(has_id) => conditions.push(has_id),
+            Condition::Nested(nested) => {
+                collect_has_id_conditions(&nested.nested.filter, conditions)
+            }
+            Condition::Filter(filter) => collect_has_id_conditions(filter, conditions),
+            Condition::Field(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasField(_) => {}
+        }
+    }
+}
+
 pub fn field_condition_index<'a>(
     index: &'a FieldIndex,
     field_condition: &FieldCondition,
@@ -90,6 +207,14 @@ pub fn field_condition_index<'a>(
         return Some(checker);
     }
 
+    if let Some(checker) = field_condition
+        .geo_polygon
+        .as_ref()
+        .and_then(|cond| get_geo_polygon_checkers(index, cond))
+    {
+        return Some(checker);
+    }
+
     None
 }
 
@@ -106,6 +231,14 @@ pub fn get_geo_radius_checkers(
                     .any(|geo_point| geo_radius.check_point(geo_point.lon, geo_point.lat)),
             }
         })),
+        FieldIndex::GeoMmapIndex(geo_index) => Some(Box::new(move |point_id: PointOffsetType| {
+            match geo_index.get_values(point_id) {
+                None => false,
+                Some(values) => values
+                    .iter()
+                    .any(|geo_point| geo_radius.check_point(geo_point.lon, geo_point.lat)),
+            }
+        })),
         _ => None,
     }
 }
@@ -123,6 +256,35 @@ pub fn get_geo_bounding_box_checkers(
                     .any(|geo_point| geo_bounding_box.check_point(geo_point.lon, geo_point.lat)),
             }
         })),
+        FieldIndex::GeoMmapIndex(geo_index) => Some(Box::new(move |point_id: PointOffsetType| {
+            match geo_index.get_values(point_id) {
+                None => false,
+                Some(values) => values
+                    .iter()
+                    .any(|geo_point| geo_bounding_box.check_point(geo_point.lon, geo_point.lat)),
+            }
+        })),
+        _ => None,
+    }
+}
+
+pub fn get_geo_polygon_checkers<'a>(
+    index: &'a FieldIndex,
+    geo_polygon: &GeoPolygon,
+) -> Option<ConditionCheckerFn<'a>> {
+    match index {
+        FieldIndex::GeoIndex(geo_index) => {
+            let polygon_wrapper = geo_polygon.convert();
+            Some(Box::new(move |point_id: PointOffsetType| {
+                geo_index.check_polygon(point_id, &polygon_wrapper)
+            }))
+        }
+        FieldIndex::GeoMmapIndex(geo_index) => {
+            let polygon_wrapper = geo_polygon.convert();
+            Some(Box::new(move |point_id: PointOffsetType| {
+                geo_index.check_polygon(point_id, &polygon_wrapper)
+            }))
+        }
         _ => None,
     }
 }
@@ -138,22 +300,52 @@ pub fn get_range_checkers(index: &FieldIndex, range: Range) -> Option<ConditionC
                     .any(|i| range.check_range(i as FloatPayloadType)),
             }
         })),
+        FieldIndex::IntMmapIndex(num_index) => Some(Box::new(move |point_id: PointOffsetType| {
+            match num_index.get_values(point_id) {
+                None => false,
+                Some(values) => values
+                    .iter()
+                    .copied()
+                    .any(|i| range.check_range(i as FloatPayloadType)),
+            }
+        })),
         FieldIndex::FloatIndex(num_index) => Some(Box::new(move |point_id: PointOffsetType| {
             match num_index.get_values(point_id) {
                 None => false,
                 Some(values) => values.iter().copied().any(|i| range.check_range(i)),
             }
         })),
+        FieldIndex::FloatMmapIndex(num_index) => {
+            Some(Box::new(move |point_id: PointOffsetType| {
+                match num_index.get_values(point_id) {
+                    None => false,
+                    Some(values) => values.iter().copied().any(|i| range.check_range(i)),
+                }
+            }))
+        }
+        FieldIndex::DatetimeIndex(num_index) => Some(Box::new(move |point_id: PointOffsetType| {
+            match num_index.get_values(point_id) {
+                None => false,
+                Some(values) => values.iter().any(|i| range.check_range(i.0)),
+            }
+        })),
+        FieldIndex::DatetimeMmapIndex(num_index) => {
+            Some(Box::new(move |point_id: PointOffsetType| {
+                match num_index.get_values(point_id) {
+                    None => false,
+                    Some(values) => values.iter().any(|i| range.check_range(i.0)),
+                }
+            }))
+        }
         _ => None,
     }
 }
 
 pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<ConditionCheckerFn> {
-    if let Match::Value(MatchValue {
-        value: value_variant,
-    }) = cond_match
-    {
-        match (value_variant, index) {
+    match cond_match {
+        Match::Value(MatchValue {
+            value: value_variant,
+        }) => match (value_variant, index) {
             (ValueVariants::Keyword(keyword), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     match index.get_values(point_id) {
@@ -162,6 +354,14 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                     }
                 }))
             }
+            (ValueVariants::Keyword(keyword), FieldIndex::KeywordMmapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|k| k == &keyword),
+                    }
+                }))
+            }
             (ValueVariants::Integer(value), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     match index.get_values(point_id) {
@@ -170,9 +370,209 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                     }
                 }))
             }
+            (ValueVariants::Integer(value), FieldIndex::IntMapMmapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|i| i == &value),
+                    }
+                }))
+            }
+            (ValueVariants::Bool(value), FieldIndex::BoolIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index.check_value(point_id, value)
+                }))
+            }
+            (ValueVariants::Keyword(keyword), FieldIndex::UuidIndex(index)) => {
+                let uuid = parse_uuid(&keyword);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match (index.get_values(point_id), uuid) {
+                        (Some(values), Some(uuid)) => values.iter().any(|u| u == &uuid),
+                        _ => false,
+                    }
+                }))
+            }
+            (ValueVariants::Keyword(keyword), FieldIndex::UuidMmapIndex(index)) => {
+                let uuid = parse_uuid(&keyword);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match (index.get_values(point_id), uuid) {
+                        (Some(values), Some(uuid)) => values.iter().any(|u| u == &uuid),
+                        _ => false,
+                    }
+                }))
+            }
             (_, _) => None,
-        }
-    } else {
-        None
+        },
+        Match::Any(MatchAny { any }) => match (any, index) {
+            (AnyVariants::Keywords(keywords), FieldIndex::KeywordIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|k| keywords.contains(k)),
+                    }
+                }))
+            }
+            (AnyVariants::Keywords(keywords), FieldIndex::KeywordMmapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|k| keywords.contains(k)),
+                    }
+                }))
+            }
+            (AnyVariants::Integers(integers), FieldIndex::IntMapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|i| integers.contains(i)),
+                    }
+                }))
+            }
+            (AnyVariants::Integers(integers), FieldIndex::IntMapMmapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|i| integers.contains(i)),
+                    }
+                }))
+            }
+            (AnyVariants::Keywords(keywords), FieldIndex::UuidIndex(index)) => {
+                let uuids: BTreeSet<UuidIntType> =
+                    keywords.iter().filter_map(|k| parse_uuid(k)).collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|u| uuids.contains(u)),
+                    }
+                }))
+            }
+            (AnyVariants::Keywords(keywords), FieldIndex::UuidMmapIndex(index)) => {
+                let uuids: BTreeSet<UuidIntType> =
+                    keywords.iter().filter_map(|k| parse_uuid(k)).collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|u| uuids.contains(u)),
+                    }
+                }))
+            }
+            (_, _) => None,
+        },
+        Match::Except(MatchExcept { except }) => match (except, index) {
+            (AnyVariants::Keywords(keywords), FieldIndex::KeywordIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|k| !keywords.contains(k)),
+                    }
+                }))
+            }
+            (AnyVariants::Keywords(keywords), FieldIndex::KeywordMmapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|k| !keywords.contains(k)),
+                    }
+                }))
+            }
+            (AnyVariants::Integers(integers), FieldIndex::IntMapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|i| !integers.contains(i)),
+                    }
+                }))
+            }
+            (AnyVariants::Integers(integers), FieldIndex::IntMapMmapIndex(index)) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|i| !integers.contains(i)),
+                    }
+                }))
+            }
+            (AnyVariants::Keywords(keywords), FieldIndex::UuidIndex(index)) => {
+                let uuids: BTreeSet<UuidIntType> =
+                    keywords.iter().filter_map(|k| parse_uuid(k)).collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|u| !uuids.contains(u)),
+                    }
+                }))
+            }
+            (AnyVariants::Keywords(keywords), FieldIndex::UuidMmapIndex(index)) => {
+                let uuids: BTreeSet<UuidIntType> =
+                    keywords.iter().filter_map(|k| parse_uuid(k)).collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|u| !uuids.contains(u)),
+                    }
+                }))
+            }
+            (_, _) => None,
+        },
+        Match::Text(MatchText { text }) => match index {
+            FieldIndex::FullTextIndex(full_text_index) => {
+                let parsed_query = full_text_index.parse_query(&text);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    full_text_index.check_match(&parsed_query, point_id)
+                }))
+            }
+            _ => None,
+        },
+        Match::Prefix(prefix) => match index {
+            FieldIndex::KeywordIndex(index) => Some(Box::new(move |point_id: PointOffsetType| {
+                match index.get_values(point_id) {
+                    None => false,
+                    Some(values) => values.iter().any(|k| prefix.check(k)),
+                }
+            })),
+            FieldIndex::KeywordMmapIndex(index) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|k| prefix.check(k)),
+                    }
+                }))
+            }
+            _ => None,
+        },
+        Match::KeywordRange(MatchKeywordRange { keyword_range }) => match index {
+            FieldIndex::KeywordIndex(index) => Some(Box::new(move |point_id: PointOffsetType| {
+                match index.get_values(point_id) {
+                    None => false,
+                    Some(values) => values.iter().any(|k| keyword_range.check(k)),
+                }
+            })),
+            FieldIndex::KeywordMmapIndex(index) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|k| keyword_range.check(k)),
+                    }
+                }))
+            }
+            _ => None,
+        },
+        Match::Wildcard(wildcard) => match index {
+            FieldIndex::KeywordIndex(index) => Some(Box::new(move |point_id: PointOffsetType| {
+                match index.get_values(point_id) {
+                    None => false,
+                    Some(values) => values.iter().any(|k| wildcard.check(k)),
+                }
+            })),
+            FieldIndex::KeywordMmapIndex(index) => {
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    match index.get_values(point_id) {
+                        None => false,
+                        Some(values) => values.iter().any(|k| wildcard.check(k)),
+                    }
+                }))
+            }
+            _ => None,
+        },
+        _ => None,
     }
 }
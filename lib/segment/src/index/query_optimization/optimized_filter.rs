@@ -15,12 +15,20 @@ pub struct OptimizedFilter<'a> {
     pub must: Option<Vec<OptimizedCondition<'a>>>,
     /// All conditions must NOT match
     pub must_not: Option<Vec<OptimizedCondition<'a>>>,
+    /// At least `min_count` of those conditions should match
+    pub min_should: Option<OptimizedMinShould<'a>>,
+}
+
+pub struct OptimizedMinShould<'a> {
+    pub conditions: Vec<OptimizedCondition<'a>>,
+    pub min_count: usize,
 }
 
 pub fn check_optimized_filter(filter: &OptimizedFilter, point_id: PointOffsetType) -> bool {
     check_should(&filter.should, point_id)
         && check_must(&filter.must, point_id)
         && check_must_not(&filter.must_not, point_id)
+        && check_min_should(&filter.min_should, point_id)
 }
 
 fn check_condition(condition: &OptimizedCondition, point_id: PointOffsetType) -> bool {
@@ -38,6 +46,23 @@ fn check_should(should: &Option<Vec<OptimizedCondition>>, point_id: PointOffsetT
     }
 }
 
+fn check_min_should(min_should: &Option<OptimizedMinShould>, point_id: PointOffsetType) -> bool {
+    match min_should {
+        None => true,
+        Some(OptimizedMinShould {
+            conditions,
+            min_count,
+        }) => {
+            conditions
+                .iter()
+                .filter(|condition| check_condition(condition, point_id))
+                .take(*min_count)
+                .count()
+                == *min_count
+        }
+    }
+}
+
 fn check_must(must: &Option<Vec<OptimizedCondition>>, point_id: PointOffsetType) -> bool {
     let check = |condition| check_condition(condition, point_id);
     match must {
@@ -5,13 +5,17 @@ use itertools::Itertools;
 
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
+use crate::index::nested_index::NestedIndexesMap;
 use crate::index::query_estimator::{
-    combine_must_estimations, combine_should_estimations, invert_estimation,
+    combine_min_should_estimations, combine_must_estimations, combine_should_estimations,
+    invert_estimation,
 };
 use crate::index::query_optimization::condition_converter::condition_converter;
-use crate::index::query_optimization::optimized_filter::{OptimizedCondition, OptimizedFilter};
+use crate::index::query_optimization::optimized_filter::{
+    OptimizedCondition, OptimizedFilter, OptimizedMinShould,
+};
 use crate::index::query_optimization::payload_provider::PayloadProvider;
-use crate::types::{Condition, Filter, PayloadKeyType};
+use crate::types::{Condition, Filter, MinShould, PayloadKeyType};
 
 pub type IndexesMap = HashMap<PayloadKeyType, Vec<FieldIndex>>;
 
@@ -39,6 +43,7 @@ pub fn optimize_filter<'a, F>(
     filter: &'a Filter,
     id_tracker: &IdTrackerSS,
     field_indexes: &'a IndexesMap,
+    nested_indexes: &'a NestedIndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
     total: usize,
@@ -55,6 +60,7 @@ where
                     conditions,
                     id_tracker,
                     field_indexes,
+                    nested_indexes,
                     payload_provider.clone(),
                     estimator,
                     total,
@@ -71,6 +77,7 @@ where
                     conditions,
                     id_tracker,
                     field_indexes,
+                    nested_indexes,
                     payload_provider.clone(),
                     estimator,
                     total,
@@ -87,6 +94,7 @@ where
                     conditions,
                     id_tracker,
                     field_indexes,
+                    nested_indexes,
                     payload_provider.clone(),
                     estimator,
                     total,
@@ -97,6 +105,19 @@ where
                 None
             }
         }),
+        min_should: filter.min_should.as_ref().map(|min_should| {
+            let (optimized_min_should, estimation) = optimize_min_should(
+                min_should,
+                id_tracker,
+                field_indexes,
+                nested_indexes,
+                payload_provider.clone(),
+                estimator,
+                total,
+            );
+            filter_estimations.push(estimation);
+            optimized_min_should
+        }),
     };
 
     (
@@ -109,6 +130,7 @@ fn convert_conditions<'a, F>(
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
     field_indexes: &'a IndexesMap,
+    nested_indexes: &'a NestedIndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
     total: usize,
@@ -124,6 +146,7 @@ where
                     filter,
                     id_tracker,
                     field_indexes,
+                    nested_indexes,
                     payload_provider.clone(),
                     estimator,
                     total,
@@ -135,6 +158,7 @@ where
                 let condition_checker = condition_converter(
                     condition,
                     field_indexes,
+                    nested_indexes,
                     payload_provider.clone(),
                     id_tracker,
                 );
@@ -148,6 +172,7 @@ fn optimize_should<'a, F>(
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
     field_indexes: &'a IndexesMap,
+    nested_indexes: &'a NestedIndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
     total: usize,
@@ -159,6 +184,7 @@ where
         conditions,
         id_tracker,
         field_indexes,
+        nested_indexes,
         payload_provider,
         estimator,
         total,
@@ -170,10 +196,45 @@ where
     (conditions, combine_should_estimations(&estimations, total))
 }
 
+fn optimize_min_should<'a, F>(
+    min_should: &'a MinShould,
+    id_tracker: &IdTrackerSS,
+    field_indexes: &'a IndexesMap,
+    nested_indexes: &'a NestedIndexesMap,
+    payload_provider: PayloadProvider,
+    estimator: &F,
+    total: usize,
+) -> (OptimizedMinShould<'a>, CardinalityEstimation)
+where
+    F: Fn(&Condition) -> CardinalityEstimation,
+{
+    let mut converted = convert_conditions(
+        &min_should.conditions,
+        id_tracker,
+        field_indexes,
+        nested_indexes,
+        payload_provider,
+        estimator,
+        total,
+    );
+    // More probable conditions first, so enough matches are found earlier
+    converted.sort_by_key(|(_, estimation)| Reverse(estimation.exp));
+    let (conditions, estimations): (Vec<_>, Vec<_>) = converted.into_iter().unzip();
+
+    (
+        OptimizedMinShould {
+            conditions,
+            min_count: min_should.min_count,
+        },
+        combine_min_should_estimations(&estimations, min_should.min_count, total),
+    )
+}
+
 fn optimize_must<'a, F>(
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
     field_indexes: &'a IndexesMap,
+    nested_indexes: &'a NestedIndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
     total: usize,
@@ -185,6 +246,7 @@ where
         conditions,
         id_tracker,
         field_indexes,
+        nested_indexes,
         payload_provider,
         estimator,
         total,
@@ -200,6 +262,7 @@ fn optimize_must_not<'a, F>(
     conditions: &'a [Condition],
     id_tracker: &IdTrackerSS,
     field_indexes: &'a IndexesMap,
+    nested_indexes: &'a NestedIndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
     total: usize,
@@ -211,6 +274,7 @@ where
         conditions,
         id_tracker,
         field_indexes,
+        nested_indexes,
         payload_provider,
         estimator,
         total,
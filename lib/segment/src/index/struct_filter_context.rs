@@ -1,5 +1,6 @@
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
+use crate::index::nested_index::NestedIndexesMap;
 use crate::index::query_optimization::optimized_filter::{check_optimized_filter, OptimizedFilter};
 use crate::index::query_optimization::optimizer::{optimize_filter, IndexesMap};
 use crate::index::query_optimization::payload_provider::PayloadProvider;
@@ -16,6 +17,7 @@ impl<'a> StructFilterContext<'a> {
         id_tracker: &IdTrackerSS,
         payload_provider: PayloadProvider,
         field_indexes: &'a IndexesMap,
+        nested_indexes: &'a NestedIndexesMap,
         estimator: &F,
         total: usize,
     ) -> Self
@@ -26,6 +28,7 @@ impl<'a> StructFilterContext<'a> {
             filter,
             id_tracker,
             field_indexes,
+            nested_indexes,
             payload_provider,
             estimator,
             total,
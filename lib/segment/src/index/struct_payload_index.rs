@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::fs::{create_dir_all, remove_file};
 use std::ops::Deref;
@@ -11,14 +12,18 @@ use schemars::_serde_json::Value;
 
 use crate::common::arc_atomic_ref_cell_iterator::ArcAtomicRefCellIterator;
 use crate::common::rocksdb_operations::open_db_with_existing_cf;
-use crate::entry::entry_point::OperationResult;
+use crate::common::utils::paths_overlap;
+use crate::entry::entry_point::{OperationError, OperationResult};
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::histogram::count_by_interval;
 use crate::index::field_index::index_selector::index_selector;
 use crate::index::field_index::{
     CardinalityEstimation, FieldIndex, PayloadBlockCondition, PrimaryCondition,
 };
+use crate::index::nested_index::{split_element_path, NestedIndex, NestedIndexesMap};
 use crate::index::payload_config::PayloadConfig;
 use crate::index::query_estimator::estimate_filter;
+use crate::index::query_optimization::condition_converter::null_index;
 use crate::index::query_optimization::optimizer::IndexesMap;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::index::struct_filter_context::StructFilterContext;
@@ -27,12 +32,23 @@ use crate::index::PayloadIndex;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::{FilterContext, PayloadStorage};
 use crate::types::{
-    infer_value_type, Condition, FieldCondition, Filter, IsEmptyCondition, Payload, PayloadKeyType,
-    PayloadKeyTypeRef, PayloadSchemaType, PointOffsetType,
+    infer_value_type, parse_datetime_seconds, Condition, FacetValue, FieldCondition, Filter,
+    HasFieldCondition, IsEmptyCondition, IsNullCondition, Match, MatchValue, NestedCondition,
+    NumericAggregation, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadIndexStats,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaParams, PayloadSchemaType, PointOffsetType,
+    ValueVariants,
 };
 
 pub const PAYLOAD_FIELD_INDEX_PATH: &str = "fields";
 
+/// Part of the integer index, which could be disabled by its parameters
+enum IntegerIndexCapability {
+    /// `lookup` - map of values, used for `match` conditions and facets
+    Lookup,
+    /// `range` - ordered values, used for ranges, ordering and aggregations
+    Range,
+}
+
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 pub struct StructPayloadIndex {
     /// Payload storage
@@ -40,11 +56,16 @@ pub struct StructPayloadIndex {
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     /// Indexes, associated with fields
     pub field_indexes: IndexesMap,
+    /// Indexes of the array elements, associated with the array fields.
+    /// Built for the indexed fields of array elements: `key[].field`
+    nested_indexes: NestedIndexesMap,
     config: PayloadConfig,
     /// Root of index persistence dir
     path: PathBuf,
     visited_pool: VisitedPool,
     db: Arc<AtomicRefCell<DB>>,
+    /// On-disk field indexes are only used if the segment is not appendable
+    is_appendable: bool,
 }
 
 impl StructPayloadIndex {
@@ -81,6 +102,66 @@ impl StructPayloadIndex {
         indexes
     }
 
+    /// Whether the condition requires a single keyword of the field with tenant index
+    fn is_tenant_condition(&self, condition: &FieldCondition) -> bool {
+        let is_keyword_match = matches!(
+            &condition.r#match,
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(_),
+            }))
+        );
+        is_keyword_match
+            && self
+                .config
+                .indexed_fields
+                .get(&condition.key)
+                .map_or(false, |payload_schema| payload_schema.is_tenant())
+    }
+
+    /// Condition on a single tenant, which is required by the filter.
+    /// It is looked up in `must` clauses, including the ones of nested filters, and in `should`
+    /// clauses with a single condition, which are equivalent to `must`.
+    /// Conditions on array elements (`nested`) are not considered,
+    /// as the tenant is a field of the point itself.
+    pub fn tenant_condition<'a>(&self, filter: &'a Filter) -> Option<&'a FieldCondition> {
+        let single_should = filter.should.as_ref().filter(|should| should.len() == 1);
+        filter
+            .must
+            .iter()
+            .chain(single_should)
+            .flatten()
+            .find_map(|condition| match condition {
+                Condition::Field(field_condition) if self.is_tenant_condition(field_condition) => {
+                    Some(field_condition)
+                }
+                Condition::Filter(nested_filter) => self.tenant_condition(nested_filter),
+                _ => None,
+            })
+    }
+
+    /// Tenant of the point by the index of the tenant field, or by the payload if it's not indexed.
+    /// The smallest keyword is used for multiple values.
+    pub fn tenant_value(
+        &self,
+        field: PayloadKeyTypeRef,
+        point_id: PointOffsetType,
+    ) -> OperationResult<Option<String>> {
+        if let Some(indexes) = self.field_indexes.get(field) {
+            return Ok(indexes.iter().find_map(|index| index.min_keyword(point_id)));
+        }
+        let payload = self.payload(point_id)?;
+        let tenant = match payload.get_value_cow(field).as_deref() {
+            Some(Value::String(keyword)) => Some(keyword.clone()),
+            Some(Value::Array(values)) => values
+                .iter()
+                .filter_map(Value::as_str)
+                .min()
+                .map(str::to_owned),
+            _ => None,
+        };
+        Ok(tenant)
+    }
+
     fn config_path(&self) -> PathBuf {
         PayloadConfig::get_config_path(&self.path)
     }
@@ -101,20 +182,109 @@ impl StructPayloadIndex {
     fn load_all_fields(&mut self) -> OperationResult<()> {
         let mut field_indexes: IndexesMap = Default::default();
 
-        for (field, payload_type) in &self.config.indexed_fields {
-            let field_index = self.load_from_db(field, payload_type.to_owned())?;
+        for (field, payload_schema) in &self.config.indexed_fields {
+            let field_index = self.load_from_db(field, payload_schema.to_owned())?;
             field_indexes.insert(field.clone(), field_index);
         }
         self.field_indexes = field_indexes;
+        self.nested_indexes = self.load_nested_indexes()?;
+        Ok(())
+    }
+
+    /// Indexed fields of array elements `key[].field`, grouped by the array `key`
+    fn nested_index_fields(
+        &self,
+    ) -> HashMap<PayloadKeyType, Vec<(PayloadKeyType, PayloadFieldSchema)>> {
+        let mut nested_fields: HashMap<PayloadKeyType, Vec<_>> = HashMap::new();
+        for (path, payload_schema) in &self.config.indexed_fields {
+            if let Some((key, field)) = split_element_path(path) {
+                nested_fields
+                    .entry(key.to_owned())
+                    .or_default()
+                    .push((field.to_owned(), payload_schema.clone()));
+            }
+        }
+        nested_fields
+    }
+
+    /// Build index of the array elements from the payload storage
+    fn build_nested_index(
+        &self,
+        key: PayloadKeyTypeRef,
+        fields: &[(PayloadKeyType, PayloadFieldSchema)],
+    ) -> OperationResult<NestedIndex> {
+        let mut nested_index = NestedIndex::new(key, fields, self.db.clone());
+        nested_index.recreate()?;
+        self.payload.borrow().iter(|point_id, point_payload| {
+            nested_index.update_point(point_id, point_payload)?;
+            Ok(true)
+        })?;
+        Ok(nested_index)
+    }
+
+    fn load_nested_indexes(&self) -> OperationResult<NestedIndexesMap> {
+        let mut nested_indexes = NestedIndexesMap::new();
+        for (key, fields) in self.nested_index_fields() {
+            let mut nested_index = NestedIndex::new(&key, &fields, self.db.clone());
+            if !nested_index.load()? {
+                debug!("Index of `{key}[]` elements was not loaded. Building...");
+                nested_index = self.build_nested_index(&key, &fields)?;
+            }
+            nested_indexes.insert(key, nested_index);
+        }
+        Ok(nested_indexes)
+    }
+
+    /// Rebuild index of the array elements after the set of indexed element fields is changed
+    fn rebuild_nested_index(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
+        let key = match split_element_path(field) {
+            Some((key, _)) => key,
+            None => return Ok(()),
+        };
+        if let Some(nested_index) = self.nested_indexes.remove(key) {
+            nested_index.clear()?;
+        }
+        if let Some(fields) = self.nested_index_fields().get(key) {
+            let nested_index = self.build_nested_index(key, fields)?;
+            self.nested_indexes.insert(key.to_owned(), nested_index);
+        }
+        Ok(())
+    }
+
+    /// Re-read array elements of the point, which might be changed by modification of the `key` path
+    fn reindex_nested_elements(
+        &mut self,
+        point_id: PointOffsetType,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()> {
+        if !self
+            .nested_indexes
+            .keys()
+            .any(|nested_key| paths_overlap(nested_key, key))
+        {
+            return Ok(());
+        }
+        let payload = self.payload.borrow().payload(point_id)?;
+        for (nested_key, nested_index) in &mut self.nested_indexes {
+            if paths_overlap(nested_key, key) {
+                nested_index.update_point(point_id, &payload)?;
+            }
+        }
         Ok(())
     }
 
     fn load_from_db(
         &self,
         field: PayloadKeyTypeRef,
-        payload_type: PayloadSchemaType,
+        payload_schema: PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
-        let mut indexes = index_selector(field, &payload_type, self.db.clone());
+        let mut indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            &Self::get_field_index_dir(&self.path),
+            self.is_appendable,
+        );
 
         let mut is_loaded = true;
         for ref mut index in indexes.iter_mut() {
@@ -125,7 +295,7 @@ impl StructPayloadIndex {
         }
         if !is_loaded {
             debug!("Index for `{field}` was not loaded. Building...");
-            indexes = self.build_field_indexes(field, payload_type)?;
+            indexes = self.build_field_indexes(field, payload_schema)?;
         }
 
         Ok(indexes)
@@ -135,6 +305,7 @@ impl StructPayloadIndex {
         payload: Arc<AtomicRefCell<PayloadStorageEnum>>,
         id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
         path: &Path,
+        is_appendable: bool,
     ) -> OperationResult<Self> {
         create_dir_all(path)?;
         let config_path = PayloadConfig::get_config_path(path);
@@ -150,10 +321,12 @@ impl StructPayloadIndex {
             payload,
             id_tracker,
             field_indexes: Default::default(),
+            nested_indexes: Default::default(),
             config,
             path: path.to_owned(),
             visited_pool: Default::default(),
             db,
+            is_appendable,
         };
 
         if !index.config_path().exists() {
@@ -169,32 +342,41 @@ impl StructPayloadIndex {
     pub fn build_field_indexes(
         &self,
         field: PayloadKeyTypeRef,
-        field_type: PayloadSchemaType,
+        payload_schema: PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
         let payload_storage = self.payload.borrow();
-        let mut field_indexes = index_selector(field, &field_type, self.db.clone());
+        let mut field_indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            &Self::get_field_index_dir(&self.path),
+            self.is_appendable,
+        );
         for index in &field_indexes {
             index.recreate()?;
         }
 
         payload_storage.iter(|point_id, point_payload| {
-            let field_value_opt = point_payload.get_value(field);
+            let field_value_opt = point_payload.get_value_cow(field);
             if let Some(field_value) = field_value_opt {
                 for field_index in field_indexes.iter_mut() {
-                    field_index.add_point(point_id, field_value)?;
+                    field_index.add_point(point_id, &field_value)?;
                 }
             }
             Ok(true)
         })?;
+        for field_index in field_indexes.iter_mut() {
+            field_index.finalize()?;
+        }
         Ok(field_indexes)
     }
 
     fn build_and_save(
         &mut self,
         field: PayloadKeyTypeRef,
-        payload_type: PayloadSchemaType,
+        payload_schema: PayloadFieldSchema,
     ) -> OperationResult<()> {
-        let field_indexes = self.build_field_indexes(field, payload_type)?;
+        let field_indexes = self.build_field_indexes(field, payload_schema)?;
         self.field_indexes.insert(field.into(), field_indexes);
         Ok(())
     }
@@ -203,6 +385,330 @@ impl StructPayloadIndex {
         self.id_tracker.borrow().points_count()
     }
 
+    /// Iterate over points ordered by the value of the field, starting from the `start_from` value.
+    /// Uses numeric index of the field if available. Otherwise reads values from the payload,
+    /// which is only suitable for small segments, e.g. temporary segments of the optimizer.
+    /// Integer fields indexed without `range` are rejected.
+    pub fn ordered_points(
+        &self,
+        key: PayloadKeyTypeRef,
+        start_from: Option<f64>,
+        descending: bool,
+    ) -> OperationResult<Box<dyn Iterator<Item = (f64, PointOffsetType)> + '_>> {
+        self.check_integer_index(key, "Ordering", IntegerIndexCapability::Range)?;
+        if let Some(indexes) = self.field_indexes.get(key) {
+            for index in indexes {
+                match index {
+                    FieldIndex::IntIndex(index) => {
+                        return Ok(index.ordered_points(start_from, descending))
+                    }
+                    FieldIndex::FloatIndex(index) => {
+                        return Ok(index.ordered_points(start_from, descending))
+                    }
+                    FieldIndex::DatetimeIndex(index) => {
+                        return Ok(index.ordered_points(start_from, descending))
+                    }
+                    FieldIndex::IntMmapIndex(index) => {
+                        return Ok(index.ordered_points(start_from, descending))
+                    }
+                    FieldIndex::FloatMmapIndex(index) => {
+                        return Ok(index.ordered_points(start_from, descending))
+                    }
+                    FieldIndex::DatetimeMmapIndex(index) => {
+                        return Ok(index.ordered_points(start_from, descending))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let payload_storage = self.payload.borrow();
+        let mut points = vec![];
+        for point_id in self.id_tracker.borrow().iter_ids() {
+            let payload = payload_storage.payload(point_id)?;
+            let values: Vec<f64> = match payload.get_value(key) {
+                Some(Value::Array(values)) => {
+                    values.iter().filter_map(json_numeric_value).collect()
+                }
+                Some(value) => json_numeric_value(value).into_iter().collect(),
+                None => vec![],
+            };
+            let order_value = if descending {
+                values.into_iter().reduce(f64::max)
+            } else {
+                values.into_iter().reduce(f64::min)
+            };
+            let is_from_start = |value: f64| match start_from {
+                None => true,
+                Some(start_from) if descending => value <= start_from,
+                Some(start_from) => value >= start_from,
+            };
+            match order_value {
+                Some(value) if is_from_start(value) => points.push((value, point_id)),
+                _ => {}
+            }
+        }
+        points.sort_by(|(value_a, id_a), (value_b, id_b)| {
+            let ordering = value_a.partial_cmp(value_b).unwrap_or(Ordering::Equal);
+            let ordering = if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            ordering.then_with(|| id_a.cmp(id_b))
+        });
+        Ok(Box::new(points.into_iter()))
+    }
+
+    /// Numeric value of the field for the point.
+    /// Taken from the numeric field index if there is one, and from the payload storage otherwise.
+    /// If the field contains several values, the first one is returned.
+    /// Datetime strings in RFC 3339 format are converted into seconds since epoch.
+    pub fn numeric_value(
+        &self,
+        point_id: PointOffsetType,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<Option<f64>> {
+        if let Some(indexes) = self.field_indexes.get(key) {
+            for index in indexes {
+                match index {
+                    FieldIndex::IntIndex(index) => {
+                        return Ok(index
+                            .get_values(point_id)
+                            .and_then(|values| values.first())
+                            .map(|value| *value as f64));
+                    }
+                    FieldIndex::FloatIndex(index) => {
+                        return Ok(index
+                            .get_values(point_id)
+                            .and_then(|values| values.first())
+                            .copied());
+                    }
+                    FieldIndex::DatetimeIndex(index) => {
+                        return Ok(index
+                            .get_values(point_id)
+                            .and_then(|values| values.first())
+                            .map(|value| value.0));
+                    }
+                    FieldIndex::IntMmapIndex(index) => {
+                        return Ok(index
+                            .get_values(point_id)
+                            .and_then(|values| values.first().copied())
+                            .map(|value| value as f64));
+                    }
+                    FieldIndex::FloatMmapIndex(index) => {
+                        return Ok(index
+                            .get_values(point_id)
+                            .and_then(|values| values.first().copied()));
+                    }
+                    FieldIndex::DatetimeMmapIndex(index) => {
+                        return Ok(index
+                            .get_values(point_id)
+                            .and_then(|values| values.first().map(|value| value.0)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let payload = self.payload.borrow().payload(point_id)?;
+        let value = match payload.get_value(key) {
+            Some(Value::Array(values)) => values.iter().find_map(json_numeric_value),
+            Some(value) => json_numeric_value(value),
+            None => None,
+        };
+        Ok(value)
+    }
+
+    /// Count points for each value of the field among the points which satisfy the filter.
+    /// Uses keyword or integer map index of the field if available. Otherwise reads values from
+    /// the payload, which is only suitable for small segments, same as `ordered_points`.
+    /// If not `exact`, counts of the filtered points are estimated with the index.
+    /// Integer fields indexed without `lookup` are rejected.
+    pub fn facet(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        exact: bool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        self.check_integer_index(key, "Facet", IntegerIndexCapability::Lookup)?;
+        let facet_index = self.field_indexes.get(key).and_then(|indexes| {
+            indexes
+                .iter()
+                .find_map(|index| index.facet_counts().map(|counts| (index, counts)))
+        });
+
+        let mut counts: HashMap<FacetValue, usize> = HashMap::new();
+        match (facet_index, filter) {
+            (Some((_index, values_counts)), None) => counts.extend(values_counts),
+            (Some((_index, values_counts)), Some(filter)) if !exact => {
+                for (value, _count) in values_counts {
+                    let value_condition =
+                        FieldCondition::new_match(key.to_owned(), value.clone().into());
+                    let value_filter = Filter {
+                        should: None,
+                        must: Some(vec![
+                            Condition::Field(value_condition),
+                            Condition::Filter(filter.clone()),
+                        ]),
+                        must_not: None,
+                        min_should: None,
+                    };
+                    let estimation = self.estimate_cardinality(&value_filter);
+                    if estimation.exp > 0 {
+                        counts.insert(value, estimation.exp);
+                    }
+                }
+            }
+            (Some((index, _values_counts)), Some(filter)) => {
+                for point_id in self.query_points(filter) {
+                    let values: HashSet<_> = index
+                        .facet_values(point_id)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect();
+                    for value in values {
+                        *counts.entry(value).or_default() += 1;
+                    }
+                }
+            }
+            (None, _) => {
+                let payload_storage = self.payload.borrow();
+                for point_id in self.filtered_point_ids(filter) {
+                    let payload = payload_storage.payload(point_id)?;
+                    let values: HashSet<_> = match payload.get_value(key) {
+                        Some(Value::Array(values)) => {
+                            values.iter().filter_map(json_facet_value).collect()
+                        }
+                        Some(value) => json_facet_value(value).into_iter().collect(),
+                        None => HashSet::new(),
+                    };
+                    for value in values {
+                        *counts.entry(value).or_default() += 1;
+                    }
+                }
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Aggregate numeric values of the field among the points which satisfy the filter.
+    /// Values are taken from the numeric index of the field if there is one,
+    /// and from the payload storage otherwise.
+    /// Integer fields indexed without `range` are rejected.
+    pub fn aggregate(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        histogram_interval: Option<f64>,
+    ) -> OperationResult<NumericAggregation> {
+        self.check_integer_index(key, "Aggregation", IntegerIndexCapability::Range)?;
+        let numeric_index = self.field_indexes.get(key).and_then(|indexes| {
+            indexes
+                .iter()
+                .find_map(|index| index.numeric_values().map(|values| (index, values)))
+        });
+
+        let aggregation = match (numeric_index, filter) {
+            (Some((index, values)), None) => {
+                let is_datetime = self.is_datetime_field(key);
+                let aggregation = NumericAggregation::from_values(values, is_datetime);
+                match histogram_interval {
+                    Some(interval) => {
+                        let bucket_counts =
+                            index.count_by_interval(interval).unwrap_or_else(|| {
+                                count_by_interval(
+                                    index.numeric_values().into_iter().flatten(),
+                                    interval,
+                                )
+                            });
+                        aggregation.with_histogram(interval, bucket_counts)
+                    }
+                    None => aggregation,
+                }
+            }
+            (Some((index, _values)), Some(filter)) => {
+                let values = self
+                    .query_points(filter)
+                    .flat_map(|point_id| index.point_numeric_values(point_id).unwrap_or_default())
+                    .collect();
+                aggregate_values(values, self.is_datetime_field(key), histogram_interval)
+            }
+            (None, _) => {
+                let payload_storage = self.payload.borrow();
+                let mut values = vec![];
+                let mut datetime_values = 0;
+                let mut add_value = |value: &Value| {
+                    if let Some(number) = json_numeric_value(value) {
+                        values.push(number);
+                        datetime_values += value.is_string() as usize;
+                    }
+                };
+                for point_id in self.filtered_point_ids(filter) {
+                    let payload = payload_storage.payload(point_id)?;
+                    match payload.get_value(key) {
+                        Some(Value::Array(array)) => array.iter().for_each(&mut add_value),
+                        Some(value) => add_value(value),
+                        None => {}
+                    }
+                }
+                // Field without index is aggregated as datetime, if all values are datetimes
+                let is_datetime = datetime_values > 0 && datetime_values == values.len();
+                aggregate_values(values, is_datetime, histogram_interval)
+            }
+        };
+        Ok(aggregation)
+    }
+
+    fn check_integer_index(
+        &self,
+        key: PayloadKeyTypeRef,
+        operation: &str,
+        capability: IntegerIndexCapability,
+    ) -> OperationResult<()> {
+        check_integer_index(&self.config.indexed_fields, key, operation, capability)
+    }
+
+    fn is_datetime_field(&self, key: PayloadKeyTypeRef) -> bool {
+        self.config
+            .indexed_fields
+            .get(key)
+            .map_or(false, |payload_schema| {
+                payload_schema.schema_type() == PayloadSchemaType::Datetime
+            })
+    }
+
+    /// Statistics of the indexes of each indexed field.
+    /// If the field has several indexes, their statistics are combined.
+    pub fn index_stats(&self) -> OperationResult<HashMap<PayloadKeyType, PayloadIndexStats>> {
+        let mut stats = HashMap::new();
+        for (field, payload_schema) in &self.config.indexed_fields {
+            let data_type = PayloadIndexInfo::new(payload_schema.clone()).data_type;
+            let mut field_stats = PayloadIndexStats::new(data_type);
+            for index in self.field_indexes.get(field).into_iter().flatten() {
+                let index_stats = index.stats(data_type)?;
+                // All indexes of the field cover the same points
+                field_stats.points_count = field_stats.points_count.max(index_stats.points_count);
+                field_stats.distinct_values =
+                    field_stats.distinct_values.or(index_stats.distinct_values);
+                field_stats.top_values = field_stats.top_values.or(index_stats.top_values);
+                field_stats.numeric = field_stats.numeric.or(index_stats.numeric);
+                field_stats.ram_usage_bytes += index_stats.ram_usage_bytes;
+                field_stats.disk_usage_bytes += index_stats.disk_usage_bytes;
+            }
+            stats.insert(field.clone(), field_stats);
+        }
+        Ok(stats)
+    }
+
+    /// All points of the segment, which satisfy the filter if it is given
+    fn filtered_point_ids(&self, filter: Option<&Filter>) -> Vec<PointOffsetType> {
+        match filter {
+            Some(filter) => self.query_points(filter).collect(),
+            None => self.id_tracker.borrow().iter_ids().collect(),
+        }
+    }
+
     fn struct_filtered_context<'a>(&'a self, filter: &'a Filter) -> StructFilterContext<'a> {
         let estimator = |condition: &Condition| self.condition_cardinality(condition);
         let id_tracker = self.id_tracker.borrow();
@@ -212,6 +718,7 @@ impl StructPayloadIndex {
             id_tracker.deref(),
             payload_provider,
             &self.field_indexes,
+            &self.nested_indexes,
             &estimator,
             self.total_points(),
         )
@@ -222,29 +729,45 @@ impl StructPayloadIndex {
             Condition::Filter(_) => panic!("Unexpected branching"),
             Condition::IsEmpty(IsEmptyCondition { is_empty: field }) => {
                 let total_points = self.total_points();
-
-                let mut indexed_points = 0;
-                if let Some(field_indexes) = self.field_indexes.get(&field.key) {
-                    for index in field_indexes {
-                        indexed_points = indexed_points.max(index.count_indexed_points())
-                    }
-                    CardinalityEstimation {
-                        primary_clauses: vec![PrimaryCondition::IsEmpty(IsEmptyCondition {
-                            is_empty: field.to_owned(),
-                        })],
-                        min: 0, // It is possible, that some non-empty payloads are not indexed
-                        exp: total_points.saturating_sub(indexed_points), // Expect field type consistency
-                        max: total_points.saturating_sub(indexed_points),
-                    }
-                } else {
-                    CardinalityEstimation {
-                        primary_clauses: vec![PrimaryCondition::IsEmpty(IsEmptyCondition {
-                            is_empty: field.to_owned(),
-                        })],
+                let primary_clauses = vec![PrimaryCondition::IsEmpty(IsEmptyCondition {
+                    is_empty: field.to_owned(),
+                })];
+                match null_index(&self.field_indexes, &field.key) {
+                    Some(null_index) => CardinalityEstimation {
+                        primary_clauses,
+                        ..CardinalityEstimation::exact(
+                            total_points.saturating_sub(null_index.non_empty_count()),
+                        )
+                    },
+                    None => CardinalityEstimation {
+                        primary_clauses,
                         min: 0,
                         exp: total_points / 2,
                         max: total_points,
-                    }
+                    },
+                }
+            }
+            // Points with the field, and the ones with `NULL` values, are tracked by the null index
+            Condition::IsNull(IsNullCondition { is_null: field }) => {
+                match null_index(&self.field_indexes, &field.key) {
+                    Some(null_index) => CardinalityEstimation {
+                        primary_clauses: vec![PrimaryCondition::IsNull(IsNullCondition {
+                            is_null: field.to_owned(),
+                        })],
+                        ..CardinalityEstimation::exact(null_index.nulls_count())
+                    },
+                    None => CardinalityEstimation::unknown(self.total_points()),
+                }
+            }
+            Condition::HasField(HasFieldCondition { has_field: field }) => {
+                match null_index(&self.field_indexes, &field.key) {
+                    Some(null_index) => CardinalityEstimation {
+                        primary_clauses: vec![PrimaryCondition::HasField(HasFieldCondition {
+                            has_field: field.to_owned(),
+                        })],
+                        ..CardinalityEstimation::exact(null_index.fields_count())
+                    },
+                    None => CardinalityEstimation::unknown(self.total_points()),
                 }
             }
             Condition::HasId(has_id) => {
@@ -262,31 +785,221 @@ impl StructPayloadIndex {
                     max: num_ids,
                 }
             }
+            Condition::Nested(nested) => self.nested_cardinality(nested),
             Condition::Field(field_condition) => self
                 .estimate_field_condition(field_condition)
                 .unwrap_or_else(|| CardinalityEstimation::unknown(self.total_points())),
         }
     }
+
+    /// Re-read values of the indexed fields, which might be changed by modification of the `key` path
+    fn reindex_affected_fields(
+        &mut self,
+        point_id: PointOffsetType,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()> {
+        if !self
+            .field_indexes
+            .keys()
+            .any(|field| paths_overlap(field, key))
+        {
+            return Ok(());
+        }
+        let payload = self.payload.borrow().payload(point_id)?;
+        for (field, field_indexes) in &mut self.field_indexes {
+            if !paths_overlap(field, key) {
+                continue;
+            }
+            let field_value = payload.get_value_cow(field);
+            for index in field_indexes {
+                index.remove_point(point_id)?;
+                if let Some(field_value) = &field_value {
+                    index.add_point(point_id, field_value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Estimate nested condition with indexes of the array element fields: `key[].field`.
+    /// These indexes can't tell if the conditions are satisfied by the same element,
+    /// so primary clauses only select candidates, which are checked with the element indexes.
+    fn nested_cardinality(&self, nested: &NestedCondition) -> CardinalityEstimation {
+        let total_points = self.total_points();
+        let estimator = |condition: &Condition| match condition {
+            Condition::Field(field_condition) => {
+                let mut array_condition = field_condition.clone();
+                array_condition.key = nested.array_key(&field_condition.key);
+                self.estimate_field_condition(&array_condition)
+                    .unwrap_or_else(|| CardinalityEstimation::unknown(total_points))
+            }
+            Condition::Nested(inner_nested) => {
+                let mut array_nested = inner_nested.clone();
+                array_nested.nested.key = nested.array_key(&inner_nested.nested.key);
+                self.nested_cardinality(&array_nested)
+            }
+            Condition::IsEmpty(_) | Condition::IsNull(_) | Condition::HasField(_) => {
+                CardinalityEstimation::unknown(total_points)
+            }
+            Condition::HasId(_) | Condition::Filter(_) => self.condition_cardinality(condition),
+        };
+        let estimation = estimate_filter(&estimator, &nested.nested.filter, total_points);
+        CardinalityEstimation {
+            min: 0,
+            ..estimation
+        }
+    }
+}
+
+/// Integer index without the required capability can't serve the request.
+/// Reading values from the payload instead would silently make it slow in large segments.
+fn check_integer_index(
+    indexed_fields: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    key: PayloadKeyTypeRef,
+    operation: &str,
+    capability: IntegerIndexCapability,
+) -> OperationResult<()> {
+    let params = match indexed_fields.get(key) {
+        Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(params))) => params,
+        _ => return Ok(()),
+    };
+    let (is_supported, option) = match capability {
+        IntegerIndexCapability::Lookup => (params.lookup(), "lookup"),
+        IntegerIndexCapability::Range => (params.range(), "range"),
+    };
+    if is_supported {
+        return Ok(());
+    }
+    Err(OperationError::ValidationError {
+        description: format!(
+            "{} by the field `{}` requires its integer index to be created with `{}: true`",
+            operation, key, option
+        ),
+    })
+}
+
+/// Check that integer indexes of the fields can serve conditions of the filter:
+/// `match` requires `lookup`, and `range` requires `range` to be enabled.
+pub fn check_filter_indexes(
+    indexed_fields: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    filter: &Filter,
+) -> OperationResult<()> {
+    check_filter_conditions(indexed_fields, filter, "")
+}
+
+/// Conditions of nested filters refer to the array elements, indexed as `key[].field`
+fn check_filter_conditions(
+    indexed_fields: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    filter: &Filter,
+    key_prefix: &str,
+) -> OperationResult<()> {
+    let conditions = filter
+        .should
+        .iter()
+        .flatten()
+        .chain(filter.must.iter().flatten())
+        .chain(filter.must_not.iter().flatten())
+        .chain(
+            filter
+                .min_should
+                .iter()
+                .flat_map(|min_should| &min_should.conditions),
+        );
+    for condition in conditions {
+        match condition {
+            Condition::Field(field_condition) => {
+                let key = format!("{}{}", key_prefix, field_condition.key);
+                if field_condition.r#match.is_some() {
+                    check_integer_index(
+                        indexed_fields,
+                        &key,
+                        "Match",
+                        IntegerIndexCapability::Lookup,
+                    )?;
+                }
+                if field_condition.range.is_some() {
+                    check_integer_index(
+                        indexed_fields,
+                        &key,
+                        "Range",
+                        IntegerIndexCapability::Range,
+                    )?;
+                }
+            }
+            Condition::Nested(nested) => {
+                let nested_prefix = format!("{}{}[].", key_prefix, nested.nested.key);
+                check_filter_conditions(indexed_fields, &nested.nested.filter, &nested_prefix)?;
+            }
+            Condition::Filter(inner_filter) => {
+                check_filter_conditions(indexed_fields, inner_filter, key_prefix)?;
+            }
+            Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasField(_)
+            | Condition::HasId(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Number, or seconds since epoch if the value is a datetime string
+fn json_numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(string) => parse_datetime_seconds(string),
+        _ => None,
+    }
+}
+
+/// Aggregate values, counting them in buckets of the `interval` width if requested
+fn aggregate_values(
+    values: Vec<f64>,
+    is_datetime: bool,
+    histogram_interval: Option<f64>,
+) -> NumericAggregation {
+    let aggregation = NumericAggregation::from_values(values.iter().copied(), is_datetime);
+    match histogram_interval {
+        Some(interval) => aggregation.with_histogram(interval, count_by_interval(values, interval)),
+        None => aggregation,
+    }
+}
+
+/// Keyword or integer value, which could be counted by facets
+fn json_facet_value(value: &Value) -> Option<FacetValue> {
+    match value {
+        Value::String(keyword) => Some(FacetValue::Keyword(keyword.clone())),
+        Value::Number(number) => number.as_i64().map(FacetValue::Integer),
+        _ => None,
+    }
 }
 
 impl PayloadIndex for StructPayloadIndex {
-    fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadSchemaType> {
+    fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
         self.config.indexed_fields.clone()
     }
 
     fn set_indexed(
         &mut self,
         field: PayloadKeyTypeRef,
-        payload_type: PayloadSchemaType,
+        payload_schema: PayloadFieldSchema,
     ) -> OperationResult<()> {
-        if self
+        payload_schema
+            .validate()
+            .map_err(|description| OperationError::ValidationError { description })?;
+        let previous_schema = self
             .config
             .indexed_fields
-            .insert(field.to_owned(), payload_type)
-            .is_none()
-        {
+            .insert(field.to_owned(), payload_schema.clone());
+        if previous_schema.as_ref() != Some(&payload_schema) {
             self.save_config()?;
-            self.build_and_save(field, payload_type)?;
+            // New schema might not require some of the previously built indexes
+            if let Some(indexes) = self.field_indexes.remove(field) {
+                for index in indexes {
+                    index.clear()?;
+                }
+            }
+            self.build_and_save(field, payload_schema)?;
+            self.rebuild_nested_index(field)?;
         }
 
         Ok(())
@@ -295,7 +1008,12 @@ impl PayloadIndex for StructPayloadIndex {
     fn drop_index(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
         self.config.indexed_fields.remove(field);
         self.save_config()?;
-        self.field_indexes.remove(field);
+        if let Some(indexes) = self.field_indexes.remove(field) {
+            for index in indexes {
+                index.clear()?;
+            }
+        }
+        self.rebuild_nested_index(field)?;
 
         let field_index_path = Self::get_field_index_path(&self.path, field);
 
@@ -355,7 +1073,11 @@ impl PayloadIndex for StructPayloadIndex {
                             )
                         }
                         PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
-                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids() /* there are no fast index for IsEmpty */
+                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids() /* there are no fast index for IsEmpty */,
+                        PrimaryCondition::IsNull(is_null) => null_index(&self.field_indexes, &is_null.is_null.key)
+                            .map_or_else(|| points_iterator_ref.iter_ids(), |null_index| null_index.iter_nulls()),
+                        PrimaryCondition::HasField(has_field) => null_index(&self.field_indexes, &has_field.has_field.key)
+                            .map_or_else(|| points_iterator_ref.iter_ids(), |null_index| null_index.iter_fields()),
                     }
                 })
                 .filter(|&id| !visited_list.check_and_update_visited(id))
@@ -391,16 +1113,39 @@ impl PayloadIndex for StructPayloadIndex {
 
     fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
         for (field, field_index) in &mut self.field_indexes {
-            match payload.get_value(field) {
+            match payload.get_value_cow(field) {
                 Some(field_value) => {
                     for index in field_index {
-                        index.add_point(point_id, field_value)?;
+                        index.add_point(point_id, &field_value)?;
+                    }
+                }
+                // Top-level value is replaced with the one, which does not have the nested field
+                None if payload.0.keys().any(|key| paths_overlap(key, field)) => {
+                    for index in field_index {
+                        index.remove_point(point_id)?;
                     }
                 }
                 None => {}
             }
         }
-        self.payload.borrow_mut().assign(point_id, payload)
+        self.payload.borrow_mut().assign(point_id, payload)?;
+        for key in payload.0.keys() {
+            self.reindex_nested_elements(point_id, key)?;
+        }
+        Ok(())
+    }
+
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()> {
+        self.payload
+            .borrow_mut()
+            .assign_by_key(point_id, payload, key)?;
+        self.reindex_affected_fields(point_id, key)?;
+        self.reindex_nested_elements(point_id, key)
     }
 
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
@@ -412,12 +1157,12 @@ impl PayloadIndex for StructPayloadIndex {
         point_id: PointOffsetType,
         key: PayloadKeyTypeRef,
     ) -> OperationResult<Option<Value>> {
-        if let Some(indexes) = self.field_indexes.get_mut(key) {
-            for index in indexes {
-                index.remove_point(point_id)?;
-            }
+        let removed = self.payload.borrow_mut().delete(point_id, key)?;
+        if removed.is_some() {
+            self.reindex_affected_fields(point_id, key)?;
+            self.reindex_nested_elements(point_id, key)?;
         }
-        self.payload.borrow_mut().delete(point_id, key)
+        Ok(removed)
     }
 
     fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
@@ -426,6 +1171,9 @@ impl PayloadIndex for StructPayloadIndex {
                 index.remove_point(point_id)?;
             }
         }
+        for nested_index in self.nested_indexes.values_mut() {
+            nested_index.remove_point(point_id)?;
+        }
         self.payload.borrow_mut().drop(point_id)
     }
 
@@ -436,6 +1184,9 @@ impl PayloadIndex for StructPayloadIndex {
                 index.clear()?;
             }
         }
+        for (_, nested_index) in self.nested_indexes.drain() {
+            nested_index.clear()?;
+        }
         self.load_all_fields()
     }
 
@@ -445,6 +1196,9 @@ impl PayloadIndex for StructPayloadIndex {
                 index.flush()?;
             }
         }
+        for nested_index in self.nested_indexes.values() {
+            nested_index.flush()?;
+        }
         self.payload.borrow().flush()
     }
 
@@ -2,8 +2,10 @@
 
 use serde_json::Value;
 
+use crate::index::field_index::full_text_index::check_text_match;
 use crate::types::{
-    GeoBoundingBox, GeoRadius, Match, MatchValue, Range, ValueVariants, ValuesCount,
+    parse_datetime_seconds, AnyVariants, GeoBoundingBox, GeoRadius, Match, MatchAny, MatchExcept,
+    MatchKeywordRange, MatchText, MatchValue, PolygonWrapper, Range, ValueVariants, ValuesCount,
 };
 
 pub trait ValueChecker {
@@ -18,6 +20,15 @@ pub trait ValueChecker {
 }
 
 impl ValueChecker for Match {
+    fn check(&self, payload: &Value) -> bool {
+        match (self, payload) {
+            // Text values of an array form a single document, as in the full-text index
+            (Match::Text(MatchText { text }), Value::Array(_)) => check_text_match(text, payload),
+            (_, Value::Array(values)) => values.iter().any(|x| self.check_match(x)),
+            _ => self.check_match(payload),
+        }
+    }
+
     fn check_match(&self, payload: &Value) -> bool {
         match self {
             Match::Value(MatchValue { value }) => match (payload, value) {
@@ -28,6 +39,36 @@ impl ValueChecker for Match {
                 }
                 _ => false,
             },
+            Match::Any(MatchAny { any }) => match (payload, any) {
+                (Value::String(stored), AnyVariants::Keywords(list)) => list.contains(stored),
+                (Value::Number(stored), AnyVariants::Integers(list)) => stored
+                    .as_i64()
+                    .map(|num| list.contains(&num))
+                    .unwrap_or(false),
+                _ => false,
+            },
+            // Values of other types are ignored, as they are not present in the index
+            Match::Except(MatchExcept { except }) => match (payload, except) {
+                (Value::String(stored), AnyVariants::Keywords(list)) => !list.contains(stored),
+                (Value::Number(stored), AnyVariants::Integers(list)) => stored
+                    .as_i64()
+                    .map(|num| !list.contains(&num))
+                    .unwrap_or(false),
+                _ => false,
+            },
+            Match::Text(MatchText { text }) => check_text_match(text, payload),
+            Match::Prefix(prefix) => match payload {
+                Value::String(stored) => prefix.check(stored),
+                _ => false,
+            },
+            Match::KeywordRange(MatchKeywordRange { keyword_range }) => match payload {
+                Value::String(stored) => keyword_range.check(stored),
+                _ => false,
+            },
+            Match::Wildcard(wildcard) => match payload {
+                Value::String(stored) => wildcard.check(stored),
+                _ => false,
+            },
             _ => panic!("use of deprecated conditions"),
         }
     }
@@ -40,6 +81,9 @@ impl ValueChecker for Range {
                 .as_f64()
                 .map(|number| self.check_range(number))
                 .unwrap_or(false),
+            Value::String(string) => parse_datetime_seconds(string)
+                .map(|seconds| self.check_range(seconds))
+                .unwrap_or(false),
             _ => false,
         }
     }
@@ -79,6 +123,23 @@ impl ValueChecker for GeoRadius {
     }
 }
 
+impl ValueChecker for PolygonWrapper {
+    fn check_match(&self, payload: &Value) -> bool {
+        match payload {
+            Value::Object(obj) => {
+                let lon_op = obj.get("lon").and_then(|x| x.as_f64());
+                let lat_op = obj.get("lat").and_then(|x| x.as_f64());
+
+                if let (Some(lon), Some(lat)) = (lon_op, lat_op) {
+                    return self.check_point(lon, lat);
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
 impl ValueChecker for ValuesCount {
     fn check_match(&self, payload: &Value) -> bool {
         self.check_count(payload)
@@ -89,6 +150,8 @@ impl ValueChecker for ValuesCount {
 mod tests {
     use serde_json::json;
 
+    use std::collections::BTreeSet;
+
     use super::*;
     use crate::types::GeoPoint;
 
@@ -123,4 +186,49 @@ mod tests {
         assert!(near_berlin_query.check(&berlin_and_moscow));
         assert!(!miss_geo_query.check(&berlin_and_moscow));
     }
+
+    #[test]
+    fn test_match_any_and_except() {
+        let colors = AnyVariants::Keywords(BTreeSet::from(["red".to_string(), "blue".to_string()]));
+        let match_any = Match::Any(MatchAny {
+            any: colors.clone(),
+        });
+        let match_except = Match::Except(MatchExcept { except: colors });
+
+        assert!(match_any.check(&json!("red")));
+        assert!(match_any.check(&json!(["green", "blue"])));
+        assert!(!match_any.check(&json!("green")));
+        assert!(!match_any.check(&json!(42)));
+
+        assert!(match_except.check(&json!("green")));
+        assert!(match_except.check(&json!(["red", "green"])));
+        assert!(!match_except.check(&json!(["red", "blue"])));
+        assert!(!match_except.check(&json!(null)));
+        // Values of other types don't satisfy the condition, like with the keyword index
+        assert!(!match_except.check(&json!(42)));
+        assert!(match_except.check(&json!([42, "green"])));
+        assert!(!match_except.check(&json!([42, "red"])));
+
+        let match_any_int = Match::Any(MatchAny {
+            any: AnyVariants::Integers(BTreeSet::from([1, 2, 3])),
+        });
+        assert!(match_any_int.check(&json!(2)));
+        assert!(!match_any_int.check(&json!(4)));
+        assert!(!match_any_int.check(&json!("2")));
+    }
+
+    #[test]
+    fn test_datetime_range() {
+        let range: Range = serde_json::from_value(json!({
+            "gte": "2022-01-01T00:00:00Z",
+            "lt": 1672531200.0
+        }))
+        .unwrap();
+
+        assert!(range.check(&json!("2022-05-01T10:00:00+03:00")));
+        assert!(!range.check(&json!("2021-12-31T23:59:59Z")));
+        assert!(!range.check(&json!("2023-01-01T00:00:00Z")));
+        assert!(!range.check(&json!("yesterday")));
+        assert!(range.check(&json!(1651399200)));
+    }
 }
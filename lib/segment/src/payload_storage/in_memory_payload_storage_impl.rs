@@ -18,6 +18,19 @@ impl PayloadStorage for InMemoryPayloadStorage {
         Ok(())
     }
 
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()> {
+        self.payload
+            .entry(point_id)
+            .or_default()
+            .merge_by_key(payload, key);
+        Ok(())
+    }
+
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         match self.payload.get(&point_id) {
             Some(payload) => Ok(payload.to_owned()),
@@ -93,6 +106,7 @@ mod tests {
                 )),
             ]),
             must_not: None,
+            min_should: None,
         };
 
         // Example:
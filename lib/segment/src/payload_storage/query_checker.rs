@@ -3,14 +3,17 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
+use crate::common::utils::get_value_from_json_map;
 use crate::id_tracker::IdTrackerSS;
 use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::ConditionChecker;
 use crate::types::{
-    Condition, FieldCondition, Filter, IsEmptyCondition, OwnedPayloadRef, Payload, PointOffsetType,
+    Condition, FieldCondition, Filter, GeoPolygon, HasFieldCondition, HasIdCondition,
+    IsEmptyCondition, IsNullCondition, MinShould, NestedCondition, OwnedPayloadRef, Payload,
+    PointOffsetType, PolygonWrapper,
 };
 
 fn check_condition<F>(checker: &F, condition: &Condition) -> bool
@@ -30,6 +33,7 @@ where
     check_should(checker, &filter.should)
         && check_must(checker, &filter.must)
         && check_must_not(checker, &filter.must_not)
+        && check_min_should(checker, &filter.min_should)
 }
 
 fn check_should<F>(checker: &F, should: &Option<Vec<Condition>>) -> bool
@@ -43,6 +47,26 @@ where
     }
 }
 
+fn check_min_should<F>(checker: &F, min_should: &Option<MinShould>) -> bool
+where
+    F: Fn(&Condition) -> bool,
+{
+    match min_should {
+        None => true,
+        Some(MinShould {
+            conditions,
+            min_count,
+        }) => {
+            conditions
+                .iter()
+                .filter(|x| check_condition(checker, x))
+                .take(*min_count)
+                .count()
+                == *min_count
+        }
+    }
+}
+
 fn check_must<F>(checker: &F, must: &Option<Vec<Condition>>) -> bool
 where
     F: Fn(&Condition) -> bool,
@@ -74,17 +98,22 @@ pub fn check_payload<'a, F>(
 where
     F: Fn() -> OwnedPayloadRef<'a>,
 {
+    let check_has_id = |has_id: &HasIdCondition| match id_tracker.external_id(point_id) {
+        None => false,
+        Some(external_id) => has_id.has_id.contains(&external_id),
+    };
     let checker = |condition: &Condition| match condition {
         Condition::Field(field_condition) => {
             check_field_condition(field_condition, get_payload().deref())
         }
         Condition::IsEmpty(is_empty) => check_is_empty_condition(is_empty, get_payload().deref()),
-        Condition::HasId(has_id) => {
-            let external_id = match id_tracker.external_id(point_id) {
-                None => return false,
-                Some(id) => id,
-            };
-            has_id.has_id.contains(&external_id)
+        Condition::IsNull(is_null) => check_is_null_condition(is_null, get_payload().deref()),
+        Condition::HasField(has_field) => {
+            check_has_field_condition(has_field, get_payload().deref())
+        }
+        Condition::HasId(has_id) => check_has_id(has_id),
+        Condition::Nested(nested) => {
+            check_nested_condition(nested, &get_payload().deref().0, &check_has_id)
         }
         Condition::Filter(_) => unreachable!(),
     };
@@ -93,7 +122,11 @@ where
 }
 
 pub fn check_is_empty_condition(is_empty: &IsEmptyCondition, payload: &Payload) -> bool {
-    match payload.get_value(&is_empty.is_empty.key) {
+    check_is_empty_value(payload.get_value_cow(&is_empty.is_empty.key).as_deref())
+}
+
+fn check_is_empty_value(value: Option<&Value>) -> bool {
+    match value {
         None => true,
         Some(value) => match value {
             Value::Null => true,
@@ -103,36 +136,120 @@ pub fn check_is_empty_condition(is_empty: &IsEmptyCondition, payload: &Payload)
     }
 }
 
+pub fn check_is_null_condition(is_null: &IsNullCondition, payload: &Payload) -> bool {
+    check_is_null_value(payload.get_value_cow(&is_null.is_null.key).as_deref())
+}
+
+fn check_is_null_value(value: Option<&Value>) -> bool {
+    matches!(value, Some(Value::Null))
+}
+
+pub fn check_has_field_condition(has_field: &HasFieldCondition, payload: &Payload) -> bool {
+    payload.get_value_cow(&has_field.has_field.key).is_some()
+}
+
 pub fn check_field_condition(field_condition: &FieldCondition, payload: &Payload) -> bool {
-    payload.get_value(&field_condition.key).map_or(false, |p| {
-        let mut res = false;
-        // ToDo: Convert onto iterator over checkers, so it would be impossible to forget a condition
-        res = res
-            || field_condition
-                .r#match
-                .as_ref()
-                .map_or(false, |condition| condition.check(p));
-        res = res
-            || field_condition
-                .range
-                .as_ref()
-                .map_or(false, |condition| condition.check(p));
-        res = res
-            || field_condition
-                .geo_radius
-                .as_ref()
-                .map_or(false, |condition| condition.check(p));
-        res = res
-            || field_condition
-                .geo_bounding_box
-                .as_ref()
-                .map_or(false, |condition| condition.check(p));
-        res = res
-            || field_condition
-                .values_count
+    FieldConditionChecker::new(field_condition).check(payload)
+}
+
+/// Checker of the field condition, prepared once to check it against many points:
+/// the polygon is converted into the geometry, suitable for repeated point checks
+pub struct FieldConditionChecker<'a> {
+    field_condition: &'a FieldCondition,
+    geo_polygon: Option<PolygonWrapper>,
+}
+
+impl<'a> FieldConditionChecker<'a> {
+    pub fn new(field_condition: &'a FieldCondition) -> Self {
+        FieldConditionChecker {
+            field_condition,
+            geo_polygon: field_condition
+                .geo_polygon
                 .as_ref()
-                .map_or(false, |condition| condition.check(p));
-        res
+                .map(GeoPolygon::convert),
+        }
+    }
+
+    pub fn check(&self, payload: &Payload) -> bool {
+        self.check_value(payload.get_value_cow(&self.field_condition.key).as_deref())
+    }
+
+    fn check_value(&self, value: Option<&Value>) -> bool {
+        let field_condition = self.field_condition;
+        value.map_or(false, |p| {
+            let mut res = false;
+            // ToDo: Convert onto iterator over checkers, so it would be impossible to forget a condition
+            res = res
+                || field_condition
+                    .r#match
+                    .as_ref()
+                    .map_or(false, |condition| condition.check(p));
+            res = res
+                || field_condition
+                    .range
+                    .as_ref()
+                    .map_or(false, |condition| condition.check(p));
+            res = res
+                || field_condition
+                    .geo_radius
+                    .as_ref()
+                    .map_or(false, |condition| condition.check(p));
+            res = res
+                || field_condition
+                    .geo_bounding_box
+                    .as_ref()
+                    .map_or(false, |condition| condition.check(p));
+            res = res
+                || self
+                    .geo_polygon
+                    .as_ref()
+                    .map_or(false, |condition| condition.check(p));
+            res = res
+                || field_condition
+                    .values_count
+                    .as_ref()
+                    .map_or(false, |condition| condition.check(p));
+            res
+        })
+    }
+}
+
+/// Check if at least one element of the array field satisfies the nested filter.
+/// Conditions of the nested filter refer to the fields of the element,
+/// except for `HasId`, which is checked with `check_has_id` against the point itself.
+pub fn check_nested_condition<F>(
+    nested: &NestedCondition,
+    payload: &Map<String, Value>,
+    check_has_id: &F,
+) -> bool
+where
+    F: Fn(&HasIdCondition) -> bool,
+{
+    let elements: Vec<&Map<String, Value>> =
+        match get_value_from_json_map(&nested.nested.key, payload) {
+            Some(Value::Array(array)) => array.iter().filter_map(|x| x.as_object()).collect(),
+            Some(Value::Object(object)) => vec![object],
+            _ => return false,
+        };
+
+    elements.into_iter().any(|element| {
+        let checker = |condition: &Condition| match condition {
+            Condition::Field(field_condition) => FieldConditionChecker::new(field_condition)
+                .check_value(get_value_from_json_map(&field_condition.key, element)),
+            Condition::IsEmpty(is_empty) => {
+                check_is_empty_value(get_value_from_json_map(&is_empty.is_empty.key, element))
+            }
+            Condition::IsNull(is_null) => {
+                check_is_null_value(get_value_from_json_map(&is_null.is_null.key, element))
+            }
+            Condition::HasField(has_field) => {
+                get_value_from_json_map(&has_field.has_field.key, element).is_some()
+            }
+            Condition::HasId(has_id) => check_has_id(has_id),
+            Condition::Nested(nested) => check_nested_condition(nested, element, check_has_id),
+            Condition::Filter(_) => unreachable!(),
+        };
+        check_filter(&checker, &nested.nested.filter)
     })
 }
 
@@ -220,7 +337,7 @@ mod tests {
     use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
     use crate::payload_storage::PayloadStorage;
     use crate::types::{
-        FieldCondition, GeoBoundingBox, GeoPoint, PayloadField, Range, ValuesCount,
+        FieldCondition, GeoBoundingBox, GeoPoint, Nested, PayloadField, Range, ValuesCount,
     };
 
     #[test]
@@ -239,6 +356,7 @@ mod tests {
             "rating": vec![3, 7, 9, 9],
             "color": "red",
             "has_delivery": true,
+            "discount": null,
         })
         .into();
 
@@ -272,6 +390,29 @@ mod tests {
         assert!(!payload_checker.check(0, &is_empty_condition_1));
         assert!(payload_checker.check(0, &is_empty_condition_2));
 
+        let is_null = |key: &str| {
+            Filter::new_must(Condition::IsNull(IsNullCondition {
+                is_null: PayloadField {
+                    key: key.to_string(),
+                },
+            }))
+        };
+        let has_field = |key: &str| {
+            Filter::new_must(Condition::HasField(HasFieldCondition {
+                has_field: PayloadField {
+                    key: key.to_string(),
+                },
+            }))
+        };
+
+        assert!(payload_checker.check(0, &is_null("discount")));
+        assert!(!payload_checker.check(0, &is_null("price")));
+        assert!(!payload_checker.check(0, &is_null("something_new")));
+
+        assert!(payload_checker.check(0, &has_field("discount")));
+        assert!(payload_checker.check(0, &has_field("price")));
+        assert!(!payload_checker.check(0, &has_field("something_new")));
+
         let match_red = Condition::Field(FieldCondition::new_match(
             "color".to_string(),
             "red".to_owned().into(),
@@ -352,6 +493,7 @@ mod tests {
             should: None,
             must: Some(vec![match_red.clone()]),
             must_not: None,
+            min_should: None,
         };
         assert!(payload_checker.check(0, &query));
 
@@ -359,6 +501,7 @@ mod tests {
             should: None,
             must: Some(vec![match_blue.clone()]),
             must_not: None,
+            min_should: None,
         };
         assert!(!payload_checker.check(0, &query));
 
@@ -366,6 +509,7 @@ mod tests {
             should: None,
             must: None,
             must_not: Some(vec![match_blue.clone()]),
+            min_should: None,
         };
         assert!(payload_checker.check(0, &query));
 
@@ -373,13 +517,31 @@ mod tests {
             should: None,
             must: None,
             must_not: Some(vec![match_red.clone()]),
+            min_should: None,
         };
         assert!(!payload_checker.check(0, &query));
 
+        let min_should_query = |min_count| Filter {
+            should: None,
+            must: None,
+            must_not: None,
+            min_should: Some(MinShould {
+                conditions: vec![
+                    match_red.clone(),
+                    match_blue.clone(),
+                    with_bad_rating.clone(),
+                ],
+                min_count,
+            }),
+        };
+        assert!(payload_checker.check(0, &min_should_query(2)));
+        assert!(!payload_checker.check(0, &min_should_query(3)));
+
         let query = Filter {
             should: Some(vec![match_red.clone(), match_blue.clone()]),
             must: Some(vec![with_delivery.clone(), in_berlin.clone()]),
             must_not: None,
+            min_should: None,
         };
         assert!(payload_checker.check(0, &query));
 
@@ -387,6 +549,7 @@ mod tests {
             should: Some(vec![match_red.clone(), match_blue.clone()]),
             must: Some(vec![with_delivery, in_moscow.clone()]),
             must_not: None,
+            min_should: None,
         };
         assert!(!payload_checker.check(0, &query));
 
@@ -396,15 +559,18 @@ mod tests {
                     should: None,
                     must: Some(vec![match_red.clone(), in_moscow.clone()]),
                     must_not: None,
+                    min_should: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
                     must: Some(vec![match_blue.clone(), in_berlin.clone()]),
                     must_not: None,
+                    min_should: None,
                 }),
             ]),
             must: None,
             must_not: None,
+            min_should: None,
         };
         assert!(!payload_checker.check(0, &query));
 
@@ -414,15 +580,18 @@ mod tests {
                     should: None,
                     must: Some(vec![match_blue, in_moscow]),
                     must_not: None,
+                    min_should: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
                     must: Some(vec![match_red, in_berlin]),
                     must_not: None,
+                    min_should: None,
                 }),
             ]),
             must: None,
             must_not: None,
+            min_should: None,
         };
         assert!(payload_checker.check(0, &query));
 
@@ -430,6 +599,7 @@ mod tests {
             should: None,
             must: None,
             must_not: Some(vec![with_bad_rating]),
+            min_should: None,
         };
         assert!(!payload_checker.check(0, &query));
 
@@ -439,6 +609,7 @@ mod tests {
             should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
+            min_should: None,
         };
         assert!(!payload_checker.check(2, &query));
 
@@ -448,6 +619,7 @@ mod tests {
             should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
+            min_should: None,
         };
         assert!(payload_checker.check(10, &query));
 
@@ -457,7 +629,71 @@ mod tests {
             should: None,
             must: Some(vec![Condition::HasId(ids.into())]),
             must_not: None,
+            min_should: None,
         };
         assert!(payload_checker.check(2, &query));
     }
+
+    #[test]
+    fn test_nested_condition() {
+        let payload: Payload = json!({
+            "shops": [
+                {"name": "a", "price": 30},
+                {"name": "b", "price": 10},
+            ]
+        })
+        .into();
+
+        let nested_filter = |name: &str, lt: f64| {
+            NestedCondition::new(Nested {
+                key: "shops".to_string(),
+                filter: Filter {
+                    should: None,
+                    must: Some(vec![
+                        Condition::Field(FieldCondition::new_match(
+                            "name".to_string(),
+                            name.to_owned().into(),
+                        )),
+                        Condition::Field(FieldCondition::new_range(
+                            "price".to_string(),
+                            Range {
+                                lt: Some(lt),
+                                gt: None,
+                                gte: None,
+                                lte: None,
+                            },
+                        )),
+                    ]),
+                    must_not: None,
+                    min_should: None,
+                },
+            })
+        };
+        let no_has_id = |_: &HasIdCondition| false;
+
+        // Both conditions must be satisfied by the same element
+        assert!(!check_nested_condition(
+            &nested_filter("a", 20.),
+            &payload.0,
+            &no_has_id
+        ));
+        assert!(check_nested_condition(
+            &nested_filter("b", 20.),
+            &payload.0,
+            &no_has_id
+        ));
+        assert!(check_nested_condition(
+            &nested_filter("a", 40.),
+            &payload.0,
+            &no_has_id
+        ));
+
+        let mut missing_key = nested_filter("b", 20.);
+        missing_key.nested.key = "other".to_string();
+        assert!(!check_nested_condition(
+            &missing_key,
+            &payload.0,
+            &no_has_id
+        ));
+    }
 }
@@ -22,6 +22,22 @@ impl PayloadStorage for SimplePayloadStorage {
         Ok(())
     }
 
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<()> {
+        self.payload
+            .entry(point_id)
+            .or_default()
+            .merge_by_key(payload, key);
+
+        self.update_storage(&point_id)?;
+
+        Ok(())
+    }
+
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
         match self.payload.get(&point_id) {
             Some(payload) => Ok(payload.to_owned()),
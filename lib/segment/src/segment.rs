@@ -3,6 +3,7 @@ use std::fs::{remove_dir_all, rename, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use atomic_refcell::AtomicRefCell;
 use atomicwrites::{AllowOverwrite, AtomicFile};
@@ -20,9 +21,10 @@ use crate::index::field_index::CardinalityEstimation;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndexSS};
 use crate::types::{
-    Filter, Payload, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
-    PointIdType, PointOffsetType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentState, SegmentType, SeqNumberType, VectorElementType, WithPayload,
+    Direction, FacetValue, Filter, NumericAggregation, OrderBy, Payload, PayloadFieldSchema,
+    PayloadIndexInfo, PayloadIndexStats, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    PointIdType, PointOffsetType, QueryExplain, ScoredPoint, SearchParams, SegmentConfig,
+    SegmentInfo, SegmentState, SegmentType, SeqNumberType, VectorElementType, WithPayload,
 };
 use crate::vector_storage::VectorStorageSS;
 
@@ -269,6 +271,20 @@ impl SegmentEntry for Segment {
         top: usize,
         params: Option<&SearchParams>,
     ) -> OperationResult<Vec<ScoredPoint>> {
+        self.search_explain(vector, with_payload, with_vector, filter, top, params)
+            .map(|(result, _explain)| result)
+    }
+
+    fn search_explain(
+        &self,
+        vector: &[VectorElementType],
+        with_payload: &WithPayload,
+        with_vector: bool,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+    ) -> OperationResult<(Vec<ScoredPoint>, QueryExplain)> {
+        let timing = Instant::now();
         let expected_vector_dim = self.vector_storage.borrow().vector_dim();
         if expected_vector_dim != vector.len() {
             return Err(OperationError::WrongVector {
@@ -277,10 +293,18 @@ impl SegmentEntry for Segment {
             });
         }
 
-        let internal_result = self
-            .vector_index
-            .borrow()
-            .search(vector, filter, top, params);
+        let mut explain = QueryExplain::default();
+
+        if self.points_count() == 0 {
+            explain.skipped = true;
+            explain.time = timing.elapsed().as_secs_f64();
+            return Ok((vec![], explain));
+        }
+
+        let internal_result =
+            self.vector_index
+                .borrow()
+                .search_explain(vector, filter, top, params, &mut explain);
 
         let id_tracker = self.id_tracker.borrow();
 
@@ -327,7 +351,8 @@ impl SegmentEntry for Segment {
                 })
             })
             .collect();
-        res
+        explain.time = timing.elapsed().as_secs_f64();
+        res.map(|result| (result, explain))
     }
 
     fn upsert_point(
@@ -429,6 +454,23 @@ impl SegmentEntry for Segment {
         })
     }
 
+    fn set_payload_by_key(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        payload: &Payload,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<bool> {
+        self.handle_version_and_failure(op_num, Some(point_id), |segment| {
+            let internal_id = segment.lookup_internal_id(point_id)?;
+            segment
+                .payload_index
+                .borrow_mut()
+                .assign_by_key(internal_id, payload, key)?;
+            Ok(true)
+        })
+    }
+
     fn delete_payload(
         &mut self,
         op_num: SeqNumberType,
@@ -467,6 +509,15 @@ impl SegmentEntry for Segment {
         self.payload_by_offset(internal_id)
     }
 
+    fn payload_numeric_value(
+        &self,
+        point_id: PointIdType,
+        key: PayloadKeyTypeRef,
+    ) -> OperationResult<Option<f64>> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        self.payload_index.borrow().numeric_value(internal_id, key)
+    }
+
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // Sorry for that, but I didn't find any way easier.
         // If you try simply return iterator - it won't work because AtomicRef should exist
@@ -503,6 +554,68 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        offset: Option<PointIdType>,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        order_by: &OrderBy,
+    ) -> OperationResult<Vec<(f64, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        let descending = order_by.direction == Direction::Desc;
+        let ordered_points =
+            payload_index.ordered_points(&order_by.key, order_by.start_from, descending)?;
+
+        let mut result: Vec<(f64, PointIdType)> = vec![];
+        for (value, internal_id) in ordered_points {
+            // Points with equal values are ordered by id, so all of them are required
+            if result.len() >= limit && result.last().map_or(false, |(last, _)| *last != value) {
+                break;
+            }
+            if let Some(filter_context) = &filter_context {
+                if !filter_context.check(internal_id) {
+                    continue;
+                }
+            }
+            let point_id = match id_tracker.external_id(internal_id) {
+                Some(point_id) => point_id,
+                None => continue,
+            };
+            if order_by.is_from_start((value, point_id), offset) {
+                result.push((value, point_id));
+            }
+        }
+        result.sort_by(|a, b| order_by.compare(*a, *b));
+        result.truncate(limit);
+        Ok(result)
+    }
+
+    fn facet(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        exact: bool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        self.payload_index.borrow().facet(key, filter, exact)
+    }
+
+    fn aggregate(
+        &self,
+        key: PayloadKeyTypeRef,
+        filter: Option<&Filter>,
+        histogram_interval: Option<f64>,
+    ) -> OperationResult<NumericAggregation> {
+        self.payload_index
+            .borrow()
+            .aggregate(key, filter, histogram_interval)
+    }
+
+    fn payload_index_stats(&self) -> OperationResult<HashMap<PayloadKeyType, PayloadIndexStats>> {
+        self.payload_index.borrow().index_stats()
+    }
+
     fn has_point(&self, point_id: PointIdType) -> bool {
         self.id_tracker.borrow().internal_id(point_id).is_some()
     }
@@ -543,14 +656,7 @@ impl SegmentEntry for Segment {
             .borrow()
             .indexed_fields()
             .into_iter()
-            .map(|(key, index_schema)| {
-                (
-                    key,
-                    PayloadIndexInfo {
-                        data_type: index_schema,
-                    },
-                )
-            })
+            .map(|(key, index_schema)| (key, PayloadIndexInfo::new(index_schema)))
             .collect();
 
         SegmentInfo {
@@ -644,14 +750,14 @@ impl SegmentEntry for Segment {
         &mut self,
         op_num: u64,
         key: PayloadKeyTypeRef,
-        field_type: &Option<PayloadSchemaType>,
+        field_type: &Option<PayloadFieldSchema>,
     ) -> OperationResult<bool> {
         self.handle_version_and_failure(op_num, None, |segment| match field_type {
-            Some(schema_type) => {
+            Some(schema) => {
                 segment
                     .payload_index
                     .borrow_mut()
-                    .set_indexed(key, *schema_type)?;
+                    .set_indexed(key, schema.clone())?;
                 Ok(true)
             }
             None => match segment.infer_from_payload_data(key)? {
@@ -662,14 +768,14 @@ impl SegmentEntry for Segment {
                     segment
                         .payload_index
                         .borrow_mut()
-                        .set_indexed(key, schema_type)?;
+                        .set_indexed(key, schema_type.into())?;
                     Ok(true)
                 }
             },
         })
     }
 
-    fn get_indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadSchemaType> {
+    fn get_indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
         self.payload_index.borrow().indexed_fields()
     }
 
@@ -788,7 +894,9 @@ mod tests {
     use super::*;
     use crate::entry::entry_point::SegmentEntry;
     use crate::segment_constructor::build_segment;
-    use crate::types::{Distance, Indexes, SegmentConfig, StorageType};
+    use crate::types::{
+        AggregationValue, Distance, Indexes, SegmentConfig, StorageType, INDEX_STATS_QUANTILES,
+    };
 
     // no longer valid since users are now allowed to store arbitrary json objects.
     // TODO(gvelo): add tests for invalid payload types on indexed fields.
@@ -904,6 +1012,364 @@ mod tests {
         assert!(results_with_invalid_filter.is_empty());
     }
 
+    #[test]
+    fn test_read_ordered_filtered() {
+        let dir = TempDir::new("payload_dir").unwrap();
+        let config = SegmentConfig {
+            vector_size: 2,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance: Distance::Dot,
+            payload_storage_type: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+        let prices = [30, 10, 20, 10, 40];
+        for (idx, price) in prices.iter().enumerate() {
+            let point_id = (idx as u64).into();
+            segment.upsert_point(1, point_id, &[1.0, 1.0]).unwrap();
+            let payload: Payload = serde_json::json!({ "price": price }).into();
+            segment.set_full_payload(1, point_id, &payload).unwrap();
+        }
+
+        let mut order_by = OrderBy {
+            key: "price".to_string(),
+            direction: Direction::Desc,
+            start_from: None,
+        };
+        // Values are read from the payload, if there is no index
+        let page = segment
+            .read_ordered_filtered(None, 2, None, &order_by)
+            .unwrap();
+        assert_eq!(page, vec![(40.0, 4.into()), (30.0, 0.into())]);
+
+        segment
+            .create_field_index(2, "price", &Some(PayloadSchemaType::Integer.into()))
+            .unwrap();
+
+        let page = segment
+            .read_ordered_filtered(None, 2, None, &order_by)
+            .unwrap();
+        assert_eq!(page, vec![(40.0, 4.into()), (30.0, 0.into())]);
+
+        // Continue from the cursor in the middle of the equal values
+        order_by.direction = Direction::Asc;
+        order_by.start_from = Some(10.0);
+        let page = segment
+            .read_ordered_filtered(Some(3.into()), 2, None, &order_by)
+            .unwrap();
+        assert_eq!(page, vec![(10.0, 3.into()), (20.0, 2.into())]);
+
+        let filter: Filter = serde_json::from_value(serde_json::json!({
+            "must_not": [{"has_id": [3]}]
+        }))
+        .unwrap();
+        let page = segment
+            .read_ordered_filtered(None, 10, Some(&filter), &order_by)
+            .unwrap();
+        assert_eq!(
+            page,
+            vec![
+                (10.0, 1.into()),
+                (20.0, 2.into()),
+                (30.0, 0.into()),
+                (40.0, 4.into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_facet() {
+        let dir = TempDir::new("payload_dir").unwrap();
+        let config = SegmentConfig {
+            vector_size: 2,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance: Distance::Dot,
+            payload_storage_type: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+        let payloads = [
+            serde_json::json!({ "color": "red", "price": 10 }),
+            serde_json::json!({ "color": ["red", "blue", "red"], "price": 20 }),
+            serde_json::json!({ "color": "blue", "price": 30 }),
+            serde_json::json!({ "color": "red", "price": 40 }),
+            serde_json::json!({ "price": 50 }),
+        ];
+        for (idx, payload) in payloads.iter().enumerate() {
+            let point_id = (idx as u64).into();
+            segment.upsert_point(1, point_id, &[1.0, 1.0]).unwrap();
+            let payload: Payload = payload.clone().into();
+            segment.set_full_payload(1, point_id, &payload).unwrap();
+        }
+
+        let expected: HashMap<_, _> = [
+            (FacetValue::Keyword("red".to_string()), 3),
+            (FacetValue::Keyword("blue".to_string()), 2),
+        ]
+        .into_iter()
+        .collect();
+
+        // Values are read from the payload, if there is no index
+        assert_eq!(segment.facet("color", None, true).unwrap(), expected);
+
+        segment
+            .create_field_index(2, "color", &Some(PayloadSchemaType::Keyword.into()))
+            .unwrap();
+        assert_eq!(segment.facet("color", None, false).unwrap(), expected);
+
+        let filter: Filter = serde_json::from_value(serde_json::json!({
+            "must": [{"key": "price", "range": {"gte": 20}}]
+        }))
+        .unwrap();
+        let counts = segment.facet("color", Some(&filter), true).unwrap();
+        let expected: HashMap<_, _> = [
+            (FacetValue::Keyword("red".to_string()), 2),
+            (FacetValue::Keyword("blue".to_string()), 2),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(counts, expected);
+
+        let estimated_counts = segment.facet("color", Some(&filter), false).unwrap();
+        assert_eq!(estimated_counts.len(), 2);
+
+        let price_counts = segment.facet("price", Some(&filter), true).unwrap();
+        assert_eq!(price_counts.len(), 4);
+        assert_eq!(price_counts.get(&FacetValue::Integer(20)), Some(&1));
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let dir = TempDir::new("payload_dir").unwrap();
+        let config = SegmentConfig {
+            vector_size: 2,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance: Distance::Dot,
+            payload_storage_type: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+        let payloads = [
+            serde_json::json!({ "price": 10, "color": "red", "time": "2022-01-01T00:00:00Z" }),
+            serde_json::json!({ "price": [15, 25], "color": "blue", "time": "2022-01-03T00:00:00Z" }),
+            serde_json::json!({ "price": 30, "color": "red" }),
+            serde_json::json!({ "color": "red" }),
+        ];
+        for (idx, payload) in payloads.iter().enumerate() {
+            let point_id = (idx as u64).into();
+            segment.upsert_point(1, point_id, &[1.0, 1.0]).unwrap();
+            let payload: Payload = payload.clone().into();
+            segment.set_full_payload(1, point_id, &payload).unwrap();
+        }
+
+        // Values are read from the payload, if there is no index
+        let aggregation = segment.aggregate("price", None, Some(10.0)).unwrap();
+        assert_eq!(aggregation.count, 4);
+        assert_eq!(aggregation.min, Some(AggregationValue::Number(10.0)));
+        assert_eq!(aggregation.max, Some(AggregationValue::Number(30.0)));
+        assert_eq!(aggregation.sum, 80.0);
+        assert_eq!(aggregation.avg, Some(AggregationValue::Number(20.0)));
+        let buckets = aggregation
+            .histogram
+            .clone()
+            .unwrap()
+            .into_iter()
+            .map(|bucket| (bucket.from.value(), bucket.count))
+            .collect::<Vec<_>>();
+        assert_eq!(buckets, vec![(10.0, 2), (20.0, 1), (30.0, 1)]);
+
+        segment
+            .create_field_index(2, "price", &Some(PayloadSchemaType::Integer.into()))
+            .unwrap();
+        let indexed_aggregation = segment.aggregate("price", None, None).unwrap();
+        assert_eq!(indexed_aggregation.count, 4);
+        assert_eq!(indexed_aggregation.sum, 80.0);
+        assert_eq!(indexed_aggregation.histogram, None);
+
+        // Buckets are counted with the histogram of the index
+        let indexed_aggregation = segment.aggregate("price", None, Some(10.0)).unwrap();
+        assert_eq!(indexed_aggregation, aggregation);
+
+        let filter: Filter = serde_json::from_value(serde_json::json!({
+            "must": [{"key": "color", "match": {"value": "red"}}]
+        }))
+        .unwrap();
+        let filtered_aggregation = segment.aggregate("price", Some(&filter), None).unwrap();
+        assert_eq!(filtered_aggregation.count, 2);
+        assert_eq!(
+            filtered_aggregation.avg,
+            Some(AggregationValue::Number(20.0))
+        );
+
+        // Values of datetime fields are returned as datetimes
+        let day = 24.0 * 60.0 * 60.0;
+        let time_aggregation = segment.aggregate("time", None, Some(day)).unwrap();
+        assert_eq!(
+            serde_json::to_value(time_aggregation.avg).unwrap(),
+            serde_json::json!("2022-01-02T00:00:00Z")
+        );
+        let buckets = time_aggregation.histogram.clone().unwrap();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(
+            serde_json::to_value(buckets[1].to).unwrap(),
+            serde_json::json!("2022-01-04T00:00:00Z")
+        );
+
+        segment
+            .create_field_index(3, "time", &Some(PayloadSchemaType::Datetime.into()))
+            .unwrap();
+        let indexed_time_aggregation = segment.aggregate("time", None, Some(day)).unwrap();
+        assert_eq!(indexed_time_aggregation, time_aggregation);
+    }
+
+    #[test]
+    fn test_payload_index_stats() {
+        let dir = TempDir::new("payload_dir").unwrap();
+        let config = SegmentConfig {
+            vector_size: 2,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance: Distance::Dot,
+            payload_storage_type: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+        let payloads = [
+            serde_json::json!({ "price": 10, "color": "red" }),
+            serde_json::json!({ "price": [15, 25], "color": "blue" }),
+            serde_json::json!({ "price": 30, "color": "red" }),
+            serde_json::json!({ "color": "red" }),
+        ];
+        for (idx, payload) in payloads.iter().enumerate() {
+            let point_id = (idx as u64).into();
+            segment.upsert_point(1, point_id, &[1.0, 1.0]).unwrap();
+            let payload: Payload = payload.clone().into();
+            segment.set_full_payload(1, point_id, &payload).unwrap();
+        }
+        segment
+            .create_field_index(2, "price", &Some(PayloadSchemaType::Integer.into()))
+            .unwrap();
+        segment
+            .create_field_index(3, "color", &Some(PayloadSchemaType::Keyword.into()))
+            .unwrap();
+
+        let stats = segment.payload_index_stats().unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let color_stats = &stats["color"];
+        assert_eq!(color_stats.data_type, PayloadSchemaType::Keyword);
+        assert_eq!(color_stats.points_count, 4);
+        assert_eq!(color_stats.distinct_values, Some(2));
+        let top_colors = color_stats
+            .top_values
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|hit| (hit.value.clone(), hit.count))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            top_colors,
+            vec![
+                (FacetValue::Keyword("red".to_string()), 3),
+                (FacetValue::Keyword("blue".to_string()), 1),
+            ]
+        );
+        assert!(color_stats.numeric.is_none());
+        assert!(color_stats.ram_usage_bytes > 0);
+
+        let price_stats = &stats["price"];
+        assert_eq!(price_stats.data_type, PayloadSchemaType::Integer);
+        assert_eq!(price_stats.points_count, 3);
+        assert_eq!(price_stats.distinct_values, Some(4));
+        let numeric = price_stats.numeric.as_ref().unwrap();
+        assert_eq!(numeric.values_count, 4);
+        assert_eq!(numeric.min, 10.0);
+        assert_eq!(numeric.max, 30.0);
+        assert_eq!(numeric.quantiles.len(), INDEX_STATS_QUANTILES.len());
+        assert!(numeric
+            .quantiles
+            .iter()
+            .all(|quantile| (10.0..=30.0).contains(&quantile.value)));
+    }
+
+    #[test]
+    fn test_set_and_delete_payload_by_key() {
+        let dir = TempDir::new("payload_dir").unwrap();
+        let config = SegmentConfig {
+            vector_size: 2,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance: Distance::Dot,
+            payload_storage_type: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+        let point_id = 0.into();
+        segment.upsert_point(1, point_id, &[1.0, 1.0]).unwrap();
+        let payload: Payload = serde_json::json!({
+            "meta": {"author": {"name": "alice"}, "tags": [{"v": 1}, {"v": 2}]}
+        })
+        .into();
+        segment.set_full_payload(1, point_id, &payload).unwrap();
+        segment
+            .create_field_index(
+                2,
+                "meta.author.name",
+                &Some(PayloadSchemaType::Keyword.into()),
+            )
+            .unwrap();
+
+        let author_filter = |name: &str| -> Filter {
+            serde_json::from_value(serde_json::json!({
+                "must": [{"key": "meta.author.name", "match": {"value": name}}]
+            }))
+            .unwrap()
+        };
+
+        let author: Payload = serde_json::json!({ "name": "bob" }).into();
+        segment
+            .set_payload_by_key(3, point_id, &author, "meta.author")
+            .unwrap();
+        let seen: Payload = serde_json::json!({ "seen": true }).into();
+        segment
+            .set_payload_by_key(4, point_id, &seen, "meta.tags[1]")
+            .unwrap();
+
+        let payload = segment.payload(point_id).unwrap();
+        assert_eq!(
+            payload.get_value("meta.author.name"),
+            Some(&serde_json::json!("bob"))
+        );
+        assert_eq!(
+            payload.0["meta"]["tags"][1],
+            serde_json::json!({"v": 2, "seen": true})
+        );
+        assert_eq!(
+            segment.read_filtered(None, 10, Some(&author_filter("bob"))),
+            vec![point_id]
+        );
+        assert!(segment
+            .read_filtered(None, 10, Some(&author_filter("alice")))
+            .is_empty());
+
+        segment.delete_payload(5, point_id, "meta.tags[0]").unwrap();
+        segment
+            .delete_payload(6, point_id, "meta.author.name")
+            .unwrap();
+
+        let payload = segment.payload(point_id).unwrap();
+        assert_eq!(
+            payload.0["meta"]["tags"],
+            serde_json::json!([{"v": 2, "seen": true}])
+        );
+        assert!(segment
+            .read_filtered(None, 10, Some(&author_filter("bob")))
+            .is_empty());
+    }
+
     #[test]
     fn test_snapshot() {
         let data = r#"
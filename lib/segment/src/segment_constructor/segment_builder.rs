@@ -4,19 +4,21 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use itertools::Itertools;
+
 use crate::common::error_logging::LogError;
 use crate::entry::entry_point::{OperationError, OperationResult, SegmentEntry};
 use crate::index::PayloadIndex;
 use crate::segment::Segment;
 use crate::segment_constructor::{build_segment, load_segment};
-use crate::types::{PayloadKeyType, PayloadSchemaType, SegmentConfig};
+use crate::types::{PayloadFieldSchema, PayloadKeyType, PointOffsetType, SegmentConfig};
 
 /// Structure for constructing segment out of several other segments
 pub struct SegmentBuilder {
     pub segment: Option<Segment>,
     pub destination_path: PathBuf,
     pub temp_path: PathBuf,
-    pub indexed_fields: HashMap<PayloadKeyType, PayloadSchemaType>,
+    pub indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
 }
 
 impl SegmentBuilder {
@@ -50,84 +52,146 @@ impl SegmentBuilder {
     /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
     ///
     pub fn update_from(&mut self, other: &Segment, stopped: &AtomicBool) -> OperationResult<bool> {
-        match &mut self.segment {
-            None => Err(OperationError::service_error(
-                "Segment building error: created segment not found",
-            )),
-            Some(self_segment) => {
-                self_segment.version = cmp::max(self_segment.version(), other.version());
-
-                let other_id_tracker = other.id_tracker.borrow();
-                let other_vector_storage = other.vector_storage.borrow();
-                let other_payload_index = other.payload_index.borrow();
-
-                let mut id_tracker = self_segment.id_tracker.borrow_mut();
-                let mut vector_storage = self_segment.vector_storage.borrow_mut();
-                let mut payload_index = self_segment.payload_index.borrow_mut();
-
-                let new_internal_range = vector_storage.update_from(&*other_vector_storage)?;
-
-                for (new_internal_id, old_internal_id) in
-                    new_internal_range.zip(other_vector_storage.iter_ids())
-                {
-                    if stopped.load(Ordering::Relaxed) {
-                        return Err(OperationError::Cancelled {
-                            description: "Cancelled by external thread".to_string(),
-                        });
-                    }
-                    let external_id = other_id_tracker.external_id(old_internal_id).unwrap();
-                    let other_version = other_id_tracker.version(external_id).unwrap();
-
-                    match id_tracker.version(external_id) {
-                        None => {
-                            // New point, just insert
-                            id_tracker.set_link(external_id, new_internal_id)?;
-                            id_tracker.set_version(external_id, other_version)?;
-                            payload_index.assign(
-                                new_internal_id,
-                                &other_payload_index.payload(old_internal_id)?,
-                            )?;
-                        }
-                        Some(existing_version) => {
-                            if existing_version < other_version {
-                                // Other version is the newest, remove the existing one and replace
-                                let existing_internal_id =
-                                    id_tracker.internal_id(external_id).unwrap();
-                                vector_storage.delete(existing_internal_id)?;
-                                id_tracker.drop(external_id)?;
-                                id_tracker.set_link(external_id, new_internal_id)?;
-                                id_tracker.set_version(external_id, other_version)?;
-                                payload_index.assign(
-                                    new_internal_id,
-                                    &other_payload_index.payload(old_internal_id)?,
-                                )?;
-                            } else {
-                                // Old version is still good, do not move anything else
-                                // Mark newly added vector as removed
-                                vector_storage.delete(new_internal_id)?;
-                            };
-                        }
-                    }
-                }
+        self.update(&[other], stopped)
+    }
 
-                for (field, payload_schema) in other.payload_index.borrow().indexed_fields() {
-                    self.indexed_fields.insert(field, payload_schema);
-                }
+    /// Update current segment builder with all (not deleted) vectors and payload from `others` segments
+    ///
+    /// If there are fields with tenant index, points of each tenant are copied one after another
+    /// across all the `others` segments, so that data of the tenant is stored together.
+    /// Tenants are read from the indexes of the source segments, points are copied only once.
+    ///
+    /// # Result
+    ///
+    /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
+    ///
+    pub fn update(&mut self, others: &[&Segment], stopped: &AtomicBool) -> OperationResult<bool> {
+        let self_segment = match &mut self.segment {
+            None => {
+                return Err(OperationError::service_error(
+                    "Segment building error: created segment not found",
+                ))
+            }
+            Some(self_segment) => self_segment,
+        };
 
-                Ok(true)
+        for other in others {
+            self_segment.version = cmp::max(self_segment.version(), other.version());
+            for (field, payload_schema) in other.payload_index.borrow().indexed_fields() {
+                self.indexed_fields.insert(field, payload_schema);
+            }
+        }
+
+        let tenant_fields = self
+            .indexed_fields
+            .iter()
+            .filter(|(_, payload_schema)| payload_schema.is_tenant())
+            .map(|(field, _)| field.clone())
+            .sorted()
+            .collect_vec();
+
+        // Points of all the segments as (tenant, segment, internal id)
+        let mut points = Vec::new();
+        for (segment_idx, other) in others.iter().enumerate() {
+            let other_vector_storage = other.vector_storage.borrow();
+            let other_payload_index = other.payload_index.borrow();
+            for point_id in other_vector_storage.iter_ids() {
+                let tenant = tenant_fields
+                    .iter()
+                    .map(|field| other_payload_index.tenant_value(field, point_id))
+                    .collect::<OperationResult<Vec<_>>>()?;
+                points.push((tenant, segment_idx, point_id));
+            }
+        }
+        // Stable sort keeps the original order of points within the tenant
+        points.sort_by(|(tenant_a, _, _), (tenant_b, _, _)| tenant_a.cmp(tenant_b));
+
+        for (segment_idx, segment_points) in &points
+            .into_iter()
+            .group_by(|(_, segment_idx, _)| *segment_idx)
+        {
+            let other_ids = segment_points
+                .map(|(_, _, point_id)| point_id)
+                .collect_vec();
+            Self::copy_points(self_segment, others[segment_idx], other_ids, stopped)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Copy points with `other_ids` from the `other` segment.
+    /// Points, which are already present, are replaced only by the newer versions.
+    fn copy_points(
+        self_segment: &mut Segment,
+        other: &Segment,
+        other_ids: Vec<PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        let other_id_tracker = other.id_tracker.borrow();
+        let other_vector_storage = other.vector_storage.borrow();
+        let other_payload_index = other.payload_index.borrow();
+
+        let mut id_tracker = self_segment.id_tracker.borrow_mut();
+        let mut vector_storage = self_segment.vector_storage.borrow_mut();
+        let mut payload_index = self_segment.payload_index.borrow_mut();
+
+        let new_internal_range =
+            vector_storage.update_from(&*other_vector_storage, &mut other_ids.iter().copied())?;
+
+        for (new_internal_id, old_internal_id) in new_internal_range.zip(other_ids) {
+            if stopped.load(Ordering::Relaxed) {
+                return Err(OperationError::Cancelled {
+                    description: "Cancelled by external thread".to_string(),
+                });
+            }
+            let external_id = other_id_tracker.external_id(old_internal_id).unwrap();
+            let other_version = other_id_tracker.version(external_id).unwrap();
+
+            match id_tracker.version(external_id) {
+                None => {
+                    // New point, just insert
+                    id_tracker.set_link(external_id, new_internal_id)?;
+                    id_tracker.set_version(external_id, other_version)?;
+                    payload_index.assign(
+                        new_internal_id,
+                        &other_payload_index.payload(old_internal_id)?,
+                    )?;
+                }
+                Some(existing_version) => {
+                    if existing_version < other_version {
+                        // Other version is the newest, remove the existing one and replace
+                        let existing_internal_id = id_tracker.internal_id(external_id).unwrap();
+                        vector_storage.delete(existing_internal_id)?;
+                        id_tracker.drop(external_id)?;
+                        id_tracker.set_link(external_id, new_internal_id)?;
+                        id_tracker.set_version(external_id, other_version)?;
+                        payload_index.assign(
+                            new_internal_id,
+                            &other_payload_index.payload(old_internal_id)?,
+                        )?;
+                    } else {
+                        // Old version is still good, do not move anything else
+                        // Mark newly added vector as removed
+                        vector_storage.delete(new_internal_id)?;
+                    };
+                }
             }
         }
+        Ok(())
     }
 
     pub fn build(mut self, stopped: &AtomicBool) -> Result<Segment, OperationError> {
         {
-            let mut segment = self.segment.ok_or_else(|| {
+            let mut segment = self.segment.take().ok_or_else(|| {
                 OperationError::service_error("Segment building error: created segment not found")
             })?;
-            self.segment = None;
 
             for (field, payload_schema) in &self.indexed_fields {
-                segment.create_field_index(segment.version(), field, &Some(*payload_schema))?;
+                segment.create_field_index(
+                    segment.version(),
+                    field,
+                    &Some(payload_schema.clone()),
+                )?;
                 if stopped.load(Ordering::Relaxed) {
                     return Err(OperationError::Cancelled {
                         description: "Cancelled by external thread".to_string(),
@@ -57,10 +57,19 @@ fn create_segment(
         PayloadStorageType::OnDisk => sp(OnDiskPayloadStorage::open(database.clone())?.into()),
     };
 
+    let segment_type = match config.index {
+        Indexes::Plain { .. } => SegmentType::Plain,
+        Indexes::Hnsw { .. } => SegmentType::Indexed,
+    };
+
+    let appendable_flag =
+        segment_type == SegmentType::Plain {} && config.storage_type == StorageType::InMemory;
+
     let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
         payload_storage,
         id_tracker.clone(),
         &payload_index_path,
+        appendable_flag,
     )?);
 
     let vector_index: Arc<AtomicRefCell<VectorIndexSS>> = match config.index {
@@ -76,14 +85,6 @@ fn create_segment(
         )?),
     };
 
-    let segment_type = match config.index {
-        Indexes::Plain { .. } => SegmentType::Plain,
-        Indexes::Hnsw { .. } => SegmentType::Indexed,
-    };
-
-    let appendable_flag =
-        segment_type == SegmentType::Plain {} && config.storage_type == StorageType::InMemory;
-
     Ok(Segment {
         version,
         persisted_version: Arc::new(Mutex::new(version)),
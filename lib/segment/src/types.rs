@@ -1,13 +1,15 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Formatter;
 use std::mem::size_of;
-use std::ops::Deref;
+use std::ops::{Bound, Deref};
 use std::rc::Rc;
 use std::str::FromStr;
 
-use geo::prelude::HaversineDistance;
-use geo::Point;
+use chrono::{DateTime, TimeZone, Utc};
+use geo::prelude::{Contains, HaversineDistance};
+use geo::{Coordinate, LineString, Point, Polygon};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
@@ -16,6 +18,7 @@ use serde_json::{Map, Value};
 use uuid::Uuid;
 
 use crate::common::utils;
+use crate::entry::entry_point::OperationResult;
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric};
 
@@ -34,6 +37,12 @@ pub type VectorElementType = f32;
 pub type FloatPayloadType = f64;
 /// Type of integer point payload
 pub type IntPayloadType = i64;
+/// Type of UUID point payload, stored in payload as a string
+pub type UuidIntType = u128;
+/// Type of datetime point payload, seconds since epoch.
+/// Stored in payload as a string in RFC 3339 format.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct DateTimePayloadType(pub f64);
 
 pub const VECTOR_ELEMENT_SIZE: usize = size_of::<VectorElementType>();
 
@@ -201,10 +210,27 @@ pub enum SegmentType {
 }
 
 /// Payload field type & index information
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct PayloadIndexInfo {
     pub data_type: PayloadSchemaType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<PayloadSchemaParams>,
+}
+
+impl PayloadIndexInfo {
+    pub fn new(field_schema: PayloadFieldSchema) -> Self {
+        match field_schema {
+            PayloadFieldSchema::FieldType(data_type) => PayloadIndexInfo {
+                data_type,
+                params: None,
+            },
+            PayloadFieldSchema::FieldParams(params) => PayloadIndexInfo {
+                data_type: params.kind(),
+                params: Some(params),
+            },
+        }
+    }
 }
 
 /// Aggregated information about segment
@@ -230,6 +256,301 @@ pub struct SearchParams {
     pub hnsw_ef: Option<usize>,
 }
 
+/// Numeric payload value, used in the score expression
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadVariable {
+    /// Payload field to read the value from.
+    /// If the field contains several values, the first one is used.
+    pub key: PayloadKeyType,
+    /// Value to use if the point has no numeric value in the field. Default: 0
+    #[serde(default)]
+    pub default: f64,
+}
+
+/// Origin of the decay function
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum DecayOrigin {
+    /// Numeric origin
+    Number(f64),
+    /// Datetime origin in RFC 3339 format, e.g. `2022-08-01T12:00:00Z`
+    Datetime(DateTime<Utc>),
+}
+
+impl DecayOrigin {
+    /// Numeric representation of the origin. Datetime is converted into seconds since epoch.
+    pub fn value(&self) -> f64 {
+        match self {
+            DecayOrigin::Number(number) => *number,
+            DecayOrigin::Datetime(datetime) => datetime_to_seconds(datetime),
+        }
+    }
+}
+
+/// Seconds since epoch, used as numeric value of the datetime payload fields
+pub fn datetime_to_seconds(datetime: &DateTime<Utc>) -> f64 {
+    datetime.timestamp_millis() as f64 / 1000.0
+}
+
+/// Datetime of the seconds since epoch, `None` if it is out of the supported range
+pub fn seconds_to_datetime(seconds: f64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_millis_opt((seconds * 1000.0).round() as i64)
+        .single()
+}
+
+/// Parse datetime in RFC 3339 format into seconds since epoch
+pub fn parse_datetime_seconds(string: &str) -> Option<f64> {
+    DateTime::parse_from_rfc3339(string)
+        .ok()
+        .map(|datetime| datetime_to_seconds(&datetime.with_timezone(&Utc)))
+}
+
+/// Parse UUID in any of the supported text formats into its integer representation
+pub fn parse_uuid(string: &str) -> Option<UuidIntType> {
+    Uuid::parse_str(string).ok().map(|uuid| uuid.as_u128())
+}
+
+pub const DEFAULT_DECAY_MIDPOINT: f64 = 0.5;
+
+/// Parameters of the decay function.
+/// Decay is equal to 1 at the origin and to `midpoint` at the `scale` distance from the origin.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct DecayParams {
+    /// Payload field with the numeric value or datetime in RFC 3339 format.
+    /// Decay of the points without value in the field is 0.
+    pub key: PayloadKeyType,
+    /// Value of the field, at which the decay is equal to 1
+    pub origin: DecayOrigin,
+    /// Distance from the origin, at which the decay is equal to `midpoint`.
+    /// For datetime fields - number of seconds.
+    pub scale: f64,
+    /// Decay at the `scale` distance from the origin, in range (0, 1). Default: 0.5
+    pub midpoint: Option<f64>,
+}
+
+impl DecayParams {
+    pub fn midpoint(&self) -> f64 {
+        self.midpoint.unwrap_or(DEFAULT_DECAY_MIDPOINT)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.scale <= 0.0 {
+            return Err(format!(
+                "Decay scale must be positive, got {} for field {}",
+                self.scale, self.key
+            ));
+        }
+        let midpoint = self.midpoint();
+        if midpoint <= 0.0 || midpoint >= 1.0 {
+            return Err(format!(
+                "Decay midpoint must be in range (0, 1), got {} for field {}",
+                midpoint, self.key
+            ));
+        }
+        Ok(())
+    }
+
+    /// Normalized distance of the value from the origin
+    fn distance<F>(&self, payload_value: &F) -> OperationResult<Option<f64>>
+    where
+        F: Fn(PayloadKeyTypeRef) -> OperationResult<Option<f64>>,
+    {
+        Ok(payload_value(&self.key)?.map(|value| (value - self.origin.value()).abs() / self.scale))
+    }
+}
+
+/// Expression for computing the final score of the found point
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreExpression {
+    /// Constant value
+    Constant(f64),
+    /// Similarity score of the point.
+    /// For `Euclid` distance it is the distance to the query, so smaller is better,
+    /// while the expression is maximized. Use negative weight, e.g. `mult: [-1, score]`,
+    /// to prefer closer points.
+    Score,
+    /// Numeric value of the payload field
+    Payload(PayloadVariable),
+    /// Product of the expressions
+    Mult(Vec<ScoreExpression>),
+    /// Sum of the expressions
+    Sum(Vec<ScoreExpression>),
+    /// Natural logarithm of the expression. Non-positive values evaluate to 0
+    Ln(Box<ScoreExpression>),
+    /// Linear decay: `max(0, 1 - (1 - midpoint) * |value - origin| / scale)`
+    LinDecay(DecayParams),
+    /// Exponential decay: `midpoint ^ (|value - origin| / scale)`
+    ExpDecay(DecayParams),
+    /// Gaussian decay: `midpoint ^ ((|value - origin| / scale) ^ 2)`
+    GaussDecay(DecayParams),
+}
+
+impl ScoreExpression {
+    /// Evaluate expression for a single point
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - similarity score of the point
+    /// * `payload_value` - returns numeric value of the given payload field for the point, if any
+    pub fn evaluate<F>(&self, score: ScoreType, payload_value: &F) -> OperationResult<f64>
+    where
+        F: Fn(PayloadKeyTypeRef) -> OperationResult<Option<f64>>,
+    {
+        let value = match self {
+            ScoreExpression::Constant(value) => *value,
+            ScoreExpression::Score => score as f64,
+            ScoreExpression::Payload(variable) => {
+                payload_value(&variable.key)?.unwrap_or(variable.default)
+            }
+            ScoreExpression::Mult(expressions) => {
+                let mut product = 1.0;
+                for expression in expressions {
+                    product *= expression.evaluate(score, payload_value)?;
+                }
+                product
+            }
+            ScoreExpression::Sum(expressions) => {
+                let mut sum = 0.0;
+                for expression in expressions {
+                    sum += expression.evaluate(score, payload_value)?;
+                }
+                sum
+            }
+            ScoreExpression::Ln(expression) => {
+                let value = expression.evaluate(score, payload_value)?;
+                if value > 0.0 {
+                    value.ln()
+                } else {
+                    0.0
+                }
+            }
+            ScoreExpression::LinDecay(params) => match params.distance(payload_value)? {
+                Some(distance) => (1.0 - (1.0 - params.midpoint()) * distance).max(0.0),
+                None => 0.0,
+            },
+            ScoreExpression::ExpDecay(params) => match params.distance(payload_value)? {
+                Some(distance) => params.midpoint().powf(distance),
+                None => 0.0,
+            },
+            ScoreExpression::GaussDecay(params) => match params.distance(payload_value)? {
+                Some(distance) => params.midpoint().powf(distance * distance),
+                None => 0.0,
+            },
+        };
+        Ok(value)
+    }
+
+    /// Check that parameters of the expression are valid
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ScoreExpression::Constant(_) | ScoreExpression::Score | ScoreExpression::Payload(_) => {
+                Ok(())
+            }
+            ScoreExpression::Mult(expressions) | ScoreExpression::Sum(expressions) => expressions
+                .iter()
+                .try_for_each(|expression| expression.validate()),
+            ScoreExpression::Ln(expression) => expression.validate(),
+            ScoreExpression::LinDecay(params)
+            | ScoreExpression::ExpDecay(params)
+            | ScoreExpression::GaussDecay(params) => params.validate(),
+        }
+    }
+}
+
+/// Re-scoring of the vector search results.
+/// Vector search returns more candidates than requested, final score of each candidate is
+/// computed with the expression, and the best candidates by the final score are returned.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct Rescore {
+    /// Expression which defines the final score of the point. Larger is better.
+    pub expression: ScoreExpression,
+    /// How many times more candidates to take from the vector search before re-scoring.
+    /// Default: 4
+    pub oversampling: Option<usize>,
+}
+
+pub const DEFAULT_RESCORE_OVERSAMPLING: usize = 4;
+
+impl Rescore {
+    pub fn oversampling(&self) -> usize {
+        self.oversampling
+            .unwrap_or(DEFAULT_RESCORE_OVERSAMPLING)
+            .max(1)
+    }
+}
+
+/// Method used to process the query in the segment
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryStrategy {
+    /// Check every point of the segment
+    FullScan,
+    /// Check the filter on every point of the segment, no suitable payload index found
+    FilteredScan,
+    /// Iterate over points, selected with the payload index
+    PayloadIndex,
+    /// Traverse the HNSW graph
+    Hnsw,
+    /// Traverse the HNSW graph, checking the filter on each visited point
+    FilteredHnsw,
+    /// Estimate number of matching points with the payload index, points are not checked
+    Estimation,
+}
+
+/// Number of points which satisfy the filter, estimated with the payload index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct FilterCardinality {
+    /// Minimal possible number of matching points
+    pub min: usize,
+    /// Expected number of matching points
+    pub exp: usize,
+    /// Maximal possible number of matching points
+    pub max: usize,
+}
+
+/// Explanation of how the query was executed in a single segment
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct QueryExplain {
+    /// Estimated number of points which satisfy the filter. Present only for filtered queries
+    pub cardinality: Option<FilterCardinality>,
+    /// Chosen method of processing the query. Absent if the segment was skipped
+    pub strategy: Option<QueryStrategy>,
+    /// Number of computed distances between the query vector and stored vectors
+    pub distance_computations: usize,
+    /// Number of points considered during the query
+    pub visited_nodes: usize,
+    /// Time spent in the segment, seconds
+    pub time: f64,
+    /// True, if the segment was not searched because it can't contain matching points
+    pub skipped: bool,
+}
+
+impl QueryExplain {
+    /// Combine explanations of the queries, executed over different parts of the same segment.
+    /// Strategy of the first part is preferred.
+    pub fn merge(&mut self, other: QueryExplain) {
+        self.cardinality = match (self.cardinality, other.cardinality) {
+            (Some(a), Some(b)) => Some(FilterCardinality {
+                min: a.min + b.min,
+                exp: a.exp + b.exp,
+                max: a.max + b.max,
+            }),
+            (a, b) => a.or(b),
+        };
+        self.strategy = self.strategy.or(other.strategy);
+        self.distance_computations += other.distance_computations;
+        self.visited_nodes += other.visited_nodes;
+        self.time += other.time;
+        self.skipped = self.skipped && other.skipped;
+    }
+}
+
 /// Vector index configuration of the segment
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -415,18 +736,41 @@ pub struct Payload(pub Map<String, Value>);
 
 impl Payload {
     pub fn merge(&mut self, value: &Payload) {
-        for (key, value) in &value.0 {
-            match value {
-                Value::Null => self.0.remove(key),
-                _ => self.0.insert(key.to_owned(), value.to_owned()),
-            };
-        }
+        utils::merge_json_maps(&mut self.0, &value.0)
+    }
+
+    /// Merge `value` into the nested object located by the `path`, see [`utils::merge_value_into_json_map`]
+    pub fn merge_by_key(&mut self, value: &Payload, path: &str) {
+        utils::merge_value_into_json_map(path, &mut self.0, &value.0)
     }
 
+    /// Value of the path, see [`utils::get_value_from_json_map`]
     pub fn get_value(&self, path: &str) -> Option<&Value> {
         utils::get_value_from_json_map(path, &self.0)
     }
 
+    /// Values of the field, where `key[]` path segments select each element of the array.
+    /// Without `[]` segments this is the same as `get_value`.
+    /// Paths are parsed the same way as by set and delete payload operations.
+    /// Values found in several array elements are combined into a single array.
+    pub fn get_value_cow(&self, path: &str) -> Option<Cow<'_, Value>> {
+        if !path.contains("[]") {
+            return self.get_value(path).map(Cow::Borrowed);
+        }
+        let values = utils::get_array_values_from_json_map(path, &self.0);
+        if values.is_empty() {
+            return None;
+        }
+        let flattened = values
+            .into_iter()
+            .flat_map(|value| match value {
+                Value::Array(array) => array.iter().cloned().collect_vec(),
+                value => vec![value.clone()],
+            })
+            .collect();
+        Some(Cow::Owned(Value::Array(flattened)))
+    }
+
     pub fn remove(&mut self, path: &str) -> Option<Value> {
         utils::remove_value_from_json_map(path, &mut self.0)
     }
@@ -562,6 +906,10 @@ pub enum PayloadSchemaType {
     Integer,
     Float,
     Geo,
+    Text,
+    Datetime,
+    Bool,
+    Uuid,
 }
 
 impl PayloadSchemaType {
@@ -571,6 +919,10 @@ impl PayloadSchemaType {
             2 => Some(PayloadSchemaType::Integer),
             3 => Some(PayloadSchemaType::Float),
             4 => Some(PayloadSchemaType::Geo),
+            5 => Some(PayloadSchemaType::Text),
+            6 => Some(PayloadSchemaType::Datetime),
+            7 => Some(PayloadSchemaType::Bool),
+            8 => Some(PayloadSchemaType::Uuid),
             _ => None,
         }
     }
@@ -581,14 +933,350 @@ impl PayloadSchemaType {
             PayloadSchemaType::Integer => 2,
             PayloadSchemaType::Float => 3,
             PayloadSchemaType::Geo => 4,
+            PayloadSchemaType::Text => 5,
+            PayloadSchemaType::Datetime => 6,
+            PayloadSchemaType::Bool => 7,
+            PayloadSchemaType::Uuid => 8,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextIndexType {
+    Text,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerType {
+    /// Split text by whitespace characters only
+    Whitespace,
+    /// Split text by any non-alphanumeric characters
+    Word,
+    /// Like `word`, but also index every prefix of each token
+    Prefix,
+}
+
+impl Default for TokenizerType {
+    fn default() -> Self {
+        TokenizerType::Word
+    }
+}
+
+/// Parameters of the full-text index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct TextIndexParams {
+    // Required for the untagged `PayloadSchemaParams` to be distinguishable
+    pub r#type: TextIndexType,
+    #[serde(default)]
+    pub tokenizer: TokenizerType,
+    /// Tokens shorter than this are not indexed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_token_len: Option<usize>,
+    /// Tokens longer than this are not indexed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_token_len: Option<usize>,
+    /// If true - lowercase all tokens. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lowercase: Option<bool>,
+}
+
+impl Default for TextIndexParams {
+    fn default() -> Self {
+        TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: Default::default(),
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeywordIndexType {
+    Keyword,
+}
+
+/// Parameters of the keyword index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct KeywordIndexParams {
+    // Required for the untagged `PayloadSchemaParams` to be distinguishable
+    pub r#type: KeywordIndexType,
+    /// If true - store index on disk in non-appendable segments. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+    /// If true - the field identifies a tenant. Points of each tenant are stored together,
+    /// additional HNSW links are built for each large enough tenant.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_tenant: Option<bool>,
+}
+
+impl Default for KeywordIndexParams {
+    fn default() -> Self {
+        KeywordIndexParams {
+            r#type: KeywordIndexType::Keyword,
+            on_disk: None,
+            is_tenant: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegerIndexType {
+    Integer,
+}
+
+/// Parameters of the integer index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct IntegerIndexParams {
+    // Required for the untagged `PayloadSchemaParams` to be distinguishable
+    pub r#type: IntegerIndexType,
+    /// If true - support direct lookups (`match` conditions). Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lookup: Option<bool>,
+    /// If true - support ranges filters. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<bool>,
+    /// If true - store index on disk in non-appendable segments. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+impl IntegerIndexParams {
+    pub fn lookup(&self) -> bool {
+        self.lookup.unwrap_or(true)
+    }
+
+    pub fn range(&self) -> bool {
+        self.range.unwrap_or(true)
+    }
+}
+
+impl Default for IntegerIndexParams {
+    fn default() -> Self {
+        IntegerIndexParams {
+            r#type: IntegerIndexType::Integer,
+            lookup: None,
+            range: None,
+            on_disk: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FloatIndexType {
+    Float,
+}
+
+/// Parameters of the float index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct FloatIndexParams {
+    // Required for the untagged `PayloadSchemaParams` to be distinguishable
+    pub r#type: FloatIndexType,
+    /// If true - store index on disk in non-appendable segments. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+impl Default for FloatIndexParams {
+    fn default() -> Self {
+        FloatIndexParams {
+            r#type: FloatIndexType::Float,
+            on_disk: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GeoIndexType {
+    Geo,
+}
+
+/// Parameters of the geo index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoIndexParams {
+    // Required for the untagged `PayloadSchemaParams` to be distinguishable
+    pub r#type: GeoIndexType,
+    /// If true - store index on disk in non-appendable segments. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+impl Default for GeoIndexParams {
+    fn default() -> Self {
+        GeoIndexParams {
+            r#type: GeoIndexType::Geo,
+            on_disk: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DatetimeIndexType {
+    Datetime,
+}
+
+/// Parameters of the datetime index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct DatetimeIndexParams {
+    // Required for the untagged `PayloadSchemaParams` to be distinguishable
+    pub r#type: DatetimeIndexType,
+    /// If true - store index on disk in non-appendable segments. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+impl Default for DatetimeIndexParams {
+    fn default() -> Self {
+        DatetimeIndexParams {
+            r#type: DatetimeIndexType::Datetime,
+            on_disk: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UuidIndexType {
+    Uuid,
+}
+
+/// Parameters of the UUID index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct UuidIndexParams {
+    // Required for the untagged `PayloadSchemaParams` to be distinguishable
+    pub r#type: UuidIndexType,
+    /// If true - store index on disk in non-appendable segments. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+impl Default for UuidIndexParams {
+    fn default() -> Self {
+        UuidIndexParams {
+            r#type: UuidIndexType::Uuid,
+            on_disk: None,
+        }
+    }
+}
+
+/// Payload type with additional index parameters
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(untagged, rename_all = "snake_case")]
+pub enum PayloadSchemaParams {
+    Text(TextIndexParams),
+    Keyword(KeywordIndexParams),
+    Integer(IntegerIndexParams),
+    Float(FloatIndexParams),
+    Geo(GeoIndexParams),
+    Datetime(DatetimeIndexParams),
+    Uuid(UuidIndexParams),
+}
+
+impl PayloadSchemaParams {
+    pub fn kind(&self) -> PayloadSchemaType {
+        match self {
+            PayloadSchemaParams::Text(_) => PayloadSchemaType::Text,
+            PayloadSchemaParams::Keyword(_) => PayloadSchemaType::Keyword,
+            PayloadSchemaParams::Integer(_) => PayloadSchemaType::Integer,
+            PayloadSchemaParams::Float(_) => PayloadSchemaType::Float,
+            PayloadSchemaParams::Geo(_) => PayloadSchemaType::Geo,
+            PayloadSchemaParams::Datetime(_) => PayloadSchemaType::Datetime,
+            PayloadSchemaParams::Uuid(_) => PayloadSchemaType::Uuid,
+        }
+    }
+
+    /// Whether the index should be stored on disk instead of RAM, where it is possible
+    pub fn on_disk(&self) -> bool {
+        let on_disk = match self {
+            PayloadSchemaParams::Text(_) => None,
+            PayloadSchemaParams::Keyword(params) => params.on_disk,
+            PayloadSchemaParams::Integer(params) => params.on_disk,
+            PayloadSchemaParams::Float(params) => params.on_disk,
+            PayloadSchemaParams::Geo(params) => params.on_disk,
+            PayloadSchemaParams::Datetime(params) => params.on_disk,
+            PayloadSchemaParams::Uuid(params) => params.on_disk,
+        };
+        on_disk.unwrap_or(false)
+    }
+
+    /// Check that parameters of the index are consistent
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            PayloadSchemaParams::Integer(params) if !params.lookup() && !params.range() => {
+                Err("Integer index must support lookup, range or both".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Type of the indexed payload field, optionally with parameters of its index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(untagged, rename_all = "snake_case")]
+pub enum PayloadFieldSchema {
+    FieldType(PayloadSchemaType),
+    FieldParams(PayloadSchemaParams),
+}
+
+impl PayloadFieldSchema {
+    pub fn schema_type(&self) -> PayloadSchemaType {
+        match self {
+            PayloadFieldSchema::FieldType(field_type) => *field_type,
+            PayloadFieldSchema::FieldParams(params) => params.kind(),
         }
     }
+
+    /// Check that parameters of the index are consistent
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            PayloadFieldSchema::FieldType(_) => Ok(()),
+            PayloadFieldSchema::FieldParams(params) => params.validate(),
+        }
+    }
+
+    /// Whether the field identifies a tenant, see [`KeywordIndexParams::is_tenant`]
+    pub fn is_tenant(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(params)) => {
+                params.is_tenant.unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<PayloadSchemaType> for PayloadFieldSchema {
+    fn from(payload_schema_type: PayloadSchemaType) -> Self {
+        PayloadFieldSchema::FieldType(payload_schema_type)
+    }
+}
+
+impl From<PayloadSchemaParams> for PayloadFieldSchema {
+    fn from(payload_schema_params: PayloadSchemaParams) -> Self {
+        PayloadFieldSchema::FieldParams(payload_schema_params)
+    }
 }
 
 pub fn value_type(value: &Value) -> Option<PayloadSchemaType> {
     match value {
         Value::Null => None,
-        Value::Bool(_) => None,
+        Value::Bool(_) => Some(PayloadSchemaType::Bool),
         Value::Number(num) => {
             if num.is_i64() {
                 Some(PayloadSchemaType::Integer)
@@ -660,12 +1348,161 @@ pub struct MatchValue {
     pub value: ValueVariants,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AnyVariants {
+    Keywords(BTreeSet<String>),
+    Integers(BTreeSet<IntPayloadType>),
+}
+
+/// Exact match on any of the given values
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchAny {
+    pub any: AnyVariants,
+}
+
+/// Should have at least one value not matching any of the given values.
+/// Only values of the same type are considered, e.g. numbers are ignored when keywords are given
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchExcept {
+    pub except: AnyVariants,
+}
+
+/// Full-text match of the strings.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchText {
+    pub text: String,
+}
+
+/// Match keywords which start with the given prefix
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPrefix {
+    pub prefix: String,
+}
+
+impl MatchPrefix {
+    pub fn check(&self, keyword: &str) -> bool {
+        keyword.starts_with(self.prefix.as_str())
+    }
+}
+
+/// Lexicographic range of keywords, compared byte-wise
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct KeywordRange {
+    /// point.key < range.lt
+    pub lt: Option<String>,
+    /// point.key > range.gt
+    pub gt: Option<String>,
+    /// point.key >= range.gte
+    pub gte: Option<String>,
+    /// point.key <= range.lte
+    pub lte: Option<String>,
+}
+
+impl KeywordRange {
+    /// Smallest keyword, which could be in the range
+    pub fn lower_bound(&self) -> Bound<&str> {
+        match (&self.gte, &self.gt) {
+            (Some(gte), Some(gt)) if gt >= gte => Bound::Excluded(gt.as_str()),
+            (Some(gte), _) => Bound::Included(gte.as_str()),
+            (None, Some(gt)) => Bound::Excluded(gt.as_str()),
+            (None, None) => Bound::Unbounded,
+        }
+    }
+
+    /// Check upper bounds only, keywords above the range don't pass it
+    pub fn check_upper(&self, keyword: &str) -> bool {
+        self.lt.as_ref().map_or(true, |lt| keyword < lt.as_str())
+            && self
+                .lte
+                .as_ref()
+                .map_or(true, |lte| keyword <= lte.as_str())
+    }
+
+    pub fn check(&self, keyword: &str) -> bool {
+        self.gt.as_ref().map_or(true, |gt| keyword > gt.as_str())
+            && self
+                .gte
+                .as_ref()
+                .map_or(true, |gte| keyword >= gte.as_str())
+            && self.check_upper(keyword)
+    }
+}
+
+/// Match keywords within the lexicographic range
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchKeywordRange {
+    pub keyword_range: KeywordRange,
+}
+
+/// Match keywords by the pattern.
+/// `*` matches any sequence of characters, `?` matches exactly one character.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchWildcard {
+    pub wildcard: String,
+}
+
+impl MatchWildcard {
+    /// Part of the pattern before the first wildcard, all matching keywords start with it
+    pub fn literal_prefix(&self) -> &str {
+        match self.wildcard.find(&['*', '?'][..]) {
+            Some(position) => &self.wildcard[..position],
+            None => &self.wildcard,
+        }
+    }
+
+    pub fn check(&self, keyword: &str) -> bool {
+        let pattern = self.wildcard.as_str();
+        let next_char = |text: &str, pos: usize| text[pos..].chars().next();
+        // Byte positions in the pattern and in the keyword
+        let (mut pattern_pos, mut text_pos) = (0, 0);
+        // Position of the last `*` in the pattern and of the text it was matched to
+        let mut backtrack: Option<(usize, usize)> = None;
+        while let Some(text_char) = next_char(keyword, text_pos) {
+            match next_char(pattern, pattern_pos) {
+                Some('*') => {
+                    backtrack = Some((pattern_pos, text_pos));
+                    pattern_pos += 1;
+                }
+                Some(c) if c == '?' || c == text_char => {
+                    pattern_pos += c.len_utf8();
+                    text_pos += text_char.len_utf8();
+                }
+                _ => match backtrack {
+                    // Let the last `*` consume one more character
+                    Some((star_pos, star_text_pos)) => {
+                        let consumed = next_char(keyword, star_text_pos).map_or(1, char::len_utf8);
+                        backtrack = Some((star_pos, star_text_pos + consumed));
+                        pattern_pos = star_pos + 1;
+                        text_pos = star_text_pos + consumed;
+                    }
+                    None => return false,
+                },
+            }
+        }
+        pattern[pattern_pos..].chars().all(|c| c == '*')
+    }
+}
+
 /// Match filter request
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 pub enum MatchInterface {
     Value(MatchValue),
+    Text(MatchText),
+    Any(MatchAny),
+    Except(MatchExcept),
+    Prefix(MatchPrefix),
+    KeywordRange(MatchKeywordRange),
+    Wildcard(MatchWildcard),
     Keyword(MatchKeyword),
     Integer(MatchInteger),
 }
@@ -676,6 +1513,12 @@ pub enum MatchInterface {
 #[serde(untagged)]
 pub enum Match {
     Value(MatchValue),
+    Text(MatchText),
+    Any(MatchAny),
+    Except(MatchExcept),
+    Prefix(MatchPrefix),
+    KeywordRange(MatchKeywordRange),
+    Wildcard(MatchWildcard),
     Keyword(MatchKeyword),
     Integer(MatchInteger),
 }
@@ -684,6 +1527,12 @@ impl From<MatchInterface> for Match {
     fn from(value: MatchInterface) -> Self {
         match value {
             MatchInterface::Value(value) => Self::Value(MatchValue { value: value.value }),
+            MatchInterface::Text(MatchText { text }) => Self::Text(MatchText { text }),
+            MatchInterface::Any(MatchAny { any }) => Self::Any(MatchAny { any }),
+            MatchInterface::Except(MatchExcept { except }) => Self::Except(MatchExcept { except }),
+            MatchInterface::Prefix(prefix) => Self::Prefix(prefix),
+            MatchInterface::KeywordRange(range) => Self::KeywordRange(range),
+            MatchInterface::Wildcard(wildcard) => Self::Wildcard(wildcard),
             MatchInterface::Keyword(MatchKeyword { keyword }) => Self::Value(MatchValue {
                 value: ValueVariants::Keyword(keyword),
             }),
@@ -718,26 +1567,487 @@ impl From<IntPayloadType> for Match {
     }
 }
 
+/// Bound of the range filter
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum RangeValue {
+    Number(FloatPayloadType),
+    /// Datetime in RFC 3339 format, compared with datetime payload values
+    Datetime(DateTime<Utc>),
+}
+
+impl RangeValue {
+    /// Numeric representation of the bound. Datetime is converted into seconds since epoch.
+    pub fn value(&self) -> FloatPayloadType {
+        match self {
+            RangeValue::Number(number) => *number,
+            RangeValue::Datetime(datetime) => datetime_to_seconds(datetime),
+        }
+    }
+}
+
+fn deserialize_range_value<'de, D>(deserializer: D) -> Result<Option<FloatPayloadType>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value: Option<RangeValue> = Option::deserialize(deserializer)?;
+    Ok(value.map(|value| value.value()))
+}
+
 /// Range filter request
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct Range {
     /// point.key < range.lt
+    #[serde(default, deserialize_with = "deserialize_range_value")]
+    #[schemars(with = "Option<RangeValue>")]
     pub lt: Option<FloatPayloadType>,
     /// point.key > range.gt
+    #[serde(default, deserialize_with = "deserialize_range_value")]
+    #[schemars(with = "Option<RangeValue>")]
     pub gt: Option<FloatPayloadType>,
     /// point.key >= range.gte
+    #[serde(default, deserialize_with = "deserialize_range_value")]
+    #[schemars(with = "Option<RangeValue>")]
     pub gte: Option<FloatPayloadType>,
     /// point.key <= range.lte
+    #[serde(default, deserialize_with = "deserialize_range_value")]
+    #[schemars(with = "Option<RangeValue>")]
     pub lte: Option<FloatPayloadType>,
 }
 
-impl Range {
-    pub fn check_range(&self, number: FloatPayloadType) -> bool {
-        self.lt.map_or(true, |x| number < x)
-            && self.gt.map_or(true, |x| number > x)
-            && self.lte.map_or(true, |x| number <= x)
-            && self.gte.map_or(true, |x| number >= x)
+impl Range {
+    pub fn check_range(&self, number: FloatPayloadType) -> bool {
+        self.lt.map_or(true, |x| number < x)
+            && self.gt.map_or(true, |x| number > x)
+            && self.lte.map_or(true, |x| number <= x)
+            && self.gte.map_or(true, |x| number >= x)
+    }
+}
+
+/// Direction of ordering
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Direction::Asc
+    }
+}
+
+/// Order points by the value of an indexed payload field.
+/// Point with multiple values is ordered by the smallest one in ascending order
+/// and by the largest one in descending order.
+/// Points with equal values are ordered by id.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct OrderBy {
+    /// Payload key to order by. The field must have an integer, float or datetime index
+    pub key: PayloadKeyType,
+    /// Direction of ordering. Default: asc
+    #[serde(default)]
+    pub direction: Direction,
+    /// Order value to start from, including.
+    /// Together with the `offset` point id forms a cursor for the pagination.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_range_value",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(with = "Option<RangeValue>")]
+    pub start_from: Option<FloatPayloadType>,
+}
+
+impl OrderBy {
+    fn compare_values(&self, a: FloatPayloadType, b: FloatPayloadType) -> Ordering {
+        let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+        match self.direction {
+            Direction::Asc => ordering,
+            Direction::Desc => ordering.reverse(),
+        }
+    }
+
+    /// Compare `(order value, point id)` pairs in the requested order
+    pub fn compare(
+        &self,
+        a: (FloatPayloadType, PointIdType),
+        b: (FloatPayloadType, PointIdType),
+    ) -> Ordering {
+        self.compare_values(a.0, b.0).then_with(|| a.1.cmp(&b.1))
+    }
+
+    /// Check that the pair is not located before the `(start_from, offset)` cursor
+    pub fn is_from_start(
+        &self,
+        item: (FloatPayloadType, PointIdType),
+        offset: Option<PointIdType>,
+    ) -> bool {
+        match (self.start_from, offset) {
+            (None, _) => true,
+            (Some(start_from), None) => self.compare_values(item.0, start_from) != Ordering::Less,
+            (Some(start_from), Some(offset)) => {
+                self.compare(item, (start_from, offset)) != Ordering::Less
+            }
+        }
+    }
+}
+
+/// Value of the payload field, counted by the facet request
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(untagged)]
+pub enum FacetValue {
+    Keyword(String),
+    Integer(IntPayloadType),
+}
+
+impl From<FacetValue> for Match {
+    fn from(value: FacetValue) -> Self {
+        match value {
+            FacetValue::Keyword(keyword) => keyword.into(),
+            FacetValue::Integer(integer) => integer.into(),
+        }
+    }
+}
+
+/// Number of points, which have the value in the payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetValueHit {
+    pub value: FacetValue,
+    pub count: usize,
+}
+
+impl FacetValueHit {
+    /// Most frequent values first, values with equal counts are ordered by value
+    pub fn compare(&self, other: &Self) -> Ordering {
+        other
+            .count
+            .cmp(&self.count)
+            .then_with(|| self.value.cmp(&other.value))
+    }
+}
+
+/// Aggregated value of the numeric or datetime field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum AggregationValue {
+    Number(f64),
+    /// Datetime in RFC 3339 format, aggregated values of the datetime fields
+    Datetime(DateTime<Utc>),
+}
+
+impl AggregationValue {
+    /// Value of the numeric or the datetime field, datetime is given as seconds since epoch
+    pub fn new(value: f64, is_datetime: bool) -> Self {
+        match seconds_to_datetime(value) {
+            Some(datetime) if is_datetime => AggregationValue::Datetime(datetime),
+            _ => AggregationValue::Number(value),
+        }
+    }
+
+    /// Numeric representation of the value. Datetime is converted into seconds since epoch.
+    pub fn value(&self) -> f64 {
+        match self {
+            AggregationValue::Number(number) => *number,
+            AggregationValue::Datetime(datetime) => datetime_to_seconds(datetime),
+        }
+    }
+}
+
+/// Bucket of the histogram, which counts values in `[from, to)`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramBucket {
+    pub from: AggregationValue,
+    pub to: AggregationValue,
+    pub count: usize,
+}
+
+/// Statistics of the numeric or datetime payload field values.
+/// Each value of a multi-valued field is counted separately.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct NumericAggregation {
+    /// Number of aggregated values
+    pub count: usize,
+    pub min: Option<AggregationValue>,
+    pub max: Option<AggregationValue>,
+    /// Sum of the values. For datetime fields - sum of seconds since epoch
+    pub sum: f64,
+    pub avg: Option<AggregationValue>,
+    /// Non-empty buckets of the requested width, ordered by value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Vec<HistogramBucket>>,
+}
+
+impl NumericAggregation {
+    /// Aggregate values, values of the datetime fields are given as seconds since epoch
+    pub fn from_values(values: impl Iterator<Item = f64>, is_datetime: bool) -> Self {
+        let mut aggregation = NumericAggregation::default();
+        let mut min: Option<f64> = None;
+        let mut max: Option<f64> = None;
+        for value in values {
+            aggregation.count += 1;
+            aggregation.sum += value;
+            min = Some(min.map_or(value, |min| min.min(value)));
+            max = Some(max.map_or(value, |max| max.max(value)));
+        }
+        aggregation.min = min.map(|min| AggregationValue::new(min, is_datetime));
+        aggregation.max = max.map(|max| AggregationValue::new(max, is_datetime));
+        aggregation.avg = aggregation.average(is_datetime);
+        aggregation
+    }
+
+    /// Set histogram from the value counts in the buckets of the `interval` width,
+    /// as counted by `histogram::count_by_interval`
+    pub fn with_histogram(mut self, interval: f64, bucket_counts: BTreeMap<i64, usize>) -> Self {
+        let is_datetime = self.is_datetime();
+        let buckets = bucket_counts
+            .into_iter()
+            .map(|(bucket, count)| HistogramBucket {
+                from: AggregationValue::new(bucket as f64 * interval, is_datetime),
+                to: AggregationValue::new((bucket + 1) as f64 * interval, is_datetime),
+                count,
+            })
+            .collect();
+        self.histogram = Some(buckets);
+        self
+    }
+
+    fn is_datetime(&self) -> bool {
+        matches!(self.min, Some(AggregationValue::Datetime(_)))
+    }
+
+    fn average(&self, is_datetime: bool) -> Option<AggregationValue> {
+        (self.count > 0).then(|| AggregationValue::new(self.sum / self.count as f64, is_datetime))
+    }
+
+    /// Combine with the aggregation of another set of values.
+    /// Histograms are expected to have the same bucket width.
+    pub fn merge(&mut self, other: NumericAggregation) {
+        let is_datetime = self.is_datetime() || other.is_datetime();
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = match (self.min, other.min) {
+            (Some(a), Some(b)) if b.value() < a.value() => Some(b),
+            (a, b) => a.or(b),
+        };
+        self.max = match (self.max, other.max) {
+            (Some(a), Some(b)) if b.value() > a.value() => Some(b),
+            (a, b) => a.or(b),
+        };
+        self.avg = self.average(is_datetime);
+        self.histogram = match (self.histogram.take(), other.histogram) {
+            (Some(a), Some(b)) => {
+                let mut buckets: BTreeMap<OrderedFloat<f64>, HistogramBucket> = BTreeMap::new();
+                for bucket in a.into_iter().chain(b) {
+                    buckets
+                        .entry(OrderedFloat(bucket.from.value()))
+                        .and_modify(|existing| existing.count += bucket.count)
+                        .or_insert(bucket);
+                }
+                Some(buckets.into_values().collect())
+            }
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// Quantiles of the numeric values, reported by the payload index statistics
+pub const INDEX_STATS_QUANTILES: [f64; 5] = [0.1, 0.25, 0.5, 0.75, 0.9];
+
+/// Number of the most frequent values, reported by the payload index statistics
+pub const INDEX_STATS_TOP_VALUES: usize = 10;
+
+/// Approximate value of the numeric field at the given quantile
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct QuantileValue {
+    pub quantile: f64,
+    pub value: f64,
+}
+
+/// Distribution of the values in the numeric payload index
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct NumericIndexStats {
+    /// Number of indexed values, each value of a multi-valued field is counted separately
+    pub values_count: usize,
+    pub min: f64,
+    pub max: f64,
+    /// Quantiles, estimated with the index histogram
+    pub quantiles: Vec<QuantileValue>,
+}
+
+impl NumericIndexStats {
+    /// Combine with the statistics of another set of values.
+    /// Min and max stay exact. Quantiles are recomputed from the combined distribution,
+    /// where values of each set are assumed to be distributed uniformly between its quantiles.
+    pub fn merge(&mut self, other: NumericIndexStats) {
+        if other.values_count == 0 {
+            return;
+        }
+        if self.values_count == 0 {
+            *self = other;
+            return;
+        }
+        let total = self.values_count + other.values_count;
+        let self_weight = self.values_count as f64 / total as f64;
+        let other_weight = other.values_count as f64 / total as f64;
+
+        // Combined distribution is linear between the borders of both distributions
+        let borders: Vec<(f64, f64)> = self
+            .borders()
+            .chain(other.borders())
+            .map(|(value, _)| value)
+            .sorted_by(|a, b| a.total_cmp(b))
+            .dedup()
+            .map(|value| {
+                let share =
+                    self.share_below(value) * self_weight + other.share_below(value) * other_weight;
+                (value, share)
+            })
+            .collect();
+
+        for quantile in self.quantiles.iter_mut() {
+            quantile.value = value_at_share(&borders, quantile.quantile);
+        }
+        self.values_count = total;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Known points of the distribution: values with the shares of values, which are not greater
+    fn borders(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        std::iter::once((self.min, 0.0))
+            .chain(
+                self.quantiles
+                    .iter()
+                    .map(|quantile| (quantile.value, quantile.quantile)),
+            )
+            .chain(std::iter::once((self.max, 1.0)))
+    }
+
+    /// Estimated share of values, which are not greater than the `value`
+    fn share_below(&self, value: f64) -> f64 {
+        if value >= self.max {
+            return 1.0;
+        }
+        self.borders()
+            .tuple_windows()
+            .find(|((from, _), (to, _))| *from <= value && value < *to)
+            .map_or(0.0, |((from, from_share), (to, to_share))| {
+                from_share + (to_share - from_share) * (value - from) / (to - from)
+            })
+    }
+}
+
+/// The smallest value, which has at least the `share` of values not greater than it.
+/// `borders` are sorted by values, shares are interpolated linearly between them.
+fn value_at_share(borders: &[(f64, f64)], share: f64) -> f64 {
+    let position = borders
+        .iter()
+        .position(|(_, border_share)| *border_share >= share)
+        .unwrap_or(borders.len() - 1);
+    if position == 0 {
+        return borders[0].0;
+    }
+    let (from, from_share) = borders[position - 1];
+    let (to, to_share) = borders[position];
+    from + (to - from) * (share - from_share) / (to_share - from_share)
+}
+
+/// Statistics of the payload index of a single field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadIndexStats {
+    pub data_type: PayloadSchemaType,
+    /// Number of points, which have at least one indexed value
+    pub points_count: usize,
+    /// Number of distinct indexed values.
+    /// Values shared by several segments are counted once per segment, so the number is an upper bound.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distinct_values: Option<usize>,
+    /// Most frequent values, only available for keyword and integer indexes.
+    /// Counts of values, which are not among the most frequent ones in some segment, might be underestimated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_values: Option<Vec<FacetValueHit>>,
+    /// Distribution of the values, only available for numeric indexes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numeric: Option<NumericIndexStats>,
+    /// Estimated memory, used by the in-memory structures of the index
+    pub ram_usage_bytes: usize,
+    /// Size of the index data, persisted on disk
+    pub disk_usage_bytes: usize,
+}
+
+impl PayloadIndexStats {
+    pub fn new(data_type: PayloadSchemaType) -> Self {
+        PayloadIndexStats {
+            data_type,
+            points_count: 0,
+            distinct_values: None,
+            top_values: None,
+            numeric: None,
+            ram_usage_bytes: 0,
+            disk_usage_bytes: 0,
+        }
+    }
+
+    /// Combine with the statistics of the same field, built over another set of points
+    pub fn merge(&mut self, other: PayloadIndexStats) {
+        self.points_count += other.points_count;
+        self.ram_usage_bytes += other.ram_usage_bytes;
+        self.disk_usage_bytes += other.disk_usage_bytes;
+        self.distinct_values = match (self.distinct_values, other.distinct_values) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        self.top_values = match (self.top_values.take(), other.top_values) {
+            (Some(a), Some(b)) => {
+                let mut counts: HashMap<FacetValue, usize> = HashMap::new();
+                for hit in a.into_iter().chain(b) {
+                    *counts.entry(hit.value).or_default() += hit.count;
+                }
+                Some(
+                    counts
+                        .into_iter()
+                        .map(|(value, count)| FacetValueHit { value, count })
+                        .sorted_by(|a, b| a.compare(b))
+                        .take(INDEX_STATS_TOP_VALUES)
+                        .collect(),
+                )
+            }
+            (a, b) => a.or(b),
+        };
+        self.numeric = match (self.numeric.take(), other.numeric) {
+            (Some(mut a), Some(b)) => {
+                a.merge(b);
+                Some(a)
+            }
+            (a, b) => a.or(b),
+        };
+    }
+
+    /// Combine statistics of each field with the statistics of the same field in `other`
+    pub fn merge_fields(
+        stats: &mut HashMap<PayloadKeyType, PayloadIndexStats>,
+        other: HashMap<PayloadKeyType, PayloadIndexStats>,
+    ) {
+        for (field, field_stats) in other {
+            match stats.get_mut(&field) {
+                Some(existing) => existing.merge(field_stats),
+                None => {
+                    stats.insert(field, field_stats);
+                }
+            }
+        }
     }
 }
 
@@ -810,6 +2120,117 @@ impl GeoRadius {
     }
 }
 
+/// Ordered sequence of GeoPoints representing the line
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoLineString {
+    pub points: Vec<GeoPoint>,
+}
+
+impl GeoLineString {
+    fn is_closed_ring(&self) -> bool {
+        self.points.len() >= 4 && self.points.first() == self.points.last()
+    }
+
+    fn to_line_string(&self) -> LineString<f64> {
+        LineString::from(
+            self.points
+                .iter()
+                .map(|point| Coordinate {
+                    x: point.lon,
+                    y: point.lat,
+                })
+                .collect_vec(),
+        )
+    }
+}
+
+/// Geo filter request
+///
+/// Matches coordinates inside the polygon, defined by `exterior` and `interiors`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(try_from = "GeoPolygonShadow")]
+#[serde(rename_all = "snake_case")]
+pub struct GeoPolygon {
+    /// The exterior line bounds the surface.
+    /// Must consist of a minimum of 4 points, and the first and last points must be the same.
+    pub exterior: GeoLineString,
+    /// Interior lines (if present) bound holes within the surface.
+    /// Each of them must consist of a minimum of 4 points, and the first and last points must be the same.
+    pub interiors: Option<Vec<GeoLineString>>,
+}
+
+#[derive(Deserialize)]
+struct GeoPolygonShadow {
+    pub exterior: GeoLineString,
+    pub interiors: Option<Vec<GeoLineString>>,
+}
+
+pub struct GeoPolygonValidationError;
+
+impl std::fmt::Display for GeoPolygonValidationError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "Wrong format of GeoPolygon: each line must consist of at least 4 points, and the first and last points must be the same")
+    }
+}
+
+impl TryFrom<GeoPolygonShadow> for GeoPolygon {
+    type Error = GeoPolygonValidationError;
+
+    fn try_from(value: GeoPolygonShadow) -> Result<Self, Self::Error> {
+        let polygon = GeoPolygon {
+            exterior: value.exterior,
+            interiors: value.interiors,
+        };
+        polygon.validate()?;
+        Ok(polygon)
+    }
+}
+
+impl GeoPolygon {
+    pub fn validate(&self) -> Result<(), GeoPolygonValidationError> {
+        let interiors_closed = self
+            .interiors
+            .iter()
+            .flatten()
+            .all(|interior| interior.is_closed_ring());
+        if self.exterior.is_closed_ring() && interiors_closed {
+            Ok(())
+        } else {
+            Err(GeoPolygonValidationError)
+        }
+    }
+
+    /// Convert into geometry, suitable for repeated point checks
+    pub fn convert(&self) -> PolygonWrapper {
+        let interiors = self
+            .interiors
+            .iter()
+            .flatten()
+            .map(|interior| interior.to_line_string())
+            .collect_vec();
+        PolygonWrapper {
+            polygon: Polygon::new(self.exterior.to_line_string(), interiors),
+        }
+    }
+
+    pub fn check_point(&self, lon: f64, lat: f64) -> bool {
+        self.convert().check_point(lon, lat)
+    }
+}
+
+/// Polygon geometry of the `GeoPolygon` condition, with `x` as longitude and `y` as latitude
+#[derive(Debug, Clone)]
+pub struct PolygonWrapper {
+    pub polygon: Polygon<f64>,
+}
+
+impl PolygonWrapper {
+    pub fn check_point(&self, lon: f64, lat: f64) -> bool {
+        self.polygon.contains(&Point::new(lon, lat))
+    }
+}
+
 /// All possible payload filtering conditions
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -824,6 +2245,8 @@ pub struct FieldCondition {
     pub geo_bounding_box: Option<GeoBoundingBox>,
     /// Check if geo point is within a given radius
     pub geo_radius: Option<GeoRadius>,
+    /// Check if geo point is within a given polygon
+    pub geo_polygon: Option<GeoPolygon>,
     /// Check number of values of the field
     pub values_count: Option<ValuesCount>,
 }
@@ -836,6 +2259,7 @@ impl FieldCondition {
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
             values_count: None,
         }
     }
@@ -847,6 +2271,7 @@ impl FieldCondition {
             range: Some(range),
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
             values_count: None,
         }
     }
@@ -858,6 +2283,7 @@ impl FieldCondition {
             range: None,
             geo_bounding_box: Some(geo_bounding_box),
             geo_radius: None,
+            geo_polygon: None,
             values_count: None,
         }
     }
@@ -869,6 +2295,19 @@ impl FieldCondition {
             range: None,
             geo_bounding_box: None,
             geo_radius: Some(geo_radius),
+            geo_polygon: None,
+            values_count: None,
+        }
+    }
+
+    pub fn new_geo_polygon(key: PayloadKeyType, geo_polygon: GeoPolygon) -> Self {
+        Self {
+            key,
+            r#match: None,
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: Some(geo_polygon),
             values_count: None,
         }
     }
@@ -880,6 +2319,7 @@ impl FieldCondition {
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
             values_count: Some(values_count),
         }
     }
@@ -898,6 +2338,18 @@ pub struct IsEmptyCondition {
     pub is_empty: PayloadField,
 }
 
+/// Select points, which have the payload field explicitly set to `NULL`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct IsNullCondition {
+    pub is_null: PayloadField,
+}
+
+/// Select points, which have the payload field with any value, including `NULL`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct HasFieldCondition {
+    pub has_field: PayloadField,
+}
+
 /// ID-based filtering condition
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 pub struct HasIdCondition {
@@ -910,6 +2362,34 @@ impl From<HashSet<PointIdType>> for HasIdCondition {
     }
 }
 
+/// Filter applied to each element of the array field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct Nested {
+    /// Path to the array of objects
+    pub key: PayloadKeyType,
+    /// Filter, which all conditions refer to the fields of a single array element
+    pub filter: Filter,
+}
+
+/// Select points, which have at least one element of the array field matching the nested filter
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct NestedCondition {
+    pub nested: Nested,
+}
+
+impl NestedCondition {
+    pub fn new(nested: Nested) -> Self {
+        Self { nested }
+    }
+
+    /// Path of the field of array elements, as used by field indexes: `key[].field`
+    pub fn array_key(&self, field: &str) -> PayloadKeyType {
+        format!("{}[].{}", self.nested.key, field)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum Condition {
@@ -917,8 +2397,14 @@ pub enum Condition {
     Field(FieldCondition),
     /// Check if payload field is empty: equals to `NULL`, empty array, or does not exists
     IsEmpty(IsEmptyCondition),
+    /// Check if payload field is set to `NULL`
+    IsNull(IsNullCondition),
+    /// Check if payload field exists, regardless of its value
+    HasField(HasFieldCondition),
     /// Check if points id is in a given set
     HasId(HasIdCondition),
+    /// Check if some element of the array field satisfies the nested filter
+    Nested(NestedCondition),
     /// Nested filter
     Filter(Filter),
 }
@@ -1057,6 +2543,15 @@ pub struct WithPayload {
     pub payload_selector: Option<PayloadSelector>,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "snake_case")]
+pub struct MinShould {
+    pub conditions: Vec<Condition>,
+    /// Minimal number of conditions which should match
+    pub min_count: usize,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
@@ -1067,6 +2562,8 @@ pub struct Filter {
     pub must: Option<Vec<Condition>>,
     /// All conditions must NOT match
     pub must_not: Option<Vec<Condition>>,
+    /// At least `min_count` of those conditions should match
+    pub min_should: Option<MinShould>,
 }
 
 impl Filter {
@@ -1075,6 +2572,7 @@ impl Filter {
             should: Some(vec![condition]),
             must: None,
             must_not: None,
+            min_should: None,
         }
     }
 
@@ -1083,6 +2581,7 @@ impl Filter {
             should: None,
             must: Some(vec![condition]),
             must_not: None,
+            min_should: None,
         }
     }
 
@@ -1091,6 +2590,7 @@ impl Filter {
             should: None,
             must: None,
             must_not: Some(vec![condition]),
+            min_should: None,
         }
     }
 }
@@ -1132,6 +2632,7 @@ mod tests {
                 "world".to_owned().into(),
             ))]),
             must_not: None,
+            min_should: None,
             should: None,
         };
         let json = serde_json::to_string_pretty(&filter).unwrap();
@@ -1150,6 +2651,75 @@ mod tests {
         assert!(filter.is_err())
     }
 
+    #[test]
+    fn test_parse_and_evaluate_score_expression() {
+        let query = r#"
+        {
+            "sum": [
+                "score",
+                { "mult": [{ "constant": 0.5 }, { "payload": { "key": "popularity" } }] },
+                { "ln": { "payload": { "key": "missing", "default": 1.0 } } }
+            ]
+        }
+        "#;
+        let expression: ScoreExpression = serde_json::from_str(query).unwrap();
+
+        let value = expression
+            .evaluate(0.8, &|key| match key {
+                "popularity" => Ok(Some(3.0)),
+                _ => Ok(None),
+            })
+            .unwrap();
+        assert!((value - 2.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_decay_expressions() {
+        let query = r#"
+        {
+            "key": "published",
+            "origin": "2022-08-01T00:00:00Z",
+            "scale": 86400
+        }
+        "#;
+        let params: DecayParams = serde_json::from_str(query).unwrap();
+        assert!(params.validate().is_ok());
+
+        let day_before = params.origin.value() - 86400.0;
+        let payload_value = |_key: PayloadKeyTypeRef| Ok(Some(day_before));
+        let no_value = |_key: PayloadKeyTypeRef| Ok(None);
+
+        for expression in [
+            ScoreExpression::LinDecay(params.clone()),
+            ScoreExpression::ExpDecay(params.clone()),
+            ScoreExpression::GaussDecay(params.clone()),
+        ] {
+            let value = expression.evaluate(1.0, &payload_value).unwrap();
+            assert!((value - 0.5).abs() < 1e-6);
+            assert_eq!(expression.evaluate(1.0, &no_value).unwrap(), 0.0);
+        }
+
+        let two_days_before = |_key: PayloadKeyTypeRef| Ok(Some(day_before - 86400.0));
+        let linear = ScoreExpression::LinDecay(params.clone())
+            .evaluate(1.0, &two_days_before)
+            .unwrap();
+        let exponential = ScoreExpression::ExpDecay(params.clone())
+            .evaluate(1.0, &two_days_before)
+            .unwrap();
+        let gaussian = ScoreExpression::GaussDecay(params.clone())
+            .evaluate(1.0, &two_days_before)
+            .unwrap();
+        assert!(linear.abs() < 1e-6);
+        assert!((exponential - 0.25).abs() < 1e-6);
+        assert!((gaussian - 0.0625).abs() < 1e-6);
+
+        let invalid = DecayParams {
+            midpoint: Some(1.0),
+            ..params
+        };
+        assert!(ScoreExpression::GaussDecay(invalid).validate().is_err());
+    }
+
     #[test]
     fn test_parse_match_query() {
         let query = r#"
@@ -1221,6 +2791,198 @@ mod tests {
                 value: ValueVariants::Keyword("world".to_owned())
             })
         );
+
+        let query = r#"
+        {
+            "key": "hello",
+            "match": { "text": "quick brown fox" }
+        }
+        "#;
+
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        assert_eq!(
+            condition.r#match.unwrap(),
+            Match::Text(MatchText {
+                text: "quick brown fox".to_owned()
+            })
+        );
+
+        let query = r#"
+        {
+            "key": "hello",
+            "match": { "any": ["world", "there"] }
+        }
+        "#;
+
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        assert_eq!(
+            condition.r#match.unwrap(),
+            Match::Any(MatchAny {
+                any: AnyVariants::Keywords(BTreeSet::from([
+                    "world".to_owned(),
+                    "there".to_owned()
+                ]))
+            })
+        );
+
+        let query = r#"
+        {
+            "key": "hello",
+            "match": { "except": [1, 2, 3] }
+        }
+        "#;
+
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        assert_eq!(
+            condition.r#match.unwrap(),
+            Match::Except(MatchExcept {
+                except: AnyVariants::Integers(BTreeSet::from([1, 2, 3]))
+            })
+        );
+
+        let query = r#"
+        {
+            "key": "sku",
+            "match": { "keyword_range": { "gte": "A-100", "lt": "A-200" } }
+        }
+        "#;
+
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        assert_eq!(
+            condition.r#match.unwrap(),
+            Match::KeywordRange(MatchKeywordRange {
+                keyword_range: KeywordRange {
+                    gte: Some("A-100".to_owned()),
+                    lt: Some("A-200".to_owned()),
+                    ..Default::default()
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_keyword_patterns() {
+        let prefix = MatchPrefix {
+            prefix: "docs/".to_owned(),
+        };
+        assert!(prefix.check("docs/index.md"));
+        assert!(!prefix.check("src/docs/index.md"));
+
+        let range = KeywordRange {
+            gt: Some("b".to_owned()),
+            lte: Some("d".to_owned()),
+            ..Default::default()
+        };
+        assert!(!range.check("b"));
+        assert!(range.check("ba"));
+        assert!(range.check("d"));
+        assert!(!range.check("da"));
+        assert_eq!(range.lower_bound(), Bound::Excluded("b"));
+
+        let wildcard = MatchWildcard {
+            wildcard: "sku-*-r?d".to_owned(),
+        };
+        assert_eq!(wildcard.literal_prefix(), "sku-");
+        assert!(wildcard.check("sku-1-red"));
+        assert!(wildcard.check("sku-1-2-rod"));
+        assert!(wildcard.check("sku--red"));
+        assert!(!wildcard.check("sku-1-reed"));
+        assert!(!wildcard.check("sku-1-red-2"));
+        assert!(MatchWildcard {
+            wildcard: "*".to_owned()
+        }
+        .check(""));
+        let multibyte = MatchWildcard {
+            wildcard: "*ß?n".to_owned(),
+        };
+        assert!(multibyte.check("straßen"));
+        assert!(multibyte.check("ßün"));
+        assert!(!multibyte.check("straße"));
+    }
+
+    #[test]
+    fn test_parse_payload_field_schema() {
+        let schema: PayloadFieldSchema = serde_json::from_str(r#""keyword""#).unwrap();
+        assert_eq!(schema, PayloadSchemaType::Keyword.into());
+
+        let schema: PayloadFieldSchema =
+            serde_json::from_str(r#"{"type": "text", "tokenizer": "prefix", "max_token_len": 10}"#)
+                .unwrap();
+        assert_eq!(
+            schema,
+            PayloadSchemaParams::Text(TextIndexParams {
+                tokenizer: TokenizerType::Prefix,
+                max_token_len: Some(10),
+                ..Default::default()
+            })
+            .into()
+        );
+        assert_eq!(schema.schema_type(), PayloadSchemaType::Text);
+
+        let schema: PayloadFieldSchema =
+            serde_json::from_str(r#"{"type": "integer", "on_disk": true}"#).unwrap();
+        assert_eq!(
+            schema,
+            PayloadSchemaParams::Integer(IntegerIndexParams {
+                on_disk: Some(true),
+                ..Default::default()
+            })
+            .into()
+        );
+        assert_eq!(schema.schema_type(), PayloadSchemaType::Integer);
+
+        let schema: PayloadFieldSchema =
+            serde_json::from_str(r#"{"type": "integer", "lookup": false}"#).unwrap();
+        assert_eq!(
+            schema,
+            PayloadSchemaParams::Integer(IntegerIndexParams {
+                lookup: Some(false),
+                ..Default::default()
+            })
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_parse_geo_polygon() {
+        let condition: FieldCondition = serde_json::from_str(
+            r#"
+            {
+                "key": "location",
+                "geo_polygon": {
+                    "exterior": {
+                        "points": [
+                            {"lon": 0.0, "lat": 0.0},
+                            {"lon": 10.0, "lat": 0.0},
+                            {"lon": 10.0, "lat": 10.0},
+                            {"lon": 0.0, "lat": 0.0}
+                        ]
+                    }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        let polygon = condition.geo_polygon.unwrap();
+        assert!(polygon.check_point(8.0, 2.0));
+        assert!(!polygon.check_point(2.0, 8.0));
+
+        // Exterior line is not closed
+        let res: Result<GeoPolygon, _> = serde_json::from_str(
+            r#"
+            {
+                "exterior": {
+                    "points": [
+                        {"lon": 0.0, "lat": 0.0},
+                        {"lon": 10.0, "lat": 0.0},
+                        {"lon": 10.0, "lat": 10.0},
+                        {"lon": 0.0, "lat": 10.0}
+                    ]
+                }
+            }
+            "#,
+        );
+        assert!(res.is_err());
     }
 
     #[test]
@@ -1337,6 +3099,134 @@ mod tests {
         remove_value_from_json_map("b", &mut payload.0);
         assert_eq!(payload, Default::default());
     }
+
+    #[test]
+    fn test_nested_paths() {
+        let mut payload: Payload = json!({
+            "a": {"b": [{"c": 1, "d": 2}, {"c": 3}]},
+            "e": 5,
+        })
+        .into();
+
+        assert_eq!(
+            remove_value_from_json_map("a.b[].c", &mut payload.0),
+            Some(json!([1, 3]))
+        );
+        assert_eq!(
+            remove_value_from_json_map("a.b[1]", &mut payload.0),
+            Some(json!({}))
+        );
+        assert_eq!(remove_value_from_json_map("a.b[5]", &mut payload.0), None);
+        assert_eq!(payload.0["a"], json!({"b": [{"d": 2}]}));
+
+        payload.merge_by_key(&json!({"f": 6, "d": null}).into(), "a.b[0]");
+        payload.merge_by_key(&json!({"h": 7}).into(), "i.g");
+        assert_eq!(
+            payload,
+            json!({"a": {"b": [{"f": 6}]}, "e": 5, "i": {"g": {"h": 7}}}).into()
+        );
+
+        // Values other than objects are not replaced
+        payload.merge_by_key(&json!({"h": 7}).into(), "e.g");
+        payload.merge_by_key(&json!({"h": 7}).into(), "a.b[0].f");
+        payload.merge_by_key(&json!({"h": 7}).into(), "a.b");
+        assert_eq!(
+            payload,
+            json!({"a": {"b": [{"f": 6}]}, "e": 5, "i": {"g": {"h": 7}}}).into()
+        );
+
+        // Paths written by the operations are readable by the same path
+        assert_eq!(payload.get_value("a.b[0].f"), Some(&json!(6)));
+        assert_eq!(payload.get_value("a.b[1].f"), None);
+        assert_eq!(
+            payload.get_value_cow("a.b[0].f").unwrap().into_owned(),
+            json!(6)
+        );
+        assert_eq!(
+            payload.get_value_cow("a.b[].f").unwrap().into_owned(),
+            json!([6])
+        );
+    }
+
+    #[test]
+    fn test_merge_numeric_stats() {
+        let stats = |from: f64, to: f64| {
+            let quantiles = INDEX_STATS_QUANTILES
+                .iter()
+                .map(|&quantile| QuantileValue {
+                    quantile,
+                    value: from + (to - from) * quantile,
+                })
+                .collect();
+            NumericIndexStats {
+                values_count: 100,
+                min: from,
+                max: to,
+                quantiles,
+            }
+        };
+        let quantile_value = |stats: &NumericIndexStats, quantile: f64| {
+            stats
+                .quantiles
+                .iter()
+                .find(|value| value.quantile == quantile)
+                .unwrap()
+                .value
+        };
+
+        // Same distribution keeps quantiles
+        let mut merged = stats(0.0, 10.0);
+        merged.merge(stats(0.0, 10.0));
+        assert_eq!(
+            merged,
+            NumericIndexStats {
+                values_count: 200,
+                ..stats(0.0, 10.0)
+            }
+        );
+
+        // Quantiles of disjoint sets are within the values of one of them
+        let mut merged = stats(0.0, 10.0);
+        merged.merge(stats(100.0, 110.0));
+        assert_eq!(quantile_value(&merged, 0.5), 10.0);
+        assert!((quantile_value(&merged, 0.25) - 5.0).abs() < 1e-9);
+        assert!((quantile_value(&merged, 0.75) - 105.0).abs() < 1e-9);
+        assert_eq!((merged.min, merged.max), (0.0, 110.0));
+
+        // Empty statistics don't change the distribution
+        let mut merged = stats(0.0, 10.0);
+        merged.merge(NumericIndexStats {
+            values_count: 0,
+            ..stats(50.0, 60.0)
+        });
+        assert_eq!(merged, stats(0.0, 10.0));
+    }
+
+    #[test]
+    fn test_get_array_element_values() {
+        let payload: Payload = json!({
+            "shops": [
+                {"name": "a", "offers": [{"price": 1}, {"price": 2}]},
+                {"name": "b", "offers": [{"price": 3}]},
+                {"title": "c"},
+            ]
+        })
+        .into();
+
+        assert_eq!(
+            payload.get_value_cow("shops[].name").unwrap().into_owned(),
+            json!(["a", "b"])
+        );
+        assert_eq!(
+            payload
+                .get_value_cow("shops[].offers[].price")
+                .unwrap()
+                .into_owned(),
+            json!([1, 2, 3])
+        );
+        assert!(payload.get_value_cow("shops[].price").is_none());
+        assert!(payload.get_value_cow("shops.name").is_none());
+    }
 }
 
 pub type TheMap<K, V> = BTreeMap<K, V>;
@@ -161,7 +161,11 @@ where
         panic!("Can't directly update vector in mmap storage")
     }
 
-    fn update_from(&mut self, other: &VectorStorageSS) -> OperationResult<Range<PointOffsetType>> {
+    fn update_from(
+        &mut self,
+        other: &VectorStorageSS,
+        other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+    ) -> OperationResult<Range<PointOffsetType>> {
         let dim = self.vector_dim();
 
         let start_index = self.mmap_store.as_ref().unwrap().num_vectors as PointOffsetType;
@@ -177,7 +181,7 @@ where
                 .create(false)
                 .open(&self.vectors_path)?;
 
-            for id in other.iter_ids() {
+            for id in other_ids {
                 let vector = &other.get_vector(id).unwrap();
                 let raw_bites = vf_to_u8(vector);
                 file.write_all(raw_bites)?;
@@ -341,7 +345,10 @@ mod tests {
                 borrowed_storage2.put_vector(vec2.clone()).unwrap();
                 borrowed_storage2.put_vector(vec3.clone()).unwrap();
             }
-            borrowed_storage.update_from(&*storage2.borrow()).unwrap();
+            let storage2 = storage2.borrow();
+            borrowed_storage
+                .update_from(&*storage2, &mut storage2.iter_ids())
+                .unwrap();
         }
 
         assert_eq!(borrowed_storage.vector_count(), 3);
@@ -363,7 +370,10 @@ mod tests {
                 borrowed_storage2.put_vector(vec4).unwrap();
                 borrowed_storage2.put_vector(vec5).unwrap();
             }
-            borrowed_storage.update_from(&*storage2.borrow()).unwrap();
+            let storage2 = storage2.borrow();
+            borrowed_storage
+                .update_from(&*storage2, &mut storage2.iter_ids())
+                .unwrap();
         }
 
         assert_eq!(borrowed_storage.vector_count(), 4);
@@ -409,7 +419,10 @@ mod tests {
                 borrowed_storage2.put_vector(vec4).unwrap();
                 borrowed_storage2.put_vector(vec5).unwrap();
             }
-            borrowed_storage.update_from(&*storage2.borrow()).unwrap();
+            let storage2 = storage2.borrow();
+            borrowed_storage
+                .update_from(&*storage2, &mut storage2.iter_ids())
+                .unwrap();
         }
 
         let query = vec![-1.0, -1.0, -1.0, -1.0];
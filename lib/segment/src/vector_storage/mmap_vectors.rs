@@ -1,11 +1,10 @@
-use std::fs::{File, OpenOptions};
-use std::io::Write;
 use std::mem::{size_of, transmute};
 use std::path::Path;
 
-use memmap::{Mmap, MmapMut, MmapOptions};
+use memmap::{Mmap, MmapMut};
 
 use crate::common::error_logging::LogError;
+use crate::common::mmap_ops::{ensure_mmap_file_exists, open_read, open_write};
 use crate::entry::entry_point::OperationResult;
 use crate::types::{PointOffsetType, VectorElementType};
 
@@ -22,36 +21,6 @@ pub struct MmapVectors {
     pub deleted_count: usize,
 }
 
-fn open_read(path: &Path) -> OperationResult<Mmap> {
-    let file = OpenOptions::new()
-        .read(true)
-        .write(false)
-        .append(true)
-        .create(true)
-        .open(path)?;
-
-    Ok(unsafe { MmapOptions::new().map(&file)? })
-}
-
-fn open_write(path: &Path) -> OperationResult<MmapMut> {
-    let file = OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(false)
-        .open(path)?;
-
-    Ok(unsafe { MmapMut::map_mut(&file)? })
-}
-
-fn ensure_mmap_file_exists(path: &Path, header: &[u8]) -> OperationResult<()> {
-    if path.exists() {
-        return Ok(());
-    }
-    let mut file = File::create(path)?;
-    file.write_all(header)?;
-    Ok(())
-}
-
 impl MmapVectors {
     pub fn open(vectors_path: &Path, deleted_path: &Path, dim: usize) -> OperationResult<Self> {
         ensure_mmap_file_exists(vectors_path, VECTORS_HEADER).describe("Create mmap data file")?;
@@ -224,9 +224,13 @@ where
         Ok(key)
     }
 
-    fn update_from(&mut self, other: &VectorStorageSS) -> OperationResult<Range<PointOffsetType>> {
+    fn update_from(
+        &mut self,
+        other: &VectorStorageSS,
+        other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+    ) -> OperationResult<Range<PointOffsetType>> {
         let start_index = self.vectors.len() as PointOffsetType;
-        for point_id in other.iter_ids() {
+        for point_id in other_ids {
             let other_vector = other.get_vector(point_id).unwrap();
             // Do not perform preprocessing - vectors should be already processed
             self.deleted.push(false);
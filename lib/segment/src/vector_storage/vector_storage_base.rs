@@ -60,7 +60,12 @@ pub trait VectorStorage {
         key: PointOffsetType,
         vector: Vec<VectorElementType>,
     ) -> OperationResult<PointOffsetType>;
-    fn update_from(&mut self, other: &VectorStorageSS) -> OperationResult<Range<PointOffsetType>>;
+    /// Append vectors of `other` storage with `other_ids` in the given order
+    fn update_from(
+        &mut self,
+        other: &VectorStorageSS,
+        other_ids: &mut dyn Iterator<Item = PointOffsetType>,
+    ) -> OperationResult<Range<PointOffsetType>>;
     fn delete(&mut self, key: PointOffsetType) -> OperationResult<()>;
     fn is_deleted(&self, key: PointOffsetType) -> bool;
     fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_>;
@@ -11,9 +11,9 @@ mod tests {
     use segment::index::{PayloadIndex, VectorIndex};
     use segment::segment_constructor::build_segment;
     use segment::types::{
-        Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, Payload,
-        PayloadSchemaType, PointOffsetType, Range, SearchParams, SegmentConfig, SeqNumberType,
-        StorageType,
+        Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, KeywordIndexParams,
+        Payload, PayloadSchemaParams, PayloadSchemaType, PointOffsetType, Range, SearchParams,
+        SegmentConfig, SeqNumberType, StorageType,
     };
     use serde_json::json;
     use tempdir::TempDir;
@@ -85,7 +85,7 @@ mod tests {
 
         payload_index_ptr
             .borrow_mut()
-            .set_indexed(int_key, PayloadSchemaType::Integer)
+            .set_indexed(int_key, PayloadSchemaType::Integer.into())
             .unwrap();
         let borrowed_payload_index = payload_index_ptr.borrow();
         let blocks = borrowed_payload_index
@@ -166,4 +166,154 @@ mod tests {
         assert!(attempts - hits < 5, "hits: {} of {}", hits, attempts); // Not more than 5% failures
         eprintln!("hits = {:#?} out of {}", hits, attempts);
     }
+
+    #[test]
+    fn test_tenant_hnsw() {
+        let stopped = AtomicBool::new(false);
+
+        let dim = 8;
+        let m = 8;
+        let num_vectors: u64 = 5_000;
+        let num_tenants = 3;
+        let ef = 32;
+        let ef_construct = 16;
+        let distance = Distance::Cosine;
+        let full_scan_threshold = 16; // KB
+
+        let mut rnd = thread_rng();
+
+        let dir = TempDir::new("segment_dir").unwrap();
+        let hnsw_dir = TempDir::new("hnsw_dir").unwrap();
+
+        let config = SegmentConfig {
+            vector_size: dim,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance,
+            payload_storage_type: Default::default(),
+        };
+
+        let tenant_key = "tenant";
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+        let mut tenants = vec![];
+        for n in 0..num_vectors {
+            let idx = n.into();
+            let vector = random_vector(&mut rnd, dim);
+
+            let tenant = format!("tenant_{}", rnd.gen_range(0..num_tenants));
+            let payload: Payload = json!({ tenant_key: tenant }).into();
+            tenants.push(tenant);
+
+            segment
+                .upsert_point(n as SeqNumberType, idx, &vector)
+                .unwrap();
+            segment
+                .set_full_payload(n as SeqNumberType, idx, &payload)
+                .unwrap();
+        }
+
+        let payload_index_ptr = segment.payload_index.clone();
+        payload_index_ptr
+            .borrow_mut()
+            .set_indexed(
+                tenant_key,
+                PayloadSchemaParams::Keyword(KeywordIndexParams {
+                    is_tenant: Some(true),
+                    ..Default::default()
+                })
+                .into(),
+            )
+            .unwrap();
+
+        let hnsw_config = HnswConfig {
+            m,
+            ef_construct,
+            full_scan_threshold,
+            max_indexing_threads: 2,
+        };
+
+        let mut hnsw_index = HNSWIndex::open(
+            hnsw_dir.path(),
+            segment.vector_storage.clone(),
+            payload_index_ptr.clone(),
+            hnsw_config,
+        )
+        .unwrap();
+
+        // Tenants are larger than the blocks of regular fields, which get additional links
+        hnsw_index.build_index(&stopped).unwrap();
+
+        let top = 3;
+        let mut hits = 0;
+        let attempts = 100;
+        for _i in 0..attempts {
+            let query = random_vector(&mut rnd, dim);
+
+            let tenant = format!("tenant_{}", rnd.gen_range(0..num_tenants));
+            let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+                tenant_key.to_owned(),
+                tenant.into(),
+            )));
+
+            let tenant_points = payload_index_ptr.borrow().query_points(&filter).count();
+            assert!(tenant_points > num_vectors as usize / hnsw_config.m * 2);
+
+            // Tenant is also required by the nested filter or by the single `should` condition
+            let nested_filter = Filter::new_must(Condition::Filter(Filter::new_should(
+                filter.must.as_ref().unwrap()[0].clone(),
+            )));
+            assert!(payload_index_ptr
+                .borrow()
+                .tenant_condition(&nested_filter)
+                .is_some());
+
+            let index_result = hnsw_index.search_with_graph(
+                &query,
+                Some(&filter),
+                top,
+                Some(&SearchParams { hnsw_ef: Some(ef) }),
+            );
+
+            let plain_result =
+                segment
+                    .vector_index
+                    .borrow()
+                    .search(&query, Some(&filter), top, None);
+
+            if plain_result == index_result {
+                hits += 1;
+            }
+        }
+        assert!(attempts - hits < 5, "hits: {} of {}", hits, attempts); // Not more than 5% failures
+        eprintln!("hits = {:#?} out of {}", hits, attempts);
+
+        // Entry point of the tenant is moved to another tenant along with the rest of the points
+        let moved_tenant = "tenant_0";
+        for (n, tenant) in tenants.iter().enumerate() {
+            if tenant == moved_tenant {
+                let payload: Payload = json!({ tenant_key: "tenant_1" }).into();
+                segment
+                    .set_full_payload(
+                        num_vectors + n as SeqNumberType,
+                        (n as u64).into(),
+                        &payload,
+                    )
+                    .unwrap();
+            }
+        }
+
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            tenant_key.to_owned(),
+            moved_tenant.to_owned().into(),
+        )));
+        let query = random_vector(&mut rnd, dim);
+        let index_result = hnsw_index.search_with_graph(
+            &query,
+            Some(&filter),
+            top,
+            Some(&SearchParams { hnsw_ef: Some(ef) }),
+        );
+        assert!(index_result.is_empty());
+    }
 }
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use std::path::Path;
+    use std::sync::atomic::AtomicBool;
 
     use itertools::Itertools;
     use rand::prelude::StdRng;
@@ -10,14 +11,19 @@ mod tests {
         generate_diverse_payload, random_filter, random_vector, FLICKING_KEY, GEO_KEY, INT_KEY,
         INT_KEY_2, LAT_RANGE, LON_RANGE, STR_KEY,
     };
+    use segment::index::field_index::FieldIndex;
+    use segment::index::struct_payload_index::check_filter_indexes;
     use segment::index::PayloadIndex;
     use segment::segment::Segment;
-    use segment::segment_constructor::build_segment;
+    use segment::segment_constructor::segment_builder::SegmentBuilder;
+    use segment::segment_constructor::{build_segment, load_segment};
     use segment::types::{
-        Condition, Distance, FieldCondition, Filter, GeoPoint, GeoRadius, Indexes,
-        IsEmptyCondition, Payload, PayloadField, PayloadSchemaType, Range, SegmentConfig,
-        StorageType, WithPayload,
+        Condition, Distance, FieldCondition, Filter, GeoIndexParams, GeoPoint, GeoRadius,
+        HasFieldCondition, Indexes, IntegerIndexParams, IsEmptyCondition, IsNullCondition,
+        KeywordIndexParams, Nested, NestedCondition, Payload, PayloadField, PayloadSchemaParams,
+        PayloadSchemaType, Range, SegmentConfig, StorageType, WithPayload,
     };
+    use serde_json::json;
     use tempdir::TempDir;
 
     fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segment) {
@@ -41,7 +47,7 @@ mod tests {
 
         let mut opnum = 0;
         struct_segment
-            .create_field_index(opnum, INT_KEY_2, &Some(PayloadSchemaType::Integer))
+            .create_field_index(opnum, INT_KEY_2, &Some(PayloadSchemaType::Integer.into()))
             .unwrap();
 
         opnum += 1;
@@ -63,16 +69,20 @@ mod tests {
         }
 
         struct_segment
-            .create_field_index(opnum, STR_KEY, &Some(PayloadSchemaType::Keyword))
+            .create_field_index(opnum, STR_KEY, &Some(PayloadSchemaType::Keyword.into()))
             .unwrap();
         struct_segment
             .create_field_index(opnum, INT_KEY, &None)
             .unwrap();
         struct_segment
-            .create_field_index(opnum, GEO_KEY, &Some(PayloadSchemaType::Geo))
+            .create_field_index(opnum, GEO_KEY, &Some(PayloadSchemaType::Geo.into()))
             .unwrap();
         struct_segment
-            .create_field_index(opnum, FLICKING_KEY, &Some(PayloadSchemaType::Integer))
+            .create_field_index(
+                opnum,
+                FLICKING_KEY,
+                &Some(PayloadSchemaType::Integer.into()),
+            )
             .unwrap();
 
         for _ in 0..points_to_clear {
@@ -157,6 +167,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_null_and_has_field_conditions() {
+        let dir1 = TempDir::new("segment1_dir").unwrap();
+        let dir2 = TempDir::new("segment2_dir").unwrap();
+
+        let (mut struct_segment, mut plain_segment) = build_test_segments(dir1.path(), dir2.path());
+
+        let null_payload: Payload = serde_json::json!({ FLICKING_KEY: null }).into();
+        let mut opnum = 10_000;
+        for n in 0..100u64 {
+            let idx = n.into();
+            if !struct_segment.has_point(idx) {
+                continue;
+            }
+            opnum += 1;
+            plain_segment
+                .set_full_payload(opnum, idx, &null_payload)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx, &null_payload)
+                .unwrap();
+        }
+
+        let field = PayloadField {
+            key: FLICKING_KEY.to_string(),
+        };
+        let filters = [
+            Filter::new_must(Condition::IsNull(IsNullCondition {
+                is_null: field.clone(),
+            })),
+            Filter::new_must(Condition::IsEmpty(IsEmptyCondition {
+                is_empty: field.clone(),
+            })),
+            Filter::new_must(Condition::HasField(HasFieldCondition { has_field: field })),
+        ];
+
+        for filter in filters {
+            let plain_result = plain_segment
+                .payload_index
+                .borrow()
+                .query_points(&filter)
+                .sorted()
+                .collect_vec();
+            let struct_result = struct_segment
+                .payload_index
+                .borrow()
+                .query_points(&filter)
+                .sorted()
+                .collect_vec();
+            assert_eq!(plain_result, struct_result);
+
+            let estimation = struct_segment
+                .payload_index
+                .borrow()
+                .estimate_cardinality(&filter);
+            // Presence of the field is tracked by the index, so the estimation is exact
+            assert_eq!(estimation.min, struct_result.len());
+            assert_eq!(estimation.max, struct_result.len());
+            assert!(!estimation.primary_clauses.is_empty());
+        }
+    }
+
     #[test]
     fn test_cardinality_estimation() {
         let dir1 = TempDir::new("segment1_dir").unwrap();
@@ -258,6 +330,295 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_on_disk_payload_index() {
+        // Compare search with plain index and on-disk indexes of a non-appendable segment
+        let dir1 = TempDir::new("segment1_dir").unwrap();
+        let dir2 = TempDir::new("segment2_dir").unwrap();
+        let temp_dir = TempDir::new("segment_temp_dir").unwrap();
+
+        let dim = 5;
+        let mut rnd = StdRng::seed_from_u64(42);
+        let stopped = AtomicBool::new(false);
+
+        let config = SegmentConfig {
+            vector_size: dim,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance: Distance::Dot,
+            payload_storage_type: Default::default(),
+        };
+
+        let mut plain_segment = build_segment(dir2.path(), &config).unwrap();
+
+        let num_points = 1000;
+        let mut opnum = 0;
+        for n in 0..num_points {
+            let idx = n.into();
+            let vector = random_vector(&mut rnd, dim);
+            let payload: Payload = generate_diverse_payload(&mut rnd);
+
+            plain_segment.upsert_point(opnum, idx, &vector).unwrap();
+            plain_segment
+                .set_full_payload(opnum, idx, &payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        let mmap_config = SegmentConfig {
+            storage_type: StorageType::Mmap,
+            ..config
+        };
+        let mut builder = SegmentBuilder::new(dir1.path(), temp_dir.path(), &mmap_config).unwrap();
+        builder.update_from(&plain_segment, &stopped).unwrap();
+        builder.indexed_fields.insert(
+            STR_KEY.to_owned(),
+            PayloadSchemaParams::Keyword(KeywordIndexParams {
+                on_disk: Some(true),
+                ..Default::default()
+            })
+            .into(),
+        );
+        builder.indexed_fields.insert(
+            INT_KEY.to_owned(),
+            PayloadSchemaParams::Integer(IntegerIndexParams {
+                on_disk: Some(true),
+                ..Default::default()
+            })
+            .into(),
+        );
+        builder.indexed_fields.insert(
+            GEO_KEY.to_owned(),
+            PayloadSchemaParams::Geo(GeoIndexParams {
+                on_disk: Some(true),
+                ..Default::default()
+            })
+            .into(),
+        );
+        let mut mmap_segment = builder.build(&stopped).unwrap();
+        assert!(!mmap_segment.appendable_flag);
+
+        for _ in 0..100 {
+            opnum += 1;
+            let idx_to_remove = rnd.gen_range(0..num_points);
+            plain_segment
+                .delete_point(opnum, idx_to_remove.into())
+                .unwrap();
+            mmap_segment
+                .delete_point(opnum, idx_to_remove.into())
+                .unwrap();
+        }
+
+        for _ in 0..100 {
+            let query_vector = random_vector(&mut rnd, dim);
+            let query_filter = random_filter(&mut rnd, 3);
+
+            let plain_result = plain_segment
+                .search(
+                    &query_vector,
+                    &WithPayload::default(),
+                    false,
+                    Some(&query_filter),
+                    5,
+                    None,
+                )
+                .unwrap();
+            let mmap_result = mmap_segment
+                .search(
+                    &query_vector,
+                    &WithPayload::default(),
+                    false,
+                    Some(&query_filter),
+                    5,
+                    None,
+                )
+                .unwrap();
+
+            let estimation = mmap_segment
+                .payload_index
+                .borrow()
+                .estimate_cardinality(&query_filter);
+            assert!(estimation.min <= estimation.exp, "{:#?}", estimation);
+            assert!(estimation.exp <= estimation.max, "{:#?}", estimation);
+
+            assert_eq!(
+                plain_result.iter().map(|hit| hit.id).collect_vec(),
+                mmap_result.iter().map(|hit| hit.id).collect_vec(),
+                "query filter {:?}",
+                query_filter
+            );
+        }
+    }
+
+    #[test]
+    fn test_integer_index_types() {
+        // Compare search with plain index and integer indexes of a single type
+        let dir1 = TempDir::new("segment1_dir").unwrap();
+        let dir2 = TempDir::new("segment2_dir").unwrap();
+
+        let dim = 5;
+        let mut rnd = StdRng::seed_from_u64(42);
+
+        let config = SegmentConfig {
+            vector_size: dim,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance: Distance::Dot,
+            payload_storage_type: Default::default(),
+        };
+
+        let mut plain_segment = build_segment(dir2.path(), &config).unwrap();
+        let mut struct_segment = build_segment(dir1.path(), &config).unwrap();
+
+        let num_points = 1000;
+        let mut opnum = 0;
+        for n in 0..num_points {
+            let idx = n.into();
+            let vector = random_vector(&mut rnd, dim);
+            let payload: Payload = generate_diverse_payload(&mut rnd);
+
+            plain_segment.upsert_point(opnum, idx, &vector).unwrap();
+            struct_segment.upsert_point(opnum, idx, &vector).unwrap();
+            plain_segment
+                .set_full_payload(opnum, idx, &payload)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx, &payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        let index_types = [(true, false), (false, true)];
+        for (lookup, range) in index_types {
+            opnum += 1;
+            struct_segment
+                .create_field_index(
+                    opnum,
+                    INT_KEY,
+                    &Some(
+                        PayloadSchemaParams::Integer(IntegerIndexParams {
+                            lookup: Some(lookup),
+                            range: Some(range),
+                            ..Default::default()
+                        })
+                        .into(),
+                    ),
+                )
+                .unwrap();
+
+            {
+                let payload_index = struct_segment.payload_index.borrow();
+                let indexes = &payload_index.field_indexes[INT_KEY];
+                // Index of the values and the null index, which tracks presence of the field
+                assert_eq!(indexes.len(), 2);
+                assert!(matches!(indexes[1], FieldIndex::NullIndex(_)));
+                assert_eq!(matches!(indexes[0], FieldIndex::IntMapIndex(_)), lookup);
+                assert_eq!(matches!(indexes[0], FieldIndex::IntIndex(_)), range);
+
+                // Requests, which the index can't serve, are rejected instead of reading payloads
+                assert_eq!(
+                    payload_index.ordered_points(INT_KEY, None, false).is_ok(),
+                    range
+                );
+                assert_eq!(payload_index.aggregate(INT_KEY, None, None).is_ok(), range);
+                assert_eq!(payload_index.facet(INT_KEY, None, true).is_ok(), lookup);
+
+                let indexed_fields = payload_index.indexed_fields();
+                let match_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+                    INT_KEY.to_string(),
+                    1.into(),
+                )));
+                let range_filter = Filter::new_must_not(Condition::Filter(Filter::new_should(
+                    Condition::Field(FieldCondition::new_range(
+                        INT_KEY.to_string(),
+                        Range {
+                            lt: None,
+                            gt: Some(1.0),
+                            gte: None,
+                            lte: None,
+                        },
+                    )),
+                )));
+                assert_eq!(
+                    check_filter_indexes(&indexed_fields, &match_filter).is_ok(),
+                    lookup
+                );
+                assert_eq!(
+                    check_filter_indexes(&indexed_fields, &range_filter).is_ok(),
+                    range
+                );
+            }
+
+            for _ in 0..100 {
+                let query_vector = random_vector(&mut rnd, dim);
+                let query_filter = Filter {
+                    should: Some(vec![
+                        Condition::Field(FieldCondition::new_match(
+                            INT_KEY.to_string(),
+                            rnd.gen_range(0..500).into(),
+                        )),
+                        Condition::Field(FieldCondition::new_range(
+                            INT_KEY.to_string(),
+                            Range {
+                                lt: None,
+                                gt: None,
+                                gte: Some(rnd.gen_range(0..500) as f64),
+                                lte: Some(rnd.gen_range(0..500) as f64),
+                            },
+                        )),
+                    ]),
+                    must: None,
+                    must_not: None,
+                    min_should: None,
+                };
+
+                let plain_result = plain_segment
+                    .search(
+                        &query_vector,
+                        &WithPayload::default(),
+                        false,
+                        Some(&query_filter),
+                        5,
+                        None,
+                    )
+                    .unwrap();
+                let struct_result = struct_segment
+                    .search(
+                        &query_vector,
+                        &WithPayload::default(),
+                        false,
+                        Some(&query_filter),
+                        5,
+                        None,
+                    )
+                    .unwrap();
+
+                assert_eq!(
+                    plain_result.iter().map(|hit| hit.id).collect_vec(),
+                    struct_result.iter().map(|hit| hit.id).collect_vec(),
+                    "query filter {:?}",
+                    query_filter
+                );
+            }
+        }
+
+        let no_index = struct_segment.create_field_index(
+            opnum + 1,
+            INT_KEY,
+            &Some(
+                PayloadSchemaParams::Integer(IntegerIndexParams {
+                    lookup: Some(false),
+                    range: Some(false),
+                    ..Default::default()
+                })
+                .into(),
+            ),
+        );
+        assert!(no_index.is_err());
+    }
+
     #[test]
     fn test_struct_payload_geo_index() {
         // Compare search with plain and struct indexes
@@ -291,6 +652,7 @@ mod tests {
                 should: None,
                 must: Some(vec![condition]),
                 must_not: None,
+                min_should: None,
             };
 
             let plain_result = plain_segment
@@ -350,4 +712,115 @@ mod tests {
                 });
         }
     }
+
+    #[test]
+    fn test_nested_condition_with_element_indexes() {
+        // Compare nested conditions, resolved with indexes of array elements, with the plain index
+        let dir1 = TempDir::new("segment1_dir").unwrap();
+        let dir2 = TempDir::new("segment2_dir").unwrap();
+        let mut rnd = StdRng::seed_from_u64(42);
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_size: dim,
+            index: Indexes::Plain {},
+            storage_type: StorageType::InMemory,
+            distance: Distance::Dot,
+            payload_storage_type: Default::default(),
+        };
+        let mut plain_segment = build_segment(dir2.path(), &config).unwrap();
+        let mut struct_segment = build_segment(dir1.path(), &config).unwrap();
+
+        let shops = ["a", "b", "c"];
+        let random_offers = |rnd: &mut StdRng| {
+            let offers = (0..rnd.gen_range(0..4))
+                .map(|_| {
+                    json!({
+                        "shop": shops[rnd.gen_range(0..shops.len())],
+                        "price": rnd.gen_range(0..100),
+                    })
+                })
+                .collect_vec();
+            Payload::from(json!({ "offers": offers }))
+        };
+
+        let num_points = 500;
+        let mut opnum = 0;
+        struct_segment
+            .create_field_index(
+                opnum,
+                "offers[].shop",
+                &Some(PayloadSchemaType::Keyword.into()),
+            )
+            .unwrap();
+        for n in 0..num_points {
+            let idx = n.into();
+            let vector = random_vector(&mut rnd, dim);
+            let payload = random_offers(&mut rnd);
+            for segment in [&mut plain_segment, &mut struct_segment] {
+                segment.upsert_point(opnum, idx, &vector).unwrap();
+                segment.set_full_payload(opnum, idx, &payload).unwrap();
+            }
+            opnum += 1;
+        }
+        // Indexes of the elements are built both on update and on creation of the index
+        struct_segment
+            .create_field_index(
+                opnum,
+                "offers[].price",
+                &Some(PayloadSchemaType::Integer.into()),
+            )
+            .unwrap();
+        for n in (0..num_points).step_by(7) {
+            let idx = n.into();
+            let payload = random_offers(&mut rnd);
+            for segment in [&mut plain_segment, &mut struct_segment] {
+                if n % 2 == 0 {
+                    segment.delete_payload(opnum, idx, "offers").unwrap();
+                } else {
+                    segment.set_payload(opnum, idx, &payload).unwrap();
+                }
+            }
+            opnum += 1;
+        }
+
+        // Element ids and element indexes are loaded along with the segment
+        struct_segment.flush().unwrap();
+        let struct_segment_path = struct_segment.current_path.clone();
+        drop(struct_segment);
+        let struct_segment = load_segment(&struct_segment_path).unwrap();
+
+        for shop in shops {
+            let filter = Filter::new_must(Condition::Nested(NestedCondition::new(Nested {
+                key: "offers".to_owned(),
+                filter: Filter {
+                    should: None,
+                    must: Some(vec![
+                        Condition::Field(FieldCondition::new_match(
+                            "shop".to_owned(),
+                            shop.to_owned().into(),
+                        )),
+                        Condition::Field(FieldCondition::new_range(
+                            "price".to_owned(),
+                            Range {
+                                lt: Some(30.),
+                                gt: None,
+                                gte: None,
+                                lte: None,
+                            },
+                        )),
+                    ]),
+                    must_not: None,
+                    min_should: None,
+                },
+            })));
+
+            let plain_result =
+                plain_segment.read_filtered(None, num_points as usize, Some(&filter));
+            let struct_result =
+                struct_segment.read_filtered(None, num_points as usize, Some(&filter));
+            assert!(!plain_result.is_empty());
+            assert_eq!(plain_result, struct_result);
+        }
+    }
 }
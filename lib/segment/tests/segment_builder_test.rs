@@ -8,9 +8,13 @@ mod tests {
 
     use itertools::Itertools;
     use segment::entry::entry_point::{OperationError, SegmentEntry};
+    use segment::index::PayloadIndex;
     use segment::segment::Segment;
     use segment::segment_constructor::segment_builder::SegmentBuilder;
-    use segment::types::{Indexes, SegmentConfig};
+    use segment::types::{
+        Indexes, KeywordIndexParams, Payload, PayloadSchemaParams, SegmentConfig,
+    };
+    use serde_json::json;
     use tempdir::TempDir;
 
     use crate::fixtures::segment::{build_segment_1, build_segment_2, empty_segment};
@@ -131,4 +135,73 @@ mod tests {
 
         assert!(time_fast < time_long);
     }
+
+    #[test]
+    fn test_building_tenant_segment() {
+        let dir = TempDir::new("segment_dir").unwrap();
+        let temp_dir = TempDir::new("segment_temp_dir").unwrap();
+
+        let stopped = AtomicBool::new(false);
+
+        let tenant_key = "tenant";
+        let num_tenants = 5;
+        let num_segments = 3;
+        let points_per_segment = 100;
+
+        // Each source segment contains points of all tenants
+        let segments = (0..num_segments)
+            .map(|segment_idx| {
+                let mut segment = empty_segment(dir.path());
+                segment
+                    .create_field_index(
+                        0,
+                        tenant_key,
+                        &Some(
+                            PayloadSchemaParams::Keyword(KeywordIndexParams {
+                                is_tenant: Some(true),
+                                ..Default::default()
+                            })
+                            .into(),
+                        ),
+                    )
+                    .unwrap();
+                for n in 0..points_per_segment {
+                    let idx = segment_idx * points_per_segment + n;
+                    let payload: Payload =
+                        json!({ tenant_key: format!("tenant_{}", idx % num_tenants) }).into();
+                    segment
+                        .upsert_point(1, idx.into(), &[idx as f32, 0., 0., 0.])
+                        .unwrap();
+                    segment.set_full_payload(1, idx.into(), &payload).unwrap();
+                }
+                segment
+            })
+            .collect_vec();
+
+        let mut builder =
+            SegmentBuilder::new(dir.path(), temp_dir.path(), &segments[0].segment_config).unwrap();
+        builder
+            .update(&segments.iter().collect_vec(), &stopped)
+            .unwrap();
+        let merged_segment: Segment = builder.build(&stopped).unwrap();
+
+        assert_eq!(
+            merged_segment.points_count() as u64,
+            num_segments * points_per_segment
+        );
+        // Intermediate segments are removed from the temp directory
+        assert_eq!(temp_dir.path().read_dir().unwrap().count(), 0);
+
+        // Points of each tenant are stored one after another across all the source segments
+        let payload_index = merged_segment.payload_index.borrow();
+        let stored_tenants = merged_segment
+            .vector_storage
+            .borrow()
+            .iter_ids()
+            .map(|point_id| payload_index.payload(point_id).unwrap())
+            .map(|payload| payload.get_value(tenant_key).cloned().unwrap())
+            .dedup()
+            .count();
+        assert_eq!(stored_tenants as u64, num_tenants);
+    }
 }
@@ -34,6 +34,7 @@ mod tests {
             should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
+            min_should: None,
         };
 
         let res = segment
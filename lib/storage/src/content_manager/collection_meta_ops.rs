@@ -1,3 +1,4 @@
+use collection::config::PayloadSchema;
 use collection::operations::config_diff::{HnswConfigDiff, OptimizersConfigDiff, WalConfigDiff};
 use collection::shard::{CollectionId, PeerId, ShardId};
 use schemars::JsonSchema;
@@ -103,6 +104,10 @@ pub struct CreateCollection {
     pub wal_config: Option<WalConfigDiff>,
     /// Custom params for Optimizers.  If none - values from service configuration file are used.
     pub optimizers_config: Option<OptimizersConfigDiff>,
+    /// Schema of the point payloads. If set - updates, which produce payloads
+    /// not matching the schema, are rejected
+    #[serde(default)]
+    pub payload_schema: Option<PayloadSchema>,
 }
 
 pub const fn default_shard_number() -> Option<u32> {
@@ -40,6 +40,7 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
                 shard_number: value.shard_number,
                 on_disk_payload: value.on_disk_payload,
+                payload_schema: value.payload_schema.map(|v| v.try_into()).transpose()?,
             },
         }))
     }
@@ -10,14 +10,18 @@ use collection::config::{CollectionConfig, CollectionParams};
 use collection::operations::config_diff::DiffConfig;
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
-    CountRequest, CountResult, PointRequest, RecommendRequest, Record, ScrollRequest, ScrollResult,
-    SearchRequest, UpdateResult,
+    AggregateRequest, CollectionScoredPoint, CollectionsSearchRequest, CountExplainResult,
+    CountRequest, CountResult, FacetRequest, FacetResult, FusionRequest, PointRequest,
+    RecommendRequest, Record, ScrollRequest, ScrollResult, SearchExplainResult, SearchRequest,
+    UpdateResult,
 };
 use collection::operations::CollectionUpdateOperations;
 use collection::shard::collection_shard_distribution::CollectionShardDistribution;
 use collection::shard::{ChannelService, CollectionId, PeerId, ShardId};
 use collection::telemetry::CollectionTelemetry;
-use segment::types::ScoredPoint;
+use futures::future::try_join_all;
+use itertools::Itertools;
+use segment::types::{NumericAggregation, Order, PayloadIndexStats, PayloadKeyType, ScoredPoint};
 use tokio::runtime::Runtime;
 use tokio::sync::{RwLock, RwLockReadGuard};
 
@@ -200,6 +204,7 @@ impl TableOfContent {
             hnsw_config: hnsw_config_diff,
             wal_config: wal_config_diff,
             optimizers_config: optimizers_config_diff,
+            payload_schema,
         } = operation;
 
         self.collections
@@ -226,6 +231,7 @@ impl TableOfContent {
                     description: "`shard_number` cannot be 0".to_string(),
                 })?,
             on_disk_payload: on_disk_payload.unwrap_or(self.storage_config.on_disk_payload),
+            payload_schema,
         };
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
@@ -482,6 +488,20 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Same as `recommend`, but also explains how the search was executed
+    pub async fn recommend_explain(
+        &self,
+        collection_name: &str,
+        request: RecommendRequest,
+        shard_selection: Option<ShardId>,
+    ) -> Result<SearchExplainResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .recommend_explain(request, self.search_runtime.handle(), shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Search for the closest points using vector similarity with given restrictions defined
     /// in the request
     ///
@@ -506,6 +526,148 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Same as `search`, but also explains how the search was executed
+    /// in each shard and segment of the collection
+    pub async fn search_explain(
+        &self,
+        collection_name: &str,
+        request: SearchRequest,
+        shard_selection: Option<ShardId>,
+    ) -> Result<SearchExplainResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .search_explain(request, self.search_runtime.handle(), shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Run several searches and merge their results with the given fusion method
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`FusionRequest`]
+    /// * `shard_selection` - which local shard to use
+    /// # Result
+    ///
+    /// Points with fused score
+    pub async fn search_fusion(
+        &self,
+        collection_name: &str,
+        request: FusionRequest,
+        shard_selection: Option<ShardId>,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .search_fusion(request, self.search_runtime.handle(), shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Search for the closest points in several collections at once
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - [`CollectionsSearchRequest`]
+    ///
+    /// # Result
+    ///
+    /// Points with search score, annotated with the collection they were found in
+    pub async fn search_collections(
+        &self,
+        request: CollectionsSearchRequest,
+    ) -> Result<Vec<CollectionScoredPoint>, StorageError> {
+        let CollectionsSearchRequest {
+            collections: collection_names,
+            search,
+        } = request;
+
+        if collection_names.is_empty() {
+            return Err(StorageError::BadRequest {
+                description: "At least one collection required".to_string(),
+            });
+        }
+
+        let read_collections = self.collections.read().await;
+
+        let mut real_collection_names: Vec<String> = Vec::with_capacity(collection_names.len());
+        for collection_name in &collection_names {
+            let real_collection_name = self.resolve_name(collection_name).await?;
+            // Same collection might be referenced by its name and by alias
+            if !real_collection_names.contains(&real_collection_name) {
+                real_collection_names.push(real_collection_name);
+            }
+        }
+
+        // resolve_name already checked collection existence, unwrap is safe here
+        let collections = real_collection_names
+            .iter()
+            .map(|collection_name| read_collections.get(collection_name).unwrap())
+            .collect_vec();
+
+        let params = collections[0].params().await;
+        for (collection_name, collection) in real_collection_names.iter().zip(&collections) {
+            let collection_params = collection.params().await;
+            if collection_params.vector_size != params.vector_size
+                || collection_params.distance != params.distance
+            {
+                return Err(StorageError::BadRequest {
+                    description: format!(
+                        "Collection {} has vector size {} and distance {:?}, expected vector size {} and distance {:?}",
+                        collection_name,
+                        collection_params.vector_size,
+                        collection_params.distance,
+                        params.vector_size,
+                        params.distance
+                    ),
+                });
+            }
+        }
+
+        // Each collection should return enough points to fill the requested page
+        let collection_request = SearchRequest {
+            limit: search.limit + search.offset,
+            offset: 0,
+            ..search.clone()
+        };
+        let all_searches = collections.iter().map(|collection| {
+            collection.search(
+                collection_request.clone(),
+                self.search_runtime.handle(),
+                None,
+            )
+        });
+        let all_results = try_join_all(all_searches).await?;
+
+        let mut result = real_collection_names
+            .into_iter()
+            .zip(all_results)
+            .flat_map(|(collection_name, points)| {
+                points.into_iter().map(move |point| CollectionScoredPoint {
+                    collection_name: collection_name.clone(),
+                    point,
+                })
+            })
+            .collect_vec();
+
+        let order = if search.rescore.is_some() {
+            Order::LargeBetter
+        } else {
+            params.distance.distance_order()
+        };
+        // `ScoredPoint` ordering is defined by score
+        match order {
+            Order::LargeBetter => result.sort_by(|a, b| b.point.cmp(&a.point)),
+            Order::SmallBetter => result.sort_by(|a, b| a.point.cmp(&b.point)),
+        }
+
+        Ok(result
+            .into_iter()
+            .skip(search.offset)
+            .take(search.limit)
+            .collect())
+    }
+
     /// Count points in the collection.
     ///
     /// # Arguments
@@ -531,6 +693,84 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Count points for each value of the payload field
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we count
+    /// * `request` - [`FacetRequest`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// The most frequent values of the field with number of points
+    ///
+    pub async fn facet(
+        &self,
+        collection_name: &str,
+        request: FacetRequest,
+        shard_selection: Option<ShardId>,
+    ) -> Result<FacetResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .facet(request, shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Compute statistics of the numeric payload field over the filtered points
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we aggregate
+    /// * `request` - [`AggregateRequest`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Count, min, max, sum and average of the field values, with optional histogram
+    ///
+    pub async fn aggregate(
+        &self,
+        collection_name: &str,
+        request: AggregateRequest,
+        shard_selection: Option<ShardId>,
+    ) -> Result<NumericAggregation, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .aggregate(request, shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Statistics of the payload indexes of the collection
+    pub async fn payload_index_stats(
+        &self,
+        collection_name: &str,
+        shard_selection: Option<ShardId>,
+    ) -> Result<HashMap<PayloadKeyType, PayloadIndexStats>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .payload_index_stats(shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Same as `count`, but also explains how the points were counted
+    /// in each shard and segment of the collection
+    pub async fn count_explain(
+        &self,
+        collection_name: &str,
+        request: CountRequest,
+        shard_selection: Option<ShardId>,
+    ) -> Result<CountExplainResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .count_explain(request, shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -62,6 +62,7 @@ mod tests {
                         optimizers_config: None,
                         shard_number: Some(1),
                         on_disk_payload: None,
+                        payload_schema: None,
                     },
                 }),
                 None,
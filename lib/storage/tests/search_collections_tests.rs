@@ -0,0 +1,168 @@
+#[cfg(all(test))]
+mod tests {
+    use std::sync::Arc;
+
+    use collection::operations::point_ops::Batch;
+    use collection::operations::types::{CollectionsSearchRequest, SearchRequest};
+    use collection::operations::CollectionUpdateOperations;
+    use collection::optimizers_builder::OptimizersConfig;
+    use segment::types::{Distance, PointIdType};
+    use storage::content_manager::collection_meta_ops::{
+        ChangeAliasesOperation, CollectionMetaOperations, CreateAlias, CreateCollection,
+        CreateCollectionOperation,
+    };
+    use storage::content_manager::errors::StorageError;
+    use storage::content_manager::toc::TableOfContent;
+    use storage::dispatcher::Dispatcher;
+    use storage::types::{PerformanceConfig, StorageConfig};
+    use tempdir::TempDir;
+    use tokio::runtime::Runtime;
+
+    fn create_collection_operation(name: &str, distance: Distance) -> CollectionMetaOperations {
+        CollectionMetaOperations::CreateCollection(CreateCollectionOperation {
+            collection_name: name.to_string(),
+            create_collection: CreateCollection {
+                vector_size: 2,
+                distance,
+                hnsw_config: None,
+                wal_config: None,
+                optimizers_config: None,
+                shard_number: Some(1),
+                on_disk_payload: None,
+                payload_schema: None,
+            },
+        })
+    }
+
+    fn insert_operation(ids: Vec<u64>, vectors: Vec<Vec<f32>>) -> CollectionUpdateOperations {
+        CollectionUpdateOperations::PointOperation(
+            Batch {
+                ids: ids.into_iter().map(|id| id.into()).collect(),
+                vectors,
+                payloads: None,
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn test_search_collections() {
+        let storage_dir = TempDir::new("storage").unwrap();
+
+        let config = StorageConfig {
+            storage_path: storage_dir.path().to_str().unwrap().to_string(),
+            snapshots_path: storage_dir
+                .path()
+                .join("snapshots")
+                .to_str()
+                .unwrap()
+                .to_string(),
+            on_disk_payload: false,
+            optimizers: OptimizersConfig {
+                deleted_threshold: 0.5,
+                vacuum_min_vector_number: 100,
+                default_segment_number: 2,
+                max_segment_size: 100_000,
+                memmap_threshold: 100,
+                indexing_threshold: 100,
+                flush_interval_sec: 2,
+                max_optimization_threads: 2,
+            },
+            wal: Default::default(),
+            performance: PerformanceConfig {
+                max_search_threads: 1,
+            },
+            hnsw_index: Default::default(),
+        };
+
+        let runtime = Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+
+        let toc = Arc::new(TableOfContent::new(&config, runtime, Default::default(), 0));
+        let dispatcher = Dispatcher::new(toc.clone());
+
+        for (name, distance) in [
+            ("first", Distance::Dot),
+            ("second", Distance::Dot),
+            ("euclid", Distance::Euclid),
+        ] {
+            handle
+                .block_on(
+                    dispatcher.submit_collection_meta_op(
+                        create_collection_operation(name, distance),
+                        None,
+                    ),
+                )
+                .unwrap();
+        }
+
+        handle
+            .block_on(dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::ChangeAliases(ChangeAliasesOperation {
+                    actions: vec![CreateAlias {
+                        collection_name: "second".to_string(),
+                        alias_name: "second_alias".to_string(),
+                    }
+                    .into()],
+                }),
+                None,
+            ))
+            .unwrap();
+
+        handle
+            .block_on(toc.update(
+                "first",
+                insert_operation(vec![1, 2], vec![vec![1.0, 0.0], vec![0.0, 1.0]]),
+                None,
+                true,
+            ))
+            .unwrap();
+        handle
+            .block_on(toc.update(
+                "second",
+                insert_operation(vec![1, 3], vec![vec![0.5, 0.0], vec![0.9, 0.1]]),
+                None,
+                true,
+            ))
+            .unwrap();
+
+        let search = SearchRequest {
+            vector: vec![1.0, 0.0],
+            filter: None,
+            params: None,
+            limit: 3,
+            offset: 0,
+            with_payload: None,
+            with_vector: false,
+            score_threshold: None,
+            rescore: None,
+            explain: false,
+        };
+
+        let result = handle
+            .block_on(toc.search_collections(CollectionsSearchRequest {
+                collections: vec!["first".to_string(), "second_alias".to_string()],
+                search: search.clone(),
+            }))
+            .unwrap();
+
+        let found: Vec<(&str, PointIdType)> = result
+            .iter()
+            .map(|scored| (scored.collection_name.as_str(), scored.point.id))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("first", 1.into()),
+                ("second", 3.into()),
+                ("second", 1.into())
+            ]
+        );
+
+        let mismatched = handle.block_on(toc.search_collections(CollectionsSearchRequest {
+            collections: vec!["first".to_string(), "euclid".to_string()],
+            search,
+        }));
+        assert!(matches!(mismatched, Err(StorageError::BadRequest { .. })));
+    }
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use collection::operations::types::AggregateRequest;
+use storage::content_manager::toc::TableOfContent;
+
+use crate::actix::helpers::process_response;
+use crate::common::points::do_aggregate_points;
+
+#[post("/collections/{name}/aggregate")]
+pub async fn aggregate_points(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+    request: web::Json<AggregateRequest>,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response = do_aggregate_points(
+        &toc.into_inner(),
+        &collection_name,
+        request.into_inner(),
+        None,
+    )
+    .await;
+    process_response(response, timing)
+}
@@ -13,6 +13,7 @@ use storage::dispatcher::Dispatcher;
 
 use crate::actix::helpers::process_response;
 use crate::common::collections::*;
+use crate::common::points::do_payload_index_stats;
 
 #[derive(Debug, Deserialize)]
 struct WaitTimeout {
@@ -129,6 +130,17 @@ async fn get_cluster_info(
     process_response(response, timing)
 }
 
+#[get("/collections/{name}/index/stats")]
+async fn get_payload_index_stats(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let name = path.into_inner();
+    let timing = Instant::now();
+    let response = do_payload_index_stats(&toc.into_inner(), &name, None).await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     cfg.service(get_collections)
@@ -137,7 +149,8 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(update_collection)
         .service(delete_collection)
         .service(update_aliases)
-        .service(get_cluster_info);
+        .service(get_cluster_info)
+        .service(get_payload_index_stats);
 }
 
 #[cfg(test)]
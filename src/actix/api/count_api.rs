@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
 use actix_web::rt::time::Instant;
-use actix_web::{post, web, Responder};
+use actix_web::{post, web, Either, Responder};
 use collection::operations::types::CountRequest;
 use storage::content_manager::toc::TableOfContent;
 
 use crate::actix::helpers::process_response;
-use crate::common::points::do_count_points;
+use crate::common::points::{do_count_explain_points, do_count_points};
 
 #[post("/collections/{name}/points/count")]
 pub async fn count_points(
@@ -16,14 +16,14 @@ pub async fn count_points(
 ) -> impl Responder {
     let collection_name = path.into_inner();
     let timing = Instant::now();
+    let request = request.into_inner();
 
-    let response = do_count_points(
-        &toc.into_inner(),
-        &collection_name,
-        request.into_inner(),
-        None,
-    )
-    .await;
-
-    process_response(response, timing)
+    if request.explain {
+        let response =
+            do_count_explain_points(&toc.into_inner(), &collection_name, request, None).await;
+        Either::Left(process_response(response, timing))
+    } else {
+        let response = do_count_points(&toc.into_inner(), &collection_name, request, None).await;
+        Either::Right(process_response(response, timing))
+    }
 }
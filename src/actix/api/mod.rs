@@ -1,6 +1,8 @@
+pub mod aggregate_api;
 pub mod cluster_api;
 pub mod collections_api;
 pub mod count_api;
+pub mod facet_api;
 pub mod recommend_api;
 pub mod retrieve_api;
 pub mod search_api;
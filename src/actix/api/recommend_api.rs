@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
 use actix_web::rt::time::Instant;
-use actix_web::{post, web, Responder};
-use collection::operations::types::RecommendRequest;
+use actix_web::{post, web, Either, Responder};
+use collection::operations::types::{RecommendRequest, SearchExplainResult};
 use segment::types::ScoredPoint;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -17,6 +17,14 @@ async fn do_recommend_points(
     toc.recommend(collection_name, request, None).await
 }
 
+async fn do_recommend_explain_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: RecommendRequest,
+) -> Result<SearchExplainResult, StorageError> {
+    toc.recommend_explain(collection_name, request, None).await
+}
+
 #[post("/collections/{name}/points/recommend")]
 pub async fn recommend_points(
     toc: web::Data<Arc<TableOfContent>>,
@@ -26,7 +34,13 @@ pub async fn recommend_points(
     let name = path.into_inner();
     let timing = Instant::now();
 
-    let response = do_recommend_points(&toc.into_inner(), &name, request.into_inner()).await;
+    let request = request.into_inner();
 
-    process_response(response, timing)
+    if request.explain {
+        let response = do_recommend_explain_points(&toc.into_inner(), &name, request).await;
+        Either::Left(process_response(response, timing))
+    } else {
+        let response = do_recommend_points(&toc.into_inner(), &name, request).await;
+        Either::Right(process_response(response, timing))
+    }
 }
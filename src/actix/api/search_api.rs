@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
 use actix_web::rt::time::Instant;
-use actix_web::{post, web, Responder};
-use collection::operations::types::SearchRequest;
+use actix_web::{post, web, Either, Responder};
+use collection::operations::types::{CollectionsSearchRequest, FusionRequest, SearchRequest};
 use storage::content_manager::toc::TableOfContent;
 
 use crate::actix::helpers::process_response;
-use crate::common::points::do_search_points;
+use crate::common::points::{
+    do_search_collections, do_search_explain_points, do_search_fusion_points, do_search_points,
+};
 
 #[post("/collections/{name}/points/search")]
 pub async fn search_points(
@@ -16,8 +18,28 @@ pub async fn search_points(
 ) -> impl Responder {
     let collection_name = path.into_inner();
     let timing = Instant::now();
+    let request = request.into_inner();
 
-    let response = do_search_points(
+    if request.explain {
+        let response =
+            do_search_explain_points(&toc.into_inner(), &collection_name, request, None).await;
+        Either::Left(process_response(response, timing))
+    } else {
+        let response = do_search_points(&toc.into_inner(), &collection_name, request, None).await;
+        Either::Right(process_response(response, timing))
+    }
+}
+
+#[post("/collections/{name}/points/search/fusion")]
+pub async fn search_fusion_points(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+    request: web::Json<FusionRequest>,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response = do_search_fusion_points(
         &toc.into_inner(),
         &collection_name,
         request.into_inner(),
@@ -27,3 +49,15 @@ pub async fn search_points(
 
     process_response(response, timing)
 }
+
+#[post("/collections/search")]
+pub async fn search_collections(
+    toc: web::Data<Arc<TableOfContent>>,
+    request: web::Json<CollectionsSearchRequest>,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = do_search_collections(&toc.into_inner(), request.into_inner()).await;
+
+    process_response(response, timing)
+}
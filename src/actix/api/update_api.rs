@@ -3,7 +3,7 @@ use std::sync::Arc;
 use actix_web::rt::time::Instant;
 use actix_web::web::Query;
 use actix_web::{delete, post, put, web, Responder};
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_ops::{DeletePayload, ModifyPayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -12,7 +12,7 @@ use storage::content_manager::toc::TableOfContent;
 use crate::actix::helpers::process_response;
 use crate::common::points::{
     do_clear_payload, do_create_index, do_delete_index, do_delete_payload, do_delete_points,
-    do_set_payload, do_upsert_points, CreateFieldIndex,
+    do_modify_payload, do_set_payload, do_upsert_points, CreateFieldIndex,
 };
 
 #[derive(Deserialize, Serialize, JsonSchema)]
@@ -87,6 +87,23 @@ pub async fn delete_payload(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/payload/modify")]
+pub async fn modify_payload(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+    operation: web::Json<ModifyPayload>,
+    params: Query<UpdateParam>,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let timing = Instant::now();
+
+    let response =
+        do_modify_payload(&toc.into_inner(), &collection_name, operation, None, wait).await;
+    process_response(response, timing)
+}
+
 #[post("/collections/{name}/points/payload/clear")]
 pub async fn clear_payload(
     toc: web::Data<Arc<TableOfContent>>,
@@ -143,6 +160,7 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(set_payload)
         .service(delete_payload)
         .service(clear_payload)
+        .service(modify_payload)
         .service(create_field_index)
         .service(delete_field_index);
 }
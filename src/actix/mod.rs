@@ -11,12 +11,14 @@ use actix_web::web::Data;
 use actix_web::{error, get, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use storage::dispatcher::Dispatcher;
 
+use crate::actix::api::aggregate_api::aggregate_points;
 use crate::actix::api::cluster_api::config_cluster_api;
 use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
+use crate::actix::api::facet_api::facet_points;
 use crate::actix::api::recommend_api::recommend_points;
 use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
-use crate::actix::api::search_api::search_points;
+use crate::actix::api::search_api::{search_collections, search_fusion_points, search_points};
 use crate::actix::api::snapshot_api::config_snapshots_api;
 use crate::actix::api::update_api::config_update_api;
 use crate::settings::{max_web_workers, Settings};
@@ -75,8 +77,12 @@ pub fn init(dispatcher: Arc<Dispatcher>, settings: Settings) -> std::io::Result<
                 .service(get_points)
                 .service(scroll_points)
                 .service(search_points)
+                .service(search_fusion_points)
+                .service(search_collections)
                 .service(recommend_points)
                 .service(count_points)
+                .service(facet_points)
+                .service(aggregate_points)
         })
         .workers(max_web_workers(&settings))
         .bind(format!(
@@ -1,13 +1,18 @@
-use collection::operations::payload_ops::{DeletePayload, PayloadOps, SetPayload};
+use std::collections::HashMap;
+
+use collection::operations::payload_ops::{DeletePayload, ModifyPayload, PayloadOps, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointOperations, PointsSelector};
 use collection::operations::types::{
-    CountRequest, CountResult, PointRequest, Record, ScrollRequest, ScrollResult, SearchRequest,
-    UpdateResult,
+    AggregateRequest, CollectionScoredPoint, CollectionsSearchRequest, CountExplainResult,
+    CountRequest, CountResult, FacetRequest, FacetResult, FusionRequest, PointRequest, Record,
+    ScrollRequest, ScrollResult, SearchExplainResult, SearchRequest, UpdateResult,
 };
 use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use collection::shard::ShardId;
 use schemars::JsonSchema;
-use segment::types::{PayloadSchemaType, ScoredPoint};
+use segment::types::{
+    NumericAggregation, PayloadFieldSchema, PayloadIndexStats, PayloadKeyType, ScoredPoint,
+};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -15,7 +20,7 @@ use storage::content_manager::toc::TableOfContent;
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct CreateFieldIndex {
     pub field_name: String,
-    pub field_type: Option<PayloadSchemaType>,
+    pub field_type: Option<PayloadFieldSchema>,
 }
 
 pub async fn do_upsert_points(
@@ -77,6 +82,19 @@ pub async fn do_delete_payload(
         .await
 }
 
+pub async fn do_modify_payload(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operation: ModifyPayload,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+) -> Result<UpdateResult, StorageError> {
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::ModifyPayload(operation));
+    toc.update(collection_name, collection_operation, shard_selection, wait)
+        .await
+}
+
 pub async fn do_clear_payload(
     toc: &TableOfContent,
     collection_name: &str,
@@ -138,6 +156,33 @@ pub async fn do_search_points(
     toc.search(collection_name, request, shard_selection).await
 }
 
+pub async fn do_search_explain_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchRequest,
+    shard_selection: Option<ShardId>,
+) -> Result<SearchExplainResult, StorageError> {
+    toc.search_explain(collection_name, request, shard_selection)
+        .await
+}
+
+pub async fn do_search_fusion_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: FusionRequest,
+    shard_selection: Option<ShardId>,
+) -> Result<Vec<ScoredPoint>, StorageError> {
+    toc.search_fusion(collection_name, request, shard_selection)
+        .await
+}
+
+pub async fn do_search_collections(
+    toc: &TableOfContent,
+    request: CollectionsSearchRequest,
+) -> Result<Vec<CollectionScoredPoint>, StorageError> {
+    toc.search_collections(request).await
+}
+
 pub async fn do_count_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -147,6 +192,44 @@ pub async fn do_count_points(
     toc.count(collection_name, request, shard_selection).await
 }
 
+pub async fn do_facet_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: FacetRequest,
+    shard_selection: Option<ShardId>,
+) -> Result<FacetResult, StorageError> {
+    toc.facet(collection_name, request, shard_selection).await
+}
+
+pub async fn do_aggregate_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: AggregateRequest,
+    shard_selection: Option<ShardId>,
+) -> Result<NumericAggregation, StorageError> {
+    toc.aggregate(collection_name, request, shard_selection)
+        .await
+}
+
+pub async fn do_payload_index_stats(
+    toc: &TableOfContent,
+    collection_name: &str,
+    shard_selection: Option<ShardId>,
+) -> Result<HashMap<PayloadKeyType, PayloadIndexStats>, StorageError> {
+    toc.payload_index_stats(collection_name, shard_selection)
+        .await
+}
+
+pub async fn do_count_explain_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CountRequest,
+    shard_selection: Option<ShardId>,
+) -> Result<CountExplainResult, StorageError> {
+    toc.count_explain(collection_name, request, shard_selection)
+        .await
+}
+
 pub async fn do_get_points(
     toc: &TableOfContent,
     collection_name: &str,
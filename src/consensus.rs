@@ -621,6 +621,7 @@ mod tests {
                         optimizers_config: None,
                         shard_number: Some(2),
                         on_disk_payload: None,
+                        payload_schema: None,
                     },
                 }),
                 None,
@@ -1,13 +1,15 @@
 use api::grpc::models::CollectionsResponse;
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_ops::{DeletePayload, ModifyPayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector};
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
-    CollectionClusterInfo, CollectionInfo, CountRequest, CountResult, PointRequest,
-    RecommendRequest, Record, ScrollRequest, ScrollResult, SearchRequest, UpdateResult,
+    AggregateRequest, CollectionClusterInfo, CollectionInfo, CollectionScoredPoint,
+    CollectionsSearchRequest, CountExplainResult, CountRequest, CountResult, FacetRequest,
+    FacetResult, FusionRequest, PointRequest, RecommendRequest, Record, ScrollRequest,
+    ScrollResult, SearchExplainResult, SearchRequest, UpdateResult,
 };
 use schemars::{schema_for, JsonSchema};
-use segment::types::ScoredPoint;
+use segment::types::{NumericAggregation, PayloadIndexStats, ScoredPoint};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
@@ -47,6 +49,17 @@ struct AllDefinitions {
     an: CountRequest,
     ao: CountResult,
     ap: CollectionClusterInfo,
+    aq: FusionRequest,
+    ar: CollectionsSearchRequest,
+    at: CollectionScoredPoint,
+    au: SearchExplainResult,
+    av: CountExplainResult,
+    aw: FacetRequest,
+    ax: FacetResult,
+    ay: AggregateRequest,
+    az: NumericAggregation,
+    ba: ModifyPayload,
+    bb: PayloadIndexStats,
 }
 
 fn save_schema<T: JsonSchema>() {
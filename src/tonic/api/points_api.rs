@@ -2,17 +2,20 @@ use std::sync::Arc;
 
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePoints, GetPoints, GetResponse,
-    PointsOperationResponse, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
-    SearchPoints, SearchResponse, SetPayloadPoints, UpsertPoints,
+    AggregatePoints, AggregateResponse, ClearPayloadPoints, CountPoints, CountResponse,
+    CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints, DeletePoints,
+    FacetPoints, FacetResponse, GetPoints, GetResponse, ModifyPayloadPoints,
+    PayloadIndexStatsRequest, PayloadIndexStatsResponse, PointsOperationResponse, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchCollections, SearchCollectionsResponse,
+    SearchFusionPoints, SearchPoints, SearchResponse, SetPayloadPoints, UpsertPoints,
 };
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
 
 use crate::tonic::api::points_common::{
-    clear_payload, count, create_field_index, delete, delete_field_index, delete_payload, get,
-    recommend, scroll, search, set_payload, upsert,
+    aggregate, clear_payload, count, create_field_index, delete, delete_field_index,
+    delete_payload, facet, get, modify_payload, payload_index_stats, recommend, scroll, search,
+    search_collections, search_fusion, set_payload, upsert,
 };
 
 pub struct PointsService {
@@ -66,6 +69,13 @@ impl Points for PointsService {
         clear_payload(self.toc.as_ref(), request.into_inner(), None).await
     }
 
+    async fn modify_payload(
+        &self,
+        request: Request<ModifyPayloadPoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        modify_payload(self.toc.as_ref(), request.into_inner(), None).await
+    }
+
     async fn create_field_index(
         &self,
         request: Request<CreateFieldIndexCollection>,
@@ -87,6 +97,20 @@ impl Points for PointsService {
         search(self.toc.as_ref(), request.into_inner(), None).await
     }
 
+    async fn search_fusion(
+        &self,
+        request: Request<SearchFusionPoints>,
+    ) -> Result<Response<SearchResponse>, Status> {
+        search_fusion(self.toc.as_ref(), request.into_inner(), None).await
+    }
+
+    async fn search_collections(
+        &self,
+        request: Request<SearchCollections>,
+    ) -> Result<Response<SearchCollectionsResponse>, Status> {
+        search_collections(self.toc.as_ref(), request.into_inner()).await
+    }
+
     async fn scroll(
         &self,
         request: Request<ScrollPoints>,
@@ -107,6 +131,27 @@ impl Points for PointsService {
     ) -> Result<Response<CountResponse>, Status> {
         count(self.toc.as_ref(), request.into_inner(), None).await
     }
+
+    async fn facet(
+        &self,
+        request: Request<FacetPoints>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        facet(self.toc.as_ref(), request.into_inner(), None).await
+    }
+
+    async fn aggregate(
+        &self,
+        request: Request<AggregatePoints>,
+    ) -> Result<Response<AggregateResponse>, Status> {
+        aggregate(self.toc.as_ref(), request.into_inner(), None).await
+    }
+
+    async fn payload_index_stats(
+        &self,
+        request: Request<PayloadIndexStatsRequest>,
+    ) -> Result<Response<PayloadIndexStatsResponse>, Status> {
+        payload_index_stats(self.toc.as_ref(), request.into_inner(), None).await
+    }
 }
 
 #[cfg(test)]
@@ -2,26 +2,34 @@ use std::time::Instant;
 
 use api::grpc::conversions::proto_to_payloads;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePoints, FieldType, GetPoints,
-    GetResponse, PointsOperationResponse, RecommendPoints, RecommendResponse, ScrollPoints,
-    ScrollResponse, SearchPoints, SearchResponse, SetPayloadPoints, UpsertPoints,
+    AggregatePoints, AggregateResponse, ClearPayloadPoints, CountPoints, CountResponse,
+    CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints, DeletePoints,
+    FacetPoints, FacetResponse, FieldType, GetPoints, GetResponse, ModifyPayloadPoints,
+    PayloadIndexStatsRequest, PayloadIndexStatsResponse, PointId, PointsOperationResponse,
+    RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchCollections,
+    SearchCollectionsResponse, SearchFusionPoints, SearchPoints, SearchResponse, SetPayloadPoints,
+    UpsertPoints,
 };
-use collection::operations::payload_ops::DeletePayload;
-use collection::operations::point_ops::PointInsertOperations;
+use collection::operations::payload_ops::{DeletePayload, ModifyPayload};
+use collection::operations::point_ops::{PointInsertOperations, PointsSelector};
 use collection::operations::types::{
-    default_exact_count, PointRequest, ScrollRequest, SearchRequest,
+    default_exact_count, default_facet_limit, AggregateRequest, CollectionsSearchRequest,
+    FacetRequest, FusionRequest, HistogramParams, PointRequest, ScrollRequest, SearchRequest,
 };
 use collection::shard::ShardId;
-use segment::types::PayloadSchemaType;
+use segment::types::{
+    Filter, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType, PointIdType,
+};
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Response, Status};
 
 use crate::common::points::{
-    do_clear_payload, do_count_points, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_get_points, do_scroll_points, do_search_points, do_set_payload,
-    do_upsert_points, CreateFieldIndex,
+    do_aggregate_points, do_clear_payload, do_count_explain_points, do_count_points,
+    do_create_index, do_delete_index, do_delete_payload, do_delete_points, do_facet_points,
+    do_get_points, do_modify_payload, do_payload_index_stats, do_scroll_points,
+    do_search_collections, do_search_explain_points, do_search_fusion_points, do_search_points,
+    do_set_payload, do_upsert_points, CreateFieldIndex,
 };
 
 pub fn points_operation_response(
@@ -105,14 +113,16 @@ pub async fn set_payload(
         wait,
         payload,
         points,
+        key,
+        points_selector,
     } = set_payload_points;
 
+    let (points, filter) = points_or_filter(points, points_selector)?;
     let operation = collection::operations::payload_ops::SetPayload {
         payload: proto_to_payloads(payload)?,
-        points: points
-            .into_iter()
-            .map(|p| p.try_into())
-            .collect::<Result<_, _>>()?,
+        points,
+        filter,
+        key,
     };
 
     let timing = Instant::now();
@@ -140,14 +150,14 @@ pub async fn delete_payload(
         wait,
         keys,
         points,
+        points_selector,
     } = delete_payload_points;
 
+    let (points, filter) = points_or_filter(points, points_selector)?;
     let operation = DeletePayload {
         keys,
-        points: points
-            .into_iter()
-            .map(|p| p.try_into())
-            .collect::<Result<_, _>>()?,
+        points,
+        filter,
     };
 
     let timing = Instant::now();
@@ -165,6 +175,25 @@ pub async fn delete_payload(
     Ok(Response::new(response))
 }
 
+/// Payload operations accept either a plain list of ids or a points selector,
+/// the selector takes precedence
+fn points_or_filter(
+    points: Vec<PointId>,
+    points_selector: Option<api::grpc::qdrant::PointsSelector>,
+) -> Result<(Option<Vec<PointIdType>>, Option<Filter>), Status> {
+    match points_selector.map(PointsSelector::try_from).transpose()? {
+        Some(PointsSelector::PointIdsSelector(selector)) => Ok((Some(selector.points), None)),
+        Some(PointsSelector::FilterSelector(selector)) => Ok((None, Some(selector.filter))),
+        None => {
+            let points = points
+                .into_iter()
+                .map(|p| p.try_into())
+                .collect::<Result<_, _>>()?;
+            Ok((Some(points), None))
+        }
+    }
+}
+
 pub async fn clear_payload(
     toc: &TableOfContent,
     clear_payload_points: ClearPayloadPoints,
@@ -196,6 +225,49 @@ pub async fn clear_payload(
     Ok(Response::new(response))
 }
 
+pub async fn modify_payload(
+    toc: &TableOfContent,
+    modify_payload_points: ModifyPayloadPoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PointsOperationResponse>, Status> {
+    let ModifyPayloadPoints {
+        collection_name,
+        wait,
+        modifications,
+        points,
+    } = modify_payload_points;
+
+    let (points, filter) = match points {
+        None => return Err(Status::invalid_argument("PointSelector is missing")),
+        Some(p) => match PointsSelector::try_from(p)? {
+            PointsSelector::PointIdsSelector(selector) => (Some(selector.points), None),
+            PointsSelector::FilterSelector(selector) => (None, Some(selector.filter)),
+        },
+    };
+    let operation = ModifyPayload {
+        modifications: modifications
+            .into_iter()
+            .map(|modification| modification.try_into())
+            .collect::<Result<_, _>>()?,
+        points,
+        filter,
+    };
+
+    let timing = Instant::now();
+    let result = do_modify_payload(
+        toc,
+        &collection_name,
+        operation,
+        shard_selection,
+        wait.unwrap_or(false),
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response(timing, result);
+    Ok(Response::new(response))
+}
+
 pub async fn create_field_index(
     toc: &TableOfContent,
     create_field_index_collection: CreateFieldIndexCollection,
@@ -206,21 +278,31 @@ pub async fn create_field_index(
         wait,
         field_name,
         field_type,
+        field_index_params,
     } = create_field_index_collection;
 
     let field_type = match field_type {
         None => None,
         Some(f) => match FieldType::from_i32(f) {
             None => return Err(Status::invalid_argument("cannot convert field_type")),
-            Some(v) => match v {
-                FieldType::Keyword => Some(PayloadSchemaType::Keyword),
-                FieldType::Integer => Some(PayloadSchemaType::Integer),
-                FieldType::Float => Some(PayloadSchemaType::Float),
-                FieldType::Geo => Some(PayloadSchemaType::Geo),
-            },
+            Some(v) => Some(PayloadSchemaType::from(v)),
         },
     };
 
+    let field_index_params = field_index_params
+        .map(PayloadSchemaParams::try_from)
+        .transpose()?;
+
+    let field_type = match (field_type, field_index_params) {
+        (Some(field_type), Some(params)) if field_type != params.kind() => {
+            return Err(Status::invalid_argument(
+                "field_index_params do not match field_type",
+            ))
+        }
+        (_, Some(params)) => Some(PayloadFieldSchema::FieldParams(params)),
+        (field_type, None) => field_type.map(PayloadFieldSchema::FieldType),
+    };
+
     let operation = CreateFieldIndex {
         field_name,
         field_type,
@@ -282,6 +364,8 @@ pub async fn search(
         with_payload,
         params,
         score_threshold,
+        rescore,
+        explain,
     } = search_points;
 
     let search_request = SearchRequest {
@@ -293,14 +377,124 @@ pub async fn search(
         with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
         with_vector: with_vector.unwrap_or(false),
         score_threshold,
+        rescore: rescore.map(|r| r.try_into()).transpose()?,
+        explain: explain.unwrap_or(false),
+    };
+
+    let timing = Instant::now();
+    let (scored_points, explain) = if search_request.explain {
+        let search_result =
+            do_search_explain_points(toc, &collection_name, search_request, shard_selection)
+                .await
+                .map_err(error_to_status)?;
+        (search_result.result, search_result.explain)
+    } else {
+        let scored_points =
+            do_search_points(toc, &collection_name, search_request, shard_selection)
+                .await
+                .map_err(error_to_status)?;
+        (scored_points, vec![])
+    };
+
+    let response = SearchResponse {
+        result: scored_points
+            .into_iter()
+            .map(|point| point.into())
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+        explain: explain.into_iter().map(|e| e.into()).collect(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_fusion(
+    toc: &TableOfContent,
+    search_fusion_points: SearchFusionPoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<SearchResponse>, Status> {
+    let SearchFusionPoints {
+        collection_name,
+        searches,
+        fusion,
+        limit,
+        offset,
+        with_vector,
+        with_payload,
+    } = search_fusion_points;
+
+    let fusion_request = FusionRequest {
+        searches: searches
+            .into_iter()
+            .map(|search| search.try_into())
+            .collect::<Result<_, _>>()?,
+        fusion: fusion
+            .map(|f| f.try_into())
+            .transpose()?
+            .unwrap_or_default(),
+        limit: limit as usize,
+        offset: offset.unwrap_or_default() as usize,
+        with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
+        with_vector: with_vector.unwrap_or(false),
+    };
+
+    let timing = Instant::now();
+    let scored_points =
+        do_search_fusion_points(toc, &collection_name, fusion_request, shard_selection)
+            .await
+            .map_err(error_to_status)?;
+
+    let response = SearchResponse {
+        result: scored_points
+            .into_iter()
+            .map(|point| point.into())
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+        explain: vec![],
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_collections(
+    toc: &TableOfContent,
+    search_collections: SearchCollections,
+) -> Result<Response<SearchCollectionsResponse>, Status> {
+    let SearchCollections {
+        collection_names,
+        vector,
+        filter,
+        limit,
+        offset,
+        with_vector,
+        with_payload,
+        params,
+        score_threshold,
+        rescore,
+    } = search_collections;
+
+    let request = CollectionsSearchRequest {
+        collections: collection_names,
+        search: SearchRequest {
+            vector,
+            filter: filter.map(|f| f.try_into()).transpose()?,
+            params: params.map(|p| p.into()),
+            limit: limit as usize,
+            offset: offset.unwrap_or_default() as usize,
+            with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
+            with_vector: with_vector.unwrap_or(false),
+            score_threshold,
+            rescore: rescore.map(|r| r.try_into()).transpose()?,
+            explain: false,
+        },
     };
 
     let timing = Instant::now();
-    let scored_points = do_search_points(toc, &collection_name, search_request, shard_selection)
+    let scored_points = do_search_collections(toc, request)
         .await
         .map_err(error_to_status)?;
 
-    let response = SearchResponse {
+    let response = SearchCollectionsResponse {
         result: scored_points
             .into_iter()
             .map(|point| point.into())
@@ -327,6 +521,7 @@ pub async fn recommend(
         with_payload,
         params,
         score_threshold,
+        explain,
     } = recommend_points;
 
     let request = collection::operations::types::RecommendRequest {
@@ -345,13 +540,23 @@ pub async fn recommend(
         with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
         with_vector: with_vector.unwrap_or(false),
         score_threshold,
+        explain: explain.unwrap_or(false),
     };
 
     let timing = Instant::now();
-    let recommended_points = toc
-        .recommend(&collection_name, request, shard_selection)
-        .await
-        .map_err(error_to_status)?;
+    let (recommended_points, explain) = if request.explain {
+        let recommend_result = toc
+            .recommend_explain(&collection_name, request, shard_selection)
+            .await
+            .map_err(error_to_status)?;
+        (recommend_result.result, recommend_result.explain)
+    } else {
+        let recommended_points = toc
+            .recommend(&collection_name, request, shard_selection)
+            .await
+            .map_err(error_to_status)?;
+        (recommended_points, vec![])
+    };
 
     let response = RecommendResponse {
         result: recommended_points
@@ -359,6 +564,7 @@ pub async fn recommend(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        explain: explain.into_iter().map(|e| e.into()).collect(),
     };
 
     Ok(Response::new(response))
@@ -376,6 +582,7 @@ pub async fn scroll(
         limit,
         with_vector,
         with_payload,
+        order_by,
     } = scroll_points;
 
     let scroll_request = ScrollRequest {
@@ -384,6 +591,7 @@ pub async fn scroll(
         filter: filter.map(|f| f.try_into()).transpose()?,
         with_payload: with_payload.map(|wp| wp.try_into()).transpose()?,
         with_vector: with_vector.unwrap_or(false),
+        order_by: order_by.map(|o| o.try_into()).transpose()?,
     };
 
     let timing = Instant::now();
@@ -393,6 +601,7 @@ pub async fn scroll(
 
     let response = ScrollResponse {
         next_page_offset: scrolled_points.next_page_offset.map(|n| n.into()),
+        next_page_start_from: scrolled_points.next_page_start_from,
         result: scrolled_points
             .points
             .into_iter()
@@ -413,21 +622,125 @@ pub async fn count(
         collection_name,
         filter,
         exact,
+        explain,
     } = count_points;
 
     let count_request = collection::operations::types::CountRequest {
         filter: filter.map(|f| f.try_into()).transpose()?,
         exact: exact.unwrap_or_else(default_exact_count),
+        explain: explain.unwrap_or(false),
     };
 
     let timing = Instant::now();
-    let count_result = do_count_points(toc, &collection_name, count_request, shard_selection)
-        .await
-        .map_err(error_to_status)?;
+    let (count_result, explain) = if count_request.explain {
+        let count_result =
+            do_count_explain_points(toc, &collection_name, count_request, shard_selection)
+                .await
+                .map_err(error_to_status)?;
+        (count_result.result, count_result.explain)
+    } else {
+        let count_result = do_count_points(toc, &collection_name, count_request, shard_selection)
+            .await
+            .map_err(error_to_status)?;
+        (count_result, vec![])
+    };
 
     let response = CountResponse {
         result: Some(count_result.into()),
         time: timing.elapsed().as_secs_f64(),
+        explain: explain.into_iter().map(|e| e.into()).collect(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn facet(
+    toc: &TableOfContent,
+    facet_points: FacetPoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<FacetResponse>, Status> {
+    let FacetPoints {
+        collection_name,
+        key,
+        filter,
+        limit,
+        exact,
+    } = facet_points;
+
+    let facet_request = FacetRequest {
+        key,
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        limit: limit.map_or_else(default_facet_limit, |l| l as usize),
+        exact: exact.unwrap_or(false),
+    };
+
+    let timing = Instant::now();
+    let facet_result = do_facet_points(toc, &collection_name, facet_request, shard_selection)
+        .await
+        .map_err(error_to_status)?;
+
+    let response = FacetResponse {
+        hits: facet_result
+            .hits
+            .into_iter()
+            .map(|hit| hit.into())
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn aggregate(
+    toc: &TableOfContent,
+    aggregate_points: AggregatePoints,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<AggregateResponse>, Status> {
+    let AggregatePoints {
+        collection_name,
+        key,
+        filter,
+        histogram_interval,
+    } = aggregate_points;
+
+    let aggregate_request = AggregateRequest {
+        key,
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        histogram: histogram_interval.map(|interval| HistogramParams { interval }),
+    };
+
+    let timing = Instant::now();
+    let aggregation =
+        do_aggregate_points(toc, &collection_name, aggregate_request, shard_selection)
+            .await
+            .map_err(error_to_status)?;
+
+    let response = AggregateResponse {
+        result: Some(aggregation.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn payload_index_stats(
+    toc: &TableOfContent,
+    request: PayloadIndexStatsRequest,
+    shard_selection: Option<ShardId>,
+) -> Result<Response<PayloadIndexStatsResponse>, Status> {
+    let PayloadIndexStatsRequest { collection_name } = request;
+
+    let timing = Instant::now();
+    let stats = do_payload_index_stats(toc, &collection_name, shard_selection)
+        .await
+        .map_err(error_to_status)?;
+
+    let response = PayloadIndexStatsResponse {
+        result: stats
+            .into_iter()
+            .map(|(field, field_stats)| (field, field_stats.into()))
+            .collect(),
+        time: timing.elapsed().as_secs_f64(),
     };
 
     Ok(Response::new(response))
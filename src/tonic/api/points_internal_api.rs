@@ -2,19 +2,21 @@ use std::sync::Arc;
 
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
-    ClearPayloadPointsInternal, CountPointsInternal, CountResponse,
-    CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, GetPointsInternal, GetResponse,
-    PointsOperationResponse, RecommendPointsInternal, RecommendResponse, ScrollPointsInternal,
-    ScrollResponse, SearchPointsInternal, SearchResponse, SetPayloadPointsInternal,
-    UpsertPointsInternal,
+    AggregatePointsInternal, AggregateResponse, ClearPayloadPointsInternal, CountPointsInternal,
+    CountResponse, CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, FacetPointsInternal, FacetResponse,
+    GetPointsInternal, GetResponse, ModifyPayloadPointsInternal, PayloadIndexStatsInternal,
+    PayloadIndexStatsResponse, PointsOperationResponse, RecommendPointsInternal, RecommendResponse,
+    ScrollPointsInternal, ScrollResponse, SearchPointsInternal, SearchResponse,
+    SetPayloadPointsInternal, UpsertPointsInternal,
 };
 use storage::content_manager::toc::TableOfContent;
 use tonic::{Request, Response, Status};
 
 use crate::tonic::api::points_common::{
-    clear_payload, count, create_field_index, delete, delete_field_index, delete_payload, get,
-    recommend, scroll, search, set_payload, upsert,
+    aggregate, clear_payload, count, create_field_index, delete, delete_field_index,
+    delete_payload, facet, get, modify_payload, payload_index_stats, recommend, scroll, search,
+    set_payload, upsert,
 };
 
 /// This API is intended for P2P communication within a distributed deployment.
@@ -105,6 +107,21 @@ impl PointsInternal for PointsInternalService {
         clear_payload(self.toc.as_ref(), clear_payload_points, Some(shard_id)).await
     }
 
+    async fn modify_payload(
+        &self,
+        request: Request<ModifyPayloadPointsInternal>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        let ModifyPayloadPointsInternal {
+            modify_payload_points,
+            shard_id,
+        } = request.into_inner();
+
+        let modify_payload_points = modify_payload_points
+            .ok_or_else(|| Status::invalid_argument("ModifyPayloadPoints is missing"))?;
+
+        modify_payload(self.toc.as_ref(), modify_payload_points, Some(shard_id)).await
+    }
+
     async fn create_field_index(
         &self,
         request: Request<CreateFieldIndexCollectionInternal>,
@@ -218,6 +235,48 @@ impl PointsInternal for PointsInternalService {
             count_points.ok_or_else(|| Status::invalid_argument("CountPoints is missing"))?;
         count(self.toc.as_ref(), count_points, Some(shard_id)).await
     }
+
+    async fn facet(
+        &self,
+        request: Request<FacetPointsInternal>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        let FacetPointsInternal {
+            facet_points,
+            shard_id,
+        } = request.into_inner();
+
+        let facet_points =
+            facet_points.ok_or_else(|| Status::invalid_argument("FacetPoints is missing"))?;
+        facet(self.toc.as_ref(), facet_points, Some(shard_id)).await
+    }
+
+    async fn aggregate(
+        &self,
+        request: Request<AggregatePointsInternal>,
+    ) -> Result<Response<AggregateResponse>, Status> {
+        let AggregatePointsInternal {
+            aggregate_points,
+            shard_id,
+        } = request.into_inner();
+
+        let aggregate_points = aggregate_points
+            .ok_or_else(|| Status::invalid_argument("AggregatePoints is missing"))?;
+        aggregate(self.toc.as_ref(), aggregate_points, Some(shard_id)).await
+    }
+
+    async fn payload_index_stats(
+        &self,
+        request: Request<PayloadIndexStatsInternal>,
+    ) -> Result<Response<PayloadIndexStatsResponse>, Status> {
+        let PayloadIndexStatsInternal {
+            payload_index_stats: stats_request,
+            shard_id,
+        } = request.into_inner();
+
+        let stats_request = stats_request
+            .ok_or_else(|| Status::invalid_argument("PayloadIndexStatsRequest is missing"))?;
+        payload_index_stats(self.toc.as_ref(), stats_request, Some(shard_id)).await
+    }
 }
 
 #[cfg(test)]